@@ -14,12 +14,26 @@ pub enum AppError {
     NoTty,
     #[error("invalid task file {path}: {reason}")]
     InvalidTaskFile { path: String, reason: String },
+    #[error(
+        "task file {path} changed on disk and both edits changed {}; both versions are kept between conflict markers in the file",
+        conflicts.join(", ")
+    )]
+    EditConflict {
+        path: String,
+        conflicts: Vec<String>,
+    },
+    #[error(
+        "repository busy: another tqs process holds {lock}; try again, or run `tqs doctor` if none is running"
+    )]
+    RepositoryBusy { lock: String },
     #[error("path traversal attempt: {0}")]
     PathTraversalAttempt(String),
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
     #[error("yaml error: {0}")]
     Yaml(#[from] serde_yaml::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
     #[error("format error: {0}")]
     Format(#[from] crate::storage::format::FormatError),
     #[error("dialoguer error: {0}")]
@@ -52,6 +66,17 @@ impl AppError {
         }
     }
 
+    pub fn edit_conflict(path: impl Into<String>, conflicts: Vec<String>) -> Self {
+        Self::EditConflict {
+            path: path.into(),
+            conflicts,
+        }
+    }
+
+    pub fn repository_busy(lock: impl Into<String>) -> Self {
+        Self::RepositoryBusy { lock: lock.into() }
+    }
+
     pub fn path_traversal_attempt(path: impl Into<String>) -> Self {
         Self::PathTraversalAttempt(path.into())
     }
@@ -64,9 +89,12 @@ impl AppError {
             | Self::AmbiguousTaskRef { .. }
             | Self::NoTty
             | Self::InvalidTaskFile { .. }
+            | Self::EditConflict { .. }
+            | Self::RepositoryBusy { .. }
             | Self::PathTraversalAttempt(_)
             | Self::Io(_)
             | Self::Yaml(_)
+            | Self::Json(_)
             | Self::Format(_)
             | Self::Dialoguer(_) => 1,
         }
@@ -93,5 +121,10 @@ mod tests {
             AppError::invalid_task_file("a.md", "bad yaml").exit_code(),
             1
         );
+        assert_eq!(
+            AppError::edit_conflict("a.md", vec!["title".to_string()]).exit_code(),
+            1
+        );
+        assert_eq!(AppError::repository_busy("a.lock").exit_code(), 1);
     }
 }
@@ -0,0 +1,345 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, Utc};
+
+use crate::app::app_error::AppError;
+use crate::app::operations::{self, DoneOutcome};
+use crate::domain::task::Queue;
+use crate::storage::config::ResolvedConfig;
+use crate::storage::journal::{FileChange, Operation, read_contents};
+use crate::storage::{atomic, daily_notes, repo::StoredTask, repo::TaskRepo};
+
+/// One action applied to every task in a bulk selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkAction {
+    Start,
+    Move(Queue),
+    Done,
+    Delete,
+}
+
+impl BulkAction {
+    /// The queue a task ends up in, or `None` for deletion.
+    pub fn target_queue(self) -> Option<Queue> {
+        match self {
+            Self::Start => Some(Queue::Now),
+            Self::Move(queue) => Some(queue),
+            Self::Done => Some(Queue::Done),
+            Self::Delete => None,
+        }
+    }
+}
+
+impl fmt::Display for BulkAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Start => f.write_str("start"),
+            Self::Move(queue) => write!(f, "move to {queue}"),
+            Self::Done => f.write_str("complete"),
+            Self::Delete => f.write_str("delete"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum BulkOutcome {
+    Moved(PathBuf),
+    Completed(Box<DoneOutcome>),
+    Deleted,
+    /// The task was already in the target queue.
+    Unchanged,
+}
+
+#[derive(Debug)]
+pub struct BulkResult {
+    pub id: String,
+    pub outcome: BulkOutcome,
+}
+
+/// Applies `action` to every task, in order. If any task fails, every file
+/// already touched is restored and the error names the failing task, so the
+/// repository is left as it was before the call. The restore is journaled as
+/// a rollback, so the failed batch has no net effect to undo.
+///
+/// The repository lock is held for the whole batch, rollback included, so
+/// the restore cannot overwrite another process's change made in between.
+pub fn apply(
+    repo: &TaskRepo,
+    config: &ResolvedConfig,
+    tasks: &[StoredTask],
+    action: BulkAction,
+) -> Result<Vec<BulkResult>, AppError> {
+    let _lock = repo.lock()?;
+    let mut snapshot = Snapshot::default();
+    if let (BulkAction::Done, Some(dir)) = (action, &config.daily_notes_dir) {
+        snapshot.record_untracked(&daily_notes::note_path(dir, Local::now().date_naive()))?;
+    }
+
+    let mut results = Vec::with_capacity(tasks.len());
+    for stored in tasks {
+        match apply_one(repo, config, stored, action, &mut snapshot) {
+            Ok(outcome) => results.push(BulkResult {
+                id: stored.task.id.clone(),
+                outcome,
+            }),
+            Err(error) => {
+                let id = &stored.task.id;
+                let restored = snapshot.restore().and_then(|changes| {
+                    let paths = changes
+                        .iter()
+                        .map(|change| change.path.clone())
+                        .collect::<Vec<_>>();
+                    repo.journal().record(Operation::Rollback, None, changes)?;
+                    repo.commit(&format!("roll back bulk {action}"), &paths);
+                    Ok(())
+                });
+                return Err(match restored {
+                    Ok(()) => AppError::message(format!(
+                        "bulk {action} stopped at task {id}: {error}; no tasks were changed"
+                    )),
+                    Err(restore_error) => AppError::message(format!(
+                        "bulk {action} stopped at task {id}: {error}; restoring earlier changes also failed: {restore_error}"
+                    )),
+                });
+            }
+        }
+    }
+    Ok(results)
+}
+
+fn apply_one(
+    repo: &TaskRepo,
+    config: &ResolvedConfig,
+    stored: &StoredTask,
+    action: BulkAction,
+    snapshot: &mut Snapshot,
+) -> Result<BulkOutcome, AppError> {
+    // The selection may have been made before the lock was taken, so find
+    // each task again to snapshot where it is now.
+    let stored = repo.find_by_id(&stored.task.id)?;
+    let id = &stored.task.id;
+    if action.target_queue() == Some(stored.task.queue) {
+        return Ok(BulkOutcome::Unchanged);
+    }
+    snapshot.record(&stored.path)?;
+
+    match action {
+        BulkAction::Start | BulkAction::Move(_) => {
+            let queue = action.target_queue().expect("moves have a target queue");
+            snapshot.record(&repo.task_path(queue, id))?;
+            let (_, path, _) = repo.move_to_queue(id, queue, Utc::now())?;
+            Ok(BulkOutcome::Moved(path))
+        }
+        BulkAction::Done => {
+            snapshot.record(&repo.task_path(Queue::Done, id))?;
+            // Archiving is left to the caller once the whole batch succeeded,
+            // since the rollback snapshot does not cover archived tasks.
+            let outcome = operations::complete(repo, config, id)?;
+            if let Some((_, next_path)) = &outcome.next {
+                snapshot.created(next_path);
+            }
+            Ok(BulkOutcome::Completed(Box::new(outcome)))
+        }
+        BulkAction::Delete => {
+            snapshot.record(&repo.trash().task_path(id))?;
+            snapshot.record(&repo.trash().metadata_path(id))?;
+            repo.delete(id)?;
+            Ok(BulkOutcome::Deleted)
+        }
+    }
+}
+
+/// Original contents of every file a bulk action touches; `None` marks a
+/// file that did not exist yet.
+#[derive(Debug, Default)]
+struct Snapshot {
+    files: Vec<SnapshotFile>,
+}
+
+#[derive(Debug)]
+struct SnapshotFile {
+    path: PathBuf,
+    content: Option<String>,
+    /// Whether the repo journals changes to this file; daily notes are
+    /// written outside it.
+    tracked: bool,
+}
+
+impl Snapshot {
+    fn record(&mut self, path: &Path) -> Result<(), AppError> {
+        self.push(path, true)
+    }
+
+    fn record_untracked(&mut self, path: &Path) -> Result<(), AppError> {
+        self.push(path, false)
+    }
+
+    fn push(&mut self, path: &Path, tracked: bool) -> Result<(), AppError> {
+        if self.contains(path) {
+            return Ok(());
+        }
+        self.files.push(SnapshotFile {
+            path: path.to_path_buf(),
+            content: read_contents(path)?,
+            tracked,
+        });
+        Ok(())
+    }
+
+    fn created(&mut self, path: &Path) {
+        if !self.contains(path) {
+            self.files.push(SnapshotFile {
+                path: path.to_path_buf(),
+                content: None,
+                tracked: true,
+            });
+        }
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        self.files.iter().any(|file| file.path == path)
+    }
+
+    /// Puts every file back and returns the journaled files' changes.
+    fn restore(self) -> Result<Vec<FileChange>, AppError> {
+        let mut changes = Vec::new();
+        for file in self.files.into_iter().rev() {
+            let current = read_contents(&file.path)?;
+            match &file.content {
+                Some(content) => {
+                    if let Some(parent) = file.path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    atomic::write(&file.path, content)?;
+                }
+                None => atomic::remove(&file.path)?,
+            }
+            if file.tracked {
+                changes.push(FileChange {
+                    path: file.path,
+                    before: current,
+                    after: file.content,
+                });
+            }
+        }
+        Ok(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BulkAction, BulkOutcome, apply};
+    use crate::domain::task::{Queue, Task};
+    use crate::storage::config::{QueueDirs, ResolvedConfig};
+    use crate::storage::journal::Operation;
+    use crate::storage::repo::{StoredTask, TaskRepo};
+    use chrono::Utc;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn scan_by_id(repo: &TaskRepo) -> Vec<StoredTask> {
+        let mut tasks = repo.scan_all().expect("scan should succeed");
+        tasks.sort_by(|left, right| left.task.id.cmp(&right.task.id));
+        tasks
+    }
+
+    fn setup(temp: &TempDir) -> (TaskRepo, ResolvedConfig) {
+        let config = ResolvedConfig {
+            obsidian_vault_dir: None,
+            tasks_root: temp.path().to_path_buf(),
+            state_dir: temp.path().join(".tqs"),
+            daily_notes_dir: None,
+            queue_dirs: QueueDirs::default(),
+            recur_queue: Queue::Inbox,
+            views: Vec::new(),
+            templates: Default::default(),
+            auto_archive_after: None,
+            git: None,
+        };
+        let repo = TaskRepo::new(temp.path().to_path_buf(), QueueDirs::default());
+        for (id, queue) in [("a", Queue::Later), ("b", Queue::Later), ("c", Queue::Done)] {
+            let mut task = Task::new(id, format!("Task {id}"), Utc::now());
+            task.queue = queue;
+            repo.create(&task).expect("task should be created");
+        }
+        (repo, config)
+    }
+
+    #[test]
+    fn apply_moves_every_task_and_skips_ones_already_there() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let (repo, config) = setup(&temp);
+        let tasks = scan_by_id(&repo);
+
+        let results =
+            apply(&repo, &config, &tasks, BulkAction::Done).expect("bulk action should succeed");
+
+        assert_eq!(results.len(), 3);
+        let unchanged = results
+            .iter()
+            .filter(|result| matches!(result.outcome, BulkOutcome::Unchanged))
+            .map(|result| result.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(unchanged, vec!["c"]);
+        assert_eq!(
+            repo.list_queue(Queue::Done)
+                .expect("queue should list")
+                .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn apply_restores_earlier_tasks_when_one_fails() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let (repo, config) = setup(&temp);
+        let tasks = scan_by_id(&repo)
+            .into_iter()
+            .filter(|stored| stored.task.queue == Queue::Later)
+            .collect::<Vec<_>>();
+        let original = fs::read_to_string(&tasks[0].path).expect("file should exist");
+        fs::remove_file(&tasks[1].path).expect("file should be removed");
+
+        let error = apply(&repo, &config, &tasks, BulkAction::Move(Queue::Now))
+            .expect_err("missing task should fail the batch");
+
+        assert!(
+            error
+                .to_string()
+                .starts_with("bulk move to now stopped at task b: task not found: b")
+        );
+        assert!(error.to_string().ends_with("no tasks were changed"));
+        assert_eq!(
+            fs::read_to_string(&tasks[0].path).expect("file should exist"),
+            original
+        );
+        assert!(
+            repo.list_queue(Queue::Now)
+                .expect("queue should list")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn apply_restores_deleted_tasks_when_one_fails() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let (repo, config) = setup(&temp);
+        let mut tasks = scan_by_id(&repo);
+        let missing = tasks.pop().expect("tasks should exist");
+        fs::remove_file(&missing.path).expect("file should be removed");
+        tasks.push(missing);
+
+        repo.journal().begin_batch();
+        apply(&repo, &config, &tasks, BulkAction::Delete).expect_err("batch should fail");
+
+        assert_eq!(repo.list().expect("tasks should list").len(), 2);
+        let batches = repo
+            .journal()
+            .batches()
+            .expect("journal should be readable");
+        assert_eq!(batches.len(), 1, "the rolled back batch has no net effect");
+        assert_eq!(batches[0].entries[0].operation, Operation::Create);
+    }
+}
@@ -1,3 +1,4 @@
 pub mod app_error;
+pub mod bulk;
 pub mod operations;
 pub mod service;
@@ -5,9 +5,15 @@ use std::path::PathBuf;
 use chrono::{Local, Utc};
 
 use crate::app::app_error::AppError;
+use crate::domain::deps::unblocked_by;
+use crate::domain::rank::{RankPosition, rerank};
 use crate::domain::task::{Queue, Task};
 use crate::storage::config::ResolvedConfig;
-use crate::storage::{daily_notes, repo::TaskRepo};
+use crate::storage::{
+    daily_notes,
+    id_state::SharedIdAllocator,
+    repo::{StoredTask, TaskRepo},
+};
 
 #[derive(Debug, Default)]
 pub struct TriageSummary {
@@ -15,6 +21,8 @@ pub struct TriageSummary {
     pub moved_next: u32,
     pub moved_later: u32,
     pub moved_done: u32,
+    /// Moves into custom queues, in the order each queue was first used.
+    pub moved_custom: Vec<(Queue, u32)>,
     pub deleted: u32,
     pub skipped: u32,
 }
@@ -26,6 +34,16 @@ impl TriageSummary {
             Queue::Next => self.moved_next += 1,
             Queue::Later => self.moved_later += 1,
             Queue::Done => self.moved_done += 1,
+            Queue::Custom(_) => {
+                match self
+                    .moved_custom
+                    .iter_mut()
+                    .find(|(moved, _)| *moved == queue)
+                {
+                    Some((_, count)) => *count += 1,
+                    None => self.moved_custom.push((queue, 1)),
+                }
+            }
             Queue::Inbox => {}
         }
     }
@@ -35,6 +53,7 @@ impl TriageSummary {
             && self.moved_next == 0
             && self.moved_later == 0
             && self.moved_done == 0
+            && self.moved_custom.is_empty()
             && self.deleted == 0
             && self.skipped == 0
     }
@@ -52,6 +71,9 @@ impl fmt::Display for TriageSummary {
         if self.moved_later > 0 {
             parts.push(format!("{} to later", self.moved_later));
         }
+        for (queue, count) in &self.moved_custom {
+            parts.push(format!("{count} to {queue}"));
+        }
         if self.moved_done > 0 {
             parts.push(format!("{} done", self.moved_done));
         }
@@ -69,25 +91,218 @@ impl fmt::Display for TriageSummary {
     }
 }
 
+/// Queues walked by a review, most urgent first.
+pub const REVIEW_QUEUES: [Queue; 3] = [Queue::Now, Queue::Next, Queue::Later];
+
+#[derive(Debug, Default)]
+pub struct ReviewSummary {
+    pub kept: u32,
+    pub promoted: u32,
+    pub demoted: u32,
+    pub done: u32,
+    pub deleted: u32,
+    pub skipped: u32,
+}
+
+impl ReviewSummary {
+    pub fn is_empty(&self) -> bool {
+        self.kept == 0
+            && self.promoted == 0
+            && self.demoted == 0
+            && self.done == 0
+            && self.deleted == 0
+            && self.skipped == 0
+    }
+}
+
+impl fmt::Display for ReviewSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts = [
+            (self.kept, "kept"),
+            (self.promoted, "promoted"),
+            (self.demoted, "demoted"),
+            (self.done, "done"),
+            (self.deleted, "deleted"),
+            (self.skipped, "skipped"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{count} {label}"))
+        .collect::<Vec<_>>();
+        if parts.is_empty() {
+            write!(f, "No changes")
+        } else {
+            write!(f, "{}", parts.join(", "))
+        }
+    }
+}
+
+/// Tasks to review: `now`, then `next`, then `later`, each starting with the
+/// task that has gone longest without an update.
+pub fn review_order(tasks: &[Task]) -> Vec<&Task> {
+    REVIEW_QUEUES
+        .iter()
+        .flat_map(|queue| {
+            let mut queued = tasks
+                .iter()
+                .filter(|task| task.queue == *queue)
+                .collect::<Vec<_>>();
+            queued.sort_by(|left, right| {
+                left.updated_at
+                    .cmp(&right.updated_at)
+                    .then_with(|| left.id.cmp(&right.id))
+            });
+            queued
+        })
+        .collect()
+}
+
+/// Keeps a task where it is and marks it reviewed by bumping `updated_at`,
+/// so the next review starts with the tasks nobody has looked at.
+pub fn keep_reviewed(repo: &TaskRepo, task_id: &str) -> Result<Task, AppError> {
+    let _lock = repo.lock()?;
+    let mut task = repo.read(task_id)?;
+    task.updated_at = Utc::now();
+    repo.update(&task)?;
+    Ok(task)
+}
+
+/// Moves a task to `position` within its queue, re-ranking neighbours when
+/// there is no gap left between them. Returns the task with its new rank.
+pub fn rank_task(
+    repo: &TaskRepo,
+    task_id: &str,
+    position: &RankPosition,
+) -> Result<Task, AppError> {
+    let _lock = repo.lock()?;
+    let task = repo.read(task_id)?;
+    if let RankPosition::Before(other) | RankPosition::After(other) = position {
+        if *other == task.id {
+            return Err(AppError::usage(format!(
+                "cannot rank {} relative to itself",
+                task.id
+            )));
+        }
+        let other = repo.read(other)?;
+        if other.queue != task.queue {
+            return Err(AppError::usage(format!(
+                "{} is in {}, not in {} with {}",
+                other.id, other.queue, task.queue, task.id
+            )));
+        }
+    }
+
+    let queued = repo.list_queue(task.queue)?;
+    let changes = rerank(&queued.iter().collect::<Vec<_>>(), &task.id, position)
+        .ok_or_else(|| AppError::not_found(task_id))?;
+    let mut ranked = task;
+    for (id, rank) in changes {
+        repo.set_rank(&id, rank)?;
+        if id == ranked.id {
+            ranked.rank = Some(rank);
+        }
+    }
+    Ok(ranked)
+}
+
+/// Result of completing a task.
+#[derive(Debug)]
+pub struct DoneOutcome {
+    pub task: Task,
+    pub path: PathBuf,
+    /// The follow-up task created for a recurring task, with its path.
+    pub next: Option<(Task, PathBuf)>,
+    /// Tasks that were waiting only on this one.
+    pub unblocked: Vec<Task>,
+    /// Done tasks archived afterwards because of `auto_archive_after`.
+    pub archived: Vec<StoredTask>,
+}
+
 /// Move a task to the done queue and append to daily notes if configured.
-/// Returns the updated task and its path.
+/// Recurring tasks also get their next occurrence created in the configured
+/// `recur_queue`; the completed instance stays in done. Afterwards, done
+/// tasks older than `auto_archive_after` are archived.
 pub fn mark_done(
     repo: &TaskRepo,
     config: &ResolvedConfig,
     task_id: &str,
-) -> Result<(Task, PathBuf), AppError> {
-    let (mut task, path, _) = repo.move_to_queue(task_id, Queue::Done, Utc::now())?;
+) -> Result<DoneOutcome, AppError> {
+    let _lock = repo.lock()?;
+    let mut outcome = complete(repo, config, task_id)?;
+    outcome.archived = auto_archive(repo, config)?;
+    Ok(outcome)
+}
+
+/// [`mark_done`] without the auto-archive, for bulk completion, whose
+/// caller archives once the whole batch has succeeded.
+pub(crate) fn complete(
+    repo: &TaskRepo,
+    config: &ResolvedConfig,
+    task_id: &str,
+) -> Result<DoneOutcome, AppError> {
+    let _lock = repo.lock()?;
+    let now = Utc::now();
+    let today = Local::now().date_naive();
+    let all_tasks = repo.list()?;
+    let unblocked = unblocked_by(&all_tasks, task_id)
+        .into_iter()
+        .cloned()
+        .collect();
+    // The follow-up is worked out first, so a rule with no next date leaves
+    // the task where it was instead of completing it halfway.
+    let current = repo.read(task_id)?;
+    let follow_up = match current.recur {
+        Some(rule) => {
+            let id = SharedIdAllocator::new(config).generate(repo)?;
+            let follow_up = current
+                .next_occurrence(id, config.recur_queue, now, today)
+                .ok_or_else(|| {
+                    AppError::usage(format!(
+                        "cannot complete {}: recur '{rule}' has no next date before the end of the calendar",
+                        current.id
+                    ))
+                })?;
+            Some(follow_up)
+        }
+        None => None,
+    };
+    let (mut task, path, _) = repo.move_to_queue(&current.id, Queue::Done, now)?;
 
     if let Some(daily_notes_dir) = &config.daily_notes_dir {
-        let note_date = Local::now().date_naive();
-        let note = daily_notes::append_completion(daily_notes_dir, &path, note_date, &task)?;
+        let note = daily_notes::append_completion(daily_notes_dir, &path, today, &task)?;
         if task.daily_note.as_deref() != Some(note.note_name.as_str()) {
             task.daily_note = Some(note.note_name);
             repo.update(&task)?;
         }
     }
 
-    Ok((task, path))
+    let next = match follow_up {
+        Some(follow_up) => {
+            let follow_up_path = repo.create(&follow_up)?;
+            Some((follow_up, follow_up_path))
+        }
+        None => None,
+    };
+
+    Ok(DoneOutcome {
+        task,
+        path,
+        next,
+        unblocked,
+        archived: Vec::new(),
+    })
+}
+
+/// Archives the done tasks finished longer ago than the configured
+/// `auto_archive_after`; does nothing when it is unset.
+pub fn auto_archive(repo: &TaskRepo, config: &ResolvedConfig) -> Result<Vec<StoredTask>, AppError> {
+    match config
+        .auto_archive_after
+        .and_then(|age| age.before(Utc::now()))
+    {
+        Some(cutoff) => repo.archive_done_before(cutoff),
+        None => Ok(Vec::new()),
+    }
 }
 
 /// Result of applying an edit: either the task was unchanged, or it was updated.
@@ -109,6 +324,7 @@ pub fn apply_edit(
     path: &std::path::Path,
     original_content: &str,
 ) -> Result<EditOutcome, AppError> {
+    let _lock = repo.lock()?;
     let edited_content = fs::read_to_string(path)?;
 
     if edited_content.trim().is_empty() {
@@ -148,6 +364,217 @@ mod tests {
         (repo, path, original)
     }
 
+    fn config(temp: &TempDir, recur_queue: Queue) -> ResolvedConfig {
+        ResolvedConfig {
+            obsidian_vault_dir: None,
+            tasks_root: temp.path().to_path_buf(),
+            state_dir: temp.path().join(".tqs"),
+            daily_notes_dir: None,
+            queue_dirs: QueueDirs::default(),
+            recur_queue,
+            views: Vec::new(),
+            templates: Default::default(),
+            auto_archive_after: None,
+            git: None,
+        }
+    }
+
+    #[test]
+    fn mark_done_leaves_plain_tasks_alone() {
+        let temp = TempDir::new().unwrap();
+        let (repo, _, _) = make_repo_with_task(&temp);
+
+        let outcome = mark_done(&repo, &config(&temp, Queue::Inbox), "abc").unwrap();
+        assert_eq!(outcome.task.queue, Queue::Done);
+        assert!(outcome.next.is_none());
+        assert_eq!(repo.scan_all().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn mark_done_creates_next_occurrence_for_recurring_task() {
+        let temp = TempDir::new().unwrap();
+        let (repo, _, _) = make_repo_with_task(&temp);
+        let mut task = repo.read("abc").unwrap();
+        task.recur = Some("every 1d".parse().unwrap());
+        repo.update(&task).unwrap();
+
+        let outcome = mark_done(&repo, &config(&temp, Queue::Next), "abc").unwrap();
+        let (next, path) = outcome.next.expect("recurring task should repeat");
+
+        assert_eq!(repo.read("abc").unwrap().queue, Queue::Done);
+        assert_ne!(next.id, "abc");
+        assert_eq!(next.queue, Queue::Next);
+        assert_eq!(next.recur, task.recur);
+        assert!(next.scheduled.is_some());
+        assert_eq!(repo.read(&next.id).unwrap().title, "Test task");
+        assert!(path.starts_with(temp.path().join("next")));
+    }
+
+    #[test]
+    fn mark_done_leaves_a_task_whose_recurrence_overflows_in_place() {
+        let temp = TempDir::new().unwrap();
+        let (repo, _, _) = make_repo_with_task(&temp);
+        let mut task = repo.read("abc").unwrap();
+        task.recur = Some("every 4000000000d".parse().unwrap());
+        repo.update(&task).unwrap();
+
+        let err = mark_done(&repo, &config(&temp, Queue::Next), "abc").unwrap_err();
+
+        assert!(matches!(err, AppError::Usage(_)));
+        assert_eq!(repo.read("abc").unwrap().queue, task.queue);
+        assert_eq!(repo.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn mark_done_reports_tasks_it_unblocks() {
+        let temp = TempDir::new().unwrap();
+        let (repo, _, _) = make_repo_with_task(&temp);
+        let mut waiting = Task::new("def".to_string(), "Waiting", Utc::now());
+        waiting.blocked_by = vec!["abc".to_string()];
+        repo.create(&waiting).unwrap();
+        let mut still_waiting = Task::new("ghi".to_string(), "Still waiting", Utc::now());
+        still_waiting.blocked_by = vec!["abc".to_string(), "def".to_string()];
+        repo.create(&still_waiting).unwrap();
+
+        let outcome = mark_done(&repo, &config(&temp, Queue::Inbox), "abc").unwrap();
+        let ids = outcome
+            .unblocked
+            .iter()
+            .map(|task| task.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["def"]);
+    }
+
+    #[test]
+    fn auto_archive_only_runs_when_configured() {
+        let temp = TempDir::new().unwrap();
+        let (repo, _, _) = make_repo_with_task(&temp);
+        let mut task = repo.read("abc").unwrap();
+        task.move_to(Queue::Done, "2026-01-05T09:00:00Z".parse().unwrap());
+        repo.update(&task).unwrap();
+
+        let mut config = config(&temp, Queue::Inbox);
+        assert!(auto_archive(&repo, &config).unwrap().is_empty());
+
+        config.auto_archive_after = Some("30d".parse().unwrap());
+        let archived = auto_archive(&repo, &config).unwrap();
+        assert_eq!(archived.len(), 1);
+        assert!(
+            archived[0]
+                .path
+                .starts_with(temp.path().join("done").join("2026"))
+        );
+        assert!(repo.scan_all().unwrap().is_empty());
+    }
+
+    #[test]
+    fn mark_done_archives_old_done_tasks_but_bulk_completion_does_not() {
+        let temp = TempDir::new().unwrap();
+        let (repo, _, _) = make_repo_with_task(&temp);
+        let mut old = repo.read("abc").unwrap();
+        old.move_to(Queue::Done, "2026-01-05T09:00:00Z".parse().unwrap());
+        repo.update(&old).unwrap();
+        for id in ["def", "ghi"] {
+            repo.create(&Task::new(id.to_string(), "Fresh task", Utc::now()))
+                .unwrap();
+        }
+        let mut config = config(&temp, Queue::Inbox);
+        config.auto_archive_after = Some("30d".parse().unwrap());
+
+        let outcome = complete(&repo, &config, "def").unwrap();
+        assert!(outcome.archived.is_empty());
+        assert_eq!(repo.read("abc").unwrap().queue, Queue::Done);
+
+        let outcome = mark_done(&repo, &config, "ghi").unwrap();
+        let archived = outcome
+            .archived
+            .iter()
+            .map(|stored| stored.task.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(archived, vec!["abc"]);
+        let remaining = repo
+            .scan_all()
+            .unwrap()
+            .into_iter()
+            .map(|stored| stored.task.id)
+            .collect::<Vec<_>>();
+        assert_eq!(remaining.len(), 2);
+        assert!(!remaining.contains(&"abc".to_string()));
+    }
+
+    #[test]
+    fn review_order_walks_queues_with_stalest_first() {
+        let at = |value: &str| value.parse().expect("timestamp should parse");
+        let mut tasks = Vec::new();
+        for (id, queue, updated) in [
+            ("fresh", Queue::Later, "2026-03-09T10:00:00Z"),
+            ("stale", Queue::Later, "2026-01-09T10:00:00Z"),
+            ("next", Queue::Next, "2026-03-01T10:00:00Z"),
+            ("now", Queue::Now, "2026-03-10T10:00:00Z"),
+            ("inbox", Queue::Inbox, "2026-01-01T10:00:00Z"),
+            ("done", Queue::Done, "2026-01-01T10:00:00Z"),
+        ] {
+            let mut task = Task::new(id, id, at(updated));
+            task.queue = queue;
+            tasks.push(task);
+        }
+
+        let ids = review_order(&tasks)
+            .iter()
+            .map(|task| task.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["now", "next", "stale", "fresh"]);
+    }
+
+    #[test]
+    fn keep_reviewed_bumps_updated_at_and_summary_lists_counts() {
+        let temp = TempDir::new().unwrap();
+        let (repo, _, _) = make_repo_with_task(&temp);
+        let before = repo.read("abc").unwrap().updated_at;
+
+        let task = keep_reviewed(&repo, "abc").unwrap();
+        assert!(task.updated_at > before);
+        assert_eq!(repo.read("abc").unwrap().queue, Queue::Inbox);
+
+        let mut summary = ReviewSummary::default();
+        assert_eq!(summary.to_string(), "No changes");
+        summary.kept = 2;
+        summary.demoted = 1;
+        assert_eq!(summary.to_string(), "2 kept, 1 demoted");
+    }
+
+    #[test]
+    fn rank_task_persists_order_and_rejects_tasks_from_other_queues() {
+        let temp = TempDir::new().unwrap();
+        let (repo, _, _) = make_repo_with_task(&temp);
+        repo.create(&Task::new("def".to_string(), "Second", Utc::now()))
+            .unwrap();
+        let mut other = Task::new("ghi".to_string(), "Elsewhere", Utc::now());
+        other.queue = Queue::Now;
+        repo.create(&other).unwrap();
+        let before = repo.read("abc").unwrap().updated_at;
+
+        let ranked = rank_task(&repo, "abc", &RankPosition::Top).unwrap();
+        assert!(ranked.rank.is_some());
+        assert_eq!(repo.read("abc").unwrap().updated_at, before);
+        let ids = |repo: &TaskRepo| {
+            repo.list_queue(Queue::Inbox)
+                .unwrap()
+                .into_iter()
+                .map(|task| task.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&repo), ["abc", "def"]);
+
+        rank_task(&repo, "def", &RankPosition::Before("abc".to_string())).unwrap();
+        assert_eq!(ids(&repo), ["def", "abc"]);
+
+        let err = rank_task(&repo, "abc", &RankPosition::After("ghi".to_string())).unwrap_err();
+        assert!(err.to_string().contains("not in inbox"));
+        let err = rank_task(&repo, "abc", &RankPosition::After("abc".to_string())).unwrap_err();
+        assert!(err.to_string().contains("relative to itself"));
+    }
+
     #[test]
     fn apply_edit_unchanged_returns_unchanged() {
         let temp = TempDir::new().unwrap();
@@ -2,26 +2,46 @@ use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
 
+use crate::io::output::OutputFormat;
+
 use super::commands::{
-    Add, Config, Delete, Doctor, Done, Edit, Find, Inbox, List, Move, Now, Show, Start, Triage,
+    Add, Archive, Block, Config, Delete, Doctor, Done, Due, Edit, Find, History, Inbox, List, Move,
+    Now, Prio, Rank, Redo, Restore, Review, Show, Start, Stats, Sync, Tag, Trash, Triage, Unblock,
+    Undo, View,
 };
 
 const TOP_LEVEL_HELP: &str = "\
 Task Commands:
   add     Add a task
   list    List tasks
-  find    Find tasks by text
+  find    Find tasks by text or query
+  view    Show a saved view from config
   show    Show task details
+  stats   Show completion counts, lead times, and queue ages
 
 Workflow Commands:
   now     List tasks in the now queue
   inbox   List tasks in the inbox queue
-  start   Move a task to the now queue
-  move    Move a task to a different queue
-  done    Mark a task as done
-  delete  Delete a task permanently
+  start   Move tasks to the now queue
+  move    Move tasks to a different queue
+  rank    Reorder a task within its queue
+  done    Mark tasks as done
+  archive Move old done tasks into dated archive folders
+  delete  Move tasks to the trash
+  restore Restore tasks from the trash
+  trash   List or purge deleted tasks
   edit    Edit a task
+  tag     Add or remove task tags
+  due     Set or clear a task due date
+  prio    Set or clear a task priority
+  block   Mark a task as blocked by other tasks
+  unblock Remove blocking tasks from a task
   triage  Triage inbox tasks interactively
+  review  Review now, next, and later tasks interactively
+  undo    Undo the most recent change
+  redo    Redo the most recently undone change
+  history Show recent changes that can be undone
+  sync    Pull and push the git repository holding the tasks
 
 Setup Commands:
   config  Show effective configuration and setup help
@@ -43,6 +63,10 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub root: Option<PathBuf>,
 
+    /// Output format for list, now, inbox, find, view, show, stats, config, and doctor
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
     /// Disable the interactive TUI dashboard (show plain text instead)
     #[arg(long)]
     pub no_tui: bool,
@@ -58,13 +82,29 @@ pub enum Command {
     Now(Now),
     Inbox(Inbox),
     Move(Move),
+    Rank(Rank),
     Start(Start),
     Delete(Delete),
+    Restore(Restore),
+    Trash(Trash),
     Done(Done),
+    Archive(Archive),
     Edit(Edit),
+    Tag(Tag),
+    Due(Due),
+    Prio(Prio),
+    Block(Block),
+    Unblock(Unblock),
     Show(Show),
+    Stats(Stats),
     Find(Find),
+    View(View),
     Config(Config),
     Triage(Triage),
+    Review(Review),
+    Undo(Undo),
+    Redo(Redo),
+    History(History),
+    Sync(Sync),
     Doctor(Doctor),
 }
@@ -1,11 +1,11 @@
 use std::{fs, path::PathBuf, process::Command};
 
-use chrono::Utc;
+use chrono::{NaiveDate, Utc};
 use clap::Parser;
 
 use crate::app::app_error::AppError;
 use crate::cli::commands::helpers;
-use crate::domain::{id::validate_user_id, task::Task};
+use crate::domain::{id::validate_user_id, priority::Priority, recur::RecurRule, task::Task};
 use crate::{
     io::{input, output},
     storage::id_state::SharedIdAllocator,
@@ -16,8 +16,27 @@ use crate::{
 pub struct Add {
     pub title: Option<String>,
 
-    #[arg(long, value_parser = helpers::parse_queue)]
-    pub queue: Option<crate::domain::task::Queue>,
+    #[arg(long, value_name = "QUEUE")]
+    pub queue: Option<String>,
+
+    #[arg(long = "tag", value_name = "TAG", value_parser = helpers::parse_tag)]
+    pub tags: Vec<String>,
+
+    /// Due date: today, tomorrow, a weekday, +3d, or YYYY-MM-DD
+    #[arg(long, value_name = "WHEN", value_parser = helpers::parse_when)]
+    pub due: Option<NaiveDate>,
+
+    /// Scheduled start date, in the same forms as --due
+    #[arg(long, value_name = "WHEN", value_parser = helpers::parse_when)]
+    pub scheduled: Option<NaiveDate>,
+
+    /// Repeat rule: every 1w, every weekday, monthly on 1, ...
+    #[arg(long, value_name = "RULE", value_parser = helpers::parse_recur)]
+    pub recur: Option<RecurRule>,
+
+    /// Priority: p0 to p3, or high, medium, or low
+    #[arg(long, value_name = "PRIORITY", value_parser = helpers::parse_priority)]
+    pub priority: Option<Priority>,
 
     #[arg(long)]
     pub no_edit: bool,
@@ -33,6 +52,11 @@ pub fn handle_add(
     Add {
         title,
         queue,
+        tags,
+        due,
+        scheduled,
+        recur,
+        priority,
         no_edit,
         content,
         id,
@@ -41,6 +65,9 @@ pub fn handle_add(
 ) -> Result<(), AppError> {
     let resolved = helpers::resolve_config(root)?;
     let repo = helpers::repo_from_config(&resolved);
+    let queue = queue
+        .map(|queue| resolved.queue_dirs.parse(&queue))
+        .transpose()?;
     let title = match title {
         Some(title) => title,
         None => input::prompt_input("Title:")?,
@@ -60,6 +87,14 @@ pub fn handle_add(
     let now = Utc::now();
     let mut task = Task::new(task_id, title, now);
 
+    for tag in tags {
+        task.add_tag(tag);
+    }
+    task.due = due;
+    task.scheduled = scheduled;
+    task.recur = recur;
+    task.priority = priority;
+
     if let Some(ref body) = content {
         task.body = format!("# {}\n\n{}\n", task.title, body);
     }
@@ -107,7 +142,7 @@ pub fn handle_add(
 
 #[cfg(test)]
 mod tests {
-    use super::Add;
+    use super::{Add, Priority};
     use clap::Parser;
 
     #[test]
@@ -115,4 +150,39 @@ mod tests {
         let add = Add::parse_from(["add", "Ship v2"]);
         assert_eq!(add.title.as_deref(), Some("Ship v2"));
     }
+
+    #[test]
+    fn parses_repeated_tags() {
+        let add = Add::parse_from(["add", "--tag", "#ops", "--tag", "aws", "Ship v2"]);
+        assert_eq!(add.tags, vec!["ops".to_string(), "aws".to_string()]);
+    }
+
+    #[test]
+    fn parses_due_and_scheduled_dates() {
+        let add = Add::parse_from([
+            "add",
+            "--due",
+            "2026-11-01",
+            "--scheduled",
+            "2026-10-28",
+            "Ship v2",
+        ]);
+        assert_eq!(add.due, "2026-11-01".parse().ok());
+        assert_eq!(add.scheduled, "2026-10-28".parse().ok());
+        assert!(Add::try_parse_from(["add", "--due", "someday", "Ship v2"]).is_err());
+    }
+
+    #[test]
+    fn parses_recur_rule() {
+        let add = Add::parse_from(["add", "--recur", "every 1w", "Water plants"]);
+        assert_eq!(add.recur, "every 1w".parse().ok());
+        assert!(Add::try_parse_from(["add", "--recur", "sometimes", "Water plants"]).is_err());
+    }
+
+    #[test]
+    fn parses_priority() {
+        let add = Add::parse_from(["add", "--priority", "high", "Ship v2"]);
+        assert_eq!(add.priority, Some(Priority::P1));
+        assert!(Add::try_parse_from(["add", "--priority", "p9", "Ship v2"]).is_err());
+    }
 }
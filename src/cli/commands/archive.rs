@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use chrono::Utc;
+use clap::Parser;
+
+use crate::app::app_error::AppError;
+use crate::cli::commands::helpers;
+use crate::domain::date::Age;
+use crate::io::output;
+
+/// Used when neither `--older-than` nor `auto_archive_after` is given.
+const DEFAULT_ARCHIVE_AGE: Age = Age::days(90);
+
+#[derive(Debug, Parser)]
+#[command(about = "Move old done tasks into dated archive folders")]
+pub struct Archive {
+    /// Archive tasks completed longer ago than this, e.g. `90d`, `2w`, or `6m`;
+    /// defaults to `auto_archive_after` from config, or 90d
+    #[arg(long, value_name = "AGE", value_parser = helpers::parse_age_span)]
+    pub older_than: Option<Age>,
+}
+
+pub fn handle_archive(
+    Archive { older_than }: Archive,
+    root: Option<PathBuf>,
+) -> Result<(), AppError> {
+    let config = helpers::resolve_config(root)?;
+    let repo = helpers::repo_from_config(&config);
+    let age = older_than
+        .or(config.auto_archive_after)
+        .unwrap_or(DEFAULT_ARCHIVE_AGE);
+    let cutoff = age
+        .before(Utc::now())
+        .ok_or_else(|| AppError::usage(format!("invalid age '{age}'; it reaches too far back")))?;
+
+    let archived = repo.archive_done_before(cutoff)?;
+    if archived.is_empty() {
+        output::print_info(&format!("No done tasks older than {age}"));
+        return Ok(());
+    }
+    for stored in &archived {
+        output::print_info(&format!(
+            "Archived task: {} ({})",
+            stored.task.id,
+            stored.path.display()
+        ));
+    }
+    output::print_info(&format!(
+        "Archived {} done tasks older than {age}",
+        archived.len()
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Archive;
+    use clap::Parser;
+
+    #[test]
+    fn parses_older_than() {
+        let archive = Archive::parse_from(["archive", "--older-than", "6m"]);
+        assert_eq!(
+            archive.older_than.map(|age| age.to_string()),
+            Some("6m".to_string())
+        );
+        assert!(Archive::parse_from(["archive"]).older_than.is_none());
+        assert!(Archive::try_parse_from(["archive", "--older-than", "soon"]).is_err());
+    }
+}
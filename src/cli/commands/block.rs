@@ -0,0 +1,87 @@
+use std::path::PathBuf;
+
+use chrono::Utc;
+use clap::Parser;
+
+use crate::app::app_error::AppError;
+use crate::cli::commands::helpers;
+use crate::domain::deps::would_create_cycle;
+use crate::io::output;
+
+#[derive(Debug, Parser)]
+#[command(about = "Mark a task as blocked by other tasks")]
+pub struct Block {
+    pub task: String,
+
+    /// Task that must be done first (repeat for several)
+    #[arg(long = "on", value_name = "TASK", required = true)]
+    pub on: Vec<String>,
+}
+
+pub fn handle_block(Block { task, on }: Block, root: Option<PathBuf>) -> Result<(), AppError> {
+    let repo = helpers::resolve_repo(root)?;
+    let Some(stored) = helpers::resolve_task_ref(Some(task), &repo, "Select task to block")? else {
+        return Ok(());
+    };
+
+    let mut task = stored.task;
+    let mut added = Vec::new();
+    for query in on {
+        let Some(blocker) = helpers::resolve_task_ref(Some(query), &repo, "Select blocking task")?
+        else {
+            return Ok(());
+        };
+        let blocker_id = blocker.task.id;
+        if blocker_id == task.id {
+            return Err(AppError::usage(format!(
+                "task {} cannot block itself",
+                task.id
+            )));
+        }
+        if would_create_cycle(&repo.list()?, &task.id, &blocker_id) {
+            return Err(AppError::usage(format!(
+                "blocking {} on {blocker_id} would create a dependency cycle",
+                task.id
+            )));
+        }
+        if task.add_blocker(blocker_id.clone()) {
+            added.push(blocker_id);
+        }
+    }
+
+    if added.is_empty() {
+        output::print_info(&format!(
+            "Task {} is already blocked by {}",
+            task.id,
+            task.blocked_by.join(", ")
+        ));
+        return Ok(());
+    }
+
+    task.updated_at = Utc::now();
+    repo.update(&task)?;
+    output::print_info(&format!(
+        "Task {} is now blocked by {}",
+        task.id,
+        task.blocked_by.join(", ")
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Block;
+    use clap::Parser;
+
+    #[test]
+    fn parses_block_command() {
+        let block = Block::parse_from(["block", "0f3", "--on", "a1", "--on", "b2"]);
+        assert_eq!(block.task, "0f3");
+        assert_eq!(block.on, vec!["a1", "b2"]);
+    }
+
+    #[test]
+    fn block_requires_a_blocking_task() {
+        assert!(Block::try_parse_from(["block", "0f3"]).is_err());
+    }
+}
@@ -3,16 +3,26 @@ use std::path::PathBuf;
 use clap::Parser;
 
 use crate::app::app_error::AppError;
-use crate::io::output;
+use crate::io::{
+    json,
+    output::{self, OutputFormat},
+};
 use crate::storage::config;
 
 #[derive(Debug, Parser)]
 #[command(about = "Show effective configuration and setup help")]
 pub struct Config;
 
-pub fn handle_config(_: Config, root: Option<PathBuf>) -> Result<(), AppError> {
+pub fn handle_config(
+    _: Config,
+    root: Option<PathBuf>,
+    format: OutputFormat,
+) -> Result<(), AppError> {
     let inspection = config::inspect(root)?;
-    output::print_config_inspection(&inspection);
+    match format {
+        OutputFormat::Text => output::print_config_inspection(&inspection),
+        format => json::print_config(format, &inspection)?,
+    }
     Ok(())
 }
 
@@ -3,31 +3,61 @@ use std::path::PathBuf;
 use clap::Parser;
 
 use crate::app::app_error::AppError;
+use crate::app::bulk::BulkAction;
 use crate::cli::commands::helpers;
 use crate::io::{input, output};
 
 #[derive(Debug, Parser)]
-#[command(about = "Delete a task permanently")]
+#[command(about = "Move tasks to the trash")]
 pub struct Delete {
-    pub task: Option<String>,
+    /// Tasks to delete; pick them interactively when none are given
+    #[arg(value_name = "TASK")]
+    pub tasks: Vec<String>,
 
-    /// Prompt for confirmation before deleting
+    /// Prompt for confirmation before deleting a single task
     #[arg(long, short = 'i')]
     pub interactive: bool,
+
+    /// Also delete every task matching a query, e.g. `--where "queue:later updated<-90d"`
+    #[arg(long = "where", value_name = "QUERY", allow_hyphen_values = true)]
+    pub filter: Option<String>,
+
+    /// Change several tasks without asking for confirmation
+    #[arg(long, short = 'y')]
+    pub yes: bool,
 }
 
 pub fn handle_delete(
-    Delete { task, interactive }: Delete,
+    Delete {
+        tasks,
+        interactive,
+        filter,
+        yes,
+    }: Delete,
     root: Option<PathBuf>,
 ) -> Result<(), AppError> {
-    let repo = helpers::resolve_repo(root)?;
-    let Some(stored) = helpers::resolve_task_ref(task, &repo, "Select task to delete")? else {
+    let config = helpers::resolve_config(root)?;
+    let filter = filter
+        .map(|filter| helpers::parse_query(&filter, &config))
+        .transpose()?;
+    let repo = helpers::repo_from_config(&config);
+    let Some(mut selected) =
+        helpers::resolve_task_refs(tasks, filter.as_ref(), &repo, "Select tasks to delete")?
+    else {
+        return Ok(());
+    };
+    if selected.len() > 1 {
+        helpers::run_bulk(&repo, &config, BulkAction::Delete, &selected, yes)?;
+        return Ok(());
+    }
+    let Some(stored) = selected.pop() else {
+        output::print_info("No matching tasks");
         return Ok(());
     };
 
     if interactive {
         let confirmed =
-            input::prompt_confirm(&format!("Permanently delete '{}'?", stored.task.title))?;
+            input::prompt_confirm(&format!("Move '{}' to the trash?", stored.task.title))?;
         if !confirmed {
             output::print_info("Delete cancelled");
             return Ok(());
@@ -36,6 +66,8 @@ pub fn handle_delete(
 
     let id = stored.task.id.clone();
     repo.delete(&id)?;
-    output::print_info(&format!("Deleted task: {id}"));
+    output::print_info(&format!(
+        "Deleted task: {id} (restore with `tqs restore {id}`)"
+    ));
     Ok(())
 }
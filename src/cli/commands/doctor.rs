@@ -4,7 +4,10 @@ use clap::Parser;
 
 use crate::app::app_error::AppError;
 use crate::cli::commands::helpers;
-use crate::io::output;
+use crate::io::{
+    json,
+    output::{self, OutputFormat},
+};
 use crate::storage::doctor;
 
 #[derive(Debug, Parser)]
@@ -15,10 +18,17 @@ pub struct Doctor {
     pub fix: bool,
 }
 
-pub fn handle_doctor(command: Doctor, root: Option<PathBuf>) -> Result<(), AppError> {
+pub fn handle_doctor(
+    command: Doctor,
+    root: Option<PathBuf>,
+    format: OutputFormat,
+) -> Result<(), AppError> {
     let resolved = helpers::resolve_config(root)?;
     let report = doctor::run(&resolved, command.fix)?;
-    output::print_doctor_report(&report);
+    match format {
+        OutputFormat::Text => output::print_doctor_report(&report),
+        format => json::print_doctor_report(format, &report)?,
+    }
 
     if report.has_errors() {
         return Err(AppError::message(format!(
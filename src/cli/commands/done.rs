@@ -5,24 +5,59 @@ use chrono::Utc;
 use clap::Parser;
 
 use crate::app::app_error::AppError;
-use crate::app::operations;
+use crate::app::{bulk::BulkAction, operations};
 use crate::cli::commands::helpers;
-use crate::domain::task::Queue;
+use crate::domain::task::{Queue, Task};
 use crate::io::output;
 
 #[derive(Debug, Parser)]
-#[command(about = "Mark a task as done")]
+#[command(about = "Mark tasks as done")]
 pub struct Done {
-    pub task: Option<String>,
+    /// Tasks to complete; pick them interactively when none are given
+    #[arg(value_name = "TASK")]
+    pub tasks: Vec<String>,
 
+    /// Skip opening the completed task in the editor (always skipped for several tasks)
     #[arg(long)]
     pub no_edit: bool,
+
+    /// Also complete every task matching a query, e.g. `--where "tag:sprint-12"`
+    #[arg(long = "where", value_name = "QUERY", allow_hyphen_values = true)]
+    pub filter: Option<String>,
+
+    /// Change several tasks without asking for confirmation
+    #[arg(long, short = 'y')]
+    pub yes: bool,
 }
 
-pub fn handle_done(Done { task, no_edit }: Done, root: Option<PathBuf>) -> Result<(), AppError> {
+pub fn handle_done(
+    Done {
+        tasks,
+        no_edit,
+        filter,
+        yes,
+    }: Done,
+    root: Option<PathBuf>,
+) -> Result<(), AppError> {
     let resolved = helpers::resolve_config(root)?;
+    let filter = filter
+        .map(|filter| helpers::parse_query(&filter, &resolved))
+        .transpose()?;
     let repo = helpers::repo_from_config(&resolved);
-    let Some(stored) = helpers::resolve_task_ref(task, &repo, "Select task to complete")? else {
+    let Some(mut selected) =
+        helpers::resolve_task_refs(tasks, filter.as_ref(), &repo, "Select tasks to complete")?
+    else {
+        return Ok(());
+    };
+    if selected.len() > 1 {
+        if helpers::run_bulk(&repo, &resolved, BulkAction::Done, &selected, yes)? {
+            let archived = operations::auto_archive(&repo, &resolved)?;
+            output::print_auto_archived(&archived, resolved.auto_archive_after);
+        }
+        return Ok(());
+    }
+    let Some(stored) = selected.pop() else {
+        output::print_info("No matching tasks");
         return Ok(());
     };
 
@@ -31,7 +66,13 @@ pub fn handle_done(Done { task, no_edit }: Done, root: Option<PathBuf>) -> Resul
         return Ok(());
     }
 
-    let (task, path) = operations::mark_done(&repo, &resolved, &stored.task.id)?;
+    let operations::DoneOutcome {
+        task,
+        path,
+        next,
+        unblocked,
+        archived,
+    } = operations::mark_done(&repo, &resolved, &stored.task.id)?;
 
     if !no_edit {
         let original_content = fs::read_to_string(&path)?;
@@ -58,17 +99,27 @@ pub fn handle_done(Done { task, no_edit }: Done, root: Option<PathBuf>) -> Resul
                         task.id,
                         path.display()
                     ));
+                    print_follow_ups(next.as_ref(), &unblocked);
                 }
                 Err(error) => {
                     fs::write(&path, original_content)?;
                     return Err(error);
                 }
             }
+            output::print_auto_archived(&archived, resolved.auto_archive_after);
             return Ok(());
         }
     }
 
     output::print_info(&format!("Completed task: {} ({})", task.id, path.display()));
-
+    print_follow_ups(next.as_ref(), &unblocked);
+    output::print_auto_archived(&archived, resolved.auto_archive_after);
     Ok(())
 }
+
+fn print_follow_ups(next: Option<&(Task, PathBuf)>, unblocked: &[Task]) {
+    if let Some((next, next_path)) = next {
+        output::print_next_occurrence(next, next_path);
+    }
+    output::print_unblocked(unblocked);
+}
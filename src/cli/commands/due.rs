@@ -0,0 +1,105 @@
+use std::path::PathBuf;
+
+use chrono::{Local, NaiveDate, Utc};
+use clap::Parser;
+
+use crate::app::app_error::AppError;
+use crate::cli::commands::helpers;
+use crate::domain::date::describe_due;
+use crate::io::output;
+
+#[derive(Debug, Parser)]
+#[command(about = "Set or clear a task due date")]
+pub struct Due {
+    pub task: String,
+
+    /// today, tomorrow, a weekday, +3d, YYYY-MM-DD, or `none` to clear
+    #[arg(value_name = "WHEN", value_parser = parse_due_value)]
+    pub when: DueValue,
+
+    /// Set the scheduled date instead of the due date
+    #[arg(long)]
+    pub scheduled: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DueValue {
+    Set(NaiveDate),
+    Clear,
+}
+
+fn parse_due_value(value: &str) -> Result<DueValue, String> {
+    if value.trim().eq_ignore_ascii_case("none") {
+        return Ok(DueValue::Clear);
+    }
+    helpers::parse_when(value).map(DueValue::Set)
+}
+
+pub fn handle_due(
+    Due {
+        task,
+        when,
+        scheduled,
+    }: Due,
+    root: Option<PathBuf>,
+) -> Result<(), AppError> {
+    let repo = helpers::resolve_repo(root)?;
+    let Some(stored) = helpers::resolve_task_ref(Some(task), &repo, "Select task")? else {
+        return Ok(());
+    };
+
+    let mut task = stored.task;
+    let date = match when {
+        DueValue::Set(date) => Some(date),
+        DueValue::Clear => None,
+    };
+    let (field, label) = if scheduled {
+        (&mut task.scheduled, "scheduled date")
+    } else {
+        (&mut task.due, "due date")
+    };
+    if *field == date {
+        output::print_info(&format!("Task {} {label} is unchanged", task.id));
+        return Ok(());
+    }
+    *field = date;
+
+    task.updated_at = Utc::now();
+    repo.update(&task)?;
+
+    let message = match (date, scheduled) {
+        (None, _) => format!("Cleared {label} for task {}", task.id),
+        (Some(date), true) => format!("Scheduled task {} for {date}", task.id),
+        (Some(date), false) => format!(
+            "Task {} is {} ({date})",
+            task.id,
+            describe_due(date, Local::now().date_naive())
+        ),
+    };
+    output::print_info(&message);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Due, DueValue};
+    use clap::Parser;
+
+    #[test]
+    fn parses_due_command() {
+        let due = Due::parse_from(["due", "0f3", "2026-11-01"]);
+        assert_eq!(due.task, "0f3");
+        assert_eq!(
+            due.when,
+            DueValue::Set("2026-11-01".parse().expect("date should parse"))
+        );
+        assert!(!due.scheduled);
+    }
+
+    #[test]
+    fn none_clears_the_date() {
+        let due = Due::parse_from(["due", "0f3", "none", "--scheduled"]);
+        assert_eq!(due.when, DueValue::Clear);
+        assert!(due.scheduled);
+    }
+}
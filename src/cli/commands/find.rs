@@ -4,23 +4,67 @@ use clap::Parser;
 
 use crate::app::app_error::AppError;
 use crate::cli::commands::helpers;
-use crate::domain::filter::matches_query;
-use crate::io::output;
+use crate::domain::deps::BlockedIndex;
+use crate::domain::filter::has_all_tags;
+use crate::io::{
+    json::{self, TaskRecord},
+    output::{self, OutputFormat},
+};
 
 #[derive(Debug, Parser)]
-#[command(about = "Find tasks by text")]
+#[command(about = "Find tasks by text or query")]
 pub struct Find {
-    pub query: String,
+    /// Query such as `billing`, `tag:ops due<7d`, or `"exact phrase" -queue:done`
+    #[arg(
+        value_name = "QUERY",
+        required_unless_present = "tags",
+        allow_hyphen_values = true
+    )]
+    pub query: Option<String>,
+
+    /// Only match tasks with this tag (repeat to require several)
+    #[arg(long = "tag", value_name = "TAG", value_parser = helpers::parse_tag)]
+    pub tags: Vec<String>,
+
+    /// Also search done tasks that were archived
+    #[arg(long)]
+    pub include_archive: bool,
 }
 
-pub fn handle_find(Find { query }: Find, root: Option<PathBuf>) -> Result<(), AppError> {
-    let repo = helpers::resolve_repo(root)?;
-    let matches = repo
-        .scan_all()?
+pub fn handle_find(
+    Find {
+        query,
+        tags,
+        include_archive,
+    }: Find,
+    root: Option<PathBuf>,
+    format: OutputFormat,
+) -> Result<(), AppError> {
+    let config = helpers::resolve_config(root)?;
+    let query = query
+        .map(|query| helpers::parse_query(&query, &config))
+        .transpose()?
+        .unwrap_or_default();
+    let repo = helpers::repo_from_config(&config);
+    let all_tasks = if include_archive {
+        repo.scan_with_archive()?
+    } else {
+        repo.scan_all()?
+    };
+    let blocked = BlockedIndex::from_tasks(all_tasks.iter().map(|stored| &stored.task));
+    let matches = all_tasks
         .into_iter()
-        .filter(|stored| matches_query(&stored.task, &query))
+        .filter(|stored| query.matches(&stored.task) && has_all_tags(&stored.task, &tags))
         .collect::<Vec<_>>();
 
+    if format != OutputFormat::Text {
+        let records = matches
+            .iter()
+            .map(|stored| TaskRecord::new(&stored.task, &stored.path, &blocked))
+            .collect::<Vec<_>>();
+        return json::print_tasks(format, &records);
+    }
+
     output::print_search_results(&matches);
     Ok(())
 }
@@ -1,8 +1,25 @@
 use std::{path::PathBuf, str::FromStr};
 
-use crate::app::app_error::AppError;
-use crate::domain::{filter::title_matches_query, task::Queue};
-use crate::io::{input, output, picker};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+
+use crate::app::{
+    app_error::AppError,
+    bulk::{self, BulkAction},
+};
+use crate::domain::{
+    date::{Age, parse_date},
+    filter::title_matches_query,
+    priority::Priority,
+    query::Query,
+    recur::RecurRule,
+    task::{Queue, normalize_tag},
+    view::ViewSort,
+};
+use crate::io::{
+    input,
+    output::{self, OutputFormat, template::Template},
+    picker,
+};
 use crate::storage::{
     config, config::ResolvedConfig, editor::ResolvedEditor, repo::StoredTask, repo::TaskRepo,
 };
@@ -21,23 +38,100 @@ pub fn resolve_config(root: Option<PathBuf>) -> Result<ResolvedConfig, AppError>
 }
 
 pub fn repo_from_config(resolved: &ResolvedConfig) -> TaskRepo {
-    TaskRepo::new(resolved.tasks_root.clone(), resolved.queue_dirs.clone())
+    let repo = TaskRepo::new(resolved.tasks_root.clone(), resolved.queue_dirs.clone())
+        .with_state_dir(&resolved.state_dir);
+    match &resolved.git {
+        Some(git) => repo.with_git(git),
+        None => repo,
+    }
 }
 
-pub fn parse_queue(value: &str) -> Result<Queue, String> {
-    Queue::from_str(value).map_err(|_| {
-        format!(
-            "invalid queue '{}'; expected one of: {}",
-            value,
-            Queue::all()
-                .iter()
-                .map(ToString::to_string)
-                .collect::<Vec<_>>()
-                .join(", ")
-        )
+pub fn parse_tag(value: &str) -> Result<String, String> {
+    normalize_tag(value).map_err(|_| {
+        format!("invalid tag '{value}'; tags cannot be empty or contain spaces or commas")
     })
 }
 
+/// Parses a date such as `tomorrow`, `fri`, `+3d`, or `2026-11-01` relative to
+/// the local date.
+pub fn parse_when(value: &str) -> Result<NaiveDate, String> {
+    parse_date(value, Local::now().date_naive()).map_err(|error| error.to_string())
+}
+
+/// Parses an age such as `30d`, `2w`, or `6m` into the moment that long ago.
+pub fn parse_age(value: &str) -> Result<DateTime<Utc>, String> {
+    parse_age_span(value)?
+        .before(Utc::now())
+        .ok_or_else(|| format!("invalid age '{value}'; it reaches too far back"))
+}
+
+/// Parses an age such as `90d` without resolving it against the clock.
+pub fn parse_age_span(value: &str) -> Result<Age, String> {
+    Age::from_str(value).map_err(|error| error.to_string())
+}
+
+/// Parses a `find` or `--where` query; `queue:` terms must name a queue in
+/// `config`.
+pub fn parse_query(value: &str, config: &ResolvedConfig) -> Result<Query, AppError> {
+    Query::parse(
+        value,
+        Local::now().date_naive(),
+        &config.queue_dirs.queues(),
+    )
+    .map_err(|error| AppError::usage(error.to_string()))
+}
+
+/// Resolves `--template` for a command printing in `format`; templates only
+/// apply to text output.
+pub fn resolve_template_for(
+    value: &str,
+    config: &ResolvedConfig,
+    format: OutputFormat,
+) -> Result<Template, AppError> {
+    if format != OutputFormat::Text {
+        return Err(AppError::usage(
+            "--template cannot be combined with --format json or jsonl",
+        ));
+    }
+    resolve_template(value, config)
+}
+
+/// Resolves a `--template` value. Values containing `{` are inline templates;
+/// anything else names an entry in the config `[templates]` table.
+pub fn resolve_template(value: &str, config: &ResolvedConfig) -> Result<Template, AppError> {
+    if value.contains('{') {
+        return Template::from_str(value).map_err(|error| AppError::usage(error.to_string()));
+    }
+
+    let source = config.templates.get(value).ok_or_else(|| {
+        let names = config.templates.keys().cloned().collect::<Vec<_>>();
+        if names.is_empty() {
+            AppError::usage(format!(
+                "unknown template '{value}'; no templates are configured, add it to the [templates] table in config.toml"
+            ))
+        } else {
+            AppError::usage(format!(
+                "unknown template '{value}'; expected one of: {}",
+                names.join(", ")
+            ))
+        }
+    })?;
+    Template::from_str(source)
+        .map_err(|error| AppError::message(format!("invalid config: template '{value}': {error}")))
+}
+
+pub fn parse_recur(value: &str) -> Result<RecurRule, String> {
+    RecurRule::from_str(value).map_err(|error| error.to_string())
+}
+
+pub fn parse_priority(value: &str) -> Result<Priority, String> {
+    Priority::from_str(value).map_err(|error| error.to_string())
+}
+
+pub fn parse_sort(value: &str) -> Result<ViewSort, String> {
+    ViewSort::from_str(value).map_err(|error| error.to_string())
+}
+
 pub fn resolve_task_ref(
     query: Option<String>,
     repo: &TaskRepo,
@@ -49,27 +143,123 @@ pub fn resolve_task_ref(
         return Ok(None);
     }
 
+    resolve_task_ref_in(query, tasks, &repo.queue_dirs().queues(), prompt)
+}
+
+/// Resolves a task ref against an explicit list, such as the trash, with the
+/// same rules as [`resolve_task_ref`]. `queues` are the queues the picker
+/// can filter by.
+pub fn resolve_task_ref_in(
+    query: Option<String>,
+    tasks: Vec<StoredTask>,
+    queues: &[Queue],
+    prompt: &str,
+) -> Result<Option<StoredTask>, AppError> {
     match query {
-        Some(query) => resolve_query_against_tasks(query, tasks, prompt),
-        None => pick_from(tasks, prompt, None),
+        Some(query) => resolve_query_against_tasks(query, tasks, queues, prompt),
+        None => pick_from(tasks, queues, prompt, None),
+    }
+}
+
+/// Resolves every task ref plus the tasks matching `filter`, in that order and
+/// without duplicates. Nothing is changed until all refs resolve. With no refs
+/// and no filter, a multi-select picker chooses the tasks. Returns `None` when
+/// the user cancels or there are no tasks at all.
+pub fn resolve_task_refs(
+    refs: Vec<String>,
+    filter: Option<&Query>,
+    repo: &TaskRepo,
+    prompt: &str,
+) -> Result<Option<Vec<StoredTask>>, AppError> {
+    let tasks = repo.scan_all()?;
+    if tasks.is_empty() {
+        output::print_info("No tasks available");
+        return Ok(None);
+    }
+
+    let queues = repo.queue_dirs().queues();
+    if refs.is_empty() && filter.is_none() {
+        return pick_many_from(tasks, &queues, prompt);
     }
+
+    let mut selected: Vec<StoredTask> = Vec::new();
+    for query in refs {
+        let Some(stored) = resolve_query_against_tasks(query, tasks.clone(), &queues, prompt)?
+        else {
+            return Ok(None);
+        };
+        if !selected.iter().any(|task| task.task.id == stored.task.id) {
+            selected.push(stored);
+        }
+    }
+    if let Some(filter) = filter {
+        for stored in tasks {
+            if filter.matches(&stored.task)
+                && !selected.iter().any(|task| task.task.id == stored.task.id)
+            {
+                selected.push(stored);
+            }
+        }
+    }
+    Ok(Some(selected))
+}
+
+/// Previews a bulk change and asks before applying it, unless `yes` was given.
+/// Without a terminal to ask on, `--yes` is required.
+pub fn confirm_bulk(action: BulkAction, tasks: &[StoredTask], yes: bool) -> Result<bool, AppError> {
+    output::print_bulk_preview(action, tasks);
+    if yes {
+        return Ok(true);
+    }
+    if !input::supports_interaction() {
+        return Err(AppError::usage(format!(
+            "refusing to {action} {} tasks without confirmation; pass --yes to apply",
+            tasks.len()
+        )));
+    }
+
+    let confirmed = input::prompt_confirm(&format!("Apply to {} tasks?", tasks.len()))?;
+    if !confirmed {
+        output::print_info("Operation cancelled");
+    }
+    Ok(confirmed)
 }
 
+/// Confirms and applies a bulk action, then reports each task. Returns
+/// whether it was applied, `false` meaning the user cancelled.
+pub fn run_bulk(
+    repo: &TaskRepo,
+    config: &ResolvedConfig,
+    action: BulkAction,
+    tasks: &[StoredTask],
+    yes: bool,
+) -> Result<bool, AppError> {
+    if !confirm_bulk(action, tasks, yes)? {
+        return Ok(false);
+    }
+    let results = bulk::apply(repo, config, tasks, action)?;
+    output::print_bulk_results(action, &results);
+    Ok(true)
+}
+
+/// Resolves the target queue, prompting with every one of `queues` except
+/// `current`.
 pub fn resolve_target_queue(
-    current: Queue,
+    current: Option<Queue>,
     queue: Option<Queue>,
+    queues: &[Queue],
 ) -> Result<Option<Queue>, AppError> {
     match queue {
         Some(queue) => Ok(Some(queue)),
-        None => pick_queue(current),
+        None => pick_queue(current, queues),
     }
 }
 
-fn pick_queue(current: Queue) -> Result<Option<Queue>, AppError> {
-    let options = Queue::all()
+fn pick_queue(current: Option<Queue>, queues: &[Queue]) -> Result<Option<Queue>, AppError> {
+    let options = queues
         .iter()
         .copied()
-        .filter(|queue| *queue != current)
+        .filter(|queue| Some(*queue) != current)
         .collect::<Vec<_>>();
     let labels = options.iter().map(ToString::to_string).collect::<Vec<_>>();
 
@@ -85,6 +275,7 @@ fn pick_queue(current: Queue) -> Result<Option<Queue>, AppError> {
 fn resolve_query_against_tasks(
     query: String,
     tasks: Vec<StoredTask>,
+    queues: &[Queue],
     prompt: &str,
 ) -> Result<Option<StoredTask>, AppError> {
     if let Some(task) = unique_match(tasks.iter().filter(|stored| stored.task.id == query)) {
@@ -119,15 +310,16 @@ fn resolve_query_against_tasks(
         return Err(AppError::not_found(query));
     }
 
-    pick_from(ambiguous, prompt, Some(&query))
+    pick_from(ambiguous, queues, prompt, Some(&query))
 }
 
 fn pick_from(
     tasks: Vec<StoredTask>,
+    queues: &[Queue],
     prompt: &str,
     ambiguous_query: Option<&str>,
 ) -> Result<Option<StoredTask>, AppError> {
-    let selection = picker::pick_task(&tasks, picker::TaskPickerOptions { prompt });
+    let selection = picker::pick_task(&tasks, picker::TaskPickerOptions { prompt, queues });
     let selection = match (selection, ambiguous_query) {
         (Err(AppError::NoTty), Some(query)) => return Err(AppError::ambiguous_task_ref(query)),
         (result, _) => result?,
@@ -145,6 +337,25 @@ fn pick_from(
     }
 }
 
+fn pick_many_from(
+    tasks: Vec<StoredTask>,
+    queues: &[Queue],
+    prompt: &str,
+) -> Result<Option<Vec<StoredTask>>, AppError> {
+    match picker::pick_tasks(&tasks, picker::TaskPickerOptions { prompt, queues })? {
+        Some(indexes) => Ok(Some(
+            indexes
+                .into_iter()
+                .filter_map(|index| tasks.get(index).cloned())
+                .collect(),
+        )),
+        None => {
+            output::print_info("Operation cancelled");
+            Ok(None)
+        }
+    }
+}
+
 fn unique_match<'a>(mut matches: impl Iterator<Item = &'a StoredTask>) -> Option<&'a StoredTask> {
     let first = matches.next()?;
     if matches.next().is_some() {
@@ -156,8 +367,9 @@ fn unique_match<'a>(mut matches: impl Iterator<Item = &'a StoredTask>) -> Option
 
 #[cfg(test)]
 mod tests {
-    use super::{resolve_target_queue, resolve_task_ref};
+    use super::{resolve_target_queue, resolve_task_ref, resolve_task_refs};
     use crate::app::app_error::AppError;
+    use crate::domain::query::Query;
     use crate::domain::task::{Queue, Task};
     use crate::storage::{config::QueueDirs, repo::TaskRepo};
     use chrono::Utc;
@@ -240,9 +452,58 @@ mod tests {
         assert!(matches!(err, AppError::NoTty));
     }
 
+    #[test]
+    fn resolve_task_refs_combines_refs_and_query_without_duplicates() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let repo = TaskRepo::new(temp.path().to_path_buf(), QueueDirs::default());
+        repo.create(&task("task-1", "Ship v2", Queue::Inbox))
+            .expect("task should be created");
+        repo.create(&task("task-2", "Old idea", Queue::Later))
+            .expect("task should be created");
+        repo.create(&task("task-3", "Older idea", Queue::Later))
+            .expect("task should be created");
+        let today = "2026-03-09".parse().expect("date should parse");
+        let filter = Query::parse("queue:later", today, Queue::all()).expect("query should parse");
+
+        let resolved = resolve_task_refs(
+            vec!["task-3".to_string(), "ship".to_string()],
+            Some(&filter),
+            &repo,
+            "Select tasks",
+        )
+        .expect("refs should resolve")
+        .expect("tasks should be found");
+
+        let ids = resolved
+            .iter()
+            .map(|stored| stored.task.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids.len(), 3);
+        assert_eq!(&ids[..2], ["task-3", "task-1"]);
+        assert!(ids.contains(&"task-2"));
+    }
+
+    #[test]
+    fn resolve_task_refs_fails_before_changes_when_any_ref_is_unknown() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let repo = TaskRepo::new(temp.path().to_path_buf(), QueueDirs::default());
+        repo.create(&task("task-1", "Ship v2", Queue::Inbox))
+            .expect("task should be created");
+
+        let err = resolve_task_refs(
+            vec!["task-1".to_string(), "missing".to_string()],
+            None,
+            &repo,
+            "Select tasks",
+        )
+        .expect_err("unknown ref should fail");
+
+        assert!(matches!(err, AppError::NotFound { .. }));
+    }
+
     #[test]
     fn resolve_target_queue_returns_supplied_queue() {
-        let resolved = resolve_target_queue(Queue::Inbox, Some(Queue::Now))
+        let resolved = resolve_target_queue(Some(Queue::Inbox), Some(Queue::Now), Queue::all())
             .expect("explicit queue should resolve");
 
         assert_eq!(resolved, Some(Queue::Now));
@@ -250,7 +511,7 @@ mod tests {
 
     #[test]
     fn resolve_target_queue_returns_no_tty_without_interaction() {
-        let err = resolve_target_queue(Queue::Inbox, None)
+        let err = resolve_target_queue(Some(Queue::Inbox), None, Queue::all())
             .expect_err("missing queue without tty should fail");
 
         assert!(matches!(err, AppError::NoTty));
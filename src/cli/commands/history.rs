@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use crate::app::app_error::AppError;
+use crate::cli::commands::helpers;
+use crate::io::output;
+
+#[derive(Debug, Parser)]
+#[command(about = "Show recent changes that can be undone")]
+pub struct History {
+    /// Number of changes to show, most recent first
+    #[arg(long, short = 'n', default_value_t = 20)]
+    pub limit: usize,
+}
+
+pub fn handle_history(History { limit }: History, root: Option<PathBuf>) -> Result<(), AppError> {
+    let repo = helpers::resolve_repo(root)?;
+    let batches = repo.journal().batches()?;
+    output::print_history(&batches, limit);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::History;
+    use clap::Parser;
+
+    #[test]
+    fn parses_history_limit() {
+        assert_eq!(History::parse_from(["history"]).limit, 20);
+        assert_eq!(History::parse_from(["history", "-n", "5"]).limit, 5);
+    }
+}
@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use clap::Parser;
 
 use crate::app::app_error::AppError;
+use crate::io::output::OutputFormat;
 
 use super::list;
 
@@ -10,8 +11,8 @@ use super::list;
 #[command(about = "List tasks in the inbox queue")]
 pub struct Inbox;
 
-pub fn handle_inbox(_: Inbox, root: Option<PathBuf>) -> Result<(), AppError> {
-    list::print_queue(list::QueueSelection::Inbox, root)
+pub fn handle_inbox(_: Inbox, root: Option<PathBuf>, format: OutputFormat) -> Result<(), AppError> {
+    list::print_queue(list::QueueSelection::Inbox, root, format)
 }
 
 #[cfg(test)]
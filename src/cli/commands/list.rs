@@ -1,17 +1,41 @@
 use std::path::PathBuf;
 
+use chrono::Local;
 use clap::Parser;
 
 use crate::app::app_error::AppError;
 use crate::cli::commands::helpers;
-use crate::domain::task::Queue;
-use crate::io::output;
+use crate::domain::{
+    deps::BlockedIndex, filter::has_all_tags, query::Query, task::Queue, view::ViewSort,
+};
+use crate::io::{
+    json::{self, TaskRecord},
+    output::{self, OutputFormat, template::Template},
+};
+use crate::storage::repo::TaskRepo;
 
 #[derive(Debug, Parser)]
 #[command(about = "List tasks")]
 pub struct List {
-    #[arg(value_parser = helpers::parse_queue)]
-    pub queue: Option<Queue>,
+    #[arg(value_name = "QUEUE")]
+    pub queue: Option<String>,
+
+    /// Only show tasks with this tag (repeat to require several)
+    #[arg(long = "tag", value_name = "TAG", value_parser = helpers::parse_tag)]
+    pub tags: Vec<String>,
+
+    /// Only show tasks matching a query, e.g. `--where "tag:ops due<7d"`
+    #[arg(long = "where", value_name = "QUERY", allow_hyphen_values = true)]
+    pub filter: Option<String>,
+
+    /// Sort tasks by id, title, queue, due, scheduled, created, updated,
+    /// completed, or priority; prefix with `-` for descending order
+    #[arg(long, value_name = "SORT", value_parser = helpers::parse_sort)]
+    pub sort: Option<ViewSort>,
+
+    /// Print each task with a template such as `{id} [{queue}] {title}`, or a named config template
+    #[arg(long, value_name = "TEMPLATE")]
+    pub template: Option<String>,
 }
 
 pub enum QueueSelection {
@@ -19,34 +43,108 @@ pub enum QueueSelection {
     Now,
 }
 
-pub fn handle_list(List { queue }: List, root: Option<PathBuf>) -> Result<(), AppError> {
-    let repo = helpers::resolve_repo(root)?;
-
-    match queue {
-        Some(queue) => print_resolved_queue(queue, &repo)?,
-        None => {
-            let tasks = repo.list()?;
-            output::print_dashboard(&tasks);
-        }
-    }
-
-    Ok(())
+pub fn handle_list(
+    List {
+        queue,
+        tags,
+        filter,
+        sort,
+        template,
+    }: List,
+    root: Option<PathBuf>,
+    format: OutputFormat,
+) -> Result<(), AppError> {
+    let config = helpers::resolve_config(root)?;
+    let queue = queue
+        .map(|queue| config.queue_dirs.parse(&queue))
+        .transpose()?;
+    let filter = filter
+        .map(|filter| helpers::parse_query(&filter, &config))
+        .transpose()?;
+    let template = template
+        .map(|template| helpers::resolve_template_for(&template, &config, format))
+        .transpose()?;
+    let repo = helpers::repo_from_config(&config);
+    print_matching(
+        queue,
+        &tags,
+        &filter.unwrap_or_default(),
+        sort,
+        &repo,
+        format,
+        template.as_ref(),
+    )
 }
 
-pub fn print_queue(selection: QueueSelection, root: Option<PathBuf>) -> Result<(), AppError> {
+pub fn print_queue(
+    selection: QueueSelection,
+    root: Option<PathBuf>,
+    format: OutputFormat,
+) -> Result<(), AppError> {
     let repo = helpers::resolve_repo(root)?;
     let queue = match selection {
         QueueSelection::Inbox => Queue::Inbox,
         QueueSelection::Now => Queue::Now,
     };
-    print_resolved_queue(queue, &repo)
+    print_matching(
+        Some(queue),
+        &[],
+        &Query::default(),
+        None,
+        &repo,
+        format,
+        None,
+    )
 }
 
-fn print_resolved_queue(
-    queue: Queue,
-    repo: &crate::storage::repo::TaskRepo,
+/// Prints one queue, or the dashboard when no queue is given. JSON and
+/// template output list every matching task instead of the dashboard sections.
+/// Without `sort`, tasks keep queue order.
+fn print_matching(
+    queue: Option<Queue>,
+    tags: &[String],
+    filter: &Query,
+    sort: Option<ViewSort>,
+    repo: &TaskRepo,
+    format: OutputFormat,
+    template: Option<&Template>,
 ) -> Result<(), AppError> {
-    let tasks = repo.list_queue(queue)?;
-    output::print_queue_tasks(queue, &tasks);
+    let all_tasks = repo.scan_all()?;
+    let blocked = BlockedIndex::from_tasks(all_tasks.iter().map(|stored| &stored.task));
+    let mut matches = all_tasks
+        .into_iter()
+        .filter(|stored| {
+            queue.is_none_or(|queue| stored.task.queue == queue)
+                && has_all_tags(&stored.task, tags)
+                && filter.matches(&stored.task)
+        })
+        .collect::<Vec<_>>();
+    if let Some(sort) = sort {
+        matches.sort_by(|left, right| sort.compare(&left.task, &right.task));
+    }
+
+    if format != OutputFormat::Text {
+        let records = matches
+            .iter()
+            .map(|stored| TaskRecord::new(&stored.task, &stored.path, &blocked))
+            .collect::<Vec<_>>();
+        return json::print_tasks(format, &records);
+    }
+    if let Some(template) = template {
+        let now = Local::now();
+        for stored in &matches {
+            println!("{}", template.render(&stored.task, &stored.path, now));
+        }
+        return Ok(());
+    }
+
+    let tasks = matches
+        .into_iter()
+        .map(|stored| stored.task)
+        .collect::<Vec<_>>();
+    match queue {
+        Some(queue) => output::print_queue_tasks(queue, &tasks, &blocked),
+        None => output::print_dashboard(&tasks, &blocked, &repo.queue_dirs().active_queues()),
+    }
     Ok(())
 }
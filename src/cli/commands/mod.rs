@@ -1,30 +1,62 @@
 pub mod add;
+pub mod archive;
+pub mod block;
 pub mod config;
 pub mod delete;
 pub mod doctor;
 pub mod done;
+pub mod due;
 pub mod edit;
 pub mod find;
 pub mod helpers;
+pub mod history;
 pub mod inbox;
 pub mod list;
 pub mod move_cmd;
 pub mod now;
+pub mod prio;
+pub mod rank;
+pub mod redo;
+pub mod restore;
+pub mod review;
 pub mod show;
 pub mod start;
+pub mod stats;
+pub mod sync;
+pub mod tag;
+pub mod trash;
 pub mod triage;
+pub mod unblock;
+pub mod undo;
+pub mod view;
 
 pub use add::Add;
+pub use archive::Archive;
+pub use block::Block;
 pub use config::Config;
 pub use delete::Delete;
 pub use doctor::Doctor;
 pub use done::Done;
+pub use due::Due;
 pub use edit::Edit;
 pub use find::Find;
+pub use history::History;
 pub use inbox::Inbox;
 pub use list::List;
 pub use move_cmd::Move;
 pub use now::Now;
+pub use prio::Prio;
+pub use rank::Rank;
+pub use redo::Redo;
+pub use restore::Restore;
+pub use review::Review;
 pub use show::Show;
 pub use start::Start;
+pub use stats::Stats;
+pub use sync::Sync;
+pub use tag::Tag;
+pub use trash::Trash;
 pub use triage::Triage;
+pub use unblock::Unblock;
+pub use undo::Undo;
+pub use view::View;
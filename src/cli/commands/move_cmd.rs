@@ -4,26 +4,71 @@ use chrono::Utc;
 use clap::Parser;
 
 use crate::app::app_error::AppError;
+use crate::app::bulk::BulkAction;
 use crate::cli::commands::helpers;
 use crate::domain::task::Queue;
 use crate::io::output;
+use crate::storage::config::QueueDirs;
 
 #[derive(Debug, Parser)]
-#[command(about = "Move a task to a different queue")]
+#[command(about = "Move tasks to a different queue")]
 pub struct Move {
-    pub task: Option<String>,
+    /// Tasks to move, optionally followed by the target queue
+    #[arg(value_name = "TASK")]
+    pub args: Vec<String>,
 
-    #[arg(value_parser = helpers::parse_queue)]
-    pub queue: Option<Queue>,
+    /// Target queue, for when the last task could be read as a queue name
+    #[arg(long = "to", value_name = "QUEUE")]
+    pub queue: Option<String>,
+
+    /// Also move every task matching a query, e.g. `--where "queue:later updated<-30d"`
+    #[arg(long = "where", value_name = "QUERY", allow_hyphen_values = true)]
+    pub filter: Option<String>,
+
+    /// Change several tasks without asking for confirmation
+    #[arg(long, short = 'y')]
+    pub yes: bool,
 }
 
-pub fn handle_move(Move { task, queue }: Move, root: Option<PathBuf>) -> Result<(), AppError> {
-    let repo = helpers::resolve_repo(root)?;
-    let Some(stored) = helpers::resolve_task_ref(task, &repo, "Select task to move")? else {
+pub fn handle_move(
+    Move {
+        args,
+        queue,
+        filter,
+        yes,
+    }: Move,
+    root: Option<PathBuf>,
+) -> Result<(), AppError> {
+    let config = helpers::resolve_config(root)?;
+    let filter = filter
+        .map(|filter| helpers::parse_query(&filter, &config))
+        .transpose()?;
+    let repo = helpers::repo_from_config(&config);
+    let queue = queue
+        .map(|queue| config.queue_dirs.parse(&queue))
+        .transpose()?;
+    let (tasks, queue) = split_target(args, queue, filter.is_some(), &config.queue_dirs);
+    let Some(mut selected) =
+        helpers::resolve_task_refs(tasks, filter.as_ref(), &repo, "Select tasks to move")?
+    else {
+        return Ok(());
+    };
+    if selected.len() > 1 {
+        let Some(queue) = helpers::resolve_target_queue(None, queue, &repo.queue_dirs().queues())?
+        else {
+            return Ok(());
+        };
+        helpers::run_bulk(&repo, &config, BulkAction::Move(queue), &selected, yes)?;
+        return Ok(());
+    }
+    let Some(stored) = selected.pop() else {
+        output::print_info("No matching tasks");
         return Ok(());
     };
 
-    let Some(queue) = helpers::resolve_target_queue(stored.task.queue, queue)? else {
+    let Some(queue) =
+        helpers::resolve_target_queue(Some(stored.task.queue), queue, &repo.queue_dirs().queues())?
+    else {
         return Ok(());
     };
     if stored.task.queue == queue {
@@ -35,3 +80,89 @@ pub fn handle_move(Move { task, queue }: Move, root: Option<PathBuf>) -> Result<
     output::print_info(&format!("Moved task: {} ({})", task.id, path.display()));
     Ok(())
 }
+
+/// Splits positional arguments into task refs and the target queue. Without
+/// `--to`, a trailing queue name is the target as long as something else
+/// selects tasks, so `tqs move task-1 now` keeps working. Only the queues in
+/// `queue_dirs` count, so a task id that merely looks like a queue name
+/// stays a task.
+fn split_target(
+    mut args: Vec<String>,
+    queue: Option<Queue>,
+    has_filter: bool,
+    queue_dirs: &QueueDirs,
+) -> (Vec<String>, Option<Queue>) {
+    if queue.is_some() || (args.len() < 2 && !has_filter) {
+        return (args, queue);
+    }
+    match args.last().map(|last| queue_dirs.parse(last)) {
+        Some(Ok(queue)) => {
+            args.pop();
+            (args, Some(queue))
+        }
+        _ => (args, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_target;
+    use crate::domain::task::Queue;
+    use crate::storage::config::QueueDirs;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn split_target_reads_a_trailing_queue() {
+        assert_eq!(
+            split_target(
+                args(&["task-1", "task-2", "later"]),
+                None,
+                false,
+                &QueueDirs::default()
+            ),
+            (args(&["task-1", "task-2"]), Some(Queue::Later))
+        );
+        assert_eq!(
+            split_target(args(&["done"]), None, true, &QueueDirs::default()),
+            (Vec::new(), Some(Queue::Done))
+        );
+    }
+
+    #[test]
+    fn split_target_keeps_lone_refs_and_explicit_queues() {
+        assert_eq!(
+            split_target(args(&["later"]), None, false, &QueueDirs::default()),
+            (args(&["later"]), None)
+        );
+        assert_eq!(
+            split_target(
+                args(&["task-1", "now"]),
+                Some(Queue::Next),
+                false,
+                &QueueDirs::default()
+            ),
+            (args(&["task-1", "now"]), Some(Queue::Next))
+        );
+        assert_eq!(
+            split_target(
+                args(&["task-1", "task-2"]),
+                None,
+                false,
+                &QueueDirs::default()
+            ),
+            (args(&["task-1", "task-2"]), None)
+        );
+        assert_eq!(
+            split_target(
+                args(&["task-1", "waiting"]),
+                None,
+                false,
+                &QueueDirs::default()
+            ),
+            (args(&["task-1", "waiting"]), None)
+        );
+    }
+}
@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use clap::Parser;
 
 use crate::app::app_error::AppError;
+use crate::io::output::OutputFormat;
 
 use super::list;
 
@@ -10,8 +11,8 @@ use super::list;
 #[command(about = "List tasks in the now queue")]
 pub struct Now;
 
-pub fn handle_now(_: Now, root: Option<PathBuf>) -> Result<(), AppError> {
-    list::print_queue(list::QueueSelection::Now, root)
+pub fn handle_now(_: Now, root: Option<PathBuf>, format: OutputFormat) -> Result<(), AppError> {
+    list::print_queue(list::QueueSelection::Now, root, format)
 }
 
 #[cfg(test)]
@@ -0,0 +1,81 @@
+use std::path::PathBuf;
+
+use chrono::Utc;
+use clap::Parser;
+
+use crate::app::app_error::AppError;
+use crate::cli::commands::helpers;
+use crate::domain::priority::Priority;
+use crate::io::output;
+
+#[derive(Debug, Parser)]
+#[command(about = "Set or clear a task priority")]
+pub struct Prio {
+    pub task: String,
+
+    /// p0 to p3, high, medium, low, or `none` to clear
+    #[arg(value_name = "PRIORITY", value_parser = parse_priority_value)]
+    pub priority: PriorityValue,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriorityValue {
+    Set(Priority),
+    Clear,
+}
+
+fn parse_priority_value(value: &str) -> Result<PriorityValue, String> {
+    if value.trim().eq_ignore_ascii_case("none") {
+        return Ok(PriorityValue::Clear);
+    }
+    helpers::parse_priority(value).map(PriorityValue::Set)
+}
+
+pub fn handle_prio(Prio { task, priority }: Prio, root: Option<PathBuf>) -> Result<(), AppError> {
+    let repo = helpers::resolve_repo(root)?;
+    let Some(stored) = helpers::resolve_task_ref(Some(task), &repo, "Select task")? else {
+        return Ok(());
+    };
+
+    let mut task = stored.task;
+    let priority = match priority {
+        PriorityValue::Set(priority) => Some(priority),
+        PriorityValue::Clear => None,
+    };
+    if task.priority == priority {
+        output::print_info(&format!("Task {} priority is unchanged", task.id));
+        return Ok(());
+    }
+    task.priority = priority;
+
+    task.updated_at = Utc::now();
+    repo.update(&task)?;
+
+    let message = match priority {
+        Some(priority) => format!("Task {} is now {priority}", task.id),
+        None => format!("Cleared priority for task {}", task.id),
+    };
+    output::print_info(&message);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Prio, PriorityValue};
+    use crate::domain::priority::Priority;
+    use clap::Parser;
+
+    #[test]
+    fn parses_prio_command() {
+        let prio = Prio::parse_from(["prio", "0f3", "high"]);
+        assert_eq!(prio.task, "0f3");
+        assert_eq!(prio.priority, PriorityValue::Set(Priority::P1));
+        assert!(Prio::try_parse_from(["prio", "0f3", "urgent"]).is_err());
+    }
+
+    #[test]
+    fn none_clears_the_priority() {
+        let prio = Prio::parse_from(["prio", "0f3", "none"]);
+        assert_eq!(prio.priority, PriorityValue::Clear);
+    }
+}
@@ -0,0 +1,94 @@
+use std::path::PathBuf;
+
+use clap::{ArgGroup, Parser};
+
+use crate::app::app_error::AppError;
+use crate::app::operations::rank_task;
+use crate::cli::commands::helpers;
+use crate::domain::rank::RankPosition;
+use crate::io::output;
+
+#[derive(Debug, Parser)]
+#[command(about = "Reorder a task within its queue")]
+#[command(group(ArgGroup::new("position").required(true).args(["top", "bottom", "before", "after"])))]
+pub struct Rank {
+    pub task: String,
+
+    /// Put the task first in its queue
+    #[arg(long)]
+    pub top: bool,
+
+    /// Put the task last in its queue
+    #[arg(long)]
+    pub bottom: bool,
+
+    /// Put the task just above another task in the same queue
+    #[arg(long, value_name = "TASK")]
+    pub before: Option<String>,
+
+    /// Put the task just below another task in the same queue
+    #[arg(long, value_name = "TASK")]
+    pub after: Option<String>,
+}
+
+pub fn handle_rank(
+    Rank {
+        task,
+        top,
+        bottom: _,
+        before,
+        after,
+    }: Rank,
+    root: Option<PathBuf>,
+) -> Result<(), AppError> {
+    let repo = helpers::resolve_repo(root)?;
+    let Some(stored) = helpers::resolve_task_ref(Some(task), &repo, "Select task to rank")? else {
+        return Ok(());
+    };
+
+    let position = match (before, after) {
+        (Some(other), _) => {
+            let Some(other) = helpers::resolve_task_ref(Some(other), &repo, "Select task")? else {
+                return Ok(());
+            };
+            RankPosition::Before(other.task.id)
+        }
+        (_, Some(other)) => {
+            let Some(other) = helpers::resolve_task_ref(Some(other), &repo, "Select task")? else {
+                return Ok(());
+            };
+            RankPosition::After(other.task.id)
+        }
+        // The `position` group guarantees exactly one flag was given.
+        _ if top => RankPosition::Top,
+        _ => RankPosition::Bottom,
+    };
+
+    let task = rank_task(&repo, &stored.task.id, &position)?;
+    output::print_info(&format!(
+        "Ranked task {} {position} in {}",
+        task.id, task.queue
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rank;
+    use clap::Parser;
+
+    #[test]
+    fn parses_rank_command() {
+        let rank = Rank::parse_from(["rank", "0f3", "--before", "a1"]);
+        assert_eq!(rank.task, "0f3");
+        assert_eq!(rank.before.as_deref(), Some("a1"));
+        assert!(!rank.top);
+    }
+
+    #[test]
+    fn rank_requires_exactly_one_position() {
+        assert!(Rank::try_parse_from(["rank", "0f3"]).is_err());
+        assert!(Rank::try_parse_from(["rank", "0f3", "--top", "--bottom"]).is_err());
+        assert!(Rank::try_parse_from(["rank", "0f3", "--top"]).is_ok());
+    }
+}
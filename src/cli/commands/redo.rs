@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use crate::app::app_error::AppError;
+use crate::cli::commands::helpers;
+use crate::io::output;
+use crate::storage::journal;
+
+#[derive(Debug, Parser)]
+#[command(about = "Redo the most recently undone change")]
+pub struct Redo {}
+
+pub fn handle_redo(_command: Redo, root: Option<PathBuf>) -> Result<(), AppError> {
+    let repo = helpers::resolve_repo(root)?;
+    let batch = repo.redo()?;
+    let batches = repo.journal().batches()?;
+    output::print_info(&format!(
+        "Redid #{}: {}",
+        batch.number,
+        journal::describe(&batch, &batches)
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Redo;
+    use clap::Parser;
+
+    #[test]
+    fn parses_redo_command() {
+        assert!(Redo::try_parse_from(["redo"]).is_ok());
+        assert!(Redo::try_parse_from(["redo", "3"]).is_err());
+    }
+}
@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use crate::app::app_error::AppError;
+use crate::cli::commands::helpers;
+use crate::io::output;
+
+#[derive(Debug, Parser)]
+#[command(about = "Restore a task from the trash")]
+pub struct Restore {
+    pub task: Option<String>,
+}
+
+pub fn handle_restore(Restore { task }: Restore, root: Option<PathBuf>) -> Result<(), AppError> {
+    let repo = helpers::resolve_repo(root)?;
+    let trashed = repo.trash().list()?;
+    if trashed.is_empty() {
+        output::print_info("Trash is empty");
+        return Ok(());
+    }
+
+    let candidates = trashed.iter().map(|entry| entry.to_stored()).collect();
+    let Some(stored) = helpers::resolve_task_ref_in(
+        task,
+        candidates,
+        &repo.queue_dirs().queues(),
+        "Select task to restore",
+    )?
+    else {
+        return Ok(());
+    };
+
+    let restored = repo.restore(&stored.task.id)?;
+    output::print_info(&format!(
+        "Restored task: {} ({})",
+        restored.task.id,
+        restored.path.display()
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Restore;
+    use clap::Parser;
+
+    #[test]
+    fn parses_restore_command() {
+        let restore = Restore::parse_from(["restore", "0f3"]);
+        assert_eq!(restore.task.as_deref(), Some("0f3"));
+    }
+}
@@ -0,0 +1,210 @@
+use std::path::PathBuf;
+
+use chrono::Utc;
+use clap::Parser;
+use dialoguer::console::style;
+
+use crate::app::app_error::AppError;
+use crate::app::operations::{self, ReviewSummary};
+use crate::cli::commands::helpers;
+use crate::domain::date::format_duration;
+use crate::domain::task::Queue;
+use crate::io::{input, output};
+use crate::storage::{config::ResolvedConfig, repo::TaskRepo};
+
+#[derive(Debug, Parser)]
+#[command(about = "Review now, next, and later tasks interactively")]
+pub struct Review;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReviewChoice {
+    Keep,
+    Promote(Queue),
+    Demote(Queue),
+    Done,
+    Delete,
+    Skip,
+    Quit,
+}
+
+impl ReviewChoice {
+    /// The choices offered for a task in `queue`; promote and demote only
+    /// appear when there is a queue to move to.
+    fn available(queue: Queue) -> Vec<Self> {
+        let mut choices = vec![Self::Keep];
+        choices.extend(queue.promoted().map(Self::Promote));
+        choices.extend(queue.demoted().map(Self::Demote));
+        choices.extend([Self::Done, Self::Delete, Self::Skip, Self::Quit]);
+        choices
+    }
+
+    fn label(self) -> String {
+        let queue_label = |verb: &str, queue: Queue| {
+            format!("{verb} to {}", style(queue.to_string()).bold().magenta())
+        };
+        match self {
+            Self::Keep => "keep".to_string(),
+            Self::Promote(queue) => queue_label("promote", queue),
+            Self::Demote(queue) => queue_label("demote", queue),
+            Self::Done => format!("mark {}", style("done").bold().magenta()),
+            Self::Delete => "delete".to_string(),
+            Self::Skip => "skip".to_string(),
+            Self::Quit => "quit".to_string(),
+        }
+    }
+}
+
+fn record_choice(summary: &mut ReviewSummary, choice: ReviewChoice) {
+    match choice {
+        ReviewChoice::Keep => summary.kept += 1,
+        ReviewChoice::Promote(_) => summary.promoted += 1,
+        ReviewChoice::Demote(_) => summary.demoted += 1,
+        ReviewChoice::Done => summary.done += 1,
+        ReviewChoice::Delete => summary.deleted += 1,
+        ReviewChoice::Skip => summary.skipped += 1,
+        ReviewChoice::Quit => {}
+    }
+}
+
+pub fn handle_review(_: Review, root: Option<PathBuf>) -> Result<(), AppError> {
+    if !input::supports_interaction() {
+        return Err(AppError::NoTty);
+    }
+
+    let resolved = helpers::resolve_config(root)?;
+    let repo = helpers::repo_from_config(&resolved);
+    let tasks = repo.list()?;
+    let queued = operations::review_order(&tasks)
+        .into_iter()
+        .map(|task| (task.id.clone(), task.queue))
+        .collect::<Vec<_>>();
+
+    if queued.is_empty() {
+        output::print_info("Nothing to review — now, next, and later are empty");
+        return Ok(());
+    }
+
+    let mut summary = ReviewSummary::default();
+    let mut current_queue = None;
+    for (task_id, queue) in &queued {
+        if current_queue != Some(*queue) {
+            if current_queue.is_some() {
+                println!();
+            }
+            let count = queued.iter().filter(|(_, other)| other == queue).count();
+            println!(
+                "{} {}",
+                style(format!("Reviewing {queue}")).bold(),
+                style(format!("({count} tasks)")).yellow()
+            );
+            println!();
+            current_queue = Some(*queue);
+        }
+
+        // Each decision is undone on its own rather than the whole session.
+        repo.journal().begin_batch();
+        let choice = review_one_task(task_id, *queue, &repo, &resolved)?;
+        record_choice(&mut summary, choice);
+        if choice == ReviewChoice::Quit {
+            break;
+        }
+    }
+
+    if !summary.is_empty() {
+        println!();
+        output::print_info(&summary.to_string());
+    }
+    Ok(())
+}
+
+fn review_one_task(
+    task_id: &str,
+    queue: Queue,
+    repo: &TaskRepo,
+    resolved: &ResolvedConfig,
+) -> Result<ReviewChoice, AppError> {
+    let tasks = repo.list()?;
+    // Skip tasks that were moved or deleted since the review started.
+    let Some(task) = tasks.iter().find(|t| t.id == task_id && t.queue == queue) else {
+        return Ok(ReviewChoice::Skip);
+    };
+
+    println!(
+        "{}  {}  {}",
+        style(&task.id).cyan(),
+        task.title,
+        style(format!(
+            "updated {} ago",
+            format_duration(Utc::now() - task.updated_at)
+        ))
+        .dim()
+    );
+
+    let choices = ReviewChoice::available(queue);
+    let labels = choices
+        .iter()
+        .map(|choice| choice.label())
+        .collect::<Vec<_>>();
+    let choice = match input::prompt_select("Action", &labels)? {
+        Some(index) => choices[index],
+        None => ReviewChoice::Quit,
+    };
+
+    match choice {
+        ReviewChoice::Keep => {
+            operations::keep_reviewed(repo, task_id)?;
+        }
+        ReviewChoice::Promote(target) | ReviewChoice::Demote(target) => {
+            repo.move_to_queue(task_id, target, Utc::now())?;
+        }
+        ReviewChoice::Done => {
+            let outcome = operations::mark_done(repo, resolved, task_id)?;
+            if let Some((next, next_path)) = &outcome.next {
+                output::print_next_occurrence(next, next_path);
+            }
+            output::print_unblocked(&outcome.unblocked);
+            output::print_auto_archived(&outcome.archived, resolved.auto_archive_after);
+        }
+        ReviewChoice::Delete => {
+            repo.delete(task_id)?;
+        }
+        ReviewChoice::Skip | ReviewChoice::Quit => {}
+    }
+    Ok(choice)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Review, ReviewChoice};
+    use crate::domain::task::Queue;
+    use clap::Parser;
+
+    #[test]
+    fn parses_review_command() {
+        Review::parse_from(["review"]);
+    }
+
+    #[test]
+    fn offers_promote_and_demote_only_between_open_queues() {
+        assert_eq!(
+            ReviewChoice::available(Queue::Now)[..2],
+            [ReviewChoice::Keep, ReviewChoice::Demote(Queue::Next)]
+        );
+        assert_eq!(
+            ReviewChoice::available(Queue::Next)[..3],
+            [
+                ReviewChoice::Keep,
+                ReviewChoice::Promote(Queue::Now),
+                ReviewChoice::Demote(Queue::Later)
+            ]
+        );
+        assert_eq!(
+            ReviewChoice::available(Queue::Later)[..3],
+            [
+                ReviewChoice::Keep,
+                ReviewChoice::Promote(Queue::Next),
+                ReviewChoice::Done
+            ]
+        );
+    }
+}
@@ -1,23 +1,72 @@
 use std::path::PathBuf;
 
+use chrono::Local;
 use clap::Parser;
 
 use crate::app::app_error::AppError;
 use crate::cli::commands::helpers;
-use crate::io::output;
+use crate::domain::deps::BlockedIndex;
+use crate::io::{
+    json::{self, TaskRecord},
+    output::{self, OutputFormat},
+};
 
 #[derive(Debug, Parser)]
 #[command(about = "Show task details")]
 pub struct Show {
     pub task: Option<String>,
+
+    /// Print the task with a template such as `{title}: {first_line}`, or a named config template
+    #[arg(long, value_name = "TEMPLATE")]
+    pub template: Option<String>,
+
+    /// Also look for the task among archived done tasks
+    #[arg(long)]
+    pub include_archive: bool,
 }
 
-pub fn handle_show(Show { task }: Show, root: Option<PathBuf>) -> Result<(), AppError> {
-    let repo = helpers::resolve_repo(root)?;
-    let Some(stored) = helpers::resolve_task_ref(task, &repo, "Select task to show")? else {
+pub fn handle_show(
+    Show {
+        task,
+        template,
+        include_archive,
+    }: Show,
+    root: Option<PathBuf>,
+    format: OutputFormat,
+) -> Result<(), AppError> {
+    let config = helpers::resolve_config(root)?;
+    let template = template
+        .map(|template| helpers::resolve_template_for(&template, &config, format))
+        .transpose()?;
+    let repo = helpers::repo_from_config(&config);
+    let prompt = "Select task to show";
+    let stored = if include_archive {
+        let tasks = repo.scan_with_archive()?;
+        if tasks.is_empty() {
+            output::print_info("No tasks available");
+            return Ok(());
+        }
+        helpers::resolve_task_ref_in(task, tasks, &repo.queue_dirs().queues(), prompt)?
+    } else {
+        helpers::resolve_task_ref(task, &repo, prompt)?
+    };
+    let Some(stored) = stored else {
         return Ok(());
     };
 
+    if format != OutputFormat::Text {
+        let blocked = BlockedIndex::from_tasks(&repo.list()?);
+        let record = TaskRecord::new(&stored.task, &stored.path, &blocked).with_body(&stored.task);
+        return json::print_task(format, &record);
+    }
+    if let Some(template) = template {
+        println!(
+            "{}",
+            template.render(&stored.task, &stored.path, Local::now())
+        );
+        return Ok(());
+    }
+
     output::print_task_detail(&stored.task, &stored.path);
     Ok(())
 }
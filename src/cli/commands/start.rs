@@ -4,19 +4,47 @@ use chrono::Utc;
 use clap::Parser;
 
 use crate::app::app_error::AppError;
+use crate::app::bulk::BulkAction;
 use crate::cli::commands::helpers;
 use crate::domain::task::Queue;
 use crate::io::output;
 
 #[derive(Debug, Parser)]
-#[command(about = "Move a task to the now queue")]
+#[command(about = "Move tasks to the now queue")]
 pub struct Start {
-    pub task: Option<String>,
+    /// Tasks to start; pick them interactively when none are given
+    #[arg(value_name = "TASK")]
+    pub tasks: Vec<String>,
+
+    /// Also start every task matching a query, e.g. `--where "queue:next tag:ops"`
+    #[arg(long = "where", value_name = "QUERY", allow_hyphen_values = true)]
+    pub filter: Option<String>,
+
+    /// Change several tasks without asking for confirmation
+    #[arg(long, short = 'y')]
+    pub yes: bool,
 }
 
-pub fn handle_start(Start { task }: Start, root: Option<PathBuf>) -> Result<(), AppError> {
-    let repo = helpers::resolve_repo(root)?;
-    let Some(stored) = helpers::resolve_task_ref(task, &repo, "Select task to start")? else {
+pub fn handle_start(
+    Start { tasks, filter, yes }: Start,
+    root: Option<PathBuf>,
+) -> Result<(), AppError> {
+    let config = helpers::resolve_config(root)?;
+    let filter = filter
+        .map(|filter| helpers::parse_query(&filter, &config))
+        .transpose()?;
+    let repo = helpers::repo_from_config(&config);
+    let Some(mut selected) =
+        helpers::resolve_task_refs(tasks, filter.as_ref(), &repo, "Select tasks to start")?
+    else {
+        return Ok(());
+    };
+    if selected.len() > 1 {
+        helpers::run_bulk(&repo, &config, BulkAction::Start, &selected, yes)?;
+        return Ok(());
+    }
+    let Some(stored) = selected.pop() else {
+        output::print_info("No matching tasks");
         return Ok(());
     };
 
@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Local, Utc};
+use clap::Parser;
+
+use crate::app::app_error::AppError;
+use crate::cli::commands::helpers;
+use crate::domain::stats::{self, Bucket, StatsOptions};
+use crate::io::{
+    json,
+    output::{self, OutputFormat},
+};
+
+#[derive(Debug, Parser)]
+#[command(about = "Show completion counts, lead times, and queue ages")]
+pub struct Stats {
+    /// Window to report on, such as 30d, 2w, or 6m
+    #[arg(long, value_name = "AGE", default_value = "30d", value_parser = helpers::parse_age)]
+    pub since: DateTime<Utc>,
+
+    /// Group completions by day or week
+    #[arg(long, value_name = "BUCKET", default_value_t = Bucket::Week)]
+    pub by: Bucket,
+
+    /// Only count tasks with this tag
+    #[arg(long)]
+    pub tag: Option<String>,
+}
+
+pub fn handle_stats(
+    command: Stats,
+    root: Option<PathBuf>,
+    format: OutputFormat,
+) -> Result<(), AppError> {
+    let repo = helpers::resolve_repo(root)?;
+    let tasks = repo.list()?;
+    let options = StatsOptions {
+        since: command.since,
+        by: command.by,
+        tag: command.tag,
+        queues: repo.queue_dirs().queues(),
+    };
+    let stats = stats::compute(&tasks, &options, Local::now());
+    match format {
+        OutputFormat::Text => output::print_stats(&stats),
+        format => json::print_stats(format, &stats)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stats;
+    use crate::domain::stats::Bucket;
+    use chrono::{TimeDelta, Utc};
+    use clap::Parser;
+
+    #[test]
+    fn parses_stats_defaults_and_flags() {
+        let cmd = Stats::parse_from(["stats"]);
+        assert_eq!(cmd.by, Bucket::Week);
+        assert!(cmd.tag.is_none());
+        let window = Utc::now() - cmd.since;
+        assert!(window >= TimeDelta::days(30) && window < TimeDelta::days(31));
+
+        let cmd = Stats::parse_from(["stats", "--since", "2w", "--by", "day", "--tag", "ops"]);
+        assert_eq!(cmd.by, Bucket::Day);
+        assert_eq!(cmd.tag.as_deref(), Some("ops"));
+
+        assert!(Stats::try_parse_from(["stats", "--by", "month"]).is_err());
+    }
+}
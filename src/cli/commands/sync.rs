@@ -0,0 +1,51 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use crate::app::app_error::AppError;
+use crate::cli::commands::helpers;
+use crate::io::output;
+
+#[derive(Debug, Parser)]
+#[command(about = "Pull and push the git repository holding the tasks")]
+pub struct Sync {}
+
+pub fn handle_sync(_command: Sync, root: Option<PathBuf>) -> Result<(), AppError> {
+    let config = helpers::resolve_config(root)?;
+    let repo = helpers::repo_from_config(&config);
+    let Some(git) = repo.git() else {
+        return Err(AppError::usage(
+            "git is not enabled; add a [git] table to the config to use sync",
+        ));
+    };
+
+    let dirs = repo
+        .queue_dirs()
+        .queues()
+        .into_iter()
+        .map(|queue| repo.queue_dir(queue))
+        .collect::<Vec<_>>();
+    // The pull rewrites task files, so other tqs processes wait it out.
+    let _lock = repo.lock()?;
+    let report = git.sync(&dirs)?;
+    if report.committed_outside_edits {
+        output::print_info("Committed task files edited outside tqs");
+    }
+    for path in &report.merged {
+        output::print_info(&format!("Merged changes in {}", path.display()));
+    }
+    output::print_info(&format!("Synced with {}/{}", git.remote(), report.branch));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sync;
+    use clap::Parser;
+
+    #[test]
+    fn parses_sync_command() {
+        assert!(Sync::try_parse_from(["sync"]).is_ok());
+        assert!(Sync::try_parse_from(["sync", "origin"]).is_err());
+    }
+}
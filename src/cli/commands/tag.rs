@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+
+use chrono::Utc;
+use clap::Parser;
+
+use crate::app::app_error::AppError;
+use crate::cli::commands::helpers;
+use crate::domain::task::format_tags;
+use crate::io::output;
+
+#[derive(Debug, Parser)]
+#[command(about = "Add or remove task tags")]
+pub struct Tag {
+    pub task: String,
+
+    /// Tag changes: `+tag` or `tag` adds, `-tag` removes
+    #[arg(value_name = "CHANGE", allow_hyphen_values = true)]
+    pub changes: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TagChange {
+    Add(String),
+    Remove(String),
+}
+
+fn parse_change(value: &str) -> Result<TagChange, AppError> {
+    let change = match value.strip_prefix('-') {
+        Some(tag) => TagChange::Remove(helpers::parse_tag(tag).map_err(AppError::usage)?),
+        None => {
+            let tag = value.strip_prefix('+').unwrap_or(value);
+            TagChange::Add(helpers::parse_tag(tag).map_err(AppError::usage)?)
+        }
+    };
+    Ok(change)
+}
+
+pub fn handle_tag(Tag { task, changes }: Tag, root: Option<PathBuf>) -> Result<(), AppError> {
+    let changes = changes
+        .iter()
+        .map(|change| parse_change(change))
+        .collect::<Result<Vec<_>, _>>()?;
+    let repo = helpers::resolve_repo(root)?;
+    let Some(stored) = helpers::resolve_task_ref(Some(task), &repo, "Select task to tag")? else {
+        return Ok(());
+    };
+
+    let mut task = stored.task;
+    let mut changed = false;
+    for change in changes {
+        changed |= match change {
+            TagChange::Add(tag) => task.add_tag(tag),
+            TagChange::Remove(tag) => task.remove_tag(&tag),
+        };
+    }
+
+    if changed {
+        task.updated_at = Utc::now();
+        repo.update(&task)?;
+    }
+
+    let tags = if task.tags.is_empty() {
+        "(none)".to_string()
+    } else {
+        format_tags(&task.tags)
+    };
+    let verb = if changed { "Updated tags" } else { "Tags" };
+    output::print_info(&format!("{verb} for {}: {tags}", task.id));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Tag, TagChange, parse_change};
+    use clap::Parser;
+
+    #[test]
+    fn parses_hyphenated_changes() {
+        let tag = Tag::parse_from(["tag", "0f3", "+ops", "-draft", "aws"]);
+        assert_eq!(tag.task, "0f3");
+        assert_eq!(tag.changes, vec!["+ops", "-draft", "aws"]);
+    }
+
+    #[test]
+    fn parse_change_understands_prefixes() {
+        assert_eq!(
+            parse_change("+ops").expect("change should parse"),
+            TagChange::Add("ops".to_string())
+        );
+        assert_eq!(
+            parse_change("-#draft").expect("change should parse"),
+            TagChange::Remove("draft".to_string())
+        );
+        assert_eq!(
+            parse_change("aws").expect("change should parse"),
+            TagChange::Add("aws".to_string())
+        );
+        assert!(parse_change("+").is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use clap::{Args, Parser, Subcommand};
+
+use crate::app::app_error::AppError;
+use crate::cli::commands::helpers;
+use crate::io::{input, output};
+
+#[derive(Debug, Parser)]
+#[command(about = "List or purge deleted tasks")]
+pub struct Trash {
+    #[command(subcommand)]
+    pub command: Option<TrashCommand>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TrashCommand {
+    /// List deleted tasks, most recently deleted first (the default)
+    List,
+    /// Permanently remove deleted tasks
+    Purge(Purge),
+}
+
+#[derive(Debug, Args)]
+pub struct Purge {
+    /// Only purge tasks deleted longer ago than this, e.g. `30d`, `2w`, or `6m`
+    #[arg(long, value_name = "AGE", value_parser = helpers::parse_age)]
+    pub older_than: Option<DateTime<Utc>>,
+
+    /// Empty the whole trash without asking for confirmation
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+}
+
+pub fn handle_trash(Trash { command }: Trash, root: Option<PathBuf>) -> Result<(), AppError> {
+    let repo = helpers::resolve_repo(root)?;
+    match command.unwrap_or(TrashCommand::List) {
+        TrashCommand::List => {
+            output::print_trash(&repo.trash().list()?);
+            Ok(())
+        }
+        TrashCommand::Purge(Purge { older_than, yes }) => {
+            let cutoff = match older_than {
+                Some(cutoff) => cutoff,
+                None => {
+                    let count = repo.trash().list()?.len();
+                    if count == 0 {
+                        output::print_info("Trash is empty");
+                        return Ok(());
+                    }
+                    if !yes && !confirm_empty(count)? {
+                        output::print_info("Purge cancelled");
+                        return Ok(());
+                    }
+                    Utc::now()
+                }
+            };
+
+            let purged = repo.trash().purge_before(cutoff)?;
+            for trashed in &purged {
+                output::print_info(&format!("Purged task: {}", trashed.task.id));
+            }
+            output::print_info(&format!("Purged {} tasks from the trash", purged.len()));
+            Ok(())
+        }
+    }
+}
+
+/// Emptying the whole trash cannot be undone, so it needs `--yes` or a
+/// confirmation; `--older-than` alone is safe to run from scripts.
+fn confirm_empty(count: usize) -> Result<bool, AppError> {
+    if !input::supports_interaction() {
+        return Err(AppError::usage(
+            "refusing to empty the trash without confirmation; pass --yes or --older-than",
+        ));
+    }
+    input::prompt_confirm(&format!("Permanently delete all {count} trashed tasks?"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Purge, Trash, TrashCommand};
+    use clap::Parser;
+
+    #[test]
+    fn parses_trash_subcommands() {
+        assert!(Trash::parse_from(["trash"]).command.is_none());
+        assert!(matches!(
+            Trash::parse_from(["trash", "list"]).command,
+            Some(TrashCommand::List)
+        ));
+        let trash = Trash::parse_from(["trash", "purge", "--older-than", "30d"]);
+        assert!(matches!(
+            trash.command,
+            Some(TrashCommand::Purge(Purge {
+                older_than: Some(_),
+                yes: false
+            }))
+        ));
+        assert!(Trash::try_parse_from(["trash", "purge", "--older-than", "soon"]).is_err());
+    }
+}
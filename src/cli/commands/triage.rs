@@ -7,6 +7,7 @@ use dialoguer::console::style;
 use crate::app::app_error::AppError;
 use crate::app::operations;
 use crate::cli::commands::helpers;
+use crate::domain::deps::BlockedIndex;
 use crate::domain::task::Queue;
 use crate::io::{input, output};
 use crate::storage::{config::ResolvedConfig, repo::TaskRepo};
@@ -55,6 +56,8 @@ pub fn handle_triage(_: Triage, root: Option<PathBuf>) -> Result<(), AppError> {
     let mut summary = operations::TriageSummary::default();
     let task_ids: Vec<String> = inbox_tasks.iter().map(|t| t.id.clone()).collect();
     for task_id in &task_ids {
+        // Each decision is undone on its own rather than the whole session.
+        repo.journal().begin_batch();
         let outcome = triage_one_task(task_id, &repo, &resolved)?;
         let quit = matches!(outcome, TriageOutcome::Quit);
         record_outcome(&mut summary, &outcome);
@@ -76,12 +79,25 @@ fn triage_one_task(
     resolved: &ResolvedConfig,
 ) -> Result<TriageOutcome, AppError> {
     loop {
-        let tasks = repo.list_queue(Queue::Inbox)?;
-        let Some(task) = tasks.iter().find(|t| t.id == task_id) else {
+        let tasks = repo.list()?;
+        let Some(task) = tasks
+            .iter()
+            .find(|t| t.id == task_id && t.queue == Queue::Inbox)
+        else {
             return Ok(TriageOutcome::Skipped);
         };
 
-        println!("{}  {}", style(&task.id).cyan(), task.title);
+        let blockers = BlockedIndex::from_tasks(&tasks).blockers(&task.id).to_vec();
+        if blockers.is_empty() {
+            println!("{}  {}", style(&task.id).cyan(), task.title);
+        } else {
+            println!(
+                "{}  {}  {}",
+                style(&task.id).cyan().dim(),
+                style(&task.title).dim(),
+                style(format!("[blocked by {}]", blockers.join(", "))).dim()
+            );
+        }
 
         let queue_label = |q: Queue| format!("move to {}", style(q.to_string()).bold().magenta());
         let actions = vec![
@@ -128,7 +144,12 @@ fn triage_one_task(
 }
 
 fn mark_done(task_id: &str, repo: &TaskRepo, resolved: &ResolvedConfig) -> Result<(), AppError> {
-    operations::mark_done(repo, resolved, task_id)?;
+    let outcome = operations::mark_done(repo, resolved, task_id)?;
+    if let Some((next, next_path)) = &outcome.next {
+        output::print_next_occurrence(next, next_path);
+    }
+    output::print_unblocked(&outcome.unblocked);
+    output::print_auto_archived(&outcome.archived, resolved.auto_archive_after);
     Ok(())
 }
 
@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+use chrono::Utc;
+use clap::Parser;
+
+use crate::app::app_error::AppError;
+use crate::cli::commands::helpers;
+use crate::io::output;
+
+#[derive(Debug, Parser)]
+#[command(about = "Remove blocking tasks from a task")]
+pub struct Unblock {
+    pub task: String,
+
+    /// Blocking task to remove (repeat for several); removes all when omitted
+    #[arg(long = "on", value_name = "TASK")]
+    pub on: Vec<String>,
+}
+
+pub fn handle_unblock(
+    Unblock { task, on }: Unblock,
+    root: Option<PathBuf>,
+) -> Result<(), AppError> {
+    let repo = helpers::resolve_repo(root)?;
+    let Some(stored) = helpers::resolve_task_ref(Some(task), &repo, "Select task to unblock")?
+    else {
+        return Ok(());
+    };
+
+    let mut task = stored.task;
+    let mut changed = false;
+    if on.is_empty() {
+        changed = !task.blocked_by.is_empty();
+        task.blocked_by.clear();
+    }
+    for query in on {
+        // Exact ids are removed directly so references to deleted tasks can be cleared.
+        let blocker_id = if task.blocked_by.contains(&query) {
+            query
+        } else {
+            match helpers::resolve_task_ref(Some(query), &repo, "Select blocking task")? {
+                Some(blocker) => blocker.task.id,
+                None => return Ok(()),
+            }
+        };
+        changed |= task.remove_blocker(&blocker_id);
+    }
+
+    if !changed {
+        output::print_info(&format!("Task {} blockers are unchanged", task.id));
+        return Ok(());
+    }
+
+    task.updated_at = Utc::now();
+    repo.update(&task)?;
+    let message = if task.blocked_by.is_empty() {
+        format!("Task {} is no longer blocked", task.id)
+    } else {
+        format!(
+            "Task {} is still blocked by {}",
+            task.id,
+            task.blocked_by.join(", ")
+        )
+    };
+    output::print_info(&message);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Unblock;
+    use clap::Parser;
+
+    #[test]
+    fn parses_unblock_command() {
+        let unblock = Unblock::parse_from(["unblock", "0f3"]);
+        assert_eq!(unblock.task, "0f3");
+        assert!(unblock.on.is_empty());
+
+        let unblock = Unblock::parse_from(["unblock", "0f3", "--on", "a1"]);
+        assert_eq!(unblock.on, vec!["a1"]);
+    }
+}
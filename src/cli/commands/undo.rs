@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use crate::app::app_error::AppError;
+use crate::cli::commands::helpers;
+use crate::io::output;
+use crate::storage::journal;
+
+#[derive(Debug, Parser)]
+#[command(about = "Undo the most recent change")]
+pub struct Undo {}
+
+pub fn handle_undo(_command: Undo, root: Option<PathBuf>) -> Result<(), AppError> {
+    let repo = helpers::resolve_repo(root)?;
+    let batch = repo.undo()?;
+    let batches = repo.journal().batches()?;
+    output::print_info(&format!(
+        "Undid #{}: {}",
+        batch.number,
+        journal::describe(&batch, &batches)
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Undo;
+    use clap::Parser;
+
+    #[test]
+    fn parses_undo_command() {
+        assert!(Undo::try_parse_from(["undo"]).is_ok());
+        assert!(Undo::try_parse_from(["undo", "3"]).is_err());
+    }
+}
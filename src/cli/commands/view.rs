@@ -0,0 +1,94 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use chrono::Local;
+use clap::Parser;
+
+use crate::app::app_error::AppError;
+use crate::cli::commands::helpers;
+use crate::domain::deps::BlockedIndex;
+use crate::io::{
+    json::{self, TaskRecord},
+    output::{self, OutputFormat},
+};
+
+#[derive(Debug, Parser)]
+#[command(about = "Show a saved view from config")]
+pub struct View {
+    /// View name from a `[views.<name>]` config table; lists views when omitted
+    pub name: Option<String>,
+}
+
+pub fn handle_view(
+    View { name }: View,
+    root: Option<PathBuf>,
+    format: OutputFormat,
+) -> Result<(), AppError> {
+    let config = helpers::resolve_config(root)?;
+    let Some(name) = name else {
+        output::print_views(&config.views);
+        return Ok(());
+    };
+
+    let view = config
+        .views
+        .iter()
+        .find(|view| view.name == name)
+        .ok_or_else(|| {
+            let known = config
+                .views
+                .iter()
+                .map(|view| view.name.as_str())
+                .collect::<Vec<_>>();
+            if known.is_empty() {
+                AppError::usage(format!(
+                    "unknown view '{name}'; no views are configured, add a [views.{name}] table to config.toml"
+                ))
+            } else {
+                AppError::usage(format!(
+                    "unknown view '{name}'; expected one of: {}",
+                    known.join(", ")
+                ))
+            }
+        })?;
+
+    let repo = helpers::repo_from_config(&config);
+    let all_tasks = repo.scan_all()?;
+    let blocked = BlockedIndex::from_tasks(all_tasks.iter().map(|stored| &stored.task));
+    let matches = view
+        .apply(
+            all_tasks.iter().map(|stored| &stored.task),
+            Local::now().date_naive(),
+            &config.queue_dirs.queues(),
+        )
+        .map_err(|error| AppError::message(format!("invalid view '{name}': {error}")))?;
+
+    if format != OutputFormat::Text {
+        let paths = all_tasks
+            .iter()
+            .map(|stored| (stored.task.id.as_str(), stored.path.as_path()))
+            .collect::<HashMap<_, _>>();
+        let records = matches
+            .iter()
+            .map(|task| TaskRecord::new(task, paths[task.id.as_str()], &blocked))
+            .collect::<Vec<_>>();
+        return json::print_tasks(format, &records);
+    }
+
+    output::print_view(view, &matches, &blocked);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::View;
+    use clap::Parser;
+
+    #[test]
+    fn parses_optional_view_name() {
+        let command = View::try_parse_from(["view", "sprint"]).expect("view should parse");
+        assert_eq!(command.name.as_deref(), Some("sprint"));
+
+        let command = View::try_parse_from(["view"]).expect("view should parse");
+        assert_eq!(command.name, None);
+    }
+}
@@ -1,33 +1,75 @@
 use crate::app::app_error::AppError;
-use crate::io::output;
+use crate::domain::deps::BlockedIndex;
+use crate::io::output::{self, OutputFormat};
 use crate::storage::config;
 
 use super::args::{Cli, Command};
 use super::commands::{
-    add, config as config_cmd, delete, doctor, done, edit, find, helpers, inbox, list, move_cmd,
-    now, show, start, triage,
+    add, archive, block, config as config_cmd, delete, doctor, done, due, edit, find, helpers,
+    history, inbox, list, move_cmd, now, prio, rank, redo, restore, review, show, start, stats,
+    sync, tag, trash, triage, unblock, undo, view,
 };
 
 pub fn handle(cli: Cli) -> Result<(), AppError> {
+    let format = cli.format;
+    if format != OutputFormat::Text && !supports_format(cli.command.as_ref()) {
+        return Err(AppError::usage(
+            "--format json and jsonl are only supported by list, now, inbox, find, view, show, stats, config, and doctor",
+        ));
+    }
+
     match cli.command {
         Some(Command::Add(command)) => add::handle_add(command, cli.root),
-        Some(Command::List(command)) => list::handle_list(command, cli.root),
-        Some(Command::Now(command)) => now::handle_now(command, cli.root),
-        Some(Command::Inbox(command)) => inbox::handle_inbox(command, cli.root),
+        Some(Command::List(command)) => list::handle_list(command, cli.root, format),
+        Some(Command::Now(command)) => now::handle_now(command, cli.root, format),
+        Some(Command::Inbox(command)) => inbox::handle_inbox(command, cli.root, format),
         Some(Command::Move(command)) => move_cmd::handle_move(command, cli.root),
+        Some(Command::Rank(command)) => rank::handle_rank(command, cli.root),
         Some(Command::Start(command)) => start::handle_start(command, cli.root),
         Some(Command::Delete(command)) => delete::handle_delete(command, cli.root),
+        Some(Command::Restore(command)) => restore::handle_restore(command, cli.root),
+        Some(Command::Trash(command)) => trash::handle_trash(command, cli.root),
         Some(Command::Done(command)) => done::handle_done(command, cli.root),
+        Some(Command::Archive(command)) => archive::handle_archive(command, cli.root),
         Some(Command::Edit(command)) => edit::handle_edit(command, cli.root),
-        Some(Command::Show(command)) => show::handle_show(command, cli.root),
-        Some(Command::Find(command)) => find::handle_find(command, cli.root),
+        Some(Command::Tag(command)) => tag::handle_tag(command, cli.root),
+        Some(Command::Due(command)) => due::handle_due(command, cli.root),
+        Some(Command::Prio(command)) => prio::handle_prio(command, cli.root),
+        Some(Command::Block(command)) => block::handle_block(command, cli.root),
+        Some(Command::Unblock(command)) => unblock::handle_unblock(command, cli.root),
+        Some(Command::Show(command)) => show::handle_show(command, cli.root, format),
+        Some(Command::Stats(command)) => stats::handle_stats(command, cli.root, format),
+        Some(Command::Find(command)) => find::handle_find(command, cli.root, format),
+        Some(Command::View(command)) => view::handle_view(command, cli.root, format),
         Some(Command::Triage(command)) => triage::handle_triage(command, cli.root),
-        Some(Command::Config(command)) => config_cmd::handle_config(command, cli.root),
-        Some(Command::Doctor(command)) => doctor::handle_doctor(command, cli.root),
+        Some(Command::Review(command)) => review::handle_review(command, cli.root),
+        Some(Command::Undo(command)) => undo::handle_undo(command, cli.root),
+        Some(Command::Redo(command)) => redo::handle_redo(command, cli.root),
+        Some(Command::History(command)) => history::handle_history(command, cli.root),
+        Some(Command::Sync(command)) => sync::handle_sync(command, cli.root),
+        Some(Command::Config(command)) => config_cmd::handle_config(command, cli.root, format),
+        Some(Command::Doctor(command)) => doctor::handle_doctor(command, cli.root, format),
         None => handle_default(cli.root, cli.no_tui),
     }
 }
 
+fn supports_format(command: Option<&Command>) -> bool {
+    matches!(
+        command,
+        Some(
+            Command::List(_)
+                | Command::Now(_)
+                | Command::Inbox(_)
+                | Command::Find(_)
+                | Command::View(_)
+                | Command::Show(_)
+                | Command::Stats(_)
+                | Command::Config(_)
+                | Command::Doctor(_)
+        )
+    )
+}
+
 fn handle_default(root: Option<std::path::PathBuf>, no_tui: bool) -> Result<(), AppError> {
     let resolved = match config::resolve(root) {
         Ok(resolved) => resolved,
@@ -49,7 +91,11 @@ fn handle_default(root: Option<std::path::PathBuf>, no_tui: bool) -> Result<(),
             let inspection = config::inspect(None)?;
             output::print_getting_started(inspection.config_path.as_deref());
         } else {
-            output::print_dashboard(&tasks);
+            output::print_dashboard(
+                &tasks,
+                &BlockedIndex::from_tasks(&tasks),
+                &resolved.queue_dirs.active_queues(),
+            );
         }
     }
 
@@ -60,6 +106,7 @@ fn handle_default(root: Option<std::path::PathBuf>, no_tui: bool) -> Result<(),
 mod tests {
     use super::handle;
     use crate::cli::args::Cli;
+    use crate::io::output::OutputFormat;
     use crate::test_support::LockedEnv;
     use tempfile::TempDir;
 
@@ -68,6 +115,7 @@ mod tests {
         let _env = LockedEnv::new(&["XDG_CONFIG_HOME", "TQS_ROOT"]);
         handle(Cli {
             root: None,
+            format: OutputFormat::Text,
             no_tui: true,
             command: None,
         })
@@ -82,6 +130,7 @@ mod tests {
 
         handle(Cli {
             root: None,
+            format: OutputFormat::Text,
             no_tui: true,
             command: None,
         })
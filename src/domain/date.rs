@@ -0,0 +1,310 @@
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, TimeDelta, Utc, Weekday};
+use std::{fmt, str::FromStr};
+
+/// How far ahead `describe_due` spells out a relative day count before
+/// falling back to the calendar date.
+const RELATIVE_DUE_HORIZON_DAYS: i64 = 14;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateParseError {
+    input: String,
+}
+
+impl fmt::Display for DateParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid date '{}'; expected today, tomorrow, a weekday (mon..sun), +<n>d/w/m, or YYYY-MM-DD",
+            self.input
+        )
+    }
+}
+
+impl std::error::Error for DateParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgeParseError {
+    input: String,
+}
+
+impl fmt::Display for AgeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid age '{}'; expected days, weeks, or months such as 30d, 2w, or 6m",
+            self.input
+        )
+    }
+}
+
+impl std::error::Error for AgeParseError {}
+
+/// A span of time written as `30d`, `2w`, or `6m`, used for cutoffs such as
+/// `--older-than`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Age {
+    amount: u32,
+    unit: AgeUnit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AgeUnit {
+    Days,
+    Weeks,
+    Months,
+}
+
+impl Age {
+    pub const fn days(amount: u32) -> Self {
+        Self {
+            amount,
+            unit: AgeUnit::Days,
+        }
+    }
+
+    /// The moment this long before `now`.
+    pub fn before(self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let amount = u64::from(self.amount);
+        match self.unit {
+            AgeUnit::Days => now.checked_sub_days(Days::new(amount)),
+            AgeUnit::Weeks => now.checked_sub_days(Days::new(amount * 7)),
+            AgeUnit::Months => now.checked_sub_months(Months::new(self.amount)),
+        }
+    }
+}
+
+impl fmt::Display for Age {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = match self.unit {
+            AgeUnit::Days => 'd',
+            AgeUnit::Weeks => 'w',
+            AgeUnit::Months => 'm',
+        };
+        write!(f, "{}{unit}", self.amount)
+    }
+}
+
+impl FromStr for Age {
+    type Err = AgeParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let error = || AgeParseError {
+            input: input.to_string(),
+        };
+        let value = input.trim();
+        let unit = value.chars().last().ok_or_else(error)?;
+        let amount = value[..value.len() - unit.len_utf8()]
+            .parse::<u32>()
+            .map_err(|_| error())?;
+        let unit = match unit {
+            'd' => AgeUnit::Days,
+            'w' => AgeUnit::Weeks,
+            'm' => AgeUnit::Months,
+            _ => return Err(error()),
+        };
+        Ok(Self { amount, unit })
+    }
+}
+
+/// Where a due date falls relative to today.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DueStatus {
+    Overdue,
+    Today,
+    Upcoming,
+}
+
+/// Parses a calendar date relative to `today`.
+///
+/// Accepted forms: `today`, `tomorrow`, `yesterday`, weekday names (full or
+/// three-letter, meaning the next such day after today), offsets such as
+/// `+3d`, `+2w`, or `+1m`, and ISO dates like `2026-11-01`.
+pub fn parse_date(input: &str, today: NaiveDate) -> Result<NaiveDate, DateParseError> {
+    let error = || DateParseError {
+        input: input.to_string(),
+    };
+    let value = input.trim().to_ascii_lowercase();
+
+    match value.as_str() {
+        "today" => return Ok(today),
+        "tomorrow" => return today.checked_add_days(Days::new(1)).ok_or_else(error),
+        "yesterday" => return today.checked_sub_days(Days::new(1)).ok_or_else(error),
+        _ => {}
+    }
+
+    if let Some(weekday) = parse_weekday(&value) {
+        return Ok(next_weekday(today, weekday));
+    }
+
+    if let Some(offset) = value.strip_prefix('+') {
+        return apply_offset(today, offset).ok_or_else(error);
+    }
+
+    NaiveDate::parse_from_str(&value, "%Y-%m-%d").map_err(|_| error())
+}
+
+pub(crate) fn parse_weekday(value: &str) -> Option<Weekday> {
+    let weekday = match value {
+        "mon" | "monday" => Weekday::Mon,
+        "tue" | "tues" | "tuesday" => Weekday::Tue,
+        "wed" | "wednesday" => Weekday::Wed,
+        "thu" | "thur" | "thurs" | "thursday" => Weekday::Thu,
+        "fri" | "friday" => Weekday::Fri,
+        "sat" | "saturday" => Weekday::Sat,
+        "sun" | "sunday" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+/// The first `weekday` strictly after `today`.
+pub(crate) fn next_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let current = today.weekday().num_days_from_monday();
+    let target = weekday.num_days_from_monday();
+    let ahead = (target + 7 - current) % 7;
+    let ahead = if ahead == 0 { 7 } else { ahead };
+    today + Days::new(u64::from(ahead))
+}
+
+fn apply_offset(today: NaiveDate, offset: &str) -> Option<NaiveDate> {
+    let unit = offset.chars().last()?;
+    let amount = offset[..offset.len() - unit.len_utf8()]
+        .parse::<u32>()
+        .ok()?;
+    match unit {
+        'd' => today.checked_add_days(Days::new(u64::from(amount))),
+        'w' => today.checked_add_days(Days::new(u64::from(amount) * 7)),
+        'm' => today.checked_add_months(Months::new(amount)),
+        _ => None,
+    }
+}
+
+pub fn due_status(due: NaiveDate, today: NaiveDate) -> DueStatus {
+    match due.cmp(&today) {
+        std::cmp::Ordering::Less => DueStatus::Overdue,
+        std::cmp::Ordering::Equal => DueStatus::Today,
+        std::cmp::Ordering::Greater => DueStatus::Upcoming,
+    }
+}
+
+/// Short human description of a due date, e.g. `overdue 2d`, `due today`,
+/// `due in 3d`, or `due 2026-11-01` for dates further out.
+pub fn describe_due(due: NaiveDate, today: NaiveDate) -> String {
+    let days = (due - today).num_days();
+    match days {
+        ..0 => format!("overdue {}d", -days),
+        0 => "due today".to_string(),
+        1 => "due tomorrow".to_string(),
+        2..=RELATIVE_DUE_HORIZON_DAYS => format!("due in {days}d"),
+        _ => format!("due {due}"),
+    }
+}
+
+/// Compact duration in the largest unit that keeps the number readable,
+/// e.g. `45m`, `30h`, `3d`, `4w`, `5mo`, or `2y`.
+pub fn format_duration(duration: TimeDelta) -> String {
+    let minutes = duration.num_minutes().max(0);
+    let hours = minutes / 60;
+    let days = hours / 24;
+    if minutes < 60 {
+        format!("{minutes}m")
+    } else if hours < 48 {
+        format!("{hours}h")
+    } else if days < 14 {
+        format!("{days}d")
+    } else if days < 60 {
+        format!("{}w", days / 7)
+    } else if days < 365 {
+        format!("{}mo", days / 30)
+    } else {
+        format!("{}y", days / 365)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Age, DueStatus, describe_due, due_status, parse_date};
+    use chrono::{DateTime, NaiveDate, Utc};
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().expect("date should parse")
+    }
+
+    // 2026-10-14 is a Wednesday.
+    fn today() -> NaiveDate {
+        date("2026-10-14")
+    }
+
+    #[test]
+    fn parses_named_days() {
+        assert_eq!(parse_date("today", today()), Ok(today()));
+        assert_eq!(parse_date("Tomorrow", today()), Ok(date("2026-10-15")));
+        assert_eq!(parse_date("yesterday", today()), Ok(date("2026-10-13")));
+    }
+
+    #[test]
+    fn weekdays_resolve_to_the_next_occurrence_after_today() {
+        assert_eq!(parse_date("fri", today()), Ok(date("2026-10-16")));
+        assert_eq!(parse_date("monday", today()), Ok(date("2026-10-19")));
+        assert_eq!(parse_date("wed", today()), Ok(date("2026-10-21")));
+    }
+
+    #[test]
+    fn parses_offsets() {
+        assert_eq!(parse_date("+3d", today()), Ok(date("2026-10-17")));
+        assert_eq!(parse_date("+2w", today()), Ok(date("2026-10-28")));
+        assert_eq!(parse_date("+1m", today()), Ok(date("2026-11-14")));
+        assert!(parse_date("+d", today()).is_err());
+        assert!(parse_date("+3y", today()).is_err());
+    }
+
+    #[test]
+    fn parses_iso_dates_and_rejects_garbage() {
+        assert_eq!(parse_date("2026-11-01", today()), Ok(date("2026-11-01")));
+        let err = parse_date("someday", today()).expect_err("input should be rejected");
+        assert!(err.to_string().contains("invalid date 'someday'"));
+    }
+
+    #[test]
+    fn due_status_compares_against_today() {
+        assert_eq!(due_status(date("2026-10-13"), today()), DueStatus::Overdue);
+        assert_eq!(due_status(today(), today()), DueStatus::Today);
+        assert_eq!(due_status(date("2026-10-15"), today()), DueStatus::Upcoming);
+    }
+
+    #[test]
+    fn describe_due_uses_relative_wording_near_today() {
+        assert_eq!(describe_due(date("2026-10-12"), today()), "overdue 2d");
+        assert_eq!(describe_due(today(), today()), "due today");
+        assert_eq!(describe_due(date("2026-10-15"), today()), "due tomorrow");
+        assert_eq!(describe_due(date("2026-10-20"), today()), "due in 6d");
+        assert_eq!(describe_due(date("2026-12-01"), today()), "due 2026-12-01");
+    }
+
+    #[test]
+    fn ages_parse_and_count_back_from_now() {
+        let now = "2026-10-14T12:00:00Z"
+            .parse::<DateTime<Utc>>()
+            .expect("timestamp should parse");
+        let before = |value: &str| {
+            value
+                .parse::<Age>()
+                .expect("age should parse")
+                .before(now)
+                .expect("cutoff should exist")
+                .to_rfc3339()
+        };
+        assert_eq!(before("90d"), "2026-07-16T12:00:00+00:00");
+        assert_eq!(before("2w"), "2026-09-30T12:00:00+00:00");
+        assert_eq!(before("6m"), "2026-04-14T12:00:00+00:00");
+        assert_eq!(
+            "90d".parse::<Age>().map(|age| age.to_string()),
+            Ok("90d".to_string())
+        );
+        assert_eq!(
+            "soon".parse::<Age>().unwrap_err().to_string(),
+            "invalid age 'soon'; expected days, weeks, or months such as 30d, 2w, or 6m"
+        );
+        assert!("3y".parse::<Age>().is_err());
+    }
+}
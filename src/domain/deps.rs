@@ -0,0 +1,214 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use super::task::Task;
+
+/// Open blockers per task: the `blocked_by` ids that name an existing task
+/// that is not done yet. References to missing tasks never block; `doctor`
+/// reports them instead.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockedIndex {
+    blockers: HashMap<String, Vec<String>>,
+}
+
+impl BlockedIndex {
+    pub fn from_tasks<'a>(tasks: impl IntoIterator<Item = &'a Task>) -> Self {
+        let tasks = tasks.into_iter().collect::<Vec<_>>();
+        let open = tasks
+            .iter()
+            .filter(|task| !task.queue.is_done())
+            .map(|task| task.id.as_str())
+            .collect::<HashSet<_>>();
+
+        let blockers = tasks
+            .iter()
+            .filter(|task| !task.queue.is_done())
+            .filter_map(|task| {
+                let open_blockers = task
+                    .blocked_by
+                    .iter()
+                    .filter(|id| **id != task.id && open.contains(id.as_str()))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                (!open_blockers.is_empty()).then(|| (task.id.clone(), open_blockers))
+            })
+            .collect();
+
+        Self { blockers }
+    }
+
+    pub fn blockers(&self, id: &str) -> &[String] {
+        self.blockers.get(id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn is_blocked(&self, id: &str) -> bool {
+        self.blockers.contains_key(id)
+    }
+}
+
+/// Tasks whose only open blocker is `done_id`, i.e. the ones completing it frees.
+pub fn unblocked_by<'a>(tasks: &'a [Task], done_id: &str) -> Vec<&'a Task> {
+    let index = BlockedIndex::from_tasks(tasks);
+    tasks
+        .iter()
+        .filter(|task| index.blockers(&task.id) == [done_id])
+        .collect()
+}
+
+/// Whether making `task_id` wait on `blocker_id` would close a cycle, which
+/// happens when the blocker already depends on the task, directly or not.
+pub fn would_create_cycle(tasks: &[Task], task_id: &str, blocker_id: &str) -> bool {
+    let edges = edges(tasks);
+    let mut stack = vec![blocker_id];
+    let mut seen = HashSet::new();
+    while let Some(id) = stack.pop() {
+        if id == task_id {
+            return true;
+        }
+        if !seen.insert(id) {
+            continue;
+        }
+        stack.extend(edges.get(id).into_iter().flatten().copied());
+    }
+    false
+}
+
+/// `(task id, missing id)` pairs for `blocked_by` entries naming no known task.
+pub fn dangling_references(tasks: &[Task]) -> Vec<(&str, &str)> {
+    let known = tasks
+        .iter()
+        .map(|task| task.id.as_str())
+        .collect::<HashSet<_>>();
+    tasks
+        .iter()
+        .flat_map(|task| {
+            task.blocked_by
+                .iter()
+                .filter(|id| !known.contains(id.as_str()))
+                .map(|id| (task.id.as_str(), id.as_str()))
+        })
+        .collect()
+}
+
+/// Every distinct dependency cycle, each listed from its smallest id, in id order.
+pub fn dependency_cycles(tasks: &[Task]) -> Vec<Vec<String>> {
+    let edges = edges(tasks);
+    let mut ids = edges.keys().copied().collect::<Vec<_>>();
+    ids.sort_unstable();
+
+    let mut cycles = BTreeSet::new();
+    for start in ids {
+        let mut path = vec![start];
+        collect_cycles(&edges, start, &mut path, &mut cycles);
+    }
+    cycles.into_iter().collect()
+}
+
+fn collect_cycles<'a>(
+    edges: &HashMap<&'a str, Vec<&'a str>>,
+    start: &'a str,
+    path: &mut Vec<&'a str>,
+    cycles: &mut BTreeSet<Vec<String>>,
+) {
+    let current = path[path.len() - 1];
+    for next in edges.get(current).into_iter().flatten().copied() {
+        // Paths only visit ids above `start`, so each cycle is found once,
+        // from its smallest member.
+        if next == start {
+            cycles.insert(path.iter().map(|id| id.to_string()).collect());
+        } else if next > start && !path.contains(&next) {
+            path.push(next);
+            collect_cycles(edges, start, path, cycles);
+            path.pop();
+        }
+    }
+}
+
+fn edges(tasks: &[Task]) -> HashMap<&str, Vec<&str>> {
+    tasks
+        .iter()
+        .map(|task| {
+            (
+                task.id.as_str(),
+                task.blocked_by.iter().map(String::as_str).collect(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BlockedIndex, dangling_references, dependency_cycles, unblocked_by, would_create_cycle,
+    };
+    use crate::domain::task::{Queue, Task};
+
+    fn task(id: &str, blocked_by: &[&str]) -> Task {
+        let mut task = Task::new(
+            id,
+            format!("Task {id}"),
+            "2026-03-09T10:34:12Z"
+                .parse()
+                .expect("timestamp should parse"),
+        );
+        task.blocked_by = blocked_by.iter().map(|id| id.to_string()).collect();
+        task
+    }
+
+    #[test]
+    fn only_open_existing_tasks_block() {
+        let mut done = task("b", &[]);
+        done.queue = Queue::Done;
+        let tasks = vec![task("a", &["b", "c", "missing"]), done, task("c", &[])];
+
+        let index = BlockedIndex::from_tasks(&tasks);
+        assert_eq!(index.blockers("a"), ["c".to_string()]);
+        assert!(index.is_blocked("a"));
+        assert!(!index.is_blocked("c"));
+    }
+
+    #[test]
+    fn unblocked_by_lists_tasks_waiting_only_on_the_completed_one() {
+        let tasks = vec![
+            task("a", &["x"]),
+            task("b", &["x", "y"]),
+            task("x", &[]),
+            task("y", &[]),
+        ];
+
+        let freed = unblocked_by(&tasks, "x");
+        assert_eq!(
+            freed
+                .iter()
+                .map(|task| task.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a"]
+        );
+    }
+
+    #[test]
+    fn detects_cycles_before_they_are_created() {
+        let tasks = vec![task("a", &["b"]), task("b", &["c"]), task("c", &[])];
+        assert!(would_create_cycle(&tasks, "c", "a"));
+        assert!(would_create_cycle(&tasks, "a", "a"));
+        assert!(!would_create_cycle(&tasks, "a", "c"));
+    }
+
+    #[test]
+    fn reports_dangling_references_and_cycles() {
+        let tasks = vec![
+            task("a", &["b"]),
+            task("b", &["c"]),
+            task("c", &["a", "gone"]),
+            task("d", &["d"]),
+        ];
+
+        assert_eq!(dangling_references(&tasks), vec![("c", "gone")]);
+        assert_eq!(
+            dependency_cycles(&tasks),
+            vec![
+                vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                vec!["d".to_string()],
+            ]
+        );
+    }
+}
@@ -1,27 +1,39 @@
 use super::task::{Queue, Task};
 
-pub fn matches_query(task: &Task, query: &str) -> bool {
+pub fn title_matches_query(task: &Task, query: &str) -> bool {
     let query = query.trim().to_ascii_lowercase();
     if query.is_empty() {
         return true;
     }
 
-    let haystack = format!("{} {} {}", task.id, task.title, task.body).to_ascii_lowercase();
+    task.title.to_ascii_lowercase().contains(&query)
+}
 
-    haystack.contains(&query)
+pub fn has_all_tags(task: &Task, tags: &[String]) -> bool {
+    tags.iter().all(|tag| task.has_tag(tag))
 }
 
-pub fn title_matches_query(task: &Task, query: &str) -> bool {
-    let query = query.trim().to_ascii_lowercase();
-    if query.is_empty() {
-        return true;
+/// Counts tasks per tag, merging case variants under the first spelling seen.
+/// Results are sorted by tag name.
+pub fn tag_counts(tasks: &[Task]) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for tag in tasks.iter().flat_map(|task| task.tags.iter()) {
+        match counts
+            .iter_mut()
+            .find(|(existing, _)| existing.eq_ignore_ascii_case(tag))
+        {
+            Some((_, count)) => *count += 1,
+            None => counts.push((tag.clone(), 1)),
+        }
     }
 
-    task.title.to_ascii_lowercase().contains(&query)
+    counts.sort_by_key(|(tag, _)| tag.to_ascii_lowercase());
+    counts
 }
 
-pub fn queue_counts(tasks: &[Task]) -> Vec<(Queue, usize)> {
-    Queue::all()
+/// Counts tasks in each of `queues`, in the order given.
+pub fn queue_counts(tasks: &[Task], queues: &[Queue]) -> Vec<(Queue, usize)> {
+    queues
         .iter()
         .copied()
         .map(|queue| {
@@ -33,8 +45,8 @@ pub fn queue_counts(tasks: &[Task]) -> Vec<(Queue, usize)> {
 
 #[cfg(test)]
 mod tests {
-    use super::{matches_query, queue_counts, title_matches_query};
-    use crate::domain::task::{Queue, Task};
+    use super::{has_all_tags, queue_counts, tag_counts, title_matches_query};
+    use crate::domain::task::{Queue, QueueName, Task};
 
     fn task() -> Task {
         let mut task = Task::new(
@@ -49,11 +61,28 @@ mod tests {
     }
 
     #[test]
-    fn matches_query_uses_title_and_body() {
-        let task = task();
-        assert!(matches_query(&task, "billing"));
-        assert!(matches_query(&task, "cost explorer"));
-        assert!(!matches_query(&task, "missing"));
+    fn has_all_tags_requires_every_tag() {
+        let mut task = task();
+        task.tags = vec!["ops".to_string(), "aws".to_string()];
+        assert!(has_all_tags(&task, &["OPS".to_string()]));
+        assert!(has_all_tags(&task, &[]));
+        assert!(!has_all_tags(
+            &task,
+            &["ops".to_string(), "release".to_string()]
+        ));
+    }
+
+    #[test]
+    fn tag_counts_merge_case_variants_and_sort() {
+        let mut first = task();
+        first.tags = vec!["ops".to_string(), "Billing".to_string()];
+        let mut second = task();
+        second.tags = vec!["OPS".to_string()];
+
+        assert_eq!(
+            tag_counts(&[first, second]),
+            vec![("Billing".to_string(), 1), ("ops".to_string(), 2)]
+        );
     }
 
     #[test]
@@ -67,7 +96,7 @@ mod tests {
     fn queue_counts_cover_every_builtin_queue() {
         let mut task = task();
         task.queue = Queue::Now;
-        let counts = queue_counts(&[task]);
+        let counts = queue_counts(&[task], Queue::all());
 
         assert_eq!(counts.len(), Queue::all().len());
         assert_eq!(
@@ -79,4 +108,14 @@ mod tests {
             1
         );
     }
+
+    #[test]
+    fn queue_counts_include_custom_queues() {
+        let someday = Queue::Custom(QueueName::new("someday").expect("name should be valid"));
+        let mut task = task();
+        task.queue = someday;
+        let counts = queue_counts(&[task], &[Queue::Inbox, someday]);
+
+        assert_eq!(counts, vec![(Queue::Inbox, 0), (someday, 1)]);
+    }
 }
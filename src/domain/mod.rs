@@ -1,3 +1,11 @@
+pub mod date;
+pub mod deps;
 pub mod filter;
 pub mod id;
+pub mod priority;
+pub mod query;
+pub mod rank;
+pub mod recur;
+pub mod stats;
 pub mod task;
+pub mod view;
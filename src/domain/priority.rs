@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriorityParseError {
+    input: String,
+}
+
+impl fmt::Display for PriorityParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid priority '{}'; expected p0, p1, p2, p3, high, medium, or low",
+            self.input
+        )
+    }
+}
+
+impl std::error::Error for PriorityParseError {}
+
+/// How urgent a task is, stored in frontmatter as `p0` (most urgent) to `p3`.
+/// `high`, `medium`, and `low` are accepted as `p1`, `p2`, and `p3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Priority {
+    P0,
+    P1,
+    P2,
+    P3,
+}
+
+impl Priority {
+    pub const ALL: [Priority; 4] = [Self::P0, Self::P1, Self::P2, Self::P3];
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Self::P0 => "p0",
+            Self::P1 => "p1",
+            Self::P2 => "p2",
+            Self::P3 => "p3",
+        };
+        f.write_str(value)
+    }
+}
+
+impl FromStr for Priority {
+    type Err = PriorityParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let priority = match input.trim().to_ascii_lowercase().as_str() {
+            "p0" => Self::P0,
+            "p1" | "high" => Self::P1,
+            "p2" | "medium" => Self::P2,
+            "p3" | "low" => Self::P3,
+            _ => {
+                return Err(PriorityParseError {
+                    input: input.to_string(),
+                });
+            }
+        };
+        Ok(priority)
+    }
+}
+
+impl TryFrom<String> for Priority {
+    type Error = PriorityParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Priority> for String {
+    fn from(priority: Priority) -> Self {
+        priority.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Priority;
+
+    #[test]
+    fn parses_levels_and_names() {
+        assert_eq!("p0".parse::<Priority>(), Ok(Priority::P0));
+        assert_eq!("P2".parse::<Priority>(), Ok(Priority::P2));
+        assert_eq!("high".parse::<Priority>(), Ok(Priority::P1));
+        assert_eq!(" Medium ".parse::<Priority>(), Ok(Priority::P2));
+        assert_eq!("low".parse::<Priority>(), Ok(Priority::P3));
+        assert_eq!(
+            "urgent".parse::<Priority>().unwrap_err().to_string(),
+            "invalid priority 'urgent'; expected p0, p1, p2, p3, high, medium, or low"
+        );
+    }
+
+    #[test]
+    fn more_urgent_priorities_sort_first() {
+        let mut priorities = vec![Priority::P3, Priority::P0, Priority::P2];
+        priorities.sort();
+        assert_eq!(priorities, [Priority::P0, Priority::P2, Priority::P3]);
+        assert_eq!(Priority::P1.to_string(), "p1");
+    }
+}
@@ -0,0 +1,835 @@
+use chrono::{DateTime, Days, Local, Months, NaiveDate, Utc};
+use std::fmt;
+
+use super::date::parse_date;
+use super::priority::Priority;
+use super::task::{Queue, Task, normalize_tag};
+
+/// A parsed search query: whitespace-separated terms that must all match.
+///
+/// ```text
+/// queue:now tag:ops due<7d -title:draft "exact phrase" created>2026-01-01
+/// priority<=p1 (tag:ops OR tag:infra) -(queue:done OR queue:later)
+/// ```
+///
+/// Bare words and quoted phrases search the id, title, `#tags`, and body.
+/// `field:value` terms target one field, `field<value` style terms compare
+/// dates or priorities, and a leading `-` negates any term. `OR` between terms matches
+/// either side, binding looser than the implicit AND, and parentheses group
+/// terms into a single term.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query {
+    terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Term {
+    pub negated: bool,
+    pub predicate: Predicate,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Predicate {
+    /// Case-insensitive substring of the id, title, `#tags`, or body.
+    Text(String),
+    /// Case-insensitive substring of a single text field; ids match by prefix.
+    Field {
+        field: TextField,
+        value: String,
+    },
+    Queue(Queue),
+    Tag(String),
+    /// A parenthesized group or `OR` chain: matches when any alternative does.
+    Any(Vec<Query>),
+    /// Tasks without the date never match.
+    Date {
+        field: DateField,
+        op: CompareOp,
+        date: NaiveDate,
+    },
+    /// Compares by urgency, so `priority<p2` is `p0` or `p1`. Tasks without
+    /// a priority never match.
+    Priority {
+        op: CompareOp,
+        priority: Priority,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextField {
+    Id,
+    Title,
+    Body,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateField {
+    Due,
+    Scheduled,
+    Created,
+    Updated,
+    Completed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Lt,
+    Le,
+    Eq,
+    Ge,
+    Gt,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    /// 1-based character column where the offending term starts.
+    pub column: usize,
+    pub kind: QueryErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryErrorKind {
+    UnterminatedQuote,
+    UnclosedGroup,
+    UnmatchedParen,
+    EmptyGroup,
+    DanglingOr,
+    MissingValue(String),
+    InvalidQueue(String),
+    UnknownQueue(String),
+    InvalidTag(String),
+    InvalidDate(String),
+    InvalidPriority(String),
+    UnsupportedComparison(String),
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid query at column {}: ", self.column)?;
+        match &self.kind {
+            QueryErrorKind::UnterminatedQuote => f.write_str("unterminated quote"),
+            QueryErrorKind::UnclosedGroup => f.write_str("'(' is never closed"),
+            QueryErrorKind::UnmatchedParen => f.write_str("')' has no matching '('"),
+            QueryErrorKind::EmptyGroup => f.write_str("empty group"),
+            QueryErrorKind::DanglingOr => f.write_str("'OR' needs a term on each side"),
+            QueryErrorKind::MissingValue(field) => write!(f, "missing value for '{field}'"),
+            QueryErrorKind::InvalidQueue(value) => write!(
+                f,
+                "invalid queue '{value}'; expected {} or a custom queue name",
+                Queue::all()
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            QueryErrorKind::UnknownQueue(value) => {
+                write!(f, "queue '{value}' is not configured")
+            }
+            QueryErrorKind::InvalidTag(value) => write!(f, "invalid tag '{value}'"),
+            QueryErrorKind::InvalidDate(value) => write!(
+                f,
+                "invalid date '{value}'; expected YYYY-MM-DD, today, a weekday, or an offset like 7d or -2w"
+            ),
+            QueryErrorKind::InvalidPriority(value) => write!(
+                f,
+                "invalid priority '{value}'; expected p0, p1, p2, p3, high, medium, or low"
+            ),
+            QueryErrorKind::UnsupportedComparison(field) => {
+                write!(f, "'{field}' cannot be compared with <, >, or =")
+            }
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+impl Query {
+    /// Parses `input`, resolving relative dates against `today`. `queue:`
+    /// terms must name one of `queues`, the configured queues.
+    pub fn parse(input: &str, today: NaiveDate, queues: &[Queue]) -> Result<Self, QueryError> {
+        let mut parser = Parser {
+            tokens: tokenize(input)?.into_iter().peekable(),
+            today,
+            queues,
+        };
+        let query = parser.parse_any()?;
+        match parser.tokens.next() {
+            Some((column, _)) => Err(QueryError {
+                column,
+                kind: QueryErrorKind::UnmatchedParen,
+            }),
+            None => Ok(query),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn terms(&self) -> &[Term] {
+        &self.terms
+    }
+
+    pub fn matches(&self, task: &Task) -> bool {
+        self.terms.iter().all(|term| term.matches(task))
+    }
+
+    /// Splits off the positive free-text terms, joined with spaces, for callers
+    /// that rank text with fuzzy matching and apply the rest as exact filters.
+    pub fn split_text(self) -> (String, Query) {
+        let (text, terms): (Vec<_>, Vec<_>) = self
+            .terms
+            .into_iter()
+            .partition(|term| !term.negated && matches!(term.predicate, Predicate::Text(_)));
+        let text = text
+            .into_iter()
+            .filter_map(|term| match term.predicate {
+                Predicate::Text(text) => Some(text),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        (text, Query { terms })
+    }
+}
+
+impl Term {
+    pub fn matches(&self, task: &Task) -> bool {
+        self.predicate.matches(task) != self.negated
+    }
+}
+
+impl Predicate {
+    pub fn matches(&self, task: &Task) -> bool {
+        match self {
+            Self::Text(text) => {
+                let text = text.to_ascii_lowercase();
+                contains_ignore_case(&task.id, &text)
+                    || contains_ignore_case(&task.title, &text)
+                    || task
+                        .tags
+                        .iter()
+                        .any(|tag| contains_ignore_case(&format!("#{tag}"), &text))
+                    || contains_ignore_case(&task.body, &text)
+            }
+            Self::Field { field, value } => {
+                let value = value.to_ascii_lowercase();
+                match field {
+                    TextField::Id => task.id.to_ascii_lowercase().starts_with(&value),
+                    TextField::Title => contains_ignore_case(&task.title, &value),
+                    TextField::Body => contains_ignore_case(&task.body, &value),
+                }
+            }
+            Self::Queue(queue) => task.queue == *queue,
+            Self::Tag(tag) => task.has_tag(tag),
+            Self::Any(alternatives) => alternatives.iter().any(|query| query.matches(task)),
+            Self::Date { field, op, date } => field
+                .value(task)
+                .is_some_and(|value| op.compare(value, *date)),
+            Self::Priority { op, priority } => task
+                .priority
+                .is_some_and(|value| op.compare(value, *priority)),
+        }
+    }
+}
+
+impl DateField {
+    fn parse(name: &str) -> Option<Self> {
+        let field = match name {
+            "due" => Self::Due,
+            "scheduled" => Self::Scheduled,
+            "created" => Self::Created,
+            "updated" => Self::Updated,
+            "completed" => Self::Completed,
+            _ => return None,
+        };
+        Some(field)
+    }
+
+    fn value(self, task: &Task) -> Option<NaiveDate> {
+        match self {
+            Self::Due => task.due,
+            Self::Scheduled => task.scheduled,
+            Self::Created => Some(local_date(task.created_at)),
+            Self::Updated => Some(local_date(task.updated_at)),
+            Self::Completed => task.completed_at.map(local_date),
+        }
+    }
+}
+
+impl CompareOp {
+    fn compare<T: Ord>(self, left: T, right: T) -> bool {
+        match self {
+            Self::Lt => left < right,
+            Self::Le => left <= right,
+            Self::Eq => left == right,
+            Self::Ge => left >= right,
+            Self::Gt => left > right,
+        }
+    }
+}
+
+fn local_date(timestamp: DateTime<Utc>) -> NaiveDate {
+    timestamp.with_timezone(&Local).date_naive()
+}
+
+fn contains_ignore_case(haystack: &str, lowercase_needle: &str) -> bool {
+    haystack.to_ascii_lowercase().contains(lowercase_needle)
+}
+
+/// Splits on whitespace outside double quotes, keeping each token's column.
+/// Outside quotes, `)` is always its own token, and `(` is one when it starts
+/// a term (`(` or `-(`).
+fn tokenize(input: &str) -> Result<Vec<(usize, String)>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut start = 0;
+    let mut quote_column = None;
+
+    for (index, ch) in input.chars().enumerate() {
+        let column = index + 1;
+        if quote_column.is_none() {
+            if ch.is_whitespace() || ch == ')' {
+                if !current.is_empty() {
+                    tokens.push((start, std::mem::take(&mut current)));
+                }
+                if ch == ')' {
+                    tokens.push((column, ch.to_string()));
+                }
+                continue;
+            }
+            if ch == '(' && (current.is_empty() || current == "-") {
+                if current.is_empty() {
+                    start = column;
+                }
+                current.push(ch);
+                tokens.push((start, std::mem::take(&mut current)));
+                continue;
+            }
+        }
+        if current.is_empty() {
+            start = column;
+        }
+        if ch == '"' {
+            quote_column = match quote_column {
+                Some(_) => None,
+                None => Some(column),
+            };
+        }
+        current.push(ch);
+    }
+
+    if let Some(column) = quote_column {
+        return Err(QueryError {
+            column,
+            kind: QueryErrorKind::UnterminatedQuote,
+        });
+    }
+    if !current.is_empty() {
+        tokens.push((start, current));
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent over the tokens: `OR` chains of implicit-AND term lists,
+/// where a term is a single token or a parenthesized group.
+struct Parser<'a> {
+    tokens: std::iter::Peekable<std::vec::IntoIter<(usize, String)>>,
+    today: NaiveDate,
+    queues: &'a [Queue],
+}
+
+impl Parser<'_> {
+    /// Parses terms up to the end or an unmatched `)`, which is left for the
+    /// caller.
+    fn parse_any(&mut self) -> Result<Query, QueryError> {
+        let mut alternatives = vec![self.parse_all()?];
+        while let Some((column, _)) = self.tokens.next_if(|(_, token)| token == "OR") {
+            let dangling = QueryError {
+                column,
+                kind: QueryErrorKind::DanglingOr,
+            };
+            if alternatives.last().is_some_and(Query::is_empty) {
+                return Err(dangling);
+            }
+            let next = self.parse_all()?;
+            if next.is_empty() {
+                return Err(dangling);
+            }
+            alternatives.push(next);
+        }
+
+        if alternatives.len() == 1 {
+            return Ok(alternatives.remove(0));
+        }
+        Ok(Query {
+            terms: vec![Term {
+                negated: false,
+                predicate: Predicate::Any(alternatives),
+            }],
+        })
+    }
+
+    fn parse_all(&mut self) -> Result<Query, QueryError> {
+        let mut terms = Vec::new();
+        while let Some((column, token)) = self
+            .tokens
+            .next_if(|(_, token)| token != "OR" && token != ")")
+        {
+            let term = match token.as_str() {
+                "(" | "-(" => self.parse_group(column, token.starts_with('-'))?,
+                _ => parse_term(&token, column, self.today, self.queues)?,
+            };
+            terms.push(term);
+        }
+        Ok(Query { terms })
+    }
+
+    fn parse_group(&mut self, column: usize, negated: bool) -> Result<Term, QueryError> {
+        let error = |kind| QueryError { column, kind };
+        let mut inner = self.parse_any()?;
+        if self.tokens.next_if(|(_, token)| token == ")").is_none() {
+            return Err(error(QueryErrorKind::UnclosedGroup));
+        }
+        if inner.is_empty() {
+            return Err(error(QueryErrorKind::EmptyGroup));
+        }
+
+        let predicate = match inner.terms.as_slice() {
+            [
+                Term {
+                    negated: false,
+                    predicate: Predicate::Any(_),
+                },
+            ] => inner.terms.remove(0).predicate,
+            _ => Predicate::Any(vec![inner]),
+        };
+        Ok(Term { negated, predicate })
+    }
+}
+
+fn parse_term(
+    token: &str,
+    column: usize,
+    today: NaiveDate,
+    queues: &[Queue],
+) -> Result<Term, QueryError> {
+    let error = |kind| QueryError { column, kind };
+    let (negated, body) = match token.strip_prefix('-') {
+        Some(rest) if !rest.is_empty() => (true, rest),
+        _ => (false, token),
+    };
+
+    let field = split_field(body)
+        .and_then(|(name, op, value)| Some((name, Field::parse(name)?, op, value)));
+    let predicate = if body.starts_with('"') {
+        Predicate::Text(unquote(body).to_string())
+    } else if let Some(tag) = body.strip_prefix('#') {
+        Predicate::Tag(
+            normalize_tag(tag).map_err(|_| error(QueryErrorKind::InvalidTag(tag.to_string())))?,
+        )
+    } else if let Some((name, field, op, value)) = field {
+        let value = unquote(value);
+        if value.is_empty() {
+            return Err(error(QueryErrorKind::MissingValue(name.to_string())));
+        }
+        parse_field(name, field, op, value, today, queues).map_err(error)?
+    } else {
+        // Words like `https://example.com` or `foo:bar` are plain text.
+        Predicate::Text(body.to_string())
+    };
+
+    Ok(Term { negated, predicate })
+}
+
+/// Splits `name:value` or `name<op>value` where `name` is alphabetic.
+fn split_field(body: &str) -> Option<(&str, Option<CompareOp>, &str)> {
+    let split = body.find([':', '<', '>', '='])?;
+    let name = &body[..split];
+    if name.is_empty() || !name.chars().all(|ch| ch.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let rest = &body[split..];
+    let (op, value) = if let Some(value) = rest.strip_prefix(':') {
+        (None, value)
+    } else if let Some(value) = rest.strip_prefix("<=") {
+        (Some(CompareOp::Le), value)
+    } else if let Some(value) = rest.strip_prefix(">=") {
+        (Some(CompareOp::Ge), value)
+    } else if let Some(value) = rest.strip_prefix('<') {
+        (Some(CompareOp::Lt), value)
+    } else if let Some(value) = rest.strip_prefix('>') {
+        (Some(CompareOp::Gt), value)
+    } else {
+        (Some(CompareOp::Eq), &rest[1..])
+    };
+    Some((name, op, value))
+}
+
+/// The field names a `name:value` term may use; any other name is text.
+#[derive(Debug, Clone, Copy)]
+enum Field {
+    Date(DateField),
+    Text(TextField),
+    Queue,
+    Tag,
+    Priority,
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Self> {
+        let lowercase = name.to_ascii_lowercase();
+        if let Some(field) = DateField::parse(&lowercase) {
+            return Some(Self::Date(field));
+        }
+        let field = match lowercase.as_str() {
+            "queue" => Self::Queue,
+            "tag" => Self::Tag,
+            "priority" => Self::Priority,
+            "id" => Self::Text(TextField::Id),
+            "title" => Self::Text(TextField::Title),
+            "body" => Self::Text(TextField::Body),
+            _ => return None,
+        };
+        Some(field)
+    }
+}
+
+fn parse_field(
+    name: &str,
+    field: Field,
+    op: Option<CompareOp>,
+    value: &str,
+    today: NaiveDate,
+    queues: &[Queue],
+) -> Result<Predicate, QueryErrorKind> {
+    if op.is_some() && !matches!(field, Field::Date(_) | Field::Priority) {
+        return Err(QueryErrorKind::UnsupportedComparison(name.to_string()));
+    }
+
+    let predicate = match field {
+        Field::Date(field) => Predicate::Date {
+            field,
+            op: op.unwrap_or(CompareOp::Eq),
+            date: parse_query_date(value, today)
+                .ok_or_else(|| QueryErrorKind::InvalidDate(value.to_string()))?,
+        },
+        Field::Text(field) => Predicate::Field {
+            field,
+            value: value.to_string(),
+        },
+        Field::Queue => {
+            let queue = value
+                .parse()
+                .map_err(|_| QueryErrorKind::InvalidQueue(value.to_string()))?;
+            if !queues.contains(&queue) {
+                return Err(QueryErrorKind::UnknownQueue(value.to_string()));
+            }
+            Predicate::Queue(queue)
+        }
+        Field::Tag => Predicate::Tag(
+            normalize_tag(value).map_err(|_| QueryErrorKind::InvalidTag(value.to_string()))?,
+        ),
+        Field::Priority => Predicate::Priority {
+            op: op.unwrap_or(CompareOp::Eq),
+            priority: value
+                .parse()
+                .map_err(|_| QueryErrorKind::InvalidPriority(value.to_string()))?,
+        },
+    };
+    Ok(predicate)
+}
+
+/// Accepts everything `parse_date` does, plus signed offsets without a
+/// leading `+` (`7d`, `-2w`, `1m`) so that `due<7d` reads naturally.
+fn parse_query_date(value: &str, today: NaiveDate) -> Option<NaiveDate> {
+    if let Ok(date) = parse_date(value, today) {
+        return Some(date);
+    }
+
+    let (backwards, offset) = match value.strip_prefix('-') {
+        Some(offset) => (true, offset),
+        None => (false, value),
+    };
+    let unit = offset.chars().last()?;
+    let amount = offset[..offset.len() - unit.len_utf8()]
+        .parse::<u32>()
+        .ok()?;
+    match (unit, backwards) {
+        ('d', false) => today.checked_add_days(Days::new(u64::from(amount))),
+        ('d', true) => today.checked_sub_days(Days::new(u64::from(amount))),
+        ('w', false) => today.checked_add_days(Days::new(u64::from(amount) * 7)),
+        ('w', true) => today.checked_sub_days(Days::new(u64::from(amount) * 7)),
+        ('m', false) => today.checked_add_months(Months::new(amount)),
+        ('m', true) => today.checked_sub_months(Months::new(amount)),
+        _ => None,
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|inner| inner.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CompareOp, DateField, Predicate, Query, QueryErrorKind, TextField};
+    use crate::domain::priority::Priority;
+    use crate::domain::task::{Queue, Task};
+    use chrono::NaiveDate;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().expect("date should parse")
+    }
+
+    fn today() -> NaiveDate {
+        date("2026-10-14")
+    }
+
+    fn parse(input: &str) -> Query {
+        Query::parse(input, today(), Queue::all()).expect("query should parse")
+    }
+
+    fn task() -> Task {
+        let mut task = Task::new(
+            "20260309-aws-billing",
+            "Reply to AWS billing alert",
+            "2026-03-09T12:00:00Z"
+                .parse()
+                .expect("timestamp should parse"),
+        );
+        task.body = "Check cost explorer".to_string();
+        task.tags = vec!["ops".to_string()];
+        task
+    }
+
+    #[test]
+    fn parses_the_documented_example() {
+        let query =
+            parse(r#"queue:now tag:ops due<7d -title:draft "exact phrase" created>2026-01-01"#);
+        let predicates = query
+            .terms()
+            .iter()
+            .map(|term| (term.negated, term.predicate.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            predicates,
+            vec![
+                (false, Predicate::Queue(Queue::Now)),
+                (false, Predicate::Tag("ops".to_string())),
+                (
+                    false,
+                    Predicate::Date {
+                        field: DateField::Due,
+                        op: CompareOp::Lt,
+                        date: date("2026-10-21"),
+                    }
+                ),
+                (
+                    true,
+                    Predicate::Field {
+                        field: TextField::Title,
+                        value: "draft".to_string(),
+                    }
+                ),
+                (false, Predicate::Text("exact phrase".to_string())),
+                (
+                    false,
+                    Predicate::Date {
+                        field: DateField::Created,
+                        op: CompareOp::Gt,
+                        date: date("2026-01-01"),
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn bare_text_searches_title_body_and_tags() {
+        let task = task();
+        assert!(parse("billing").matches(&task));
+        assert!(parse("\"cost explorer\"").matches(&task));
+        assert!(parse("#ops").matches(&task));
+        assert!(parse("billing explorer").matches(&task));
+        assert!(!parse("missing").matches(&task));
+        assert!(!parse("\"billing explorer\"").matches(&task));
+    }
+
+    #[test]
+    fn field_terms_and_negation() {
+        let task = task();
+        assert!(parse("queue:inbox tag:OPS").matches(&task));
+        assert!(parse("id:20260309").matches(&task));
+        assert!(!parse("id:aws").matches(&task));
+        assert!(parse("title:\"AWS billing\"").matches(&task));
+        assert!(!parse("-title:billing").matches(&task));
+        assert!(parse("-queue:done -body:draft").matches(&task));
+    }
+
+    #[test]
+    fn unknown_field_names_are_plain_text() {
+        let query = parse("owner:me https://example.com/a");
+        let predicates = query
+            .terms()
+            .iter()
+            .map(|term| term.predicate.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            predicates,
+            vec![
+                Predicate::Text("owner:me".to_string()),
+                Predicate::Text("https://example.com/a".to_string()),
+            ]
+        );
+
+        let mut task = task();
+        task.body = "See https://example.com/a".to_string();
+        assert!(parse("https://example.com/a").matches(&task));
+        assert!(!parse("owner:me").matches(&task));
+    }
+
+    #[test]
+    fn or_and_groups_combine_terms() {
+        let task = task();
+        assert!(parse("tag:infra OR tag:ops").matches(&task));
+        assert!(!parse("tag:infra OR queue:done").matches(&task));
+        assert!(parse("queue:now OR billing explorer").matches(&task));
+        assert!(!parse("queue:now OR billing missing").matches(&task));
+        assert!(parse("billing (queue:now OR queue:inbox)").matches(&task));
+        assert!(!parse("billing -(queue:now OR queue:inbox)").matches(&task));
+        assert!(parse("(billing missing) OR (tag:ops)").matches(&task));
+        assert!(parse("((tag:ops))").matches(&task));
+        assert!(!parse("billing or missing").matches(&task));
+        assert_eq!(
+            parse("\"(draft)\"").terms()[0].predicate,
+            Predicate::Text("(draft)".to_string())
+        );
+
+        let query = parse("a OR b c");
+        assert_eq!(query.terms().len(), 1);
+        let Predicate::Any(alternatives) = &query.terms()[0].predicate else {
+            panic!("OR should build an Any predicate");
+        };
+        assert_eq!(alternatives.len(), 2);
+        assert_eq!(alternatives[1].terms().len(), 2);
+    }
+
+    #[test]
+    fn reports_group_errors_with_columns() {
+        let error =
+            Query::parse("tag:ops (queue:now", today(), Queue::all()).expect_err("group is open");
+        assert_eq!(error.kind, QueryErrorKind::UnclosedGroup);
+        assert_eq!(error.column, 9);
+
+        let error =
+            Query::parse("tag:ops)", today(), Queue::all()).expect_err("paren is unmatched");
+        assert_eq!(error.kind, QueryErrorKind::UnmatchedParen);
+        assert_eq!(error.column, 8);
+
+        let error = Query::parse("a -()", today(), Queue::all()).expect_err("group is empty");
+        assert_eq!(error.kind, QueryErrorKind::EmptyGroup);
+        assert_eq!(error.column, 3);
+
+        for (input, column) in [("OR a", 1), ("a OR", 3), ("a OR OR b", 3), ("(a OR) b", 4)] {
+            let error = Query::parse(input, today(), Queue::all()).expect_err("OR is dangling");
+            assert_eq!(error.kind, QueryErrorKind::DanglingOr, "{input}");
+            assert_eq!(error.column, column, "{input}");
+        }
+    }
+
+    #[test]
+    fn priority_terms_compare_by_urgency() {
+        let mut task = task();
+        assert!(!parse("priority:p1").matches(&task));
+        assert!(parse("-priority<p3").matches(&task));
+
+        task.priority = Some(Priority::P1);
+        assert!(parse("priority:p1").matches(&task));
+        assert!(parse("priority:high").matches(&task));
+        assert!(parse("priority<=p1 priority>p0").matches(&task));
+        assert!(!parse("priority<p1").matches(&task));
+
+        let error = Query::parse("priority:urgent", today(), Queue::all())
+            .expect_err("priority is invalid");
+        assert_eq!(
+            error.kind,
+            QueryErrorKind::InvalidPriority("urgent".to_string())
+        );
+    }
+
+    #[test]
+    fn date_comparisons_skip_tasks_without_the_date() {
+        let mut task = task();
+        assert!(!parse("due<7d").matches(&task));
+        assert!(parse("-due<7d").matches(&task));
+
+        task.due = Some(date("2026-10-16"));
+        assert!(parse("due<7d").matches(&task));
+        assert!(parse("due>=today").matches(&task));
+        assert!(!parse("due<=2026-10-15").matches(&task));
+        assert!(parse("created<-30d updated:2026-03-09").matches(&task));
+    }
+
+    #[test]
+    fn split_text_separates_free_text_from_filters() {
+        let (text, rest) = parse("aws -draft tag:ops billing").split_text();
+        assert_eq!(text, "aws billing");
+        assert_eq!(rest.terms().len(), 2);
+    }
+
+    #[test]
+    fn reports_errors_with_columns() {
+        let error =
+            Query::parse("tag:ops due<soon", today(), Queue::all()).expect_err("date is invalid");
+        assert_eq!(error.column, 9);
+        assert!(
+            error
+                .to_string()
+                .starts_with("invalid query at column 9: invalid date 'soon'")
+        );
+
+        let error =
+            Query::parse("title:\"draft", today(), Queue::all()).expect_err("quote is open");
+        assert_eq!(error.kind, QueryErrorKind::UnterminatedQuote);
+        assert_eq!(error.column, 7);
+
+        let error = Query::parse("queue:9am", today(), Queue::all()).expect_err("queue is invalid");
+        assert_eq!(error.kind, QueryErrorKind::InvalidQueue("9am".to_string()));
+
+        let error =
+            Query::parse("queue:nosuch", today(), Queue::all()).expect_err("queue is unknown");
+        assert_eq!(
+            error.kind,
+            QueryErrorKind::UnknownQueue("nosuch".to_string())
+        );
+        assert_eq!(
+            error.to_string(),
+            "invalid query at column 1: queue 'nosuch' is not configured"
+        );
+
+        let error =
+            Query::parse("due<soonish", today(), Queue::all()).expect_err("date is invalid");
+        assert_eq!(
+            error.kind,
+            QueryErrorKind::InvalidDate("soonish".to_string())
+        );
+
+        let error =
+            Query::parse("tag>ops", today(), Queue::all()).expect_err("tags are not ordered");
+        assert_eq!(
+            error.kind,
+            QueryErrorKind::UnsupportedComparison("tag".to_string())
+        );
+
+        let error = Query::parse("title:", today(), Queue::all()).expect_err("value is missing");
+        assert_eq!(
+            error.kind,
+            QueryErrorKind::MissingValue("title".to_string())
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use super::task::Task;
+
+/// Gap left between neighbouring ranks, so most reorders rewrite one file.
+pub const RANK_STEP: i64 = 1024;
+
+/// Where `rerank` puts a task within its queue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RankPosition {
+    Top,
+    Bottom,
+    Before(String),
+    After(String),
+}
+
+impl fmt::Display for RankPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Top => f.write_str("at the top"),
+            Self::Bottom => f.write_str("at the bottom"),
+            Self::Before(id) => write!(f, "before {id}"),
+            Self::After(id) => write!(f, "after {id}"),
+        }
+    }
+}
+
+/// Queue order: ranked tasks first by rank, then unranked tasks most recently
+/// updated first, with ids breaking ties.
+pub fn compare(left: &Task, right: &Task) -> Ordering {
+    match (left.rank, right.rank) {
+        (Some(left_rank), Some(right_rank)) => left_rank.cmp(&right_rank),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => right.updated_at.cmp(&left.updated_at),
+    }
+    .then_with(|| left.id.cmp(&right.id))
+}
+
+/// Returns the `(id, rank)` pairs that move `id` to `position` within
+/// `queue_tasks`, which must be in queue order. Only the moved task is ranked
+/// when there is room between its new neighbours; otherwise every task up to
+/// it, and every task ranked before, is renumbered `RANK_STEP` apart.
+/// Returns `None` when `id` or the task named by `position` is not listed.
+pub fn rerank(
+    queue_tasks: &[&Task],
+    id: &str,
+    position: &RankPosition,
+) -> Option<Vec<(String, i64)>> {
+    let mut order = queue_tasks.to_vec();
+    let from = order.iter().position(|task| task.id == id)?;
+    let moved = order.remove(from);
+    let to = match position {
+        RankPosition::Top => 0,
+        RankPosition::Bottom => order.len(),
+        RankPosition::Before(other) => order.iter().position(|task| task.id == *other)?,
+        RankPosition::After(other) => order.iter().position(|task| task.id == *other)? + 1,
+    };
+    order.insert(to, moved);
+
+    let previous = to.checked_sub(1).map(|index| order[index].rank);
+    let next = order.get(to + 1).map(|task| task.rank);
+    let rank = match (previous, next) {
+        (None, Some(Some(next))) => Some(next - RANK_STEP),
+        (None, _) => Some(moved.rank.unwrap_or(RANK_STEP)),
+        (Some(Some(previous)), None | Some(None)) => Some(previous + RANK_STEP),
+        (Some(Some(previous)), Some(Some(next))) if next - previous > 1 => {
+            Some(previous + (next - previous) / 2)
+        }
+        _ => None,
+    };
+    if let Some(rank) = rank {
+        return Some(if moved.rank == Some(rank) {
+            Vec::new()
+        } else {
+            vec![(moved.id.clone(), rank)]
+        });
+    }
+
+    let last = order
+        .iter()
+        .rposition(|task| task.rank.is_some() && task.id != moved.id)
+        .map_or(to, |last| last.max(to));
+    Some(
+        order[..=last]
+            .iter()
+            .zip(1..)
+            .filter_map(|(task, step)| {
+                let rank = step * RANK_STEP;
+                (task.rank != Some(rank)).then(|| (task.id.clone(), rank))
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RANK_STEP, RankPosition, compare, rerank};
+    use crate::domain::task::Task;
+
+    fn task(id: &str, rank: Option<i64>, updated_at: &str) -> Task {
+        let mut task = Task::new(
+            id,
+            format!("Task {id}"),
+            updated_at.parse().expect("timestamp should parse"),
+        );
+        task.rank = rank;
+        task
+    }
+
+    fn sorted(mut tasks: Vec<Task>) -> Vec<Task> {
+        tasks.sort_by(compare);
+        tasks
+    }
+
+    fn ids(tasks: &[Task]) -> Vec<&str> {
+        tasks.iter().map(|task| task.id.as_str()).collect()
+    }
+
+    fn apply(tasks: Vec<Task>, id: &str, position: RankPosition) -> Vec<Task> {
+        let changes = rerank(&tasks.iter().collect::<Vec<_>>(), id, &position)
+            .expect("tasks should be listed");
+        let mut tasks = tasks;
+        for (id, rank) in changes {
+            let task = tasks
+                .iter_mut()
+                .find(|task| task.id == id)
+                .expect("changed task should exist");
+            task.rank = Some(rank);
+        }
+        sorted(tasks)
+    }
+
+    #[test]
+    fn ranked_tasks_come_first_then_most_recently_updated() {
+        let tasks = sorted(vec![
+            task("old", None, "2026-03-01T00:00:00Z"),
+            task("new", None, "2026-03-05T00:00:00Z"),
+            task("second", Some(2048), "2026-03-01T00:00:00Z"),
+            task("first", Some(-5), "2026-03-01T00:00:00Z"),
+        ]);
+        assert_eq!(ids(&tasks), ["first", "second", "new", "old"]);
+    }
+
+    #[test]
+    fn moving_between_ranked_neighbours_only_ranks_the_moved_task() {
+        let tasks = vec![
+            task("a", Some(RANK_STEP), "2026-03-01T00:00:00Z"),
+            task("b", Some(2 * RANK_STEP), "2026-03-01T00:00:00Z"),
+            task("c", Some(3 * RANK_STEP), "2026-03-01T00:00:00Z"),
+        ];
+        let changes = rerank(
+            &tasks.iter().collect::<Vec<_>>(),
+            "c",
+            &RankPosition::Before("b".to_string()),
+        );
+        assert_eq!(changes, Some(vec![("c".to_string(), 1536)]));
+
+        let tasks = apply(tasks, "a", RankPosition::Bottom);
+        assert_eq!(ids(&tasks), ["b", "c", "a"]);
+        let tasks = apply(tasks, "a", RankPosition::Top);
+        assert_eq!(ids(&tasks), ["a", "b", "c"]);
+        let tasks = apply(tasks, "a", RankPosition::After("b".to_string()));
+        assert_eq!(ids(&tasks), ["b", "a", "c"]);
+    }
+
+    #[test]
+    fn moving_below_unranked_tasks_ranks_them_in_their_current_order() {
+        let tasks = sorted(vec![
+            task("a", None, "2026-03-03T00:00:00Z"),
+            task("b", None, "2026-03-02T00:00:00Z"),
+            task("c", None, "2026-03-01T00:00:00Z"),
+        ]);
+        let tasks = apply(tasks, "a", RankPosition::After("b".to_string()));
+        assert_eq!(ids(&tasks), ["b", "a", "c"]);
+        assert_eq!(tasks[0].rank, Some(RANK_STEP));
+        assert_eq!(tasks[1].rank, Some(2 * RANK_STEP));
+        assert_eq!(tasks[2].rank, None);
+
+        let tasks = apply(tasks, "c", RankPosition::Top);
+        assert_eq!(ids(&tasks), ["c", "b", "a"]);
+        assert_eq!(tasks[0].rank, Some(0));
+    }
+
+    #[test]
+    fn renumbers_when_neighbours_leave_no_gap() {
+        let tasks = vec![
+            task("a", Some(1), "2026-03-01T00:00:00Z"),
+            task("b", Some(2), "2026-03-01T00:00:00Z"),
+            task("c", Some(3), "2026-03-01T00:00:00Z"),
+        ];
+        let tasks = apply(tasks, "c", RankPosition::Before("b".to_string()));
+        assert_eq!(ids(&tasks), ["a", "c", "b"]);
+        assert_eq!(
+            tasks.iter().map(|task| task.rank).collect::<Vec<_>>(),
+            [Some(RANK_STEP), Some(2 * RANK_STEP), Some(3 * RANK_STEP)]
+        );
+    }
+
+    #[test]
+    fn rerank_rejects_unknown_tasks() {
+        let tasks = [task("a", None, "2026-03-01T00:00:00Z")];
+        let listed = tasks.iter().collect::<Vec<_>>();
+        assert_eq!(rerank(&listed, "zz", &RankPosition::Top), None);
+        assert_eq!(
+            rerank(&listed, "a", &RankPosition::Before("zz".to_string())),
+            None
+        );
+        assert_eq!(
+            rerank(&listed, "a", &RankPosition::Top),
+            Some(vec![("a".to_string(), RANK_STEP)])
+        );
+    }
+}
@@ -0,0 +1,352 @@
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use super::date::{next_weekday, parse_weekday};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurParseError {
+    input: String,
+}
+
+impl fmt::Display for RecurParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid recurrence '{}'; expected every <n>d/w/m/y, every weekday, every <mon..sun>, or monthly on <1..31>",
+            self.input
+        )
+    }
+}
+
+impl std::error::Error for RecurParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecurUnit {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl RecurUnit {
+    fn suffix(self) -> char {
+        match self {
+            Self::Day => 'd',
+            Self::Week => 'w',
+            Self::Month => 'm',
+            Self::Year => 'y',
+        }
+    }
+}
+
+/// How a task repeats once it is completed, stored in frontmatter as `recur`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum RecurRule {
+    /// `every 3d`, `every 1w`, `every 2m`, `every 1y`.
+    Every { interval: u32, unit: RecurUnit },
+    /// `every weekday`: Monday through Friday.
+    Weekdays,
+    /// `every fri`.
+    Weekly(Weekday),
+    /// `monthly on 15`; short months clamp to their last day.
+    MonthlyOn(u32),
+}
+
+impl RecurRule {
+    /// The first occurrence strictly after `date`, or `None` when it would
+    /// fall past the last date the calendar can represent, as a huge interval
+    /// like `every 4000000000d` does.
+    pub fn next_after(self, date: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Self::Every { interval, unit } => match unit {
+                RecurUnit::Day => date.checked_add_days(Days::new(u64::from(interval))),
+                RecurUnit::Week => date.checked_add_days(Days::new(u64::from(interval) * 7)),
+                RecurUnit::Month => date.checked_add_months(Months::new(interval)),
+                RecurUnit::Year => date.checked_add_months(Months::new(interval.checked_mul(12)?)),
+            },
+            Self::Weekdays => {
+                let mut next = date.checked_add_days(Days::new(1))?;
+                while matches!(next.weekday(), Weekday::Sat | Weekday::Sun) {
+                    next = next.checked_add_days(Days::new(1))?;
+                }
+                Some(next)
+            }
+            Self::Weekly(weekday) => {
+                // `next_weekday` is at most a week ahead.
+                date.checked_add_days(Days::new(7))?;
+                Some(next_weekday(date, weekday))
+            }
+            Self::MonthlyOn(day) => {
+                let this_month = day_in_month(date, day)?;
+                if this_month > date {
+                    Some(this_month)
+                } else {
+                    day_in_month(
+                        first_of_month(date).checked_add_months(Months::new(1))?,
+                        day,
+                    )
+                }
+            }
+        }
+    }
+
+    /// The first occurrence after `anchor` that also falls after `today`, so a
+    /// task completed late does not respawn already overdue. `None` when that
+    /// is past the end of the calendar.
+    ///
+    /// Month and year steps are counted from `anchor` rather than from the
+    /// previous step, so `every 1m` from January 31 lands on March 31 even
+    /// though February clamps to its last day.
+    pub fn next_after_today(self, anchor: NaiveDate, today: NaiveDate) -> Option<NaiveDate> {
+        if let Self::Every { interval, unit } = self {
+            let months = match unit {
+                RecurUnit::Month => Some(interval),
+                RecurUnit::Year => Some(interval.checked_mul(12)?),
+                RecurUnit::Day | RecurUnit::Week => None,
+            };
+            if let Some(months) = months {
+                for step in 1.. {
+                    let next = anchor.checked_add_months(Months::new(months.checked_mul(step)?))?;
+                    if next > today {
+                        return Some(next);
+                    }
+                }
+            }
+        }
+        let mut next = self.next_after(anchor)?;
+        while next <= today {
+            next = self.next_after(next)?;
+        }
+        Some(next)
+    }
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+/// `day` within the month of `date`, clamped to the month's last day.
+fn day_in_month(date: NaiveDate, day: u32) -> Option<NaiveDate> {
+    let first = first_of_month(date);
+    let last = first.checked_add_months(Months::new(1))?.pred_opt()?;
+    Some(first.with_day(day.min(last.day())).unwrap_or(last))
+}
+
+impl fmt::Display for RecurRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Every { interval, unit } => write!(f, "every {interval}{}", unit.suffix()),
+            Self::Weekdays => f.write_str("every weekday"),
+            Self::Weekly(weekday) => {
+                write!(f, "every {}", weekday.to_string().to_ascii_lowercase())
+            }
+            Self::MonthlyOn(day) => write!(f, "monthly on {day}"),
+        }
+    }
+}
+
+impl FromStr for RecurRule {
+    type Err = RecurParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let error = || RecurParseError {
+            input: input.to_string(),
+        };
+        let value = input.trim().to_ascii_lowercase();
+        let words = value.split_whitespace().collect::<Vec<_>>();
+
+        let rule = match words.as_slice() {
+            ["daily"] | ["every", "day"] => every(1, RecurUnit::Day),
+            ["weekly"] | ["every", "week"] => every(1, RecurUnit::Week),
+            ["monthly"] | ["every", "month"] => every(1, RecurUnit::Month),
+            ["yearly"] | ["every", "year"] => every(1, RecurUnit::Year),
+            ["every", "weekday"] => Self::Weekdays,
+            ["every", amount, unit] => {
+                let interval = parse_interval(amount).ok_or_else(error)?;
+                every(interval, parse_unit(unit).ok_or_else(error)?)
+            }
+            ["every", spec] => {
+                if let Some(weekday) = parse_weekday(spec) {
+                    Self::Weekly(weekday)
+                } else {
+                    let unit = spec.chars().last().ok_or_else(error)?;
+                    let amount = &spec[..spec.len() - unit.len_utf8()];
+                    let interval = parse_interval(amount).ok_or_else(error)?;
+                    every(interval, parse_unit(&unit.to_string()).ok_or_else(error)?)
+                }
+            }
+            ["monthly", "on", day] => {
+                let day = day.parse::<u32>().ok().ok_or_else(error)?;
+                if !(1..=31).contains(&day) {
+                    return Err(error());
+                }
+                Self::MonthlyOn(day)
+            }
+            _ => return Err(error()),
+        };
+        Ok(rule)
+    }
+}
+
+fn every(interval: u32, unit: RecurUnit) -> RecurRule {
+    RecurRule::Every { interval, unit }
+}
+
+fn parse_interval(value: &str) -> Option<u32> {
+    value.parse::<u32>().ok().filter(|interval| *interval > 0)
+}
+
+fn parse_unit(value: &str) -> Option<RecurUnit> {
+    let unit = match value {
+        "d" | "day" | "days" => RecurUnit::Day,
+        "w" | "week" | "weeks" => RecurUnit::Week,
+        "m" | "month" | "months" => RecurUnit::Month,
+        "y" | "year" | "years" => RecurUnit::Year,
+        _ => return None,
+    };
+    Some(unit)
+}
+
+impl TryFrom<String> for RecurRule {
+    type Error = RecurParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<RecurRule> for String {
+    fn from(rule: RecurRule) -> Self {
+        rule.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RecurRule, RecurUnit};
+    use chrono::{NaiveDate, Weekday};
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().expect("date should parse")
+    }
+
+    fn rule(value: &str) -> RecurRule {
+        value.parse().expect("rule should parse")
+    }
+
+    #[test]
+    fn parses_interval_rules() {
+        assert_eq!(
+            rule("every 1w"),
+            RecurRule::Every {
+                interval: 1,
+                unit: RecurUnit::Week
+            }
+        );
+        assert_eq!(rule("every 3 days"), rule("every 3d"));
+        assert_eq!(rule("Every Month"), rule("every 1m"));
+        assert_eq!(rule("daily"), rule("every 1d"));
+        assert!("every 0d".parse::<RecurRule>().is_err());
+        assert!("every 2x".parse::<RecurRule>().is_err());
+    }
+
+    #[test]
+    fn parses_weekday_and_monthly_rules() {
+        assert_eq!(rule("every weekday"), RecurRule::Weekdays);
+        assert_eq!(rule("every friday"), RecurRule::Weekly(Weekday::Fri));
+        assert_eq!(rule("monthly on 1"), RecurRule::MonthlyOn(1));
+        assert!("monthly on 32".parse::<RecurRule>().is_err());
+        let err = "sometimes"
+            .parse::<RecurRule>()
+            .expect_err("rule is invalid");
+        assert!(err.to_string().contains("invalid recurrence 'sometimes'"));
+    }
+
+    #[test]
+    fn display_roundtrips_through_parse() {
+        for value in [
+            "every 1w",
+            "every 3d",
+            "every 2m",
+            "every 1y",
+            "every weekday",
+            "every fri",
+            "monthly on 15",
+        ] {
+            assert_eq!(rule(value).to_string(), value);
+        }
+    }
+
+    #[test]
+    fn next_after_advances_by_interval() {
+        assert_eq!(
+            rule("every 3d").next_after(date("2026-10-14")),
+            Some(date("2026-10-17"))
+        );
+        assert_eq!(
+            rule("every 1w").next_after(date("2026-10-14")),
+            Some(date("2026-10-21"))
+        );
+        assert_eq!(
+            rule("every 1m").next_after(date("2026-01-31")),
+            Some(date("2026-02-28"))
+        );
+    }
+
+    #[test]
+    fn next_after_skips_weekends_for_weekday_rule() {
+        // 2026-10-16 is a Friday.
+        assert_eq!(
+            rule("every weekday").next_after(date("2026-10-16")),
+            Some(date("2026-10-19"))
+        );
+        assert_eq!(
+            rule("every weekday").next_after(date("2026-10-14")),
+            Some(date("2026-10-15"))
+        );
+    }
+
+    #[test]
+    fn monthly_on_picks_the_next_matching_day() {
+        let monthly = rule("monthly on 1");
+        assert_eq!(
+            monthly.next_after(date("2026-10-01")),
+            Some(date("2026-11-01"))
+        );
+        assert_eq!(
+            monthly.next_after(date("2026-10-14")),
+            Some(date("2026-11-01"))
+        );
+        let end = rule("monthly on 31");
+        assert_eq!(end.next_after(date("2026-10-31")), Some(date("2026-11-30")));
+        assert_eq!(end.next_after(date("2026-11-14")), Some(date("2026-11-30")));
+    }
+
+    #[test]
+    fn next_after_today_catches_up_past_today() {
+        let weekly = rule("every 1w");
+        assert_eq!(
+            weekly.next_after_today(date("2026-09-30"), date("2026-10-14")),
+            Some(date("2026-10-21"))
+        );
+    }
+
+    #[test]
+    fn next_after_today_keeps_the_anchor_day_across_short_months() {
+        let monthly = rule("every 1m");
+        assert_eq!(
+            monthly.next_after_today(date("2026-01-31"), date("2026-03-01")),
+            Some(date("2026-03-31"))
+        );
+        assert_eq!(
+            monthly.next_after_today(date("2026-01-31"), date("2026-02-15")),
+            Some(date("2026-02-28"))
+        );
+        assert_eq!(
+            rule("every 1y").next_after_today(date("2024-02-29"), date("2027-06-01")),
+            Some(date("2028-02-29"))
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use chrono::{DateTime, Datelike, Days, NaiveDate, TimeDelta, TimeZone, Utc};
+use std::{fmt, str::FromStr};
+
+use super::task::{Queue, Task};
+
+/// How many of the longest-waiting tasks are listed for each open queue.
+pub const OLDEST_PER_QUEUE: usize = 3;
+
+/// The length of one row in the completion breakdown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Bucket {
+    Day,
+    #[default]
+    Week,
+}
+
+impl Bucket {
+    /// The first day of the bucket containing `date`; weeks start on Monday.
+    pub fn start_of(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Day => date,
+            Self::Week => date - Days::new(u64::from(date.weekday().num_days_from_monday())),
+        }
+    }
+
+    fn step(self) -> Days {
+        match self {
+            Self::Day => Days::new(1),
+            Self::Week => Days::new(7),
+        }
+    }
+}
+
+impl fmt::Display for Bucket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Day => "day",
+            Self::Week => "week",
+        })
+    }
+}
+
+impl FromStr for Bucket {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "day" => Ok(Self::Day),
+            "week" => Ok(Self::Week),
+            _ => Err(format!("invalid bucket '{value}'; expected day or week")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatsOptions {
+    pub since: DateTime<Utc>,
+    pub by: Bucket,
+    /// Only tasks carrying this tag are counted.
+    pub tag: Option<String>,
+    /// Queues to report ages for, in display order; done is skipped.
+    pub queues: Vec<Queue>,
+}
+
+/// Throughput and waiting-time figures for the tasks in a repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stats {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub by: Bucket,
+    pub tag: Option<String>,
+    /// Tasks completed within the window.
+    pub completed: usize,
+    /// Completions per bucket, oldest first, including empty buckets.
+    pub periods: Vec<Period>,
+    /// From `created_at` to `completed_at` for the tasks completed within the window.
+    pub lead_time: Option<LeadTime>,
+    pub inbox: InboxFlow,
+    pub queues: Vec<QueueAges>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Period {
+    pub start: NaiveDate,
+    pub completed: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeadTime {
+    pub median: TimeDelta,
+    pub mean: TimeDelta,
+    pub max: TimeDelta,
+}
+
+/// Tasks entering and leaving the inbox within the window. Tasks moved
+/// before queue history was recorded only count as inflow when created.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InboxFlow {
+    pub inflow: usize,
+    pub outflow: usize,
+}
+
+/// How long the open tasks in a queue have been waiting there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueAges {
+    pub queue: Queue,
+    pub open: usize,
+    pub median_age: Option<TimeDelta>,
+    /// The longest-waiting tasks, oldest first.
+    pub oldest: Vec<AgedTask>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgedTask {
+    pub id: String,
+    pub title: String,
+    /// Time since the task entered its current queue.
+    pub age: TimeDelta,
+}
+
+/// Computes stats for `tasks` up to `now`; buckets follow the calendar days
+/// of `now`'s time zone.
+pub fn compute<Tz: TimeZone>(tasks: &[Task], options: &StatsOptions, now: DateTime<Tz>) -> Stats {
+    let zone = now.timezone();
+    let until = now.with_timezone(&Utc);
+    let tasks = tasks
+        .iter()
+        .filter(|task| options.tag.as_ref().is_none_or(|tag| task.has_tag(tag)))
+        .collect::<Vec<_>>();
+    let in_window = |at: DateTime<Utc>| at >= options.since && at <= until;
+
+    let completed = tasks
+        .iter()
+        .filter_map(|task| {
+            let completed_at = task.completed_at.filter(|at| in_window(*at))?;
+            Some((task, completed_at))
+        })
+        .collect::<Vec<_>>();
+
+    let first = options
+        .by
+        .start_of(options.since.with_timezone(&zone).date_naive());
+    let last = now.date_naive();
+    let mut periods = Vec::new();
+    let mut start = first;
+    while start <= last {
+        periods.push(Period {
+            start,
+            completed: 0,
+        });
+        let Some(next) = start.checked_add_days(options.by.step()) else {
+            break;
+        };
+        start = next;
+    }
+    for (_, completed_at) in &completed {
+        let bucket = options
+            .by
+            .start_of(completed_at.with_timezone(&zone).date_naive());
+        if let Some(period) = periods.iter_mut().find(|period| period.start == bucket) {
+            period.completed += 1;
+        }
+    }
+
+    let mut lead_times = completed
+        .iter()
+        .map(|(task, completed_at)| *completed_at - task.created_at)
+        .collect::<Vec<_>>();
+    lead_times.sort();
+    let lead_time = median(&lead_times).map(|median| LeadTime {
+        median,
+        mean: lead_times.iter().sum::<TimeDelta>() / lead_times.len() as i32,
+        max: lead_times[lead_times.len() - 1],
+    });
+
+    let mut inbox = InboxFlow::default();
+    for task in &tasks {
+        let stints = task.queue_stints();
+        if stints[0].queue == Queue::Inbox && in_window(task.created_at) {
+            inbox.inflow += 1;
+        }
+        for transition in task.history.iter().filter(|t| in_window(t.at)) {
+            if transition.to == Queue::Inbox {
+                inbox.inflow += 1;
+            }
+            if transition.from == Queue::Inbox {
+                inbox.outflow += 1;
+            }
+        }
+    }
+
+    let queues = options
+        .queues
+        .iter()
+        .filter(|queue| !queue.is_done())
+        .map(|queue| {
+            let mut aged = tasks
+                .iter()
+                .filter(|task| task.queue == *queue)
+                .map(|task| AgedTask {
+                    id: task.id.clone(),
+                    title: task.title.clone(),
+                    age: task
+                        .queue_stints()
+                        .last()
+                        .map_or(TimeDelta::zero(), |stint| stint.duration(until)),
+                })
+                .collect::<Vec<_>>();
+            aged.sort_by(|left, right| right.age.cmp(&left.age).then(left.id.cmp(&right.id)));
+            let mut ages = aged.iter().map(|task| task.age).collect::<Vec<_>>();
+            ages.sort();
+            QueueAges {
+                queue: *queue,
+                open: aged.len(),
+                median_age: median(&ages),
+                oldest: aged.into_iter().take(OLDEST_PER_QUEUE).collect(),
+            }
+        })
+        .collect();
+
+    Stats {
+        since: options.since,
+        until,
+        by: options.by,
+        tag: options.tag.clone(),
+        completed: completed.len(),
+        periods,
+        lead_time,
+        inbox,
+        queues,
+    }
+}
+
+/// The middle of sorted `values`, averaging the two middle ones for an even count.
+fn median(values: &[TimeDelta]) -> Option<TimeDelta> {
+    let middle = values.len() / 2;
+    match values.len() {
+        0 => None,
+        len if len % 2 == 1 => Some(values[middle]),
+        _ => Some((values[middle - 1] + values[middle]) / 2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bucket, InboxFlow, StatsOptions, compute};
+    use crate::domain::task::{Queue, Task};
+    use chrono::{DateTime, TimeDelta, Utc};
+
+    fn timestamp(value: &str) -> DateTime<Utc> {
+        value.parse().expect("timestamp should parse")
+    }
+
+    fn task(id: &str, created: &str) -> Task {
+        Task::new(id, format!("Task {id}"), timestamp(created))
+    }
+
+    fn options(by: Bucket) -> StatsOptions {
+        StatsOptions {
+            since: timestamp("2026-03-01T00:00:00Z"),
+            by,
+            tag: None,
+            queues: Queue::all().to_vec(),
+        }
+    }
+
+    fn sample() -> Vec<Task> {
+        let mut shipped = task("a1", "2026-03-02T12:00:00Z");
+        shipped.move_to(Queue::Next, timestamp("2026-03-03T12:00:00Z"));
+        shipped.move_to(Queue::Done, timestamp("2026-03-06T12:00:00Z"));
+        shipped.tags = vec!["ops".to_string()];
+
+        let mut quick = task("b2", "2026-03-09T12:00:00Z");
+        quick.move_to(Queue::Done, timestamp("2026-03-10T12:00:00Z"));
+
+        let mut old = task("c3", "2026-02-01T12:00:00Z");
+        old.move_to(Queue::Done, timestamp("2026-02-10T12:00:00Z"));
+
+        let mut waiting = task("d4", "2026-02-20T12:00:00Z");
+        waiting.move_to(Queue::Later, timestamp("2026-03-04T12:00:00Z"));
+
+        let fresh = task("e5", "2026-03-11T12:00:00Z");
+        let stale = task("f6", "2026-02-15T12:00:00Z");
+        vec![shipped, quick, old, waiting, fresh, stale]
+    }
+
+    #[test]
+    fn counts_completions_per_bucket_with_lead_times() {
+        let now = timestamp("2026-03-12T12:00:00Z");
+        let stats = compute(&sample(), &options(Bucket::Week), now);
+
+        assert_eq!(stats.completed, 2);
+        let periods = stats
+            .periods
+            .iter()
+            .map(|period| (period.start.to_string(), period.completed))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            periods,
+            [
+                ("2026-02-23".to_string(), 0),
+                ("2026-03-02".to_string(), 1),
+                ("2026-03-09".to_string(), 1)
+            ]
+        );
+
+        let lead_time = stats.lead_time.expect("lead time");
+        assert_eq!(lead_time.median, TimeDelta::days(2) + TimeDelta::hours(12));
+        assert_eq!(lead_time.mean, lead_time.median);
+        assert_eq!(lead_time.max, TimeDelta::days(4));
+
+        let daily = compute(&sample(), &options(Bucket::Day), now);
+        assert_eq!(daily.periods.len(), 12);
+        assert_eq!(daily.periods[5].completed, 1);
+    }
+
+    #[test]
+    fn reports_inbox_flow_and_queue_ages() {
+        let now = timestamp("2026-03-12T12:00:00Z");
+        let stats = compute(&sample(), &options(Bucket::Week), now);
+
+        assert_eq!(
+            stats.inbox,
+            InboxFlow {
+                inflow: 3,
+                outflow: 3
+            }
+        );
+
+        let inbox = &stats.queues[0];
+        assert_eq!(inbox.queue, Queue::Inbox);
+        assert_eq!(inbox.open, 2);
+        assert_eq!(inbox.oldest[0].id, "f6");
+        assert_eq!(inbox.oldest[0].age, TimeDelta::days(25));
+        assert_eq!(inbox.median_age, Some(TimeDelta::days(13)));
+
+        let later = &stats.queues[3];
+        assert_eq!(later.oldest[0].age, TimeDelta::days(8));
+        assert_eq!(stats.queues[1].median_age, None);
+    }
+
+    #[test]
+    fn filters_by_tag_and_parses_buckets() {
+        let mut options = options(Bucket::Week);
+        options.tag = Some("OPS".to_string());
+        let stats = compute(&sample(), &options, timestamp("2026-03-12T12:00:00Z"));
+        assert_eq!(stats.completed, 1);
+        assert!(stats.queues.iter().all(|queue| queue.open == 0));
+
+        assert_eq!("Day".parse::<Bucket>(), Ok(Bucket::Day));
+        assert_eq!(
+            "month".parse::<Bucket>().expect_err("unknown bucket"),
+            "invalid bucket 'month'; expected day or week"
+        );
+    }
+}
@@ -1,15 +1,20 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use std::{fmt, str::FromStr};
+use std::{fmt, str::FromStr, sync::Arc, time::SystemTime};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
-#[serde(rename_all = "lowercase")]
+use super::date::{DueStatus, due_status};
+use super::priority::Priority;
+use super::recur::RecurRule;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Queue {
     Inbox,
     Now,
     Next,
     Later,
     Done,
+    /// A queue defined in the `[queues.<name>]` config tables.
+    Custom(QueueName),
 }
 
 impl Queue {
@@ -21,6 +26,8 @@ impl Queue {
         Queue::Done,
     ];
 
+    /// The built-in queues. Custom queues come from config; see
+    /// `QueueDirs::queues` for the full ordered list.
     pub fn all() -> &'static [Queue] {
         &Self::ORDERED
     }
@@ -28,6 +35,24 @@ impl Queue {
     pub fn is_done(self) -> bool {
         matches!(self, Self::Done)
     }
+
+    /// The next more urgent open queue: `later` → `next` → `now`.
+    pub fn promoted(self) -> Option<Queue> {
+        match self {
+            Self::Next => Some(Self::Now),
+            Self::Later => Some(Self::Next),
+            Self::Inbox | Self::Now | Self::Done | Self::Custom(_) => None,
+        }
+    }
+
+    /// The next less urgent open queue: `now` → `next` → `later`.
+    pub fn demoted(self) -> Option<Queue> {
+        match self {
+            Self::Now => Some(Self::Next),
+            Self::Next => Some(Self::Later),
+            Self::Inbox | Self::Later | Self::Done | Self::Custom(_) => None,
+        }
+    }
 }
 
 impl fmt::Display for Queue {
@@ -38,6 +63,7 @@ impl fmt::Display for Queue {
             Self::Next => "next",
             Self::Later => "later",
             Self::Done => "done",
+            Self::Custom(name) => name.as_str(),
         };
 
         f.write_str(value)
@@ -59,15 +85,254 @@ impl FromStr for Queue {
     type Err = QueueParseError;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        match value.trim().to_ascii_lowercase().as_str() {
+        let value = value.trim().to_ascii_lowercase();
+        match value.as_str() {
             "inbox" => Ok(Self::Inbox),
             "now" => Ok(Self::Now),
             "next" => Ok(Self::Next),
             "later" => Ok(Self::Later),
             "done" => Ok(Self::Done),
-            _ => Err(QueueParseError),
+            _ => QueueName::new(&value).map(Self::Custom),
+        }
+    }
+}
+
+impl Serialize for Queue {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Queue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value
+            .parse()
+            .map_err(|_| serde::de::Error::custom(format!("invalid queue '{value}'")))
+    }
+}
+
+/// The longest custom queue name, so that names fit in a [`QueueName`].
+pub const MAX_QUEUE_NAME_LEN: usize = 32;
+
+/// The name of a custom queue, kept inline so that `Queue` stays `Copy`.
+///
+/// Any well-formed name parses; whether it is a queue the config defines is
+/// up to the caller to check against `QueueDirs`, which holds that set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct QueueName {
+    bytes: [u8; MAX_QUEUE_NAME_LEN],
+    len: u8,
+}
+
+impl QueueName {
+    /// Names are lowercase letters, digits, `-`, and `_`, start with a letter,
+    /// are at most [`MAX_QUEUE_NAME_LEN`] long, and cannot shadow a built-in
+    /// queue or `all`.
+    pub fn new(name: &str) -> Result<Self, QueueParseError> {
+        let valid = name.chars().next().is_some_and(|c| c.is_ascii_lowercase())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+            && name.len() <= MAX_QUEUE_NAME_LEN
+            && name != "all"
+            && !Queue::ORDERED.iter().any(|queue| queue.to_string() == name);
+        if !valid {
+            return Err(QueueParseError);
+        }
+
+        let mut bytes = [0; MAX_QUEUE_NAME_LEN];
+        bytes[..name.len()].copy_from_slice(name.as_bytes());
+        Ok(Self {
+            bytes,
+            len: u8::try_from(name.len()).map_err(|_| QueueParseError)?,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.bytes[..usize::from(self.len)]).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagParseError;
+
+impl fmt::Display for TagParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid tag")
+    }
+}
+
+impl std::error::Error for TagParseError {}
+
+/// Normalizes a user-supplied tag: surrounding whitespace and a leading `#`
+/// are dropped, and the result must be non-empty without inner whitespace or commas.
+pub fn normalize_tag(value: &str) -> Result<String, TagParseError> {
+    let tag = value.trim();
+    let tag = tag.strip_prefix('#').unwrap_or(tag);
+    if tag.is_empty() || tag.chars().any(|ch| ch.is_whitespace() || ch == ',') {
+        return Err(TagParseError);
+    }
+
+    Ok(tag.to_string())
+}
+
+/// Renders tags as space-separated `#tag` labels.
+pub fn format_tags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|tag| format!("#{tag}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Frontmatter keys that tqs does not manage, kept in file order.
+///
+/// Each entry stores the original YAML text of the key so that rewriting a
+/// task reproduces untouched keys byte-for-byte.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ExtraFrontmatter {
+    entries: Vec<(String, String)>,
+}
+
+impl ExtraFrontmatter {
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(key, _)| key.as_str())
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.entries.iter().any(|(existing, _)| existing == key)
+    }
+
+    /// Parses the stored YAML for `key` into a value.
+    pub fn get(&self, key: &str) -> Option<serde_yaml::Value> {
+        let raw = self.raw(key)?;
+        let mapping: serde_yaml::Mapping = serde_yaml::from_str(raw).ok()?;
+        mapping.into_iter().next().map(|(_, value)| value)
+    }
+
+    /// Returns the verbatim YAML text stored for `key`, including the key itself.
+    pub fn raw(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(existing, _)| existing == key)
+            .map(|(_, raw)| raw.as_str())
+    }
+
+    /// Sets `key` to `value`, keeping its position if it already exists.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        value: serde_yaml::Value,
+    ) -> Result<(), serde_yaml::Error> {
+        let key = key.into();
+        let mut mapping = serde_yaml::Mapping::new();
+        mapping.insert(serde_yaml::Value::String(key.clone()), value);
+        let raw = serde_yaml::to_string(&mapping)?.trim_end().to_string();
+        self.insert_raw(key, raw);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, key: &str) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|(existing, _)| existing != key);
+        self.entries.len() != before
+    }
+
+    pub fn iter_raw(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(key, raw)| (key.as_str(), raw.as_str()))
+    }
+
+    pub(crate) fn insert_raw(&mut self, key: String, raw: String) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|(existing, _)| *existing == key)
+        {
+            Some(entry) => entry.1 = raw,
+            None => self.entries.push((key, raw)),
+        }
+    }
+}
+
+/// The version of a task file a [`Task`] was read from, so that writing the
+/// task back can tell whether the file changed in between.
+#[derive(Debug, Clone)]
+pub struct FileVersion {
+    /// The file's modification time when it was read.
+    pub modified: Option<SystemTime>,
+    /// Hash of the file's contents; see [`FileVersion::content_hash`].
+    pub hash: u64,
+    /// The task as it was read: the common base for merging an edit made to
+    /// the file since.
+    pub base: Arc<Task>,
+}
+
+impl FileVersion {
+    pub fn new(modified: Option<SystemTime>, hash: u64, task: &Task) -> Self {
+        Self {
+            modified,
+            hash,
+            base: Arc::new(Task {
+                loaded_from: None,
+                ..task.clone()
+            }),
         }
     }
+
+    /// FNV-1a over the file's bytes, which stays the same across builds so
+    /// the task index can keep it.
+    pub fn content_hash(content: &str) -> u64 {
+        let mut hash = 0xcbf29ce484222325u64;
+        for byte in content.bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        hash
+    }
+}
+
+/// Two versions are the same when the contents are; a file that was only
+/// touched has not changed.
+impl PartialEq for FileVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash
+    }
+}
+
+impl Eq for FileVersion {}
+
+/// One recorded move between queues.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueTransition {
+    pub from: Queue,
+    pub to: Queue,
+    pub at: DateTime<Utc>,
+}
+
+/// A span of time a task spent in one queue; `until` is `None` for the
+/// queue it is in now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueStint {
+    pub queue: Queue,
+    pub since: DateTime<Utc>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl QueueStint {
+    pub fn duration(&self, now: DateTime<Utc>) -> chrono::TimeDelta {
+        self.until.unwrap_or(now) - self.since
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -81,8 +346,32 @@ pub struct Task {
     pub completed_at: Option<DateTime<Utc>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub daily_note: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recur: Option<RecurRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_by: Vec<String>,
+    /// Queue transitions, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<QueueTransition>,
+    /// Manual position within the queue; lower ranks come first. See
+    /// `domain::rank`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rank: Option<i64>,
+    #[serde(skip)]
+    pub extra: ExtraFrontmatter,
     #[serde(default)]
     pub body: String,
+    /// Set when the task was read from a file; `None` for a new task.
+    #[serde(skip)]
+    pub loaded_from: Option<FileVersion>,
 }
 
 impl Task {
@@ -97,7 +386,17 @@ impl Task {
             updated_at: now,
             completed_at: None,
             daily_note: None,
+            due: None,
+            scheduled: None,
+            recur: None,
+            priority: None,
+            tags: Vec::new(),
+            blocked_by: Vec::new(),
+            history: Vec::new(),
+            rank: None,
+            extra: ExtraFrontmatter::default(),
             body: Self::default_body(&title),
+            loaded_from: None,
         }
     }
 
@@ -110,12 +409,134 @@ impl Task {
             return false;
         }
 
+        self.record_transition(queue, now);
         self.queue = queue;
+        // A rank only orders tasks within the queue it was set in.
+        self.rank = None;
         self.updated_at = now;
         self.completed_at = if queue.is_done() { Some(now) } else { None };
         true
     }
 
+    /// Appends a move from the current queue to `queue` to the history.
+    pub fn record_transition(&mut self, queue: Queue, at: DateTime<Utc>) {
+        self.history.push(QueueTransition {
+            from: self.queue,
+            to: queue,
+            at,
+        });
+    }
+
+    /// The queues the task has been in, oldest first. The first stint starts
+    /// at `created_at`; tasks moved before history was kept only show their
+    /// current queue since the last recorded transition, or since creation.
+    pub fn queue_stints(&self) -> Vec<QueueStint> {
+        let mut stints = Vec::with_capacity(self.history.len() + 1);
+        let mut queue = self.history.first().map_or(self.queue, |first| first.from);
+        let mut since = self.created_at;
+        for transition in &self.history {
+            stints.push(QueueStint {
+                queue,
+                since,
+                until: Some(transition.at),
+            });
+            queue = transition.to;
+            since = transition.at;
+        }
+        stints.push(QueueStint {
+            queue: self.queue,
+            since,
+            until: None,
+        });
+        stints
+    }
+
+    /// Due status for open tasks; completed tasks are never overdue.
+    pub fn due_status(&self, today: NaiveDate) -> Option<DueStatus> {
+        if self.queue.is_done() {
+            return None;
+        }
+        self.due.map(|due| due_status(due, today))
+    }
+
+    /// Tags compare case-insensitively.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(tag))
+    }
+
+    pub fn add_tag(&mut self, tag: impl Into<String>) -> bool {
+        let tag = tag.into();
+        if self.has_tag(&tag) {
+            return false;
+        }
+
+        self.tags.push(tag);
+        true
+    }
+
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let before = self.tags.len();
+        self.tags
+            .retain(|existing| !existing.eq_ignore_ascii_case(tag));
+        self.tags.len() != before
+    }
+
+    pub fn add_blocker(&mut self, id: impl Into<String>) -> bool {
+        let id = id.into();
+        if self.blocked_by.contains(&id) {
+            return false;
+        }
+
+        self.blocked_by.push(id);
+        true
+    }
+
+    pub fn remove_blocker(&mut self, id: &str) -> bool {
+        let before = self.blocked_by.len();
+        self.blocked_by.retain(|existing| existing != id);
+        self.blocked_by.len() != before
+    }
+
+    /// Builds the follow-up task for a recurring task, or `None` when the task
+    /// has no `recur` rule or its next occurrence is past the end of the
+    /// calendar.
+    ///
+    /// Dates move to the next occurrence after `today`, anchored on the due
+    /// date, then the scheduled date; `scheduled` keeps its lead time before
+    /// `due`. A task without dates gets `scheduled` set to the next occurrence.
+    /// Checked boxes in the body are cleared; unknown frontmatter is not copied.
+    pub fn next_occurrence(
+        &self,
+        id: impl Into<String>,
+        queue: Queue,
+        now: DateTime<Utc>,
+        today: NaiveDate,
+    ) -> Option<Task> {
+        let rule = self.recur?;
+        let anchor = self.due.or(self.scheduled).unwrap_or(today);
+        let next = rule.next_after_today(anchor, today)?;
+
+        let mut task = Task::new(id, self.title.clone(), now);
+        task.queue = queue;
+        task.recur = self.recur;
+        task.priority = self.priority;
+        task.tags = self.tags.clone();
+        task.body = reset_checkboxes(&self.body);
+        match (self.due, self.scheduled) {
+            (Some(due), scheduled) => {
+                task.due = Some(next);
+                task.scheduled = match scheduled {
+                    Some(scheduled) => Some(next.checked_sub_signed(due - scheduled)?),
+                    None => None,
+                };
+            }
+            (None, _) => task.scheduled = Some(next),
+        }
+        Some(task)
+    }
+
     pub fn normalize(&mut self, now: DateTime<Utc>) {
         self.updated_at = now;
         if self.queue.is_done() {
@@ -128,10 +549,28 @@ impl Task {
     }
 }
 
+/// Unchecks Markdown task list items (`- [x]`) so a repeated checklist starts fresh.
+fn reset_checkboxes(body: &str) -> String {
+    body.split('\n')
+        .map(|line| {
+            let indent = line.len() - line.trim_start().len();
+            let rest = &line[indent..];
+            for marker in ["- [x]", "- [X]", "* [x]", "* [X]"] {
+                if let Some(tail) = rest.strip_prefix(marker) {
+                    return format!("{}{} [ ]{tail}", &line[..indent], &marker[..1]);
+                }
+            }
+            line.to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Queue, Task};
-    use chrono::{DateTime, Utc};
+    use super::{ExtraFrontmatter, MAX_QUEUE_NAME_LEN, Queue, QueueName, Task, normalize_tag};
+    use crate::domain::date::DueStatus;
+    use chrono::{DateTime, NaiveDate, Utc};
 
     fn now() -> DateTime<Utc> {
         "2026-03-09T10:34:12Z"
@@ -148,7 +587,39 @@ mod tests {
 
     #[test]
     fn queue_rejects_invalid_names() {
-        assert!("archive".parse::<Queue>().is_err());
+        assert!("on hold".parse::<Queue>().is_err());
+        assert!("all".parse::<Queue>().is_err());
+        assert!("9lives".parse::<Queue>().is_err());
+        assert!("a".repeat(MAX_QUEUE_NAME_LEN + 1).parse::<Queue>().is_err());
+    }
+
+    #[test]
+    fn custom_queue_names_parse_and_roundtrip() {
+        let name = QueueName::new("review-later").expect("name should be valid");
+        let queue = Queue::Custom(name);
+        assert_eq!("Review-Later".parse::<Queue>(), Ok(queue));
+        assert_eq!(queue.to_string(), "review-later");
+        assert_eq!(QueueName::new("review-later"), Ok(name));
+
+        let rendered = serde_yaml::to_string(&queue).expect("queue should serialize");
+        let parsed: Queue = serde_yaml::from_str(&rendered).expect("queue should deserialize");
+        assert_eq!(parsed, queue);
+        assert_eq!(queue.promoted(), None);
+
+        assert!(QueueName::new("later").is_err());
+        assert!(QueueName::new("all").is_err());
+        assert!(QueueName::new("Waiting").is_err());
+        assert!(QueueName::new("on hold").is_err());
+    }
+
+    #[test]
+    fn promotes_and_demotes_between_open_queues() {
+        assert_eq!(Queue::Later.promoted(), Some(Queue::Next));
+        assert_eq!(Queue::Next.promoted(), Some(Queue::Now));
+        assert_eq!(Queue::Now.promoted(), None);
+        assert_eq!(Queue::Now.demoted(), Some(Queue::Next));
+        assert_eq!(Queue::Later.demoted(), None);
+        assert_eq!(Queue::Inbox.demoted(), None);
     }
 
     #[test]
@@ -192,6 +663,33 @@ mod tests {
         assert!(task.completed_at.is_none());
     }
 
+    #[test]
+    fn move_to_records_transitions_and_stints() {
+        let mut task = Task::new("task-1", "Ship v2", now());
+        let later = |value: &str| value.parse::<DateTime<Utc>>().unwrap();
+        task.move_to(Queue::Next, later("2026-03-10T10:34:12Z"));
+        task.move_to(Queue::Next, later("2026-03-11T10:34:12Z"));
+        task.move_to(Queue::Done, later("2026-03-13T10:34:12Z"));
+
+        assert_eq!(task.history.len(), 2);
+        assert_eq!(task.history[0].from, Queue::Inbox);
+        assert_eq!(task.history[1].to, Queue::Done);
+        let stints = task.queue_stints();
+        let summary = stints
+            .iter()
+            .map(|stint| {
+                (
+                    stint.queue,
+                    stint.duration(later("2026-03-14T10:34:12Z")).num_days(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![(Queue::Inbox, 1), (Queue::Next, 3), (Queue::Done, 1)]
+        );
+    }
+
     #[test]
     fn normalize_clears_completed_at_outside_done() {
         let mut task = Task::new("task-1", "Ship v2", now());
@@ -218,4 +716,112 @@ mod tests {
         assert_eq!(task.updated_at, normalized_at);
         assert_eq!(task.completed_at, Some(normalized_at));
     }
+
+    #[test]
+    fn due_status_ignores_done_tasks() {
+        let today = "2026-03-10".parse().expect("date should parse");
+        let mut task = Task::new("task-1", "Ship v2", now());
+        assert_eq!(task.due_status(today), None);
+
+        task.due = Some("2026-03-09".parse().expect("date should parse"));
+        assert_eq!(task.due_status(today), Some(DueStatus::Overdue));
+
+        task.move_to(Queue::Done, now());
+        assert_eq!(task.due_status(today), None);
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().expect("date should parse")
+    }
+
+    #[test]
+    fn next_occurrence_requires_a_recur_rule() {
+        let task = Task::new("task-1", "Water plants", now());
+        assert!(
+            task.next_occurrence("task-2", Queue::Inbox, now(), date("2026-03-09"))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn next_occurrence_shifts_due_and_keeps_scheduled_lead_time() {
+        let mut task = Task::new("task-1", "Water plants", now());
+        task.recur = Some("every 1w".parse().expect("rule should parse"));
+        task.due = Some(date("2026-03-06"));
+        task.scheduled = Some(date("2026-03-04"));
+        task.tags = vec!["home".to_string()];
+        task.body = "# Water plants\n\n- [x] ficus\n  - [X] fern\n- [ ] cactus".to_string();
+        task.move_to(Queue::Done, now());
+
+        let next = task
+            .next_occurrence("task-2", Queue::Next, now(), date("2026-03-09"))
+            .expect("recurring task should repeat");
+
+        assert_eq!(next.id, "task-2");
+        assert_eq!(next.queue, Queue::Next);
+        assert!(next.completed_at.is_none());
+        assert_eq!(next.due, Some(date("2026-03-13")));
+        assert_eq!(next.scheduled, Some(date("2026-03-11")));
+        assert_eq!(next.recur, task.recur);
+        assert_eq!(next.tags, task.tags);
+        assert_eq!(
+            next.body,
+            "# Water plants\n\n- [ ] ficus\n  - [ ] fern\n- [ ] cactus"
+        );
+    }
+
+    #[test]
+    fn next_occurrence_without_dates_is_scheduled() {
+        let mut task = Task::new("task-1", "Pay rent", now());
+        task.recur = Some("monthly on 1".parse().expect("rule should parse"));
+
+        let next = task
+            .next_occurrence("task-2", Queue::Inbox, now(), date("2026-03-09"))
+            .expect("recurring task should repeat");
+
+        assert_eq!(next.due, None);
+        assert_eq!(next.scheduled, Some(date("2026-04-01")));
+    }
+
+    #[test]
+    fn normalize_tag_strips_hash_and_rejects_whitespace() {
+        assert_eq!(normalize_tag(" #ops "), Ok("ops".to_string()));
+        assert!(normalize_tag("#").is_err());
+        assert!(normalize_tag("two words").is_err());
+        assert!(normalize_tag("a,b").is_err());
+    }
+
+    #[test]
+    fn tags_are_deduplicated_case_insensitively() {
+        let mut task = Task::new("task-1", "Ship v2", now());
+
+        assert!(task.add_tag("Ops"));
+        assert!(!task.add_tag("ops"));
+        assert!(task.has_tag("OPS"));
+        assert!(task.remove_tag("ops"));
+        assert!(task.tags.is_empty());
+    }
+
+    #[test]
+    fn extra_frontmatter_insert_replaces_in_place() {
+        let mut extra = ExtraFrontmatter::default();
+        extra
+            .insert("jira", serde_yaml::Value::String("OPS-1".to_string()))
+            .expect("value should serialize");
+        extra
+            .insert("cssclass", serde_yaml::Value::String("wide".to_string()))
+            .expect("value should serialize");
+        extra
+            .insert("jira", serde_yaml::Value::String("OPS-2".to_string()))
+            .expect("value should serialize");
+
+        assert_eq!(extra.keys().collect::<Vec<_>>(), vec!["jira", "cssclass"]);
+        assert_eq!(extra.raw("jira"), Some("jira: OPS-2"));
+        assert_eq!(
+            extra.get("jira"),
+            Some(serde_yaml::Value::String("OPS-2".to_string()))
+        );
+        assert!(extra.remove("jira"));
+        assert!(!extra.contains_key("jira"));
+    }
 }
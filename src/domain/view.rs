@@ -0,0 +1,326 @@
+use chrono::NaiveDate;
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use super::query::{Query, QueryError};
+use super::task::{Queue, Task};
+
+/// A named query from the `[views.<name>]` config tables, with the order
+/// and columns used when it is shown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedView {
+    pub name: String,
+    pub query: String,
+    pub sort: Option<ViewSort>,
+    pub columns: Vec<ViewColumn>,
+}
+
+impl SavedView {
+    /// Columns shown when a view does not list its own; the same as `tqs list`.
+    pub const DEFAULT_COLUMNS: &[ViewColumn] = &[
+        ViewColumn::Id,
+        ViewColumn::Title,
+        ViewColumn::Tags,
+        ViewColumn::Due,
+        ViewColumn::Blocked,
+    ];
+
+    pub fn parse_query(&self, today: NaiveDate, queues: &[Queue]) -> Result<Query, QueryError> {
+        Query::parse(&self.query, today, queues)
+    }
+
+    /// The matching tasks in view order. Without a sort the input order is kept.
+    pub fn apply<'a>(
+        &self,
+        tasks: impl IntoIterator<Item = &'a Task>,
+        today: NaiveDate,
+        queues: &[Queue],
+    ) -> Result<Vec<&'a Task>, QueryError> {
+        let query = self.parse_query(today, queues)?;
+        let mut matches = tasks
+            .into_iter()
+            .filter(|task| query.matches(task))
+            .collect::<Vec<_>>();
+        if let Some(sort) = self.sort {
+            matches.sort_by(|left, right| sort.compare(left, right));
+        }
+        Ok(matches)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewParseError {
+    kind: &'static str,
+    input: String,
+    expected: &'static str,
+}
+
+impl fmt::Display for ViewParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid {} '{}'; expected {}",
+            self.kind, self.input, self.expected
+        )
+    }
+}
+
+impl std::error::Error for ViewParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Id,
+    Title,
+    Queue,
+    Due,
+    Scheduled,
+    Created,
+    Updated,
+    Completed,
+    Priority,
+}
+
+/// A sort key, written as `due` for ascending or `-updated` for descending.
+/// Tasks without the sorted date or priority always come last; `priority`
+/// ascending puts `p0` first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewSort {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+impl ViewSort {
+    pub fn compare(self, left: &Task, right: &Task) -> Ordering {
+        let ordering = match self.key {
+            SortKey::Id => left.id.cmp(&right.id),
+            SortKey::Title => left
+                .title
+                .to_ascii_lowercase()
+                .cmp(&right.title.to_ascii_lowercase()),
+            SortKey::Queue => left.queue.cmp(&right.queue),
+            SortKey::Due => return self.compare_optional(left.due, right.due),
+            SortKey::Scheduled => return self.compare_optional(left.scheduled, right.scheduled),
+            SortKey::Created => left.created_at.cmp(&right.created_at),
+            SortKey::Updated => left.updated_at.cmp(&right.updated_at),
+            SortKey::Completed => {
+                return self.compare_optional(left.completed_at, right.completed_at);
+            }
+            SortKey::Priority => return self.compare_optional(left.priority, right.priority),
+        };
+        self.direct(ordering)
+    }
+
+    fn compare_optional<T: Ord>(self, left: Option<T>, right: Option<T>) -> Ordering {
+        match (left, right) {
+            (Some(left), Some(right)) => self.direct(left.cmp(&right)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+
+    fn direct(self, ordering: Ordering) -> Ordering {
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+impl fmt::Display for ViewSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self.key {
+            SortKey::Id => "id",
+            SortKey::Title => "title",
+            SortKey::Queue => "queue",
+            SortKey::Due => "due",
+            SortKey::Scheduled => "scheduled",
+            SortKey::Created => "created",
+            SortKey::Updated => "updated",
+            SortKey::Completed => "completed",
+            SortKey::Priority => "priority",
+        };
+        if self.descending {
+            write!(f, "-{name}")
+        } else {
+            f.write_str(name)
+        }
+    }
+}
+
+impl FromStr for ViewSort {
+    type Err = ViewParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let trimmed = value.trim();
+        let (descending, name) = match trimmed.strip_prefix('-') {
+            Some(name) => (true, name),
+            None => (false, trimmed),
+        };
+        let key = match name.to_ascii_lowercase().as_str() {
+            "id" => SortKey::Id,
+            "title" => SortKey::Title,
+            "queue" => SortKey::Queue,
+            "due" => SortKey::Due,
+            "scheduled" => SortKey::Scheduled,
+            "created" => SortKey::Created,
+            "updated" => SortKey::Updated,
+            "completed" => SortKey::Completed,
+            "priority" => SortKey::Priority,
+            _ => {
+                return Err(ViewParseError {
+                    kind: "sort",
+                    input: value.to_string(),
+                    expected: "id, title, queue, due, scheduled, created, updated, completed, or priority, optionally prefixed with '-'",
+                });
+            }
+        };
+        Ok(Self { key, descending })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewColumn {
+    Id,
+    Queue,
+    Title,
+    Tags,
+    Due,
+    Scheduled,
+    Created,
+    Updated,
+    Blocked,
+}
+
+impl fmt::Display for ViewColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Id => "id",
+            Self::Queue => "queue",
+            Self::Title => "title",
+            Self::Tags => "tags",
+            Self::Due => "due",
+            Self::Scheduled => "scheduled",
+            Self::Created => "created",
+            Self::Updated => "updated",
+            Self::Blocked => "blocked",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for ViewColumn {
+    type Err = ViewParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let column = match value.trim().to_ascii_lowercase().as_str() {
+            "id" => Self::Id,
+            "queue" => Self::Queue,
+            "title" => Self::Title,
+            "tags" => Self::Tags,
+            "due" => Self::Due,
+            "scheduled" => Self::Scheduled,
+            "created" => Self::Created,
+            "updated" => Self::Updated,
+            "blocked" => Self::Blocked,
+            _ => {
+                return Err(ViewParseError {
+                    kind: "column",
+                    input: value.to_string(),
+                    expected: "id, queue, title, tags, due, scheduled, created, updated, or blocked",
+                });
+            }
+        };
+        Ok(column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SavedView, SortKey, ViewColumn, ViewSort};
+    use crate::domain::priority::Priority;
+    use crate::domain::task::{Queue, Task};
+    use chrono::NaiveDate;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().expect("date should parse")
+    }
+
+    fn task(id: &str, queue: Queue, due: Option<&str>) -> Task {
+        let mut task = Task::new(
+            id,
+            format!("Task {id}"),
+            "2026-03-09T12:00:00Z"
+                .parse()
+                .expect("timestamp should parse"),
+        );
+        task.queue = queue;
+        task.due = due.map(date);
+        task
+    }
+
+    #[test]
+    fn parses_sort_and_columns() {
+        assert_eq!(
+            "-updated".parse::<ViewSort>(),
+            Ok(ViewSort {
+                key: SortKey::Updated,
+                descending: true,
+            })
+        );
+        assert_eq!(
+            "-updated".parse::<ViewSort>().map(|sort| sort.to_string()),
+            Ok("-updated".to_string())
+        );
+        assert_eq!("Due".parse::<ViewColumn>(), Ok(ViewColumn::Due));
+        assert_eq!(ViewColumn::Blocked.to_string(), "blocked");
+        assert_eq!(
+            "owner".parse::<ViewColumn>().unwrap_err().to_string(),
+            "invalid column 'owner'; expected id, queue, title, tags, due, scheduled, created, updated, or blocked"
+        );
+        assert!("-".parse::<ViewSort>().is_err());
+    }
+
+    #[test]
+    fn apply_filters_and_sorts_with_missing_dates_last() {
+        let tasks = vec![
+            task("a", Queue::Now, None),
+            task("b", Queue::Now, Some("2026-10-20")),
+            task("c", Queue::Inbox, Some("2026-10-15")),
+            task("d", Queue::Next, Some("2026-10-18")),
+        ];
+        let view = SavedView {
+            name: "sprint".to_string(),
+            query: "-queue:inbox".to_string(),
+            sort: Some("-due".parse().expect("sort should parse")),
+            columns: SavedView::DEFAULT_COLUMNS.to_vec(),
+        };
+
+        let ids = view
+            .apply(&tasks, date("2026-10-14"), Queue::all())
+            .expect("query should parse")
+            .into_iter()
+            .map(|task| task.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["b", "d", "a"]);
+    }
+
+    #[test]
+    fn priority_sort_puts_most_urgent_first_and_unset_last() {
+        let mut tasks = [
+            task("a", Queue::Now, None),
+            task("b", Queue::Now, None),
+            task("c", Queue::Now, None),
+        ];
+        tasks[1].priority = Some(Priority::P2);
+        tasks[2].priority = Some(Priority::P0);
+        let sort = "priority".parse::<ViewSort>().expect("sort should parse");
+        tasks.sort_by(|left, right| sort.compare(left, right));
+        let ids = tasks
+            .iter()
+            .map(|task| task.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["c", "b", "a"]);
+        assert_eq!(sort.to_string(), "priority");
+    }
+}
@@ -0,0 +1,470 @@
+use std::{collections::BTreeMap, path::Path};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::{
+    app::app_error::AppError,
+    domain::{
+        deps::BlockedIndex,
+        priority::Priority,
+        stats::{LeadTime, Stats},
+        task::{Queue, QueueTransition, Task},
+        view::SavedView,
+    },
+    io::output::OutputFormat,
+    storage::{
+        config::{ConfigInspection, QueueGroup, ResolvedConfig},
+        doctor::{Diagnostic, DiagnosticSeverity, DoctorReport},
+    },
+};
+
+/// Version of the JSON documents. Keys may be added within a version;
+/// renaming or removing one, or changing its type, needs a new version.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize)]
+pub struct TaskRecord<'a> {
+    pub id: &'a str,
+    pub title: &'a str,
+    pub queue: Queue,
+    pub path: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub due: Option<NaiveDate>,
+    pub scheduled: Option<NaiveDate>,
+    pub recur: Option<String>,
+    pub priority: Option<Priority>,
+    pub tags: &'a [String],
+    pub blocked_by: &'a [String],
+    /// Whether any `blocked_by` entry names an open task.
+    pub blocked: bool,
+    pub daily_note: Option<&'a str>,
+    /// Queue transitions, oldest first.
+    pub history: &'a [QueueTransition],
+    /// Manual position within the queue; lower ranks come first.
+    pub rank: Option<i64>,
+    /// Only included by `show`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<&'a str>,
+}
+
+impl<'a> TaskRecord<'a> {
+    pub fn new(task: &'a Task, path: &Path, blocked: &BlockedIndex) -> Self {
+        Self {
+            id: &task.id,
+            title: &task.title,
+            queue: task.queue,
+            path: path.display().to_string(),
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+            completed_at: task.completed_at,
+            due: task.due,
+            scheduled: task.scheduled,
+            recur: task.recur.map(|rule| rule.to_string()),
+            priority: task.priority,
+            tags: &task.tags,
+            blocked_by: &task.blocked_by,
+            blocked: blocked.is_blocked(&task.id),
+            daily_note: task.daily_note.as_deref(),
+            history: &task.history,
+            rank: task.rank,
+            body: None,
+        }
+    }
+
+    pub fn with_body(mut self, task: &'a Task) -> Self {
+        self.body = Some(&task.body);
+        self
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct TasksDocument<'a> {
+    version: u32,
+    tasks: &'a [TaskRecord<'a>],
+}
+
+#[derive(Debug, Serialize)]
+struct TaskDocument<'a> {
+    version: u32,
+    task: &'a TaskRecord<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct ConfigRecord<'a> {
+    config_path: Option<String>,
+    config_file_present: bool,
+    root_cli: Option<String>,
+    root_env: Option<String>,
+    resolved: Option<ResolvedRecord<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct ResolvedRecord<'a> {
+    obsidian_vault_dir: Option<String>,
+    tasks_root: String,
+    state_dir: String,
+    daily_notes_dir: Option<String>,
+    queues: QueueDirsRecord<'a>,
+    custom_queues: Vec<CustomQueueRecord<'a>>,
+    recur_queue: Queue,
+    auto_archive_after: Option<String>,
+    views: Vec<ViewRecord<'a>>,
+    templates: &'a BTreeMap<String, String>,
+    git: Option<GitRecord<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct QueueDirsRecord<'a> {
+    inbox: &'a str,
+    now: &'a str,
+    next: &'a str,
+    later: &'a str,
+    done: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct CustomQueueRecord<'a> {
+    name: Queue,
+    dir: &'a str,
+    position: i64,
+    group: QueueGroup,
+    active: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct GitRecord<'a> {
+    remote: &'a str,
+    branch: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+struct ViewRecord<'a> {
+    name: &'a str,
+    query: &'a str,
+    sort: Option<String>,
+    columns: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ConfigDocument<'a> {
+    version: u32,
+    config: &'a ConfigRecord<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct DiagnosticRecord<'a> {
+    severity: &'static str,
+    scope: &'a str,
+    message: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct DoctorDocument<'a> {
+    version: u32,
+    errors: usize,
+    warnings: usize,
+    diagnostics: &'a [DiagnosticRecord<'a>],
+}
+
+/// Durations are whole seconds.
+#[derive(Debug, Serialize)]
+struct StatsRecord<'a> {
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+    by: String,
+    tag: Option<&'a str>,
+    completed: usize,
+    periods: Vec<PeriodRecord>,
+    lead_time: Option<LeadTimeRecord>,
+    inbox: InboxFlowRecord,
+    queues: Vec<QueueAgesRecord<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct PeriodRecord {
+    start: NaiveDate,
+    completed: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct LeadTimeRecord {
+    median_seconds: i64,
+    mean_seconds: i64,
+    max_seconds: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct InboxFlowRecord {
+    inflow: usize,
+    outflow: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct QueueAgesRecord<'a> {
+    queue: Queue,
+    open: usize,
+    median_age_seconds: Option<i64>,
+    oldest: Vec<AgedTaskRecord<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct AgedTaskRecord<'a> {
+    id: &'a str,
+    title: &'a str,
+    age_seconds: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct StatsDocument<'a> {
+    version: u32,
+    stats: &'a StatsRecord<'a>,
+}
+
+/// Prints a task list as one `{"version", "tasks"}` document, or one task per line.
+pub fn print_tasks(format: OutputFormat, records: &[TaskRecord<'_>]) -> Result<(), AppError> {
+    match format {
+        OutputFormat::Jsonl => print_lines(records),
+        _ => print_document(&TasksDocument {
+            version: SCHEMA_VERSION,
+            tasks: records,
+        }),
+    }
+}
+
+pub fn print_task(format: OutputFormat, record: &TaskRecord<'_>) -> Result<(), AppError> {
+    match format {
+        OutputFormat::Jsonl => print_lines(std::slice::from_ref(record)),
+        _ => print_document(&TaskDocument {
+            version: SCHEMA_VERSION,
+            task: record,
+        }),
+    }
+}
+
+pub fn print_config(format: OutputFormat, inspection: &ConfigInspection) -> Result<(), AppError> {
+    let record = ConfigRecord {
+        config_path: inspection.config_path.as_deref().map(display),
+        config_file_present: inspection.file_exists,
+        root_cli: inspection.explicit_root.as_deref().map(display),
+        root_env: inspection.env_root.as_deref().map(display),
+        resolved: inspection.resolved.as_ref().map(resolved_record),
+    };
+    match format {
+        OutputFormat::Jsonl => print_lines(std::slice::from_ref(&record)),
+        _ => print_document(&ConfigDocument {
+            version: SCHEMA_VERSION,
+            config: &record,
+        }),
+    }
+}
+
+/// Prints the doctor report as one document with counts, or one diagnostic per line.
+pub fn print_doctor_report(format: OutputFormat, report: &DoctorReport) -> Result<(), AppError> {
+    let records = report
+        .diagnostics
+        .iter()
+        .map(diagnostic_record)
+        .collect::<Vec<_>>();
+    match format {
+        OutputFormat::Jsonl => print_lines(&records),
+        _ => print_document(&DoctorDocument {
+            version: SCHEMA_VERSION,
+            errors: report.error_count(),
+            warnings: report.warning_count(),
+            diagnostics: &records,
+        }),
+    }
+}
+
+/// Prints stats as one document, or as a single line for `jsonl`.
+pub fn print_stats(format: OutputFormat, stats: &Stats) -> Result<(), AppError> {
+    let record = stats_record(stats);
+    match format {
+        OutputFormat::Jsonl => print_lines(std::slice::from_ref(&record)),
+        _ => print_document(&StatsDocument {
+            version: SCHEMA_VERSION,
+            stats: &record,
+        }),
+    }
+}
+
+fn stats_record(stats: &Stats) -> StatsRecord<'_> {
+    StatsRecord {
+        since: stats.since,
+        until: stats.until,
+        by: stats.by.to_string(),
+        tag: stats.tag.as_deref(),
+        completed: stats.completed,
+        periods: stats
+            .periods
+            .iter()
+            .map(|period| PeriodRecord {
+                start: period.start,
+                completed: period.completed,
+            })
+            .collect(),
+        lead_time: stats.lead_time.as_ref().map(lead_time_record),
+        inbox: InboxFlowRecord {
+            inflow: stats.inbox.inflow,
+            outflow: stats.inbox.outflow,
+        },
+        queues: stats
+            .queues
+            .iter()
+            .map(|queue| QueueAgesRecord {
+                queue: queue.queue,
+                open: queue.open,
+                median_age_seconds: queue.median_age.map(|age| age.num_seconds()),
+                oldest: queue
+                    .oldest
+                    .iter()
+                    .map(|task| AgedTaskRecord {
+                        id: &task.id,
+                        title: &task.title,
+                        age_seconds: task.age.num_seconds(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+fn lead_time_record(lead_time: &LeadTime) -> LeadTimeRecord {
+    LeadTimeRecord {
+        median_seconds: lead_time.median.num_seconds(),
+        mean_seconds: lead_time.mean.num_seconds(),
+        max_seconds: lead_time.max.num_seconds(),
+    }
+}
+
+fn resolved_record(config: &ResolvedConfig) -> ResolvedRecord<'_> {
+    ResolvedRecord {
+        obsidian_vault_dir: config.obsidian_vault_dir.as_deref().map(display),
+        tasks_root: display(&config.tasks_root),
+        state_dir: display(&config.state_dir),
+        daily_notes_dir: config.daily_notes_dir.as_deref().map(display),
+        queues: QueueDirsRecord {
+            inbox: config.queue_dirs.dir_name(&Queue::Inbox),
+            now: config.queue_dirs.dir_name(&Queue::Now),
+            next: config.queue_dirs.dir_name(&Queue::Next),
+            later: config.queue_dirs.dir_name(&Queue::Later),
+            done: config.queue_dirs.dir_name(&Queue::Done),
+        },
+        custom_queues: config
+            .queue_dirs
+            .custom_queues()
+            .iter()
+            .map(|custom| CustomQueueRecord {
+                name: custom.queue,
+                dir: &custom.dir,
+                position: custom.position,
+                group: custom.group,
+                active: custom.active,
+            })
+            .collect(),
+        recur_queue: config.recur_queue,
+        auto_archive_after: config.auto_archive_after.map(|age| age.to_string()),
+        views: config.views.iter().map(view_record).collect(),
+        templates: &config.templates,
+        git: config.git.as_ref().map(|git| GitRecord {
+            remote: &git.remote,
+            branch: git.branch.as_deref(),
+        }),
+    }
+}
+
+fn view_record(view: &SavedView) -> ViewRecord<'_> {
+    ViewRecord {
+        name: &view.name,
+        query: &view.query,
+        sort: view.sort.map(|sort| sort.to_string()),
+        columns: view.columns.iter().map(ToString::to_string).collect(),
+    }
+}
+
+fn diagnostic_record(diagnostic: &Diagnostic) -> DiagnosticRecord<'_> {
+    DiagnosticRecord {
+        severity: match diagnostic.severity {
+            DiagnosticSeverity::Ok => "ok",
+            DiagnosticSeverity::Warning => "warn",
+            DiagnosticSeverity::Error => "error",
+        },
+        scope: &diagnostic.scope,
+        message: &diagnostic.message,
+    }
+}
+
+fn display(path: &Path) -> String {
+    path.display().to_string()
+}
+
+fn print_document(document: &impl Serialize) -> Result<(), AppError> {
+    println!("{}", serde_json::to_string_pretty(document)?);
+    Ok(())
+}
+
+fn print_lines<T: Serialize>(records: &[T]) -> Result<(), AppError> {
+    for record in records {
+        println!("{}", serde_json::to_string(record)?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TaskRecord;
+    use crate::domain::{deps::BlockedIndex, task::Task};
+    use std::path::Path;
+
+    #[test]
+    fn task_record_has_stable_keys_with_nulls() {
+        let mut task = Task::new(
+            "0f3",
+            "Reply to AWS billing alert",
+            "2026-03-09T10:34:12Z"
+                .parse()
+                .expect("timestamp should parse"),
+        );
+        task.tags = vec!["ops".to_string()];
+        task.due = Some("2026-03-12".parse().expect("date should parse"));
+        let blocked = BlockedIndex::default();
+
+        let value = serde_json::to_value(TaskRecord::new(
+            &task,
+            Path::new("/tasks/inbox/0f3.md"),
+            &blocked,
+        ))
+        .expect("record should serialize");
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "id": "0f3",
+                "title": "Reply to AWS billing alert",
+                "queue": "inbox",
+                "path": "/tasks/inbox/0f3.md",
+                "created_at": "2026-03-09T10:34:12Z",
+                "updated_at": "2026-03-09T10:34:12Z",
+                "completed_at": null,
+                "due": "2026-03-12",
+                "scheduled": null,
+                "recur": null,
+                "priority": null,
+                "tags": ["ops"],
+                "blocked_by": [],
+                "blocked": false,
+                "daily_note": null,
+                "history": [],
+                "rank": null,
+            })
+        );
+
+        let value = serde_json::to_value(
+            TaskRecord::new(&task, Path::new("/tasks/inbox/0f3.md"), &blocked).with_body(&task),
+        )
+        .expect("record should serialize");
+        assert_eq!(value["body"], task.body);
+    }
+}
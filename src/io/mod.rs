@@ -1,3 +1,4 @@
 pub mod input;
+pub mod json;
 pub mod output;
 pub mod picker;
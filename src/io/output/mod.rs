@@ -0,0 +1,697 @@
+pub mod template;
+
+use crate::{
+    app::bulk::{BulkAction, BulkOutcome, BulkResult},
+    domain::date::{Age, DueStatus, describe_due, format_duration},
+    domain::deps::BlockedIndex,
+    domain::priority::Priority,
+    domain::stats::Stats,
+    domain::task::{Queue, Task, format_tags},
+    domain::view::{SavedView, ViewColumn},
+    storage::config::{ConfigInspection, ResolvedConfig},
+    storage::doctor::{DiagnosticSeverity, DoctorReport},
+    storage::journal::{self, Batch, BatchState},
+    storage::repo::StoredTask,
+    storage::trash::TrashedTask,
+};
+use chrono::{Local, NaiveDate, Utc};
+use dialoguer::console::style;
+use std::path::Path;
+
+/// How read commands print their results, chosen with the global `--format`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Styled text for people
+    #[default]
+    Text,
+    /// One pretty-printed JSON document
+    Json,
+    /// One compact JSON record per line
+    Jsonl,
+}
+
+fn styled_field_label(label: &str) -> String {
+    style(label).bold().cyan().to_string()
+}
+
+fn tag_suffix(task: &Task) -> String {
+    if task.tags.is_empty() {
+        String::new()
+    } else {
+        format!("  {}", style(format_tags(&task.tags)).dim())
+    }
+}
+
+/// `[p0]` to `[p3]` before a task title, most urgent in the brightest colour.
+fn priority_prefix(task: &Task) -> String {
+    let Some(priority) = task.priority else {
+        return String::new();
+    };
+    let marker = style(format!("[{priority}]"));
+    let styled = match priority {
+        Priority::P0 => marker.red().bold(),
+        Priority::P1 => marker.red(),
+        Priority::P2 => marker.yellow(),
+        Priority::P3 => marker.dim(),
+    };
+    format!("{styled} ")
+}
+
+fn due_suffix(task: &Task, today: NaiveDate) -> String {
+    let (Some(due), Some(status)) = (task.due, task.due_status(today)) else {
+        return String::new();
+    };
+    let label = describe_due(due, today);
+    let styled = match status {
+        DueStatus::Overdue => style(format!("[{label}]")).red().bold(),
+        DueStatus::Today => style(format!("[{label}]")).yellow().bold(),
+        DueStatus::Upcoming => style(format!("[{label}]")).dim(),
+    };
+    format!("  {styled}")
+}
+
+pub fn print_info(message: &str) {
+    println!("{}", style(message).cyan());
+}
+
+pub fn print_error(message: &str) {
+    eprintln!("{message}");
+}
+
+fn blocked_suffix(blockers: &[String]) -> String {
+    if blockers.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "  {}",
+            style(format!("[blocked by {}]", blockers.join(", "))).dim()
+        )
+    }
+}
+
+pub fn print_queue_tasks(queue: Queue, tasks: &[Task], blocked: &BlockedIndex) {
+    println!(
+        "{} {}",
+        style(queue.to_string()).bold().magenta(),
+        style(format!("({})", tasks.len())).yellow()
+    );
+
+    if tasks.is_empty() {
+        println!("No tasks found");
+        return;
+    }
+
+    let today = Local::now().date_naive();
+    for task in tasks {
+        let blockers = blocked.blockers(&task.id);
+        if blockers.is_empty() {
+            println!(
+                "{}  {}{}{}{}",
+                style(&task.id).cyan(),
+                priority_prefix(task),
+                task.title,
+                tag_suffix(task),
+                due_suffix(task, today)
+            );
+        } else {
+            println!(
+                "{}  {}{}{}{}{}",
+                style(&task.id).cyan().dim(),
+                priority_prefix(task),
+                style(&task.title).dim(),
+                tag_suffix(task),
+                due_suffix(task, today),
+                blocked_suffix(blockers)
+            );
+        }
+    }
+}
+
+pub fn print_view(view: &SavedView, tasks: &[&Task], blocked: &BlockedIndex) {
+    println!(
+        "{} {}",
+        style(&view.name).bold().magenta(),
+        style(format!("({})", tasks.len())).yellow()
+    );
+
+    if tasks.is_empty() {
+        println!("No tasks found");
+        return;
+    }
+
+    let today = Local::now().date_naive();
+    for task in tasks {
+        let blockers = blocked.blockers(&task.id);
+        let cells = view
+            .columns
+            .iter()
+            .map(|column| view_cell(task, *column, blockers, today))
+            .filter(|cell| !cell.is_empty())
+            .collect::<Vec<_>>();
+        println!("{}", cells.join("  "));
+    }
+}
+
+fn view_cell(task: &Task, column: ViewColumn, blockers: &[String], today: NaiveDate) -> String {
+    let is_blocked = !blockers.is_empty();
+    match column {
+        ViewColumn::Id if is_blocked => style(&task.id).cyan().dim().to_string(),
+        ViewColumn::Id => style(&task.id).cyan().to_string(),
+        ViewColumn::Queue => style(format!("{:<5}", task.queue)).magenta().to_string(),
+        ViewColumn::Title if is_blocked => style(&task.title).dim().to_string(),
+        ViewColumn::Title => task.title.clone(),
+        ViewColumn::Tags => tag_suffix(task).trim_start().to_string(),
+        ViewColumn::Due => due_suffix(task, today).trim_start().to_string(),
+        ViewColumn::Scheduled => task
+            .scheduled
+            .map(|date| style(format!("scheduled {date}")).dim().to_string())
+            .unwrap_or_default(),
+        ViewColumn::Created => style(format!(
+            "created {}",
+            task.created_at.with_timezone(&Local).date_naive()
+        ))
+        .dim()
+        .to_string(),
+        ViewColumn::Updated => style(format!(
+            "updated {}",
+            task.updated_at.with_timezone(&Local).date_naive()
+        ))
+        .dim()
+        .to_string(),
+        ViewColumn::Blocked => blocked_suffix(blockers).trim_start().to_string(),
+    }
+}
+
+pub fn print_views(views: &[SavedView]) {
+    if views.is_empty() {
+        print_info("No views configured; add [views.<name>] tables to config.toml");
+        return;
+    }
+
+    for view in views {
+        println!(
+            "{}  {}",
+            style(&view.name).bold().magenta(),
+            style(&view.query).dim()
+        );
+    }
+}
+
+/// Prints the active queues, then the inbox, with overdue and due-today counts
+/// on top.
+pub fn print_dashboard(tasks: &[Task], blocked: &BlockedIndex, active_queues: &[Queue]) {
+    let today = Local::now().date_naive();
+    let overdue = tasks
+        .iter()
+        .filter(|task| task.due_status(today) == Some(DueStatus::Overdue))
+        .count();
+    let due_today = tasks
+        .iter()
+        .filter(|task| task.due_status(today) == Some(DueStatus::Today))
+        .count();
+    if overdue > 0 || due_today > 0 {
+        println!(
+            "{}, {}",
+            style(format!("{overdue} overdue")).red().bold(),
+            style(format!("{due_today} due today")).yellow().bold()
+        );
+        println!();
+    }
+
+    for (i, queue) in active_queues.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print_queue_tasks(
+            *queue,
+            &tasks
+                .iter()
+                .filter(|task| task.queue == *queue)
+                .cloned()
+                .collect::<Vec<_>>(),
+            blocked,
+        );
+    }
+
+    println!();
+    println!("{}", style("───").dim());
+    println!();
+
+    print_queue_tasks(
+        Queue::Inbox,
+        &tasks
+            .iter()
+            .filter(|task| task.queue == Queue::Inbox)
+            .cloned()
+            .collect::<Vec<_>>(),
+        blocked,
+    );
+}
+
+pub fn print_task_detail(task: &Task, path: &Path) {
+    println!("{} {}", styled_field_label("ID:"), style(&task.id).cyan());
+    println!(
+        "{} {}",
+        styled_field_label("Queue:"),
+        style(task.queue.to_string()).magenta()
+    );
+    println!(
+        "{} {}",
+        styled_field_label("Path:"),
+        style(path.display().to_string()).dim()
+    );
+    println!(
+        "{} {}",
+        styled_field_label("Created:"),
+        style(task.created_at.to_rfc3339()).dim()
+    );
+    println!(
+        "{} {}",
+        styled_field_label("Updated:"),
+        style(task.updated_at.to_rfc3339()).dim()
+    );
+    println!("{} {}", styled_field_label("Title:"), task.title);
+
+    if let Some(due) = task.due {
+        println!(
+            "{} {}{}",
+            styled_field_label("Due:"),
+            due,
+            due_suffix(task, Local::now().date_naive())
+        );
+    }
+
+    if let Some(scheduled) = task.scheduled {
+        println!("{} {}", styled_field_label("Scheduled:"), scheduled);
+    }
+
+    if !task.blocked_by.is_empty() {
+        println!(
+            "{} {}",
+            styled_field_label("Blocked by:"),
+            task.blocked_by.join(", ")
+        );
+    }
+
+    if let Some(recur) = task.recur {
+        println!("{} {}", styled_field_label("Repeats:"), recur);
+    }
+
+    if task.priority.is_some() {
+        println!(
+            "{} {}",
+            styled_field_label("Priority:"),
+            priority_prefix(task).trim_end()
+        );
+    }
+
+    if !task.tags.is_empty() {
+        println!(
+            "{} {}",
+            styled_field_label("Tags:"),
+            format_tags(&task.tags)
+        );
+    }
+
+    if let Some(completed_at) = task.completed_at {
+        println!(
+            "{} {}",
+            styled_field_label("Completed:"),
+            style(completed_at.to_rfc3339()).dim()
+        );
+    }
+
+    if !task.history.is_empty() {
+        println!("{}", styled_field_label("History:"));
+        let now = Utc::now();
+        for stint in task.queue_stints() {
+            let mut spent = format_duration(stint.duration(now));
+            if stint.until.is_none() {
+                spent.push_str(" so far");
+            }
+            println!(
+                "  {}  {:<5}  {}",
+                style(stint.since.with_timezone(&Local).format("%Y-%m-%d %H:%M")).dim(),
+                stint.queue.to_string(),
+                spent
+            );
+        }
+    }
+
+    println!();
+    println!("{}", task.body);
+}
+
+/// Reports the follow-up created when a recurring task is completed.
+pub fn print_next_occurrence(task: &Task, path: &Path) {
+    let when = match (task.due, task.scheduled) {
+        (Some(due), _) => format!("due {due}"),
+        (None, Some(scheduled)) => format!("scheduled {scheduled}"),
+        (None, None) => String::new(),
+    };
+    print_info(&format!(
+        "Next occurrence: {} in {}, {when} ({})",
+        task.id,
+        task.queue,
+        path.display()
+    ));
+}
+
+/// Lists the tasks that completing another task has unblocked.
+pub fn print_unblocked(tasks: &[Task]) {
+    for task in tasks {
+        println!(
+            "{} {}  {}",
+            style("Unblocked:").green(),
+            style(&task.id).cyan(),
+            task.title
+        );
+    }
+}
+
+/// Reports the done tasks that `auto_archive_after` archived, if any.
+pub fn print_auto_archived(archived: &[StoredTask], age: Option<Age>) {
+    if let Some(age) = age
+        && !archived.is_empty()
+    {
+        print_info(&format!(
+            "Archived {} done tasks older than {age}",
+            archived.len()
+        ));
+    }
+}
+
+pub fn print_search_results(results: &[StoredTask]) {
+    if results.is_empty() {
+        println!("No tasks found");
+        return;
+    }
+
+    for stored in results {
+        println!(
+            "[{}] {}  {}{}",
+            stored.task.queue,
+            style(&stored.task.id).cyan(),
+            stored.task.title,
+            tag_suffix(&stored.task)
+        );
+    }
+}
+
+/// Lists the tasks a bulk action is about to change.
+pub fn print_bulk_preview(action: BulkAction, tasks: &[StoredTask]) {
+    println!("{} tasks to {action}:", tasks.len());
+    for stored in tasks {
+        println!(
+            "  [{}] {}  {}",
+            stored.task.queue,
+            style(&stored.task.id).cyan(),
+            stored.task.title
+        );
+    }
+}
+
+/// Reports each task of a bulk action, then a one-line summary.
+pub fn print_bulk_results(action: BulkAction, results: &[BulkResult]) {
+    let mut unchanged = 0;
+    for result in results {
+        let id = &result.id;
+        match &result.outcome {
+            BulkOutcome::Moved(path) if action == BulkAction::Start => {
+                print_info(&format!("Started task: {id} ({})", path.display()));
+            }
+            BulkOutcome::Moved(path) => {
+                print_info(&format!("Moved task: {id} ({})", path.display()));
+            }
+            BulkOutcome::Completed(outcome) => {
+                print_info(&format!(
+                    "Completed task: {id} ({})",
+                    outcome.path.display()
+                ));
+                if let Some((next, next_path)) = &outcome.next {
+                    print_next_occurrence(next, next_path);
+                }
+                print_unblocked(&outcome.unblocked);
+            }
+            BulkOutcome::Deleted => print_info(&format!("Deleted task: {id}")),
+            BulkOutcome::Unchanged => {
+                unchanged += 1;
+                match action.target_queue() {
+                    Some(Queue::Done) => print_info(&format!("Task {id} is already done")),
+                    Some(queue) => print_info(&format!("Task {id} is already in {queue}")),
+                    None => {}
+                }
+            }
+        }
+    }
+
+    let changed = results.len() - unchanged;
+    let mut summary = match action {
+        BulkAction::Start => format!("Started {changed} tasks"),
+        BulkAction::Move(queue) => format!("Moved {changed} tasks to {queue}"),
+        BulkAction::Done => format!("Completed {changed} tasks"),
+        BulkAction::Delete => format!("Deleted {changed} tasks"),
+    };
+    if unchanged > 0 {
+        summary.push_str(&format!(", {unchanged} unchanged"));
+    }
+    println!("{}", style(summary).green().bold());
+}
+
+pub fn print_trash(trashed: &[TrashedTask]) {
+    if trashed.is_empty() {
+        println!("Trash is empty");
+        return;
+    }
+
+    for entry in trashed {
+        println!(
+            "{}  [{}] {}  {}",
+            style(
+                entry
+                    .deleted_at
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M")
+            )
+            .dim(),
+            entry.queue,
+            style(&entry.task.id).cyan(),
+            entry.task.title
+        );
+    }
+}
+
+pub fn print_history(batches: &[Batch], limit: usize) {
+    if batches.is_empty() {
+        println!("No history");
+        return;
+    }
+
+    for batch in batches.iter().rev().take(limit) {
+        let state = match batch.state {
+            BatchState::Applied => String::new(),
+            BatchState::Undone => format!("  {}", style("(undone)").yellow()),
+        };
+        println!(
+            "{}  {}  {}{state}",
+            style(format!("#{}", batch.number)).cyan(),
+            style(batch.at().with_timezone(&Local).format("%Y-%m-%d %H:%M")).dim(),
+            journal::describe(batch, batches)
+        );
+    }
+}
+
+/// Widest completion bar in `print_stats`, in characters.
+const STATS_BAR_WIDTH: usize = 30;
+
+pub fn print_stats(stats: &Stats) {
+    let tag = stats
+        .tag
+        .as_ref()
+        .map(|tag| format!(" tagged #{tag}"))
+        .unwrap_or_default();
+    println!(
+        "{} {} to {}{tag}",
+        styled_field_label("Stats"),
+        stats.since.with_timezone(&Local).format("%Y-%m-%d"),
+        stats.until.with_timezone(&Local).format("%Y-%m-%d"),
+    );
+
+    println!();
+    println!(
+        "{} {} (by {})",
+        styled_field_label("Completed:"),
+        stats.completed,
+        stats.by
+    );
+    let most = stats
+        .periods
+        .iter()
+        .map(|period| period.completed)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    for period in &stats.periods {
+        let bar = "#".repeat(period.completed * STATS_BAR_WIDTH / most);
+        let count = format!("  {}  {:>3}", style(period.start).dim(), period.completed);
+        if bar.is_empty() {
+            println!("{count}");
+        } else {
+            println!("{count}  {}", style(bar).green());
+        }
+    }
+
+    let lead_time = match &stats.lead_time {
+        Some(lead_time) => format!(
+            "median {}, mean {}, longest {}",
+            format_duration(lead_time.median),
+            format_duration(lead_time.mean),
+            format_duration(lead_time.max)
+        ),
+        None => "no completed tasks".to_string(),
+    };
+    println!("{} {lead_time}", styled_field_label("Lead time:"));
+    println!(
+        "{} {} in, {} out",
+        styled_field_label("Inbox flow:"),
+        stats.inbox.inflow,
+        stats.inbox.outflow
+    );
+
+    println!();
+    println!("{}", styled_field_label("Queue ages:"));
+    for queue in &stats.queues {
+        let median = queue
+            .median_age
+            .map(|age| format!(", median {}", format_duration(age)))
+            .unwrap_or_default();
+        println!(
+            "  {:<5}  {} open{median}",
+            queue.queue.to_string(),
+            queue.open
+        );
+        for task in &queue.oldest {
+            println!(
+                "    {}  {:>4}  {}",
+                style(&task.id).cyan(),
+                format_duration(task.age),
+                task.title
+            );
+        }
+    }
+}
+
+pub fn print_config(config: &ResolvedConfig) {
+    if let Some(path) = &config.obsidian_vault_dir {
+        println!("obsidian_vault_dir = {}", path.display());
+    }
+
+    println!("tasks_root = {}", config.tasks_root.display());
+    println!("state_dir = {}", config.state_dir.display());
+
+    match &config.daily_notes_dir {
+        Some(path) => println!("daily_notes_dir = {}", path.display()),
+        None => println!("daily_notes_dir = <unset>"),
+    }
+
+    println!("queue.inbox = {}", config.queue_dirs.inbox);
+    println!("queue.now = {}", config.queue_dirs.now);
+    println!("queue.next = {}", config.queue_dirs.next);
+    println!("queue.later = {}", config.queue_dirs.later);
+    println!("queue.done = {}", config.queue_dirs.done);
+    for custom in config.queue_dirs.custom_queues() {
+        println!(
+            "queue.{} = {} (position {}, {} group{})",
+            custom.queue,
+            custom.dir,
+            custom.position,
+            custom.group,
+            if custom.active { ", active" } else { "" }
+        );
+    }
+    println!("recur_queue = {}", config.recur_queue);
+    if let Some(age) = config.auto_archive_after {
+        println!("auto_archive_after = {age}");
+    }
+    for view in &config.views {
+        println!("views.{}.query = {}", view.name, view.query);
+    }
+    for (name, template) in &config.templates {
+        println!("templates.{name} = {template}");
+    }
+    if let Some(git) = &config.git {
+        println!("git.remote = {}", git.remote);
+        if let Some(branch) = &git.branch {
+            println!("git.branch = {branch}");
+        }
+    }
+}
+
+pub fn print_config_inspection(inspection: &ConfigInspection) {
+    match &inspection.config_path {
+        Some(path) => println!("config_path = {}", path.display()),
+        None => println!("config_path = <unavailable>"),
+    }
+
+    println!(
+        "config_file = {}",
+        if inspection.file_exists {
+            "present"
+        } else {
+            "missing"
+        }
+    );
+
+    match &inspection.explicit_root {
+        Some(path) => println!("root_cli = {}", path.display()),
+        None => println!("root_cli = <unset>"),
+    }
+
+    match &inspection.env_root {
+        Some(path) => println!("root_env = {}", path.display()),
+        None => println!("root_env = <unset>"),
+    }
+
+    match &inspection.resolved {
+        Some(config) => {
+            println!();
+            print_config(config);
+        }
+        None => {
+            println!("tasks_root = <unset>");
+            println!();
+            println!(
+                "{}",
+                crate::storage::config::starter_config(inspection.config_path.as_deref())
+            );
+        }
+    }
+}
+
+pub fn print_getting_started(config_path: Option<&Path>) {
+    println!("{}", style("Welcome to tqs!").bold());
+    println!();
+    println!("{}", crate::storage::config::starter_config(config_path));
+    println!(
+        "Run {} for detailed configuration info.",
+        style("tqs config").cyan()
+    );
+}
+
+pub fn print_doctor_report(report: &DoctorReport) {
+    for diagnostic in &report.diagnostics {
+        let label = match diagnostic.severity {
+            DiagnosticSeverity::Ok => style("ok").green().bold().to_string(),
+            DiagnosticSeverity::Warning => style("warn").yellow().bold().to_string(),
+            DiagnosticSeverity::Error => style("error").red().bold().to_string(),
+        };
+        println!("[{label}] {}: {}", diagnostic.scope, diagnostic.message);
+    }
+
+    println!(
+        "summary: {} ok, {} warning(s), {} error(s)",
+        report.ok_count(),
+        report.warning_count(),
+        report.error_count()
+    );
+}
@@ -0,0 +1,400 @@
+use std::{fmt, path::Path, str::FromStr};
+
+use chrono::{DateTime, Local, Utc};
+
+use crate::domain::{
+    date::{describe_due, format_duration},
+    task::{Task, format_tags},
+};
+
+/// A user-defined line format such as `{id} [{queue}] {title} ({age})`.
+///
+/// `{field}` inserts a task field, `{field:N}` pads it to `N` characters
+/// (`{field:>N}` aligns right), `{{` and `}}` are literal braces, and `\n`
+/// and `\t` are a newline and a tab. Missing values render as empty text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Field {
+        field: Field,
+        width: Option<usize>,
+        align_right: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Id,
+    Title,
+    Queue,
+    Path,
+    Created,
+    Updated,
+    Completed,
+    Due,
+    Scheduled,
+    Recur,
+    Priority,
+    Rank,
+    Tags,
+    BlockedBy,
+    DailyNote,
+    Body,
+    /// First non-empty body line that is not a Markdown heading.
+    FirstLine,
+    /// Time since the task was created, such as `5h`, `3d`, or `2w`.
+    Age,
+    /// The due marker used by `tqs list`, such as `overdue 2d`.
+    DueStatus,
+}
+
+impl Field {
+    pub const NAMES: &[(&str, Field)] = &[
+        ("id", Field::Id),
+        ("title", Field::Title),
+        ("queue", Field::Queue),
+        ("path", Field::Path),
+        ("created", Field::Created),
+        ("updated", Field::Updated),
+        ("completed", Field::Completed),
+        ("due", Field::Due),
+        ("scheduled", Field::Scheduled),
+        ("recur", Field::Recur),
+        ("priority", Field::Priority),
+        ("rank", Field::Rank),
+        ("tags", Field::Tags),
+        ("blocked_by", Field::BlockedBy),
+        ("daily_note", Field::DailyNote),
+        ("body", Field::Body),
+        ("first_line", Field::FirstLine),
+        ("age", Field::Age),
+        ("due_status", Field::DueStatus),
+    ];
+
+    fn parse(name: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(candidate, _)| *candidate == name)
+            .map(|(_, field)| *field)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateError {
+    /// 1-based character column of the offending brace.
+    pub column: usize,
+    pub kind: TemplateErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateErrorKind {
+    UnclosedBrace,
+    UnmatchedClosingBrace,
+    UnknownField(String),
+    InvalidWidth(String),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid template at column {}: ", self.column)?;
+        match &self.kind {
+            TemplateErrorKind::UnclosedBrace => f.write_str("unclosed '{'"),
+            TemplateErrorKind::UnmatchedClosingBrace => {
+                f.write_str("unmatched '}'; write '}}' for a literal brace")
+            }
+            TemplateErrorKind::UnknownField(name) => write!(
+                f,
+                "unknown field '{name}'; expected one of: {}",
+                Field::NAMES
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            TemplateErrorKind::InvalidWidth(width) => write!(f, "invalid width '{width}'"),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+impl FromStr for Template {
+    type Err = TemplateError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let chars = value.chars().collect::<Vec<_>>();
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut index = 0;
+
+        while index < chars.len() {
+            let column = index + 1;
+            match (chars[index], chars.get(index + 1)) {
+                ('{', Some('{')) | ('}', Some('}')) => {
+                    literal.push(chars[index]);
+                    index += 2;
+                }
+                ('\\', Some('n')) => {
+                    literal.push('\n');
+                    index += 2;
+                }
+                ('\\', Some('t')) => {
+                    literal.push('\t');
+                    index += 2;
+                }
+                ('}', _) => {
+                    return Err(TemplateError {
+                        column,
+                        kind: TemplateErrorKind::UnmatchedClosingBrace,
+                    });
+                }
+                ('{', _) => {
+                    let Some(length) = chars[index + 1..].iter().position(|ch| *ch == '}') else {
+                        return Err(TemplateError {
+                            column,
+                            kind: TemplateErrorKind::UnclosedBrace,
+                        });
+                    };
+                    let spec = chars[index + 1..index + 1 + length]
+                        .iter()
+                        .collect::<String>();
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(parse_field(&spec, column)?);
+                    index += length + 2;
+                }
+                (ch, _) => {
+                    literal.push(ch);
+                    index += 1;
+                }
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        Ok(Self { segments })
+    }
+}
+
+fn parse_field(spec: &str, column: usize) -> Result<Segment, TemplateError> {
+    let (name, format) = match spec.split_once(':') {
+        Some((name, format)) => (name.trim(), Some(format.trim())),
+        None => (spec.trim(), None),
+    };
+    let field = Field::parse(name).ok_or_else(|| TemplateError {
+        column,
+        kind: TemplateErrorKind::UnknownField(name.to_string()),
+    })?;
+
+    let (width, align_right) = match format {
+        None => (None, false),
+        Some(format) => {
+            let (align_right, digits) = match format.strip_prefix('>') {
+                Some(digits) => (true, digits),
+                None => (false, format.strip_prefix('<').unwrap_or(format)),
+            };
+            let width = digits.parse::<usize>().map_err(|_| TemplateError {
+                column,
+                kind: TemplateErrorKind::InvalidWidth(format.to_string()),
+            })?;
+            (Some(width), align_right)
+        }
+    };
+
+    Ok(Segment::Field {
+        field,
+        width,
+        align_right,
+    })
+}
+
+impl Template {
+    /// Renders the template for a task stored at `path`, relative to `now`.
+    pub fn render(&self, task: &Task, path: &Path, now: DateTime<Local>) -> String {
+        let mut rendered = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => rendered.push_str(text),
+                Segment::Field {
+                    field,
+                    width,
+                    align_right,
+                } => {
+                    let value = field_value(*field, task, path, now);
+                    let width = width.unwrap_or(0);
+                    if *align_right {
+                        rendered.push_str(&format!("{value:>width$}"));
+                    } else {
+                        rendered.push_str(&format!("{value:<width$}"));
+                    }
+                }
+            }
+        }
+        rendered
+    }
+}
+
+fn field_value(field: Field, task: &Task, path: &Path, now: DateTime<Local>) -> String {
+    let today = now.date_naive();
+    match field {
+        Field::Id => task.id.clone(),
+        Field::Title => task.title.clone(),
+        Field::Queue => task.queue.to_string(),
+        Field::Path => path.display().to_string(),
+        Field::Created => format_timestamp(task.created_at),
+        Field::Updated => format_timestamp(task.updated_at),
+        Field::Completed => task.completed_at.map(format_timestamp).unwrap_or_default(),
+        Field::Due => task.due.map(|due| due.to_string()).unwrap_or_default(),
+        Field::Scheduled => task
+            .scheduled
+            .map(|scheduled| scheduled.to_string())
+            .unwrap_or_default(),
+        Field::Recur => task.recur.map(|rule| rule.to_string()).unwrap_or_default(),
+        Field::Priority => task
+            .priority
+            .map(|priority| priority.to_string())
+            .unwrap_or_default(),
+        Field::Rank => task.rank.map(|rank| rank.to_string()).unwrap_or_default(),
+        Field::Tags => format_tags(&task.tags),
+        Field::BlockedBy => task.blocked_by.join(", "),
+        Field::DailyNote => task.daily_note.clone().unwrap_or_default(),
+        Field::Body => task.body.clone(),
+        Field::FirstLine => task
+            .body
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#'))
+            .unwrap_or_default()
+            .to_string(),
+        Field::Age => format_age(task.created_at, now.with_timezone(&Utc)),
+        Field::DueStatus => match (task.due, task.due_status(today)) {
+            (Some(due), Some(_)) => describe_due(due, today),
+            _ => String::new(),
+        },
+    }
+}
+
+fn format_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp
+        .with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+fn format_age(from: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    format_duration(now - from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Template, TemplateErrorKind, format_age};
+    use crate::domain::priority::Priority;
+    use crate::domain::task::{Queue, Task};
+    use chrono::{DateTime, Local, Utc};
+    use std::path::Path;
+
+    fn timestamp(value: &str) -> DateTime<Utc> {
+        value.parse().expect("timestamp should parse")
+    }
+
+    fn task() -> Task {
+        let mut task = Task::new(
+            "0f3",
+            "Reply to AWS billing alert",
+            timestamp("2026-03-09T10:00:00Z"),
+        );
+        task.queue = Queue::Now;
+        task.tags = vec!["ops".to_string(), "aws".to_string()];
+        task.body = "# Reply to AWS billing alert\n\nCheck cost explorer.\nThen reply.".to_string();
+        task
+    }
+
+    fn render(template: &str) -> String {
+        let now = timestamp("2026-03-12T10:00:00Z").with_timezone(&Local);
+        template
+            .parse::<Template>()
+            .expect("template should parse")
+            .render(&task(), Path::new("/tasks/now/0f3.md"), now)
+    }
+
+    #[test]
+    fn renders_fields_and_derived_values() {
+        assert_eq!(
+            render("{id} [{queue}] {title} ({age})"),
+            "0f3 [now] Reply to AWS billing alert (3d)"
+        );
+        assert_eq!(
+            render("{tags}|{first_line}|{path}|{due}"),
+            "#ops #aws|Check cost explorer.|/tasks/now/0f3.md|"
+        );
+    }
+
+    #[test]
+    fn renders_priority_and_rank_when_set() {
+        let template = "[{priority}] {rank}|"
+            .parse::<Template>()
+            .expect("template should parse");
+        let now = timestamp("2026-03-12T10:00:00Z").with_timezone(&Local);
+        let path = Path::new("/tasks/now/0f3.md");
+        let mut task = task();
+        assert_eq!(template.render(&task, path, now), "[] |");
+
+        task.priority = Some(Priority::P1);
+        task.rank = Some(-3);
+        assert_eq!(template.render(&task, path, now), "[p1] -3|");
+    }
+
+    #[test]
+    fn supports_widths_escapes_and_literal_braces() {
+        assert_eq!(render("{id:5}|{queue:>5}|"), "0f3  |  now|");
+        assert_eq!(render("{{{id}}}\\t{queue}\\n"), "{0f3}\tnow\n");
+    }
+
+    #[test]
+    fn reports_errors_with_columns() {
+        let error = "{id} {owner}"
+            .parse::<Template>()
+            .expect_err("unknown field");
+        assert_eq!(error.column, 6);
+        assert_eq!(
+            error.kind,
+            TemplateErrorKind::UnknownField("owner".to_string())
+        );
+        assert!(error.to_string().starts_with(
+            "invalid template at column 6: unknown field 'owner'; expected one of: id, title"
+        ));
+
+        let error = "{id".parse::<Template>().expect_err("unclosed");
+        assert_eq!(error.kind, TemplateErrorKind::UnclosedBrace);
+        let error = "id}".parse::<Template>().expect_err("unmatched");
+        assert_eq!(error.kind, TemplateErrorKind::UnmatchedClosingBrace);
+        let error = "{id:wide}".parse::<Template>().expect_err("bad width");
+        assert_eq!(
+            error.kind,
+            TemplateErrorKind::InvalidWidth("wide".to_string())
+        );
+    }
+
+    #[test]
+    fn age_uses_compact_units() {
+        let created = timestamp("2026-01-01T00:00:00Z");
+        assert_eq!(
+            format_age(created, timestamp("2026-01-01T00:30:00Z")),
+            "30m"
+        );
+        assert_eq!(format_age(created, timestamp("2026-01-04T00:00:00Z")), "3d");
+        assert_eq!(format_age(created, timestamp("2026-01-29T00:00:00Z")), "4w");
+        assert_eq!(
+            format_age(created, timestamp("2026-06-01T00:00:00Z")),
+            "5mo"
+        );
+        assert_eq!(format_age(created, timestamp("2028-01-02T00:00:00Z")), "2y");
+    }
+}
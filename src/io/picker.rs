@@ -1,6 +1,10 @@
 use crate::app::app_error::AppError;
-use crate::domain::task::Queue;
+use crate::domain::{
+    query::Query,
+    task::{Queue, format_tags},
+};
 use crate::storage::repo::StoredTask;
+use chrono::{Local, NaiveDate};
 use dialoguer::console::{Key, Term, style};
 use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
 
@@ -65,6 +69,8 @@ impl Drop for TerminalGuard<'_> {
 #[derive(Debug, Clone, Copy)]
 pub struct TaskPickerOptions<'a> {
     pub prompt: &'a str,
+    /// Queues that Tab and Shift-Tab cycle the queue filter through.
+    pub queues: &'a [Queue],
 }
 
 #[derive(Debug, Clone)]
@@ -76,7 +82,10 @@ struct VisibleItem {
 #[derive(Debug, Clone, Copy)]
 struct RenderState<'a> {
     prompt: &'a str,
+    /// Task indexes marked for a multi-select, or `None` for a single pick.
+    marked: Option<&'a [usize]>,
     search: &'a str,
+    search_error: Option<&'a str>,
     queue_filter: Option<Queue>,
     selected: Option<usize>,
     scroll: usize,
@@ -87,6 +96,24 @@ pub fn pick_task(
     tasks: &[StoredTask],
     options: TaskPickerOptions<'_>,
 ) -> Result<Option<usize>, AppError> {
+    Ok(run_picker(tasks, options, false)?.and_then(|indexes| indexes.first().copied()))
+}
+
+/// Picks several tasks: Right arrow marks or unmarks the highlighted task,
+/// Ctrl-A marks every visible task, and Enter returns the marked tasks in the
+/// order they were marked, or the highlighted task when nothing is marked.
+pub fn pick_tasks(
+    tasks: &[StoredTask],
+    options: TaskPickerOptions<'_>,
+) -> Result<Option<Vec<usize>>, AppError> {
+    run_picker(tasks, options, true)
+}
+
+fn run_picker(
+    tasks: &[StoredTask],
+    options: TaskPickerOptions<'_>,
+    multi: bool,
+) -> Result<Option<Vec<usize>>, AppError> {
     if tasks.is_empty() {
         return Ok(None);
     }
@@ -102,16 +129,22 @@ pub fn pick_task(
     let mut selected = Some(0usize);
     let mut scroll = 0usize;
     let mut rendered_lines = 0usize;
+    let mut marked = Vec::new();
     let mut guard = TerminalGuard::new(&term)?;
 
+    let today = Local::now().date_naive();
+
     loop {
-        let visible = build_visible_items(tasks, &search, queue_filter, &matcher);
+        let (text, filter, search_error) = split_search(&search, today, options.queues);
+        let visible = build_visible_items(tasks, &text, &filter, queue_filter, &matcher);
         sync_selection(&visible, &mut selected, &mut scroll);
         rendered_lines = render_picker(
             &term,
             RenderState {
                 prompt: options.prompt,
+                marked: multi.then_some(marked.as_slice()),
                 search: &search,
+                search_error: search_error.as_deref(),
                 queue_filter,
                 selected,
                 scroll,
@@ -127,12 +160,12 @@ pub fn pick_task(
             Key::ArrowUp => move_selection_up(&visible, &mut selected, &mut scroll),
             Key::ArrowDown => move_selection_down(&visible, &mut selected, &mut scroll),
             Key::Tab => {
-                queue_filter = next_queue_filter(queue_filter);
+                queue_filter = next_queue_filter(queue_filter, options.queues);
                 selected = Some(0);
                 scroll = 0;
             }
             Key::BackTab => {
-                queue_filter = prev_queue_filter(queue_filter);
+                queue_filter = prev_queue_filter(queue_filter, options.queues);
                 selected = Some(0);
                 scroll = 0;
             }
@@ -141,9 +174,19 @@ pub fn pick_task(
                 selected = Some(0);
                 scroll = 0;
             }
+            Key::ArrowRight if multi => {
+                if let Some(choice) = selected.and_then(|index| visible.get(index)) {
+                    toggle_mark(&mut marked, choice.task_index);
+                    move_selection_down(&visible, &mut selected, &mut scroll);
+                }
+            }
+            Key::Char('\x01') if multi => mark_all(&mut marked, &visible),
             Key::Enter => {
+                if multi && !marked.is_empty() {
+                    break Ok(Some(marked));
+                }
                 if let Some(choice) = selected.and_then(|index| visible.get(index)) {
-                    break Ok(Some(choice.task_index));
+                    break Ok(Some(vec![choice.task_index]));
                 }
             }
             Key::Char(ch) if !ch.is_ascii_control() => {
@@ -156,31 +199,77 @@ pub fn pick_task(
     }
 }
 
-fn next_queue_filter(current: Option<Queue>) -> Option<Queue> {
+fn toggle_mark(marked: &mut Vec<usize>, task_index: usize) {
+    match marked.iter().position(|index| *index == task_index) {
+        Some(position) => {
+            marked.remove(position);
+        }
+        None => marked.push(task_index),
+    }
+}
+
+/// Marks every visible task, or clears them all when they are already marked.
+fn mark_all(marked: &mut Vec<usize>, visible: &[VisibleItem]) {
+    if visible.iter().all(|item| marked.contains(&item.task_index)) {
+        marked.retain(|index| !visible.iter().any(|item| item.task_index == *index));
+    } else {
+        for item in visible {
+            if !marked.contains(&item.task_index) {
+                marked.push(item.task_index);
+            }
+        }
+    }
+}
+
+/// Cycles no filter → each queue in order → no filter.
+fn next_queue_filter(current: Option<Queue>, queues: &[Queue]) -> Option<Queue> {
     match current {
-        None => Some(Queue::Inbox),
-        Some(Queue::Inbox) => Some(Queue::Now),
-        Some(Queue::Now) => Some(Queue::Next),
-        Some(Queue::Next) => Some(Queue::Later),
-        Some(Queue::Later) => Some(Queue::Done),
-        Some(Queue::Done) => None,
+        None => queues.first().copied(),
+        Some(queue) => queues
+            .iter()
+            .position(|other| *other == queue)
+            .and_then(|index| queues.get(index + 1))
+            .copied(),
     }
 }
 
-fn prev_queue_filter(current: Option<Queue>) -> Option<Queue> {
+fn prev_queue_filter(current: Option<Queue>, queues: &[Queue]) -> Option<Queue> {
     match current {
-        None => Some(Queue::Done),
-        Some(Queue::Done) => Some(Queue::Later),
-        Some(Queue::Later) => Some(Queue::Next),
-        Some(Queue::Next) => Some(Queue::Now),
-        Some(Queue::Now) => Some(Queue::Inbox),
-        Some(Queue::Inbox) => None,
+        None => queues.last().copied(),
+        Some(queue) => queues
+            .iter()
+            .position(|other| *other == queue)
+            .and_then(|index| index.checked_sub(1))
+            .and_then(|index| queues.get(index))
+            .copied(),
+    }
+}
+
+/// Splits the search into free text for fuzzy matching and the remaining
+/// query terms, which must match exactly. Until the search parses, all of it
+/// is treated as fuzzy text and the parse error is returned for display.
+fn split_search(
+    search: &str,
+    today: NaiveDate,
+    queues: &[Queue],
+) -> (String, Query, Option<String>) {
+    match Query::parse(search, today, queues) {
+        Ok(query) => {
+            let (text, filter) = query.split_text();
+            (text, filter, None)
+        }
+        Err(error) => (
+            search.to_string(),
+            Query::default(),
+            Some(error.to_string()),
+        ),
     }
 }
 
 fn build_visible_items(
     tasks: &[StoredTask],
-    search: &str,
+    text: &str,
+    filter: &Query,
     queue_filter: Option<Queue>,
     matcher: &SkimMatcherV2,
 ) -> Vec<VisibleItem> {
@@ -193,15 +282,18 @@ fn build_visible_items(
             {
                 return None;
             }
+            if !filter.matches(&stored.task) {
+                return None;
+            }
 
             let display = format!(
                 "{} {} - {}",
                 stored.task.queue, stored.task.id, stored.task.title
             );
-            let score = if search.is_empty() {
+            let score = if text.is_empty() {
                 Some(0)
             } else {
-                matcher.fuzzy_match(&display, search)
+                matcher.fuzzy_match(&display, text)
             }?;
 
             Some(VisibleItem {
@@ -247,13 +339,22 @@ fn render_picker(
     if !state.search.is_empty() {
         prompt_line.push_str(&format!("  search: {}", state.search));
     }
+    if let Some(error) = state.search_error {
+        prompt_line.push_str(&format!("  {}", style(error).red()));
+    }
+    if let Some(marked) = state.marked
+        && !marked.is_empty()
+    {
+        prompt_line.push_str(&format!("  marked: {}", style(marked.len()).green()));
+    }
 
     term.write_line(&prompt_line)?;
-    term.write_line(&format!(
-        "{}",
-        style("Up/Down: navigate  Tab/Shift-Tab: filter by queue  Enter: select  Esc: cancel")
-            .cyan()
-    ))?;
+    let hints = if state.marked.is_some() {
+        "Up/Down: navigate  Right: mark  Ctrl-A: mark all  Tab/Shift-Tab: filter by queue  tag:ops due<7d: filter  Enter: select  Esc: cancel"
+    } else {
+        "Up/Down: navigate  Tab/Shift-Tab: filter by queue  tag:ops due<7d: filter  Enter: select  Esc: cancel"
+    };
+    term.write_line(&format!("{}", style(hints).cyan()))?;
 
     let rows = term.size().0 as usize;
     let max_items = rows.saturating_sub(2).max(1);
@@ -273,8 +374,16 @@ fn render_picker(
     for (visible_index, item) in visible.iter().enumerate().skip(start).take(max_items) {
         let is_selected = Some(visible_index) == state.selected;
         let stored = &tasks[item.task_index];
-        let queue_str = format!("{:<5}", stored.task.queue);
-        let rest = format!("  {} - {}", stored.task.id, stored.task.title);
+        let mark = match state.marked {
+            Some(marked) if marked.contains(&item.task_index) => "[x] ",
+            Some(_) => "[ ] ",
+            None => "",
+        };
+        let queue_str = format!("{mark}{:<5}", stored.task.queue);
+        let mut rest = format!("  {} - {}", stored.task.id, stored.task.title);
+        if !stored.task.tags.is_empty() {
+            rest.push_str(&format!("  {}", format_tags(&stored.task.tags)));
+        }
 
         if is_selected {
             term.write_line(&format!(
@@ -322,11 +431,23 @@ fn move_selection_down(visible: &[VisibleItem], selected: &mut Option<usize>, _s
 
 #[cfg(test)]
 mod tests {
-    use super::{build_visible_items, sync_selection};
-    use crate::{domain::task::Task, storage::repo::StoredTask};
+    use super::{
+        VisibleItem, build_visible_items, mark_all, next_queue_filter, prev_queue_filter,
+        split_search, sync_selection, toggle_mark,
+    };
+    use crate::{
+        domain::task::{Queue, Task},
+        storage::repo::StoredTask,
+    };
     use fuzzy_matcher::skim::SkimMatcherV2;
     use std::path::PathBuf;
 
+    fn visible(items: &[StoredTask], search: &str) -> Vec<VisibleItem> {
+        let today = "2026-03-09".parse().expect("date should parse");
+        let (text, filter, _) = split_search(search, today, Queue::all());
+        build_visible_items(items, &text, &filter, None, &SkimMatcherV2::default())
+    }
+
     fn stored_task(id: &str, title: &str) -> StoredTask {
         StoredTask {
             task: Task::new(
@@ -340,12 +461,74 @@ mod tests {
         }
     }
 
+    #[test]
+    fn queue_filter_cycles_through_the_given_queues_and_back_to_none() {
+        let queues = [Queue::Inbox, Queue::Now, Queue::Done];
+        assert_eq!(next_queue_filter(None, &queues), Some(Queue::Inbox));
+        assert_eq!(
+            next_queue_filter(Some(Queue::Now), &queues),
+            Some(Queue::Done)
+        );
+        assert_eq!(next_queue_filter(Some(Queue::Done), &queues), None);
+        assert_eq!(prev_queue_filter(None, &queues), Some(Queue::Done));
+        assert_eq!(prev_queue_filter(Some(Queue::Inbox), &queues), None);
+    }
+
     #[test]
     fn build_visible_items_uses_fuzzy_search() {
-        let matcher = SkimMatcherV2::default();
         let items = vec![stored_task("task-1", "Reply to AWS billing alert")];
-        let visible = build_visible_items(&items, "aws", None, &matcher);
-        assert_eq!(visible.len(), 1);
+        assert_eq!(visible(&items, "aws").len(), 1);
+    }
+
+    #[test]
+    fn build_visible_items_filters_by_hash_tags() {
+        let mut tagged = stored_task("task-1", "Reply to AWS billing alert");
+        tagged.task.tags = vec!["ops".to_string()];
+        let items = vec![tagged, stored_task("task-2", "Reply to AWS support")];
+
+        let matches = visible(&items, "#ops");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].task_index, 0);
+
+        assert!(visible(&items, "support #ops").is_empty());
+    }
+
+    #[test]
+    fn build_visible_items_applies_query_terms_and_falls_back_on_errors() {
+        let mut tagged = stored_task("task-1", "Reply to AWS billing alert");
+        tagged.task.tags = vec!["ops".to_string()];
+        let items = vec![tagged, stored_task("task-2", "Reply to AWS support")];
+
+        let matches = visible(&items, "aws -tag:ops");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].task_index, 1);
+
+        let today = "2026-03-09".parse().expect("date should parse");
+        let (text, filter, error) = split_search("aws due<", today, Queue::all());
+        assert_eq!(text, "aws due<");
+        assert!(filter.is_empty());
+        assert!(error.is_some_and(|error| error.contains("missing value for 'due'")));
+    }
+
+    #[test]
+    fn marks_toggle_and_mark_all_covers_visible_items() {
+        let items = vec![
+            stored_task("task-1", "Reply to AWS billing alert"),
+            stored_task("task-2", "Reply to AWS support"),
+            stored_task("task-3", "Plan offsite"),
+        ];
+        let mut marked = Vec::new();
+        toggle_mark(&mut marked, 2);
+        toggle_mark(&mut marked, 0);
+        assert_eq!(marked, vec![2, 0]);
+        toggle_mark(&mut marked, 2);
+        assert_eq!(marked, vec![0]);
+
+        let aws = visible(&items, "aws");
+        mark_all(&mut marked, &aws);
+        assert_eq!(marked, vec![0, 1]);
+        mark_all(&mut marked, &aws);
+        assert!(marked.is_empty());
     }
 
     #[test]
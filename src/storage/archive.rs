@@ -0,0 +1,122 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Datelike, Utc};
+
+use crate::app::app_error::AppError;
+use crate::domain::task::Task;
+
+/// When a done task counts as finished for archiving: its completion time,
+/// or its last update for files written before `completed_at` existed.
+pub fn archived_on(task: &Task) -> DateTime<Utc> {
+    task.completed_at.unwrap_or(task.updated_at)
+}
+
+/// The dated folder under the done directory that an archived task lives in,
+/// such as `2026/10`.
+pub fn archive_subdir(task: &Task) -> PathBuf {
+    let date = archived_on(task);
+    Path::new(&format!("{:04}", date.year())).join(format!("{:02}", date.month()))
+}
+
+/// The `<year>/<month>` folders under `done_dir`, oldest first. Other
+/// directories are left alone.
+pub fn archive_dirs(done_dir: &Path) -> Result<Vec<PathBuf>, AppError> {
+    let mut dirs = Vec::new();
+    for year in dated_subdirs(done_dir, is_year)? {
+        dirs.extend(dated_subdirs(&year, is_month)?);
+    }
+    Ok(dirs)
+}
+
+/// Every Markdown file in the archive folders under `done_dir`.
+pub fn archived_files(done_dir: &Path) -> Result<Vec<PathBuf>, AppError> {
+    let mut files = Vec::new();
+    for dir in archive_dirs(done_dir)? {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().and_then(|ext| ext.to_str()) == Some("md") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn dated_subdirs(dir: &Path, accepts: fn(&str) -> bool) -> Result<Vec<PathBuf>, AppError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(AppError::Io(error)),
+    };
+
+    let mut dirs = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let named = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(accepts);
+        if named && path.is_dir() {
+            dirs.push(path);
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
+
+fn is_year(name: &str) -> bool {
+    name.len() == 4 && name.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+fn is_month(name: &str) -> bool {
+    name.len() == 2 && matches!(name.parse::<u32>(), Ok(1..=12))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{archive_subdir, archived_files};
+    use crate::domain::task::Task;
+    use std::{fs, path::Path};
+    use tempfile::TempDir;
+
+    #[test]
+    fn archive_folders_follow_the_completion_month() {
+        let mut task = Task::new(
+            "abc",
+            "Ship it",
+            "2026-03-09T10:34:12Z"
+                .parse()
+                .expect("timestamp should parse"),
+        );
+        assert_eq!(archive_subdir(&task), Path::new("2026").join("03"));
+        task.completed_at = Some(
+            "2026-10-01T08:00:00Z"
+                .parse()
+                .expect("timestamp should parse"),
+        );
+        assert_eq!(archive_subdir(&task), Path::new("2026").join("10"));
+    }
+
+    #[test]
+    fn archived_files_only_come_from_dated_folders() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let done = temp.path();
+        for dir in ["2026/10", "2025/01", "2025/13", "notes"] {
+            fs::create_dir_all(done.join(dir)).expect("dir should exist");
+            fs::write(done.join(dir).join("abc.md"), "task").expect("file should be written");
+        }
+        fs::write(done.join("2026/10/readme.txt"), "notes").expect("file should be written");
+        fs::write(done.join("live.md"), "task").expect("file should be written");
+
+        let files = archived_files(done).expect("archive should be listed");
+        assert_eq!(
+            files,
+            vec![done.join("2025/01/abc.md"), done.join("2026/10/abc.md")]
+        );
+    }
+}
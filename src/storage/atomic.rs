@@ -0,0 +1,119 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::Path,
+    process,
+};
+
+/// Replaces `path` with `contents` so that, even after a crash or power loss,
+/// it holds either its old contents or all of the new ones.
+///
+/// The contents go to a hidden temporary file next to `path`, which is
+/// flushed to disk and then renamed over it; the directory is synced last so
+/// the rename itself is durable. The temporary name does not end in `.md`, so
+/// one left behind by a crash is never read as a task.
+pub fn write(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let dir = parent(path);
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+    let temp_path = dir.join(format!(".{}.{}.tmp", name.to_string_lossy(), process::id()));
+
+    let written = File::create(&temp_path).and_then(|mut file| {
+        file.write_all(contents.as_ref())?;
+        file.sync_all()
+    });
+    if let Err(error) = written.and_then(|()| fs::rename(&temp_path, path)) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(error);
+    }
+    sync_dir(dir)
+}
+
+/// Removes `path` and syncs its directory, so the removal is on disk before
+/// whatever comes next. A file that is already gone is not an error.
+pub fn remove(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => sync_dir(parent(path)),
+    }
+}
+
+/// Renames `from` to `to` and syncs both directories, so that after a crash
+/// the file is found under exactly one of the two names. The destination
+/// directory is synced first: losing the old name before the new one would
+/// lose the file.
+pub fn rename(from: &Path, to: &Path) -> io::Result<()> {
+    fs::rename(from, to)?;
+    sync_dir(parent(to))?;
+    if parent(from) != parent(to) {
+        sync_dir(parent(from))?;
+    }
+    Ok(())
+}
+
+/// Flushes a directory's entries, which makes creating, renaming, and
+/// removing files in it durable. Only Unix can open a directory to sync it;
+/// elsewhere the rename is left to the file system.
+pub fn sync_dir(dir: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    File::open(dir)?.sync_all()?;
+    #[cfg(not(unix))]
+    let _ = dir;
+    Ok(())
+}
+
+fn parent(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{remove, rename, write};
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn write_replaces_the_file_without_leaving_a_temp_file() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let path = temp.path().join("abc.md");
+        fs::write(&path, "old contents that are longer").expect("file should be written");
+
+        write(&path, "new").expect("file should be replaced");
+
+        assert_eq!(fs::read_to_string(&path).expect("file should exist"), "new");
+        let names = fs::read_dir(temp.path())
+            .expect("dir should be readable")
+            .map(|entry| entry.expect("entry should be readable").file_name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["abc.md"]);
+    }
+
+    #[test]
+    fn remove_tolerates_a_missing_file() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let path = temp.path().join("abc.md");
+        fs::write(&path, "task").expect("file should be written");
+
+        remove(&path).expect("file should be removed");
+        assert!(!path.exists());
+        remove(&path).expect("a missing file should be fine");
+    }
+
+    #[test]
+    fn rename_moves_the_file_into_another_directory() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let from = temp.path().join("abc.md");
+        let to = temp.path().join("2026").join("abc.md");
+        fs::write(&from, "task").expect("file should be written");
+        fs::create_dir(temp.path().join("2026")).expect("dir should be created");
+
+        rename(&from, &to).expect("file should be renamed");
+
+        assert!(!from.exists());
+        assert_eq!(fs::read_to_string(&to).expect("file should exist"), "task");
+    }
+}
@@ -1,11 +1,20 @@
 use std::{
-    env, fs,
+    collections::BTreeMap,
+    env, fmt, fs,
     path::{Component, Path, PathBuf},
 };
 
-use serde::Deserialize;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
 
-use crate::{app::app_error::AppError, domain::task::Queue};
+use crate::{
+    app::app_error::AppError,
+    domain::{
+        date::Age,
+        task::{MAX_QUEUE_NAME_LEN, Queue, QueueName},
+        view::SavedView,
+    },
+};
 
 const CONFIG_FILE_NAME: &str = "config.toml";
 
@@ -25,8 +34,29 @@ pub struct ResolvedConfig {
     pub state_dir: PathBuf,
     pub daily_notes_dir: Option<PathBuf>,
     pub queue_dirs: QueueDirs,
+    /// Queue that receives the next occurrence of a completed recurring task.
+    pub recur_queue: Queue,
+    /// Saved views from `[views.<name>]` tables, in name order.
+    pub views: Vec<SavedView>,
+    /// Named output templates from the `[templates]` table, checked when used.
+    pub templates: BTreeMap<String, String>,
+    /// How long done tasks stay in the done directory before `tqs done`
+    /// archives them; unset keeps them there until `tqs archive` runs.
+    pub auto_archive_after: Option<Age>,
+    /// Set when the `[git]` table turns on committing and syncing.
+    pub git: Option<GitConfig>,
+}
+
+/// Settings from the `[git]` table, for a tasks root kept in a git repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitConfig {
+    /// Remote that `tqs sync` pulls from and pushes to.
+    pub remote: String,
+    /// Branch to sync; the checked-out branch when unset.
+    pub branch: Option<String>,
 }
 
+/// Queue directory names, plus the custom queues defined in config.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct QueueDirs {
     pub(crate) inbox: String,
@@ -34,8 +64,48 @@ pub struct QueueDirs {
     pub(crate) next: String,
     pub(crate) later: String,
     pub(crate) done: String,
+    pub(crate) custom: Vec<CustomQueue>,
+}
+
+/// A queue from a `[queues.<name>]` config table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomQueue {
+    pub queue: Queue,
+    pub dir: String,
+    /// Sort position among all queues; the built-ins sit at inbox 10, now 20,
+    /// next 30, later 40, and done 50.
+    pub position: i64,
+    pub group: QueueGroup,
+    /// Whether the queue is listed with now and next on the dashboard.
+    pub active: bool,
 }
 
+/// The sidebar group a queue is listed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QueueGroup {
+    /// With now, next, and later.
+    #[default]
+    Active,
+    /// With the inbox.
+    Inbox,
+    /// With done and "all".
+    Done,
+}
+
+impl fmt::Display for QueueGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Active => "active",
+            Self::Inbox => "inbox",
+            Self::Done => "done",
+        })
+    }
+}
+
+/// Where custom queues go when config gives no `position`: after later.
+const DEFAULT_CUSTOM_POSITION: i64 = 45;
+
 impl Default for QueueDirs {
     fn default() -> Self {
         Self {
@@ -44,18 +114,105 @@ impl Default for QueueDirs {
             next: "next".to_string(),
             later: "later".to_string(),
             done: "done".to_string(),
+            custom: Vec::new(),
         }
     }
 }
 
 impl QueueDirs {
-    pub fn dir_name(&self, queue: Queue) -> &str {
+    pub fn dir_name<'a>(&'a self, queue: &'a Queue) -> &'a str {
         match queue {
             Queue::Inbox => &self.inbox,
             Queue::Now => &self.now,
             Queue::Next => &self.next,
             Queue::Later => &self.later,
             Queue::Done => &self.done,
+            Queue::Custom(name) => self
+                .custom_queue(*queue)
+                .map_or(name.as_str(), |custom| &custom.dir),
+        }
+    }
+
+    pub fn custom_queues(&self) -> &[CustomQueue] {
+        &self.custom
+    }
+
+    fn custom_queue(&self, queue: Queue) -> Option<&CustomQueue> {
+        self.custom.iter().find(|custom| custom.queue == queue)
+    }
+
+    fn position(&self, queue: Queue) -> i64 {
+        match queue {
+            Queue::Inbox => 10,
+            Queue::Now => 20,
+            Queue::Next => 30,
+            Queue::Later => 40,
+            Queue::Done => 50,
+            Queue::Custom(_) => self
+                .custom_queue(queue)
+                .map_or(DEFAULT_CUSTOM_POSITION, |custom| custom.position),
+        }
+    }
+
+    /// Every queue, built-in and custom, in sort position order.
+    pub fn queues(&self) -> Vec<Queue> {
+        let mut queues = Queue::all().to_vec();
+        queues.extend(self.custom.iter().map(|custom| custom.queue));
+        queues.sort_by_key(|queue| self.position(*queue));
+        queues
+    }
+
+    pub fn contains(&self, queue: Queue) -> bool {
+        !matches!(queue, Queue::Custom(_)) || self.custom_queue(queue).is_some()
+    }
+
+    /// Parses a queue name, accepting only the built-in queues and the custom
+    /// queues configured here.
+    pub fn parse(&self, value: &str) -> Result<Queue, AppError> {
+        value
+            .parse::<Queue>()
+            .ok()
+            .filter(|queue| self.contains(*queue))
+            .ok_or_else(|| {
+                AppError::usage(format!(
+                    "invalid queue '{}'; expected one of: {}",
+                    value.trim(),
+                    Queue::all()
+                        .iter()
+                        .chain(self.custom.iter().map(|custom| &custom.queue))
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+            })
+    }
+
+    pub fn group(&self, queue: Queue) -> QueueGroup {
+        match queue {
+            Queue::Now | Queue::Next | Queue::Later => QueueGroup::Active,
+            Queue::Inbox => QueueGroup::Inbox,
+            Queue::Done => QueueGroup::Done,
+            Queue::Custom(_) => self
+                .custom_queue(queue)
+                .map_or(QueueGroup::Active, |custom| custom.group),
+        }
+    }
+
+    /// Queues shown on the dashboard: now, next, and active custom queues.
+    pub fn active_queues(&self) -> Vec<Queue> {
+        self.queues()
+            .into_iter()
+            .filter(|queue| self.is_active(*queue))
+            .collect()
+    }
+
+    /// Whether a queue counts as active work: now, next, and custom queues
+    /// marked `active = true`.
+    pub fn is_active(&self, queue: Queue) -> bool {
+        match queue {
+            Queue::Now | Queue::Next => true,
+            Queue::Inbox | Queue::Later | Queue::Done => false,
+            Queue::Custom(_) => self.custom_queue(queue).is_some_and(|custom| custom.active),
         }
     }
 }
@@ -65,8 +222,31 @@ struct FileConfig {
     obsidian_vault_dir: Option<PathBuf>,
     tasks_root: Option<PathBuf>,
     daily_notes_dir: Option<PathBuf>,
+    recur_queue: Option<String>,
+    auto_archive_after: Option<String>,
     #[serde(default)]
     queues: QueueDirsOverride,
+    #[serde(default)]
+    views: BTreeMap<String, ViewConfig>,
+    #[serde(default)]
+    templates: BTreeMap<String, String>,
+    git: Option<GitFileConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GitFileConfig {
+    /// Defaults to on, so `[git]` alone is enough.
+    enabled: Option<bool>,
+    remote: Option<String>,
+    branch: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ViewConfig {
+    query: String,
+    sort: Option<String>,
+    columns: Option<Vec<String>>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -76,6 +256,20 @@ struct QueueDirsOverride {
     next: Option<String>,
     later: Option<String>,
     done: Option<String>,
+    /// Any other `[queues.<name>]` table defines a custom queue.
+    #[serde(flatten)]
+    custom: BTreeMap<String, CustomQueueConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CustomQueueConfig {
+    dir: Option<String>,
+    position: Option<i64>,
+    #[serde(default)]
+    group: QueueGroup,
+    #[serde(default)]
+    active: bool,
 }
 
 impl QueueDirsOverride {
@@ -107,6 +301,47 @@ pub fn resolve(explicit_root: Option<PathBuf>) -> Result<ResolvedConfig, AppErro
         .map(|config| build_queue_dirs(&config.queues))
         .transpose()?
         .unwrap_or_default();
+    let recur_queue = file_config
+        .as_ref()
+        .and_then(|config| config.recur_queue.as_deref())
+        .map(|value| {
+            queue_dirs.parse(value).map_err(|_| {
+                AppError::message(format!("invalid config: unknown recur_queue '{value}'"))
+            })
+        })
+        .transpose()?
+        .unwrap_or(Queue::Inbox);
+    if recur_queue.is_done() {
+        return Err(AppError::message(
+            "invalid config: recur_queue cannot be the done queue",
+        ));
+    }
+    let auto_archive_after = file_config
+        .as_ref()
+        .and_then(|config| config.auto_archive_after.as_deref())
+        .map(|value| {
+            value.parse::<Age>().map_err(|error| {
+                AppError::message(format!("invalid config: auto_archive_after: {error}"))
+            })
+        })
+        .transpose()?;
+    let views = file_config
+        .as_ref()
+        .map(|config| build_views(&config.views, &queue_dirs))
+        .transpose()?
+        .unwrap_or_default();
+    let templates = file_config
+        .as_ref()
+        .map(|config| config.templates.clone())
+        .unwrap_or_default();
+    let git = file_config
+        .as_ref()
+        .and_then(|config| config.git.as_ref())
+        .filter(|git| git.enabled != Some(false))
+        .map(|git| GitConfig {
+            remote: git.remote.clone().unwrap_or_else(|| "origin".to_string()),
+            branch: git.branch.clone(),
+        });
     let state_dir = file_config
         .as_ref()
         .and_then(|config| config.obsidian_vault_dir.clone())
@@ -121,6 +356,11 @@ pub fn resolve(explicit_root: Option<PathBuf>) -> Result<ResolvedConfig, AppErro
         state_dir,
         daily_notes_dir,
         queue_dirs,
+        recur_queue,
+        views,
+        templates,
+        auto_archive_after,
+        git,
     })
 }
 
@@ -229,6 +469,24 @@ fn apply_obsidian_alias(config: &mut FileConfig) -> Result<(), AppError> {
 
 fn build_queue_dirs(overrides: &QueueDirsOverride) -> Result<QueueDirs, AppError> {
     let defaults = QueueDirs::default();
+    let custom = overrides
+        .custom
+        .iter()
+        .map(|(name, config)| {
+            let name = QueueName::new(name).map_err(|_| {
+                AppError::message(format!(
+                    "invalid config: queue name '{name}' must start with a lowercase letter, use only lowercase letters, digits, '-', and '_', be at most {MAX_QUEUE_NAME_LEN} characters, and not be a built-in queue or 'all'"
+                ))
+            })?;
+            Ok(CustomQueue {
+                queue: Queue::Custom(name),
+                dir: queue_dir_name(config.dir.as_deref(), name.as_str())?,
+                position: config.position.unwrap_or(DEFAULT_CUSTOM_POSITION),
+                group: config.group,
+                active: config.active,
+            })
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
 
     Ok(QueueDirs {
         inbox: queue_dir_name(overrides.inbox.as_deref(), &defaults.inbox)?,
@@ -236,6 +494,7 @@ fn build_queue_dirs(overrides: &QueueDirsOverride) -> Result<QueueDirs, AppError
         next: queue_dir_name(overrides.next.as_deref(), &defaults.next)?,
         later: queue_dir_name(overrides.later.as_deref(), &defaults.later)?,
         done: queue_dir_name(overrides.done.as_deref(), &defaults.done)?,
+        custom,
     })
 }
 
@@ -262,6 +521,52 @@ fn queue_dir_name(value: Option<&str>, default: &str) -> Result<String, AppError
     }
 }
 
+fn build_views(
+    views: &BTreeMap<String, ViewConfig>,
+    queue_dirs: &QueueDirs,
+) -> Result<Vec<SavedView>, AppError> {
+    let today = Local::now().date_naive();
+    let queues = queue_dirs.queues();
+    views
+        .iter()
+        .map(|(name, view)| {
+            let invalid = |error: String| {
+                AppError::message(format!("invalid config: view '{name}': {error}"))
+            };
+            if name.trim().is_empty() || name.chars().any(char::is_whitespace) {
+                return Err(invalid(
+                    "view names cannot be empty or contain spaces".to_string(),
+                ));
+            }
+
+            let sort = view
+                .sort
+                .as_deref()
+                .map(str::parse)
+                .transpose()
+                .map_err(|error| invalid(format!("{error}")))?;
+            let columns = match &view.columns {
+                Some(columns) if !columns.is_empty() => columns
+                    .iter()
+                    .map(|column| column.parse())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|error| invalid(format!("{error}")))?,
+                _ => SavedView::DEFAULT_COLUMNS.to_vec(),
+            };
+            let saved = SavedView {
+                name: name.clone(),
+                query: view.query.clone(),
+                sort,
+                columns,
+            };
+            saved
+                .parse_query(today, &queues)
+                .map_err(|error| invalid(error.to_string()))?;
+            Ok(saved)
+        })
+        .collect()
+}
+
 fn config_path() -> Option<PathBuf> {
     if let Some(xdg_config) = env::var_os("XDG_CONFIG_HOME")
         .filter(|value| !value.is_empty())
@@ -294,7 +599,9 @@ fn absolutize_from(base_dir: &Path, value: PathBuf) -> PathBuf {
 
 #[cfg(test)]
 mod tests {
-    use super::{QueueDirsOverride, build_queue_dirs, inspect, resolve};
+    use super::{GitConfig, QueueDirsOverride, QueueGroup, build_queue_dirs, inspect, resolve};
+    use crate::domain::task::Queue;
+    use crate::domain::view::{SavedView, ViewColumn};
     use crate::test_support::LockedEnv;
     use std::{fs, path::PathBuf};
     use tempfile::TempDir;
@@ -342,17 +649,244 @@ mod tests {
         assert_eq!(
             resolved
                 .queue_dirs
-                .dir_name(crate::domain::task::Queue::Now),
+                .dir_name(&crate::domain::task::Queue::Now),
             "focus"
         );
         assert_eq!(
             resolved
                 .queue_dirs
-                .dir_name(crate::domain::task::Queue::Done),
+                .dir_name(&crate::domain::task::Queue::Done),
             "archive"
         );
     }
 
+    #[test]
+    fn resolve_reads_custom_queues_and_orders_them_by_position() {
+        let mut env = LockedEnv::new(&["XDG_CONFIG_HOME", "TQS_ROOT"]);
+        let temp = TempDir::new().expect("temp dir should exist");
+        let config_home = temp.path().join("config-home");
+        let config_dir = config_home.join("tqs");
+        fs::create_dir_all(&config_dir).expect("config dir should exist");
+        fs::write(
+            config_dir.join("config.toml"),
+            "tasks_root = 'tasks'\nrecur_queue = 'waiting'\n[queues]\nnow = 'focus'\n[queues.waiting]\ndir = 'waiting-on'\nposition = 25\nactive = true\n[queues.someday]\ngroup = 'done'\n",
+        )
+        .expect("config file should exist");
+        env.remove("TQS_ROOT");
+        env.set("XDG_CONFIG_HOME", config_home.as_os_str());
+
+        let resolved = resolve(None).expect("config should resolve");
+        let waiting: Queue = "waiting".parse().expect("waiting should parse");
+        let someday: Queue = "someday".parse().expect("someday should parse");
+        let dirs = &resolved.queue_dirs;
+        assert_eq!(resolved.recur_queue, waiting);
+        assert_eq!(dirs.dir_name(&waiting), "waiting-on");
+        assert_eq!(dirs.dir_name(&someday), "someday");
+        assert_eq!(
+            dirs.queues(),
+            vec![
+                Queue::Inbox,
+                Queue::Now,
+                waiting,
+                Queue::Next,
+                Queue::Later,
+                someday,
+                Queue::Done
+            ]
+        );
+        assert_eq!(dirs.group(waiting), QueueGroup::Active);
+        assert_eq!(dirs.group(someday), QueueGroup::Done);
+        assert_eq!(dirs.active_queues(), vec![Queue::Now, waiting, Queue::Next]);
+
+        fs::write(
+            config_dir.join("config.toml"),
+            "tasks_root = 'tasks'\n[queues.Later]\n",
+        )
+        .expect("config file should exist");
+        let error = resolve(None).expect_err("invalid name should be rejected");
+        assert!(error.to_string().contains("queue name 'Later'"));
+    }
+
+    #[test]
+    fn resolve_reads_recur_queue_and_rejects_done() {
+        let mut env = LockedEnv::new(&["XDG_CONFIG_HOME", "TQS_ROOT"]);
+        let temp = TempDir::new().expect("temp dir should exist");
+        let config_home = temp.path().join("config-home");
+        let config_dir = config_home.join("tqs");
+        fs::create_dir_all(&config_dir).expect("config dir should exist");
+        env.remove("TQS_ROOT");
+        env.set("XDG_CONFIG_HOME", config_home.as_os_str());
+
+        fs::write(config_dir.join("config.toml"), "tasks_root = 'tasks'\n")
+            .expect("config file should exist");
+        let resolved = resolve(None).expect("config should resolve");
+        assert_eq!(resolved.recur_queue, crate::domain::task::Queue::Inbox);
+
+        fs::write(
+            config_dir.join("config.toml"),
+            "tasks_root = 'tasks'\nrecur_queue = 'next'\n",
+        )
+        .expect("config file should exist");
+        let resolved = resolve(None).expect("config should resolve");
+        assert_eq!(resolved.recur_queue, crate::domain::task::Queue::Next);
+
+        fs::write(
+            config_dir.join("config.toml"),
+            "tasks_root = 'tasks'\nrecur_queue = 'done'\n",
+        )
+        .expect("config file should exist");
+        let error = resolve(None).expect_err("done should be rejected");
+        assert!(error.to_string().contains("recur_queue"));
+    }
+
+    #[test]
+    fn resolve_reads_auto_archive_after() {
+        let mut env = LockedEnv::new(&["XDG_CONFIG_HOME", "TQS_ROOT"]);
+        let temp = TempDir::new().expect("temp dir should exist");
+        let config_home = temp.path().join("config-home");
+        let config_dir = config_home.join("tqs");
+        fs::create_dir_all(&config_dir).expect("config dir should exist");
+        env.remove("TQS_ROOT");
+        env.set("XDG_CONFIG_HOME", config_home.as_os_str());
+
+        fs::write(
+            config_dir.join("config.toml"),
+            "tasks_root = 'tasks'\nauto_archive_after = '90d'\n",
+        )
+        .expect("config file should exist");
+        let resolved = resolve(None).expect("config should resolve");
+        assert_eq!(
+            resolved.auto_archive_after.map(|age| age.to_string()),
+            Some("90d".to_string())
+        );
+
+        fs::write(
+            config_dir.join("config.toml"),
+            "tasks_root = 'tasks'\nauto_archive_after = 'soon'\n",
+        )
+        .expect("config file should exist");
+        let error = resolve(None).expect_err("invalid age should be rejected");
+        assert!(
+            error
+                .to_string()
+                .contains("invalid config: auto_archive_after: invalid age 'soon'")
+        );
+    }
+
+    #[test]
+    fn resolve_reads_git_table() {
+        let mut env = LockedEnv::new(&["XDG_CONFIG_HOME", "TQS_ROOT"]);
+        let temp = TempDir::new().expect("temp dir should exist");
+        let config_home = temp.path().join("config-home");
+        let config_dir = config_home.join("tqs");
+        fs::create_dir_all(&config_dir).expect("config dir should exist");
+        env.remove("TQS_ROOT");
+        env.set("XDG_CONFIG_HOME", config_home.as_os_str());
+
+        fs::write(
+            config_dir.join("config.toml"),
+            "tasks_root = 'tasks'
+",
+        )
+        .expect("config file should exist");
+        assert_eq!(resolve(None).expect("config should resolve").git, None);
+
+        fs::write(
+            config_dir.join("config.toml"),
+            "tasks_root = 'tasks'\n\n[git]\n",
+        )
+        .expect("config file should exist");
+        assert_eq!(
+            resolve(None).expect("config should resolve").git,
+            Some(GitConfig {
+                remote: "origin".to_string(),
+                branch: None,
+            })
+        );
+
+        fs::write(
+            config_dir.join("config.toml"),
+            "tasks_root = 'tasks'\n\n[git]\nremote = 'team'\nbranch = 'main'\n",
+        )
+        .expect("config file should exist");
+        assert_eq!(
+            resolve(None).expect("config should resolve").git,
+            Some(GitConfig {
+                remote: "team".to_string(),
+                branch: Some("main".to_string()),
+            })
+        );
+
+        fs::write(
+            config_dir.join("config.toml"),
+            "tasks_root = 'tasks'\n\n[git]\nenabled = false\nremote = 'team'\n",
+        )
+        .expect("config file should exist");
+        assert_eq!(resolve(None).expect("config should resolve").git, None);
+    }
+
+    #[test]
+    fn resolve_reads_views_and_validates_them() {
+        let mut env = LockedEnv::new(&["XDG_CONFIG_HOME", "TQS_ROOT"]);
+        let temp = TempDir::new().expect("temp dir should exist");
+        let config_home = temp.path().join("config-home");
+        let config_dir = config_home.join("tqs");
+        fs::create_dir_all(&config_dir).expect("config dir should exist");
+        env.remove("TQS_ROOT");
+        env.set("XDG_CONFIG_HOME", config_home.as_os_str());
+
+        fs::write(
+            config_dir.join("config.toml"),
+            "tasks_root = 'tasks'\n\n[views.sprint]\nquery = 'tag:sprint -queue:done'\nsort = 'due'\ncolumns = ['id', 'queue', 'title']\n\n[views.stale-inbox]\nquery = 'queue:inbox updated<-14d'\n",
+        )
+        .expect("config file should exist");
+        let resolved = resolve(None).expect("config should resolve");
+        let names = resolved
+            .views
+            .iter()
+            .map(|view| view.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["sprint", "stale-inbox"]);
+        assert_eq!(
+            resolved.views[0].columns,
+            vec![ViewColumn::Id, ViewColumn::Queue, ViewColumn::Title]
+        );
+        assert_eq!(resolved.views[1].columns, SavedView::DEFAULT_COLUMNS);
+        assert_eq!(resolved.views[1].sort, None);
+
+        fs::write(
+            config_dir.join("config.toml"),
+            "tasks_root = 'tasks'\n\n[views.broken]\nquery = '(tag:ops'\n",
+        )
+        .expect("config file should exist");
+        let error = resolve(None).expect_err("unclosed group should be rejected");
+        assert!(
+            error
+                .to_string()
+                .starts_with("invalid config: view 'broken': invalid query at column 1")
+        );
+
+        fs::write(
+            config_dir.join("config.toml"),
+            "tasks_root = 'tasks'\n\n[views.stuck]\nquery = 'queue:waiting'\n",
+        )
+        .expect("config file should exist");
+        let error = resolve(None).expect_err("unconfigured queue should be rejected");
+        assert!(
+            error
+                .to_string()
+                .contains("queue 'waiting' is not configured")
+        );
+
+        fs::write(
+            config_dir.join("config.toml"),
+            "tasks_root = 'tasks'\n\n[views.sprint]\nquery = 'tag:sprint'\nsort = 'effort'\n",
+        )
+        .expect("config file should exist");
+        let error = resolve(None).expect_err("unknown sort should be rejected");
+        assert!(error.to_string().contains("invalid sort 'effort'"));
+    }
+
     #[test]
     fn inspect_reports_missing_config_file_and_root_sources() {
         let mut env = LockedEnv::new(&["XDG_CONFIG_HOME", "TQS_ROOT"]);
@@ -417,6 +951,7 @@ mod tests {
             next: None,
             later: None,
             done: None,
+            custom: Default::default(),
         })
         .expect_err("invalid queue dir should error");
 
@@ -16,6 +16,11 @@ pub struct DailyNoteUpdate {
     pub appended: bool,
 }
 
+/// Path of the daily note for `note_date`.
+pub fn note_path(daily_notes_dir: &Path, note_date: NaiveDate) -> PathBuf {
+    daily_notes_dir.join(format!("{}.md", note_date.format("%F")))
+}
+
 pub fn append_completion(
     daily_notes_dir: &Path,
     task_path: &Path,
@@ -24,8 +29,8 @@ pub fn append_completion(
 ) -> Result<DailyNoteUpdate, AppError> {
     fs::create_dir_all(daily_notes_dir)?;
 
+    let note_path = note_path(daily_notes_dir, note_date);
     let note_name = format!("{}.md", note_date.format("%F"));
-    let note_path = daily_notes_dir.join(&note_name);
     let entry = completion_entry(daily_notes_dir, task_path, task);
     let existing = if note_path.exists() {
         fs::read_to_string(&note_path)?
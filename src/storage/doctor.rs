@@ -1,13 +1,20 @@
+use std::cmp::Reverse;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use crate::app::app_error::AppError;
-use crate::domain::task::Queue;
+use crate::domain::deps::{dangling_references, dependency_cycles};
+use crate::domain::task::{Queue, Task};
+use crate::storage::archive;
 use crate::storage::config::ResolvedConfig;
 use crate::storage::editor::{ResolvedEditor, format_program_name, format_program_path};
 use crate::storage::format::parse_task_markdown;
 use crate::storage::id_state;
+use crate::storage::lock;
+use crate::storage::repo::TaskRepo;
+use crate::storage::trash::{Trash, TrashedTask};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum DiagnosticSeverity {
@@ -108,8 +115,17 @@ pub fn run(config: &ResolvedConfig, fix: bool) -> Result<DoctorReport, AppError>
 
     diagnose_editor(&mut diagnostics);
 
+    let trash = Trash::new(&config.state_dir);
+    let (trashed, malformed_trash) = trash.scan()?;
+    let trashed_ids = trashed
+        .iter()
+        .map(|entry| entry.task.id.clone())
+        .collect::<BTreeSet<_>>();
+
+    let mut tasks = Vec::new();
     if duplicate_queue_dirs(config).is_empty() {
-        diagnose_task_files(&mut diagnostics, config)?;
+        tasks = diagnose_task_files(&mut diagnostics, config, &trashed_ids, fix)?;
+        diagnose_index(&mut diagnostics, config)?;
     } else {
         diagnostics.push(Diagnostic {
             severity: DiagnosticSeverity::Warning,
@@ -118,7 +134,16 @@ pub fn run(config: &ResolvedConfig, fix: bool) -> Result<DoctorReport, AppError>
         });
     }
 
+    diagnose_trash(
+        &mut diagnostics,
+        &trash,
+        &trashed,
+        &malformed_trash,
+        &tasks,
+        fix,
+    )?;
     diagnose_state_files(&mut diagnostics, config, fix)?;
+    diagnose_locks(&mut diagnostics, config, fix)?;
 
     Ok(DoctorReport { diagnostics })
 }
@@ -203,26 +228,30 @@ fn diagnose_editor(diagnostics: &mut Vec<Diagnostic>) {
     }
 }
 
+/// Checks every task file and returns the tasks that parsed.
 fn diagnose_task_files(
     diagnostics: &mut Vec<Diagnostic>,
     config: &ResolvedConfig,
-) -> Result<(), AppError> {
+    trashed_ids: &BTreeSet<String>,
+    fix: bool,
+) -> Result<Vec<Task>, AppError> {
     let root_metadata = match fs::metadata(&config.tasks_root) {
         Ok(metadata) => metadata,
-        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
         Err(error) => return Err(AppError::Io(error)),
     };
 
     if !root_metadata.is_dir() {
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     let mut per_id_paths: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    let mut tasks = Vec::new();
     let mut scanned_files = 0usize;
     let mut seen_dirs = HashSet::new();
 
-    for queue in Queue::all().iter().copied() {
-        let dir_name = config.queue_dirs.dir_name(queue).to_string();
+    for queue in config.queue_dirs.queues() {
+        let dir_name = config.queue_dirs.dir_name(&queue).to_string();
         if !seen_dirs.insert(dir_name.clone()) {
             continue;
         }
@@ -254,7 +283,7 @@ fn diagnose_task_files(
 
                     queue_file_count += 1;
                     scanned_files += 1;
-                    diagnose_task_file(diagnostics, &path, queue, &mut per_id_paths)?;
+                    diagnose_task_file(diagnostics, &path, queue, &mut per_id_paths, &mut tasks)?;
                 }
 
                 diagnostics.push(Diagnostic {
@@ -266,6 +295,31 @@ fn diagnose_task_files(
                         dir.display()
                     ),
                 });
+
+                if queue.is_done() {
+                    let archived = archive::archived_files(&dir)?;
+                    for path in &archived {
+                        scanned_files += 1;
+                        diagnose_task_file(
+                            diagnostics,
+                            path,
+                            queue,
+                            &mut per_id_paths,
+                            &mut tasks,
+                        )?;
+                    }
+                    if !archived.is_empty() {
+                        diagnostics.push(Diagnostic {
+                            severity: DiagnosticSeverity::Ok,
+                            scope: "tasks".to_string(),
+                            message: format!(
+                                "scanned {} archived task file(s) under {}",
+                                archived.len(),
+                                dir.display()
+                            ),
+                        });
+                    }
+                }
             }
             Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
                 diagnostics.push(Diagnostic {
@@ -278,17 +332,12 @@ fn diagnose_task_files(
         }
     }
 
+    diagnose_unknown_queue_dirs(diagnostics, &config.tasks_root, &seen_dirs)?;
+
+    let repo = task_repo(config);
     for (id, paths) in per_id_paths {
         if paths.len() > 1 {
-            diagnostics.push(Diagnostic {
-                severity: DiagnosticSeverity::Error,
-                scope: "tasks".to_string(),
-                message: format!(
-                    "duplicate task id '{}' found in {}",
-                    id,
-                    display_paths(&paths)
-                ),
-            });
+            diagnose_duplicate(diagnostics, &repo, &id, &paths, fix)?;
         }
     }
 
@@ -298,7 +347,51 @@ fn diagnose_task_files(
         message: format!("scanned {} Markdown task file(s) total", scanned_files),
     });
 
-    Ok(())
+    diagnose_dependencies(diagnostics, &tasks, trashed_ids);
+
+    Ok(tasks)
+}
+
+fn diagnose_dependencies(
+    diagnostics: &mut Vec<Diagnostic>,
+    tasks: &[Task],
+    trashed_ids: &BTreeSet<String>,
+) {
+    let dangling = dangling_references(tasks);
+    let cycles = dependency_cycles(tasks);
+
+    for (task_id, missing) in &dangling {
+        let message = if trashed_ids.contains(*missing) {
+            format!(
+                "task '{task_id}' is blocked by '{missing}', which is in the trash (restore it or unblock)"
+            )
+        } else {
+            format!("task '{task_id}' is blocked by unknown task '{missing}'")
+        };
+        diagnostics.push(Diagnostic {
+            severity: DiagnosticSeverity::Warning,
+            scope: "dependencies".to_string(),
+            message,
+        });
+    }
+
+    for cycle in &cycles {
+        let mut path = cycle.clone();
+        path.push(cycle[0].clone());
+        diagnostics.push(Diagnostic {
+            severity: DiagnosticSeverity::Error,
+            scope: "dependencies".to_string(),
+            message: format!("dependency cycle: {}", path.join(" -> ")),
+        });
+    }
+
+    if dangling.is_empty() && cycles.is_empty() {
+        diagnostics.push(Diagnostic {
+            severity: DiagnosticSeverity::Ok,
+            scope: "dependencies".to_string(),
+            message: "blocked_by references are valid".to_string(),
+        });
+    }
 }
 
 fn diagnose_task_file(
@@ -306,6 +399,7 @@ fn diagnose_task_file(
     path: &Path,
     expected_queue: Queue,
     per_id_paths: &mut BTreeMap<String, Vec<PathBuf>>,
+    tasks: &mut Vec<Task>,
 ) -> Result<(), AppError> {
     let content = fs::read_to_string(path)?;
     match parse_task_markdown(&content) {
@@ -339,9 +433,10 @@ fn diagnose_task_file(
             }
 
             per_id_paths
-                .entry(task.id)
+                .entry(task.id.clone())
                 .or_default()
                 .push(path.to_path_buf());
+            tasks.push(task);
         }
         Err(error) => diagnostics.push(Diagnostic {
             severity: DiagnosticSeverity::Error,
@@ -353,11 +448,136 @@ fn diagnose_task_file(
     Ok(())
 }
 
+/// Reports a task id found in more than one file. A move interrupted by a
+/// crash leaves the task in both queues, with the moved copy updated later,
+/// so `--fix` keeps the copy with the latest `updated_at`, or the longest
+/// history when those match, and removes the others through the journal, so
+/// `tqs undo` can bring them back. Copies that cannot be told apart, or read,
+/// are left for the user.
+fn diagnose_duplicate(
+    diagnostics: &mut Vec<Diagnostic>,
+    repo: &TaskRepo,
+    id: &str,
+    paths: &[PathBuf],
+    fix: bool,
+) -> Result<(), AppError> {
+    let duplicate = |hint: &str| Diagnostic {
+        severity: DiagnosticSeverity::Error,
+        scope: "tasks".to_string(),
+        message: format!(
+            "duplicate task id '{id}' found in {}{hint}",
+            display_paths(paths)
+        ),
+    };
+    if !fix {
+        diagnostics.push(duplicate(
+            " (use --fix to keep the most recently updated copy)",
+        ));
+        return Ok(());
+    }
+
+    let _lock = repo.lock()?;
+    let keep = match newest_copy(paths) {
+        Ok(Some(keep)) => keep,
+        Ok(None) => {
+            diagnostics.push(duplicate(
+                "; the copies are equally recent, so none was removed",
+            ));
+            return Ok(());
+        }
+        Err((path, reason)) => {
+            diagnostics.push(duplicate("; a copy could not be read, so none was removed"));
+            diagnostics.push(Diagnostic {
+                severity: DiagnosticSeverity::Error,
+                scope: "tasks".to_string(),
+                message: format!("could not read {}: {reason}", path.display()),
+            });
+            return Ok(());
+        }
+    };
+
+    for path in paths.iter().filter(|path| *path != keep) {
+        repo.remove_copy(id, path)?;
+        diagnostics.push(Diagnostic {
+            severity: DiagnosticSeverity::Ok,
+            scope: "tasks".to_string(),
+            message: format!(
+                "removed {}, an older copy of task '{id}' than {}",
+                path.display(),
+                keep.display()
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// The copy that is strictly newer than every other, if there is one, or
+/// the first copy that could not be read and why.
+fn newest_copy(paths: &[PathBuf]) -> Result<Option<&PathBuf>, (&PathBuf, String)> {
+    let mut copies = Vec::new();
+    for path in paths {
+        let task = fs::read_to_string(path)
+            .map_err(|error| error.to_string())
+            .and_then(|content| parse_task_markdown(&content).map_err(|error| error.to_string()))
+            .map_err(|reason| (path, reason))?;
+        copies.push(((task.updated_at, task.history.len()), path));
+    }
+    copies.sort_by_key(|(recency, _)| Reverse(*recency));
+    Ok(match copies.as_slice() {
+        [(newest, path), (next, _), ..] if newest > next => Some(*path),
+        _ => None,
+    })
+}
+
+/// Warns about directories under the tasks root that hold task files but are
+/// not a configured queue, such as a custom queue removed from config. Only
+/// Markdown files that parse as tasks count, so the ordinary folders of a
+/// notes vault around the tasks are left alone.
+fn diagnose_unknown_queue_dirs(
+    diagnostics: &mut Vec<Diagnostic>,
+    tasks_root: &Path,
+    queue_dirs: &HashSet<String>,
+) -> Result<(), AppError> {
+    let mut unknown = Vec::new();
+    for entry in fs::read_dir(tasks_root)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if !path.is_dir() || name.starts_with('.') || queue_dirs.contains(name) {
+            continue;
+        }
+        let has_tasks = fs::read_dir(&path)?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|value| value.to_str()) == Some("md"))
+            .any(|path| {
+                fs::read_to_string(path).is_ok_and(|content| parse_task_markdown(&content).is_ok())
+            });
+        if has_tasks {
+            unknown.push(path);
+        }
+    }
+
+    unknown.sort();
+    for path in unknown {
+        diagnostics.push(Diagnostic {
+            severity: DiagnosticSeverity::Warning,
+            scope: "tasks".to_string(),
+            message: format!(
+                "{} holds task files but is not a configured queue",
+                path.display()
+            ),
+        });
+    }
+    Ok(())
+}
+
 fn duplicate_queue_dirs(config: &ResolvedConfig) -> Vec<(String, Vec<String>)> {
     let mut by_dir: HashMap<String, BTreeSet<String>> = HashMap::new();
-    for queue in Queue::all().iter().copied() {
+    for queue in config.queue_dirs.queues() {
         by_dir
-            .entry(config.queue_dirs.dir_name(queue).to_string())
+            .entry(config.queue_dirs.dir_name(&queue).to_string())
             .or_default()
             .insert(queue.to_string());
     }
@@ -376,6 +596,121 @@ fn duplicate_queue_dirs(config: &ResolvedConfig) -> Vec<(String, Vec<String>)> {
     duplicates
 }
 
+fn diagnose_trash(
+    diagnostics: &mut Vec<Diagnostic>,
+    trash: &Trash,
+    trashed: &[TrashedTask],
+    malformed: &[(PathBuf, String)],
+    tasks: &[Task],
+    fix: bool,
+) -> Result<(), AppError> {
+    diagnostics.push(Diagnostic {
+        severity: DiagnosticSeverity::Ok,
+        scope: "trash".to_string(),
+        message: format!(
+            "{} task(s) in trash at {}",
+            trashed.len(),
+            trash.dir().display()
+        ),
+    });
+
+    for (path, reason) in malformed {
+        diagnostics.push(Diagnostic {
+            severity: DiagnosticSeverity::Warning,
+            scope: "trash".to_string(),
+            message: format!("trashed task {} is malformed: {reason}", path.display()),
+        });
+    }
+
+    let live_ids = tasks
+        .iter()
+        .map(|task| task.id.as_str())
+        .collect::<HashSet<_>>();
+    for entry in trashed {
+        if live_ids.contains(entry.task.id.as_str()) {
+            diagnostics.push(Diagnostic {
+                severity: DiagnosticSeverity::Warning,
+                scope: "trash".to_string(),
+                message: format!(
+                    "trashed task '{}' shares its id with a live task and cannot be restored until one is renamed",
+                    entry.task.id
+                ),
+            });
+        }
+    }
+
+    for path in trash.orphaned_metadata()? {
+        if fix {
+            fs::remove_file(&path)?;
+            diagnostics.push(Diagnostic {
+                severity: DiagnosticSeverity::Ok,
+                scope: "trash".to_string(),
+                message: format!("removed orphaned trash metadata {}", path.display()),
+            });
+        } else {
+            diagnostics.push(Diagnostic {
+                severity: DiagnosticSeverity::Warning,
+                scope: "trash".to_string(),
+                message: format!(
+                    "orphaned trash metadata {} (use --fix to remove)",
+                    path.display()
+                ),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// The repository doctor's fixes go through, so they are locked, journaled,
+/// and committed like any other change.
+fn task_repo(config: &ResolvedConfig) -> TaskRepo {
+    let repo = TaskRepo::new(config.tasks_root.clone(), config.queue_dirs.clone())
+        .with_state_dir(&config.state_dir);
+    match &config.git {
+        Some(git) => repo.with_git(git),
+        None => repo,
+    }
+}
+
+/// Rebuilds the task index from scratch, warning when the old one could not
+/// be read.
+fn diagnose_index(
+    diagnostics: &mut Vec<Diagnostic>,
+    config: &ResolvedConfig,
+) -> Result<(), AppError> {
+    let repo = task_repo(config);
+    let problem = repo.index().load_error();
+    let path = repo.index().path().display();
+    let count = match repo.rebuild_index() {
+        Ok(count) => count,
+        Err(error) => {
+            // Unusable queue directories are already reported by the task scan.
+            diagnostics.push(Diagnostic {
+                severity: DiagnosticSeverity::Warning,
+                scope: "index".to_string(),
+                message: format!("could not rebuild task index {path}: {error}"),
+            });
+            return Ok(());
+        }
+    };
+    diagnostics.push(match problem {
+        Some(reason) => Diagnostic {
+            severity: DiagnosticSeverity::Warning,
+            scope: "index".to_string(),
+            message: format!(
+                "task index {path} was unreadable ({reason}); rebuilt it with {count} tasks"
+            ),
+        },
+        None => Diagnostic {
+            severity: DiagnosticSeverity::Ok,
+            scope: "index".to_string(),
+            message: format!("rebuilt task index {path} with {count} tasks"),
+        },
+    });
+    Ok(())
+}
+
 fn diagnose_state_files(
     diagnostics: &mut Vec<Diagnostic>,
     config: &ResolvedConfig,
@@ -444,6 +779,68 @@ fn diagnose_state_files(
     Ok(())
 }
 
+/// Reports repository and allocator lock files left behind by a process
+/// that died while holding them. Nobody holds such a file's lock, so the
+/// next change takes it over anyway; a file naming another host is only
+/// reported after `STALE_LOCK_AGE`.
+fn diagnose_locks(
+    diagnostics: &mut Vec<Diagnostic>,
+    config: &ResolvedConfig,
+    fix: bool,
+) -> Result<(), AppError> {
+    let now = SystemTime::now();
+    let mut stale = lock::stale_locks(&config.state_dir.join(lock::LOCKS_DIR), now)?;
+    stale.extend(lock::stale_locks(
+        &config.state_dir.join("id-generator"),
+        now,
+    )?);
+
+    if stale.is_empty() {
+        diagnostics.push(Diagnostic {
+            severity: DiagnosticSeverity::Ok,
+            scope: "locks".to_string(),
+            message: "no stale locks".to_string(),
+        });
+    }
+    for found in stale {
+        let owner = match (found.pid, &found.host) {
+            (Some(pid), Some(host)) => format!("process {pid} on {host}"),
+            (Some(pid), None) => format!("process {pid}"),
+            (None, _) => "an unknown process".to_string(),
+        };
+        let minutes = found.age.as_secs() / 60;
+        if fix && !lock::remove_stale(&found.path)? {
+            diagnostics.push(Diagnostic {
+                severity: DiagnosticSeverity::Warning,
+                scope: "locks".to_string(),
+                message: format!(
+                    "stale lock {} was taken again before it could be removed",
+                    found.path.display()
+                ),
+            });
+        } else if fix {
+            diagnostics.push(Diagnostic {
+                severity: DiagnosticSeverity::Ok,
+                scope: "locks".to_string(),
+                message: format!(
+                    "removed stale lock {} left by {owner}",
+                    found.path.display()
+                ),
+            });
+        } else {
+            diagnostics.push(Diagnostic {
+                severity: DiagnosticSeverity::Warning,
+                scope: "locks".to_string(),
+                message: format!(
+                    "stale lock {} left by {owner} {minutes} min ago (use --fix to remove)",
+                    found.path.display()
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
 fn display_paths(paths: &[PathBuf]) -> String {
     paths
         .iter()
@@ -454,8 +851,11 @@ fn display_paths(paths: &[PathBuf]) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{DiagnosticSeverity, run};
-    use crate::storage::config::{QueueDirs, ResolvedConfig};
+    use super::{DiagnosticSeverity, newest_copy, run};
+    use crate::domain::task::{Queue, QueueName, Task};
+    use crate::storage::config::{CustomQueue, QueueDirs, QueueGroup, ResolvedConfig};
+    use crate::storage::lock::STALE_LOCK_AGE;
+    use crate::storage::repo::TaskRepo;
     use crate::test_support::LockedEnv;
     use std::fs;
     use std::path::Path;
@@ -468,6 +868,11 @@ mod tests {
             state_dir: root.join(".tqs"),
             daily_notes_dir: None,
             queue_dirs: QueueDirs::default(),
+            recur_queue: crate::domain::task::Queue::Inbox,
+            views: Vec::new(),
+            templates: Default::default(),
+            auto_archive_after: None,
+            git: None,
         }
     }
 
@@ -495,6 +900,148 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn doctor_fix_keeps_the_newer_copy_left_by_an_interrupted_move() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let root = temp.path();
+        let repo = TaskRepo::new(root.to_path_buf(), QueueDirs::default());
+        let task = Task::new("task-1", "Ship v2", chrono::Utc::now());
+        let inbox_path = repo.create(&task).expect("task should be created");
+        let stale = fs::read_to_string(&inbox_path).expect("task should be readable");
+        let (_, now_path, _) = repo
+            .move_to_queue("task-1", Queue::Now, chrono::Utc::now())
+            .expect("task should move");
+        fs::write(&inbox_path, &stale).expect("stale copy should be written");
+
+        let report = run(&config(root), true).expect("doctor should succeed");
+
+        assert!(!inbox_path.exists());
+        assert!(now_path.exists());
+        assert!(report.diagnostics.iter().any(|diagnostic| {
+            diagnostic.severity == DiagnosticSeverity::Ok
+                && diagnostic
+                    .message
+                    .contains("an older copy of task 'task-1'")
+        }));
+        assert!(!report.has_errors());
+
+        repo.undo().expect("the removal should be undoable");
+        assert_eq!(
+            fs::read_to_string(&inbox_path).expect("copy should be back"),
+            stale
+        );
+        run(&config(root), true).expect("doctor should succeed");
+        assert!(!inbox_path.exists());
+
+        fs::create_dir_all(root.join("later")).expect("later dir should exist");
+        fs::write(
+            root.join("later").join("task-1.md"),
+            fs::read_to_string(&now_path)
+                .expect("task should be readable")
+                .replace("queue: now", "queue: later"),
+        )
+        .expect("copy should be written");
+
+        let report = run(&config(root), true).expect("doctor should succeed");
+        assert!(now_path.exists());
+        assert!(report.diagnostics.iter().any(|diagnostic| {
+            diagnostic.severity == DiagnosticSeverity::Error
+                && diagnostic
+                    .message
+                    .contains("equally recent, so none was removed")
+        }));
+    }
+
+    #[test]
+    fn newest_copy_names_a_copy_it_cannot_read_instead_of_failing() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let readable = temp.path().join("now").join("task-1.md");
+        fs::create_dir_all(readable.parent().expect("path has a parent"))
+            .expect("now dir should exist");
+        fs::write(
+            &readable,
+            "---\nid: task-1\ntitle: Ship v2\nqueue: now\ncreated_at: 2026-03-09T10:34:12Z\nupdated_at: 2026-03-09T10:34:12Z\n---\n",
+        )
+        .expect("task should be written");
+        let missing = temp.path().join("inbox").join("task-1.md");
+        let paths = [readable, missing.clone()];
+
+        let (path, _) = newest_copy(&paths).expect_err("the missing copy should be reported");
+
+        assert_eq!(path, &missing);
+    }
+
+    #[test]
+    fn doctor_reports_dangling_blockers_and_cycles() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let root = temp.path();
+        fs::create_dir_all(root.join("now")).expect("now dir should exist");
+        for (id, blocked_by) in [("a1", "[b2]"), ("b2", "[a1, gone]")] {
+            fs::write(
+                root.join("now").join(format!("{id}.md")),
+                format!(
+                    "---\nid: {id}\ntitle: Task {id}\nqueue: now\ncreated_at: 2026-03-09T10:34:12Z\nupdated_at: 2026-03-09T10:34:12Z\nblocked_by: {blocked_by}\n---\n# Task\n"
+                ),
+            )
+            .expect("task should be written");
+        }
+
+        let report = run(&config(root), false).expect("doctor should succeed");
+        assert!(report.diagnostics.iter().any(|diagnostic| {
+            diagnostic.severity == DiagnosticSeverity::Warning
+                && diagnostic.scope == "dependencies"
+                && diagnostic.message == "task 'b2' is blocked by unknown task 'gone'"
+        }));
+        assert!(report.diagnostics.iter().any(|diagnostic| {
+            diagnostic.severity == DiagnosticSeverity::Error
+                && diagnostic.message == "dependency cycle: a1 -> b2 -> a1"
+        }));
+    }
+
+    #[test]
+    fn doctor_reports_trash_contents_and_fixes_orphaned_metadata() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let root = temp.path();
+        let repo = TaskRepo::new(root.to_path_buf(), QueueDirs::default());
+        for id in ["a1", "b2", "c3"] {
+            let mut task = Task::new(id, format!("Task {id}"), chrono::Utc::now());
+            if id == "a1" {
+                task.blocked_by = vec!["b2".to_string()];
+            }
+            repo.create(&task).expect("task should be created");
+        }
+        repo.delete("b2").expect("task should be trashed");
+        repo.delete("c3").expect("task should be trashed");
+        repo.create(&Task::new("c3", "Task c3 again", chrono::Utc::now()))
+            .expect("id should be reusable");
+        let orphan = repo.trash().metadata_path("gone");
+        fs::write(&orphan, "stale").expect("orphan should be written");
+
+        let report = run(&config(root), false).expect("doctor should succeed");
+        let trash_messages = report
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.scope == "trash")
+            .map(|diagnostic| diagnostic.message.as_str())
+            .collect::<Vec<_>>();
+        assert!(trash_messages[0].starts_with("2 task(s) in trash at "));
+        assert!(trash_messages.iter().any(|message| {
+            message.starts_with("trashed task 'c3' shares its id with a live task")
+        }));
+        assert!(
+            trash_messages
+                .iter()
+                .any(|message| message.contains("orphaned trash metadata"))
+        );
+        assert!(report.diagnostics.iter().any(|diagnostic| {
+            diagnostic.message
+                == "task 'a1' is blocked by 'b2', which is in the trash (restore it or unblock)"
+        }));
+
+        run(&config(root), true).expect("doctor --fix should succeed");
+        assert!(!orphan.exists());
+    }
+
     #[test]
     fn doctor_reports_queue_dir_overlap() {
         let temp = TempDir::new().expect("temp dir should exist");
@@ -510,7 +1057,13 @@ mod tests {
                     next: "next".to_string(),
                     later: "later".to_string(),
                     done: "done".to_string(),
+                    custom: Vec::new(),
                 },
+                recur_queue: crate::domain::task::Queue::Inbox,
+                views: Vec::new(),
+                templates: Default::default(),
+                auto_archive_after: None,
+                git: None,
             },
             false,
         )
@@ -604,6 +1157,11 @@ mod tests {
                 state_dir: temp.path().join("tasks").join(".tqs"),
                 daily_notes_dir: Some(daily_notes.clone()),
                 queue_dirs: QueueDirs::default(),
+                recur_queue: crate::domain::task::Queue::Inbox,
+                views: Vec::new(),
+                templates: Default::default(),
+                auto_archive_after: None,
+                git: None,
             },
             false,
         )
@@ -636,6 +1194,91 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn doctor_scans_custom_queues_and_warns_about_unknown_queue_dirs() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let root = temp.path();
+        let waiting = Queue::Custom(QueueName::new("waiting").expect("name should be valid"));
+        let mut config = config(root);
+        config.queue_dirs.custom.push(CustomQueue {
+            queue: waiting,
+            dir: "waiting-on".to_string(),
+            position: 35,
+            group: QueueGroup::Active,
+            active: true,
+        });
+        fs::create_dir_all(root.join("waiting-on")).expect("queue dir should exist");
+        fs::write(
+            root.join("waiting-on").join("task-1.md"),
+            "---\nid: task-1\ntitle: Ship v2\nqueue: waiting\ncreated_at: 2026-03-09T10:34:12Z\nupdated_at: 2026-03-09T10:34:12Z\n---\n# Ship v2\n",
+        )
+        .expect("task should be written");
+        fs::create_dir_all(root.join("someday")).expect("stray dir should exist");
+        fs::write(
+            root.join("someday").join("idea.md"),
+            "---\nid: idea\ntitle: Idea\nqueue: someday\ncreated_at: 2026-03-09T10:34:12Z\nupdated_at: 2026-03-09T10:34:12Z\n---\n",
+        )
+        .expect("task should be written");
+        fs::create_dir_all(root.join("Journal")).expect("notes dir should exist");
+        fs::write(root.join("Journal").join("monday.md"), "# Monday\n").expect("note should exist");
+
+        let report = run(&config, false).expect("doctor should succeed");
+
+        assert_eq!(report.error_count(), 0, "{:?}", report.diagnostics);
+        assert!(report.diagnostics.iter().any(|diagnostic| {
+            diagnostic.severity == DiagnosticSeverity::Ok
+                && diagnostic
+                    .message
+                    .contains("scanned 1 Markdown task file(s)")
+                && diagnostic.message.contains("waiting-on")
+        }));
+        assert!(report.diagnostics.iter().any(|diagnostic| {
+            diagnostic.severity == DiagnosticSeverity::Warning
+                && diagnostic.message.contains("someday")
+                && diagnostic.message.contains("is not a configured queue")
+        }));
+        assert!(
+            !report
+                .diagnostics
+                .iter()
+                .any(|diagnostic| diagnostic.message.contains("Journal"))
+        );
+    }
+
+    #[test]
+    fn doctor_validates_archived_tasks_against_live_ones() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let root = temp.path();
+        let archive = root.join("done").join("2026").join("03");
+        fs::create_dir_all(&archive).expect("archive dir should exist");
+        fs::create_dir_all(root.join("now")).expect("now dir should exist");
+        let task = |queue: &str| {
+            format!(
+                "---\nid: task-1\ntitle: Ship v2\nqueue: {queue}\ncreated_at: 2026-03-09T10:34:12Z\nupdated_at: 2026-03-09T10:34:12Z\n---\n# Ship v2\n"
+            )
+        };
+        fs::write(archive.join("task-1.md"), task("done")).expect("task should be written");
+        fs::write(root.join("now").join("task-1.md"), task("now")).expect("task should be written");
+        fs::write(archive.join("task-2.md"), "not a task").expect("file should be written");
+
+        let report = run(&config(root), false).expect("doctor should succeed");
+
+        assert!(report.diagnostics.iter().any(|diagnostic| {
+            diagnostic.severity == DiagnosticSeverity::Ok
+                && diagnostic
+                    .message
+                    .contains("scanned 2 archived task file(s)")
+        }));
+        assert!(report.diagnostics.iter().any(|diagnostic| {
+            diagnostic.severity == DiagnosticSeverity::Error
+                && diagnostic.message.contains("duplicate task id 'task-1'")
+        }));
+        assert!(report.diagnostics.iter().any(|diagnostic| {
+            diagnostic.severity == DiagnosticSeverity::Error
+                && diagnostic.message.contains("task-2.md is malformed")
+        }));
+    }
+
     #[test]
     fn doctor_reports_filename_mismatch_diagnostics() {
         let temp = TempDir::new().expect("temp dir should exist");
@@ -680,7 +1323,13 @@ mod tests {
                     next: "next".to_string(),
                     later: "later".to_string(),
                     done: "done".to_string(),
+                    custom: Vec::new(),
                 },
+                recur_queue: crate::domain::task::Queue::Inbox,
+                views: Vec::new(),
+                templates: Default::default(),
+                auto_archive_after: None,
+                git: None,
             },
             false,
         )
@@ -752,6 +1401,42 @@ mod tests {
         assert!(active_path.exists());
     }
 
+    #[test]
+    fn doctor_reports_and_fixes_stale_locks() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let root = temp.path();
+        let locks = root.join(".tqs").join("locks");
+        fs::create_dir_all(&locks).expect("locks dir should exist");
+        let stale = locks.join("stale.lock");
+        fs::write(&stale, "4242\n").expect("lock should be written");
+        fs::File::options()
+            .write(true)
+            .open(&stale)
+            .and_then(|file| file.set_modified(std::time::SystemTime::now() - 2 * STALE_LOCK_AGE))
+            .expect("lock should be backdated");
+        let fresh = locks.join("fresh.lock");
+        fs::write(&fresh, "4243\n").expect("lock should be written");
+
+        let report = run(&config(root), false).expect("doctor should succeed");
+        assert!(report.diagnostics.iter().any(|diagnostic| {
+            diagnostic.severity == DiagnosticSeverity::Warning
+                && diagnostic.scope == "locks"
+                && diagnostic
+                    .message
+                    .contains("stale.lock left by process 4242")
+        }));
+        assert!(
+            !report
+                .diagnostics
+                .iter()
+                .any(|diagnostic| diagnostic.message.contains("fresh.lock"))
+        );
+
+        run(&config(root), true).expect("doctor should succeed");
+        assert!(!stale.exists());
+        assert!(fresh.exists());
+    }
+
     #[test]
     fn doctor_ignores_non_toml_files_in_state_dir() {
         let temp = TempDir::new().expect("temp dir should exist");
@@ -769,4 +1454,36 @@ mod tests {
             diagnostic.scope == "state" && diagnostic.message.contains("orphaned")
         }));
     }
+
+    #[test]
+    fn doctor_rebuilds_the_task_index_and_warns_when_it_was_unreadable() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let root = temp.path();
+        let repo = TaskRepo::new(root.to_path_buf(), QueueDirs::default());
+        repo.create(&Task::new("abc", "Indexed", chrono::Utc::now()))
+            .expect("task should be created");
+
+        let report = run(&config(root), false).expect("doctor should succeed");
+        assert!(report.diagnostics.iter().any(|diagnostic| {
+            diagnostic.severity == DiagnosticSeverity::Ok
+                && diagnostic.scope == "index"
+                && diagnostic.message.contains("with 1 tasks")
+        }));
+
+        let index_path = repo.index().path().to_path_buf();
+        fs::create_dir_all(index_path.parent().expect("index has a parent"))
+            .expect("index dir should exist");
+        fs::write(&index_path, "not json").expect("index should be written");
+        let report = run(&config(root), false).expect("doctor should succeed");
+        assert!(report.diagnostics.iter().any(|diagnostic| {
+            diagnostic.severity == DiagnosticSeverity::Warning
+                && diagnostic.scope == "index"
+                && diagnostic.message.contains("was unreadable")
+        }));
+        assert!(
+            fs::read_to_string(&index_path)
+                .expect("index should be rewritten")
+                .starts_with("{")
+        );
+    }
 }
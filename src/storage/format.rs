@@ -1,10 +1,29 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::domain::task::{Queue, Task};
+use crate::domain::priority::Priority;
+use crate::domain::recur::RecurRule;
+use crate::domain::task::{ExtraFrontmatter, Queue, QueueTransition, Task, normalize_tag};
 
 const FRONTMATTER_DELIMITER: &str = "---";
+const KNOWN_FRONTMATTER_KEYS: &[&str] = &[
+    "id",
+    "title",
+    "queue",
+    "created_at",
+    "updated_at",
+    "completed_at",
+    "daily_note",
+    "due",
+    "scheduled",
+    "recur",
+    "priority",
+    "tags",
+    "blocked_by",
+    "history",
+    "rank",
+];
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParsedTaskFile {
@@ -37,6 +56,26 @@ struct TaskFrontmatter {
     completed_at: Option<DateTime<Utc>>,
     #[serde(default)]
     daily_note: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    scheduled: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    recur: Option<RecurRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<Priority>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_tags",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    blocked_by: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    history: Vec<QueueTransition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rank: Option<i64>,
 }
 
 impl From<TaskFrontmatter> for Task {
@@ -49,7 +88,17 @@ impl From<TaskFrontmatter> for Task {
             updated_at: frontmatter.updated_at,
             completed_at: frontmatter.completed_at,
             daily_note: frontmatter.daily_note,
+            due: frontmatter.due,
+            scheduled: frontmatter.scheduled,
+            recur: frontmatter.recur,
+            priority: frontmatter.priority,
+            tags: frontmatter.tags,
+            blocked_by: frontmatter.blocked_by,
+            history: frontmatter.history,
+            rank: frontmatter.rank,
+            extra: ExtraFrontmatter::default(),
             body: String::new(),
+            loaded_from: None,
         }
     }
 }
@@ -64,10 +113,63 @@ impl From<&Task> for TaskFrontmatter {
             updated_at: task.updated_at,
             completed_at: task.completed_at,
             daily_note: task.daily_note.clone(),
+            due: task.due,
+            scheduled: task.scheduled,
+            recur: task.recur,
+            priority: task.priority,
+            tags: task.tags.clone(),
+            blocked_by: task.blocked_by.clone(),
+            history: task.history.clone(),
+            rank: task.rank,
         }
     }
 }
 
+/// Accepts `tags` as a YAML list or as a single comma/space separated string,
+/// the two forms Obsidian understands. An entry that is not a valid tag fails
+/// the parse rather than being dropped, since the next rewrite would delete it.
+fn deserialize_tags<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<serde_yaml::Value>::deserialize(deserializer)?;
+    let raw: Vec<String> = match value {
+        None | Some(serde_yaml::Value::Null) => Vec::new(),
+        Some(serde_yaml::Value::String(value)) => value
+            .split(|ch: char| ch == ',' || ch.is_whitespace())
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Some(serde_yaml::Value::Sequence(values)) => values
+            .into_iter()
+            .map(|value| match value {
+                serde_yaml::Value::String(value) => Ok(value),
+                serde_yaml::Value::Number(value) => Ok(value.to_string()),
+                _ => Err(serde::de::Error::custom("tags must be strings or numbers")),
+            })
+            .collect::<Result<_, _>>()?,
+        Some(_) => {
+            return Err(serde::de::Error::custom("tags must be a list or a string"));
+        }
+    };
+
+    let mut tags: Vec<String> = Vec::new();
+    for raw_tag in &raw {
+        let tag = normalize_tag(raw_tag).map_err(|_| {
+            serde::de::Error::custom(format!(
+                "invalid tag '{raw_tag}'; tags cannot be empty or contain spaces or commas"
+            ))
+        })?;
+        if !tags
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(&tag))
+        {
+            tags.push(tag);
+        }
+    }
+    Ok(tags)
+}
+
 pub fn parse_task_file(input: &str) -> Result<ParsedTaskFile, FormatError> {
     let mut lines = input.lines();
     let Some(first_line) = lines.next() else {
@@ -97,6 +199,7 @@ pub fn parse_task_markdown(input: &str) -> Result<Task, FormatError> {
     let parsed = parse_task_file(input)?;
     let frontmatter: TaskFrontmatter = serde_yaml::from_str(&parsed.frontmatter)?;
     let mut task = Task::from(frontmatter);
+    task.extra = parse_extra_frontmatter(&parsed.frontmatter)?;
     task.body = parsed.body;
     validate_task(&task)?;
     Ok(task)
@@ -106,7 +209,15 @@ pub fn render_task_markdown(task: &Task) -> Result<String, FormatError> {
     validate_task(task)?;
 
     let frontmatter = TaskFrontmatter::from(task);
-    let yaml = serde_yaml::to_string(&frontmatter)?;
+    let mut yaml = serde_yaml::to_string(&frontmatter)?;
+    for (key, raw) in task.extra.iter_raw() {
+        if is_known_key(key) {
+            continue;
+        }
+        yaml.push_str(raw);
+        yaml.push('\n');
+    }
+
     let mut output = String::new();
     output.push_str(FRONTMATTER_DELIMITER);
     output.push('\n');
@@ -121,6 +232,83 @@ pub fn render_task_markdown(task: &Task) -> Result<String, FormatError> {
     Ok(output)
 }
 
+fn is_known_key(key: &str) -> bool {
+    KNOWN_FRONTMATTER_KEYS.contains(&key)
+}
+
+/// Collects the frontmatter keys tqs does not manage, keeping their original text.
+///
+/// Top-level entries are split at column-0 lines and each is parsed on its own
+/// to find its key. If that split does not account for every key (unusual YAML
+/// layouts), the unknown values are re-serialized instead.
+fn parse_extra_frontmatter(frontmatter: &str) -> Result<ExtraFrontmatter, FormatError> {
+    let mapping: serde_yaml::Mapping = serde_yaml::from_str(frontmatter)?;
+    let mut extra = ExtraFrontmatter::default();
+
+    if let Some(entries) = split_top_level_entries(frontmatter, mapping.len()) {
+        for (key, raw) in entries {
+            if !is_known_key(&key) {
+                extra.insert_raw(key, raw);
+            }
+        }
+        return Ok(extra);
+    }
+
+    for (key, value) in mapping {
+        let key_name = yaml_key_name(&key)?;
+        if is_known_key(&key_name) {
+            continue;
+        }
+        let mut entry = serde_yaml::Mapping::new();
+        entry.insert(key, value);
+        let raw = serde_yaml::to_string(&entry)?.trim_end().to_string();
+        extra.insert_raw(key_name, raw);
+    }
+
+    Ok(extra)
+}
+
+/// Every top-level frontmatter entry as its key and original text, in file
+/// order, or `None` when the frontmatter does not split cleanly.
+pub(crate) fn frontmatter_entries(frontmatter: &str) -> Option<Vec<(String, String)>> {
+    let mapping: serde_yaml::Mapping = serde_yaml::from_str(frontmatter).ok()?;
+    split_top_level_entries(frontmatter, mapping.len())
+}
+
+fn split_top_level_entries(frontmatter: &str, expected: usize) -> Option<Vec<(String, String)>> {
+    let mut blocks: Vec<Vec<&str>> = Vec::new();
+    for line in frontmatter.lines() {
+        let starts_entry = line
+            .chars()
+            .next()
+            .is_some_and(|first| !first.is_whitespace() && first != '#' && first != '-');
+        match blocks.last_mut() {
+            Some(block) if !starts_entry => block.push(line),
+            _ => blocks.push(vec![line]),
+        }
+    }
+
+    let mut entries = Vec::with_capacity(blocks.len());
+    for block in blocks {
+        let raw = block.join("\n").trim_end().to_string();
+        let mapping: serde_yaml::Mapping = serde_yaml::from_str(&raw).ok()?;
+        if mapping.len() != 1 {
+            return None;
+        }
+        let (key, _) = mapping.into_iter().next()?;
+        entries.push((yaml_key_name(&key).ok()?, raw));
+    }
+
+    (entries.len() == expected).then_some(entries)
+}
+
+fn yaml_key_name(key: &serde_yaml::Value) -> Result<String, FormatError> {
+    match key {
+        serde_yaml::Value::String(value) => Ok(value.clone()),
+        other => Ok(serde_yaml::to_string(other)?.trim_end().to_string()),
+    }
+}
+
 fn validate_task(task: &Task) -> Result<(), FormatError> {
     if task.completed_at.is_some() && !task.queue.is_done() {
         return Err(FormatError::CompletedAtWithoutDoneQueue);
@@ -131,7 +319,8 @@ fn validate_task(task: &Task) -> Result<(), FormatError> {
 
 #[cfg(test)]
 mod tests {
-    use super::{FormatError, parse_task_markdown, render_task_markdown};
+    use super::{FormatError, parse_task_file, parse_task_markdown, render_task_markdown};
+    use crate::domain::priority::Priority;
     use crate::domain::task::{Queue, Task};
 
     fn task() -> Task {
@@ -200,4 +389,180 @@ mod tests {
         let err = parse_task_markdown(markdown).expect_err("markdown should fail validation");
         assert!(matches!(err, FormatError::CompletedAtWithoutDoneQueue));
     }
+
+    const MARKDOWN_WITH_EXTRA_KEYS: &str = "---\nid: task-1\ntitle: Ship v2\nqueue: inbox\ncreated_at: 2026-03-09T10:34:12Z\nupdated_at: 2026-03-09T10:34:12Z\ncompleted_at: null\ndaily_note: null\naliases: [ship,   v2]\ncssclass: wide # obsidian\nproject:\n  name: Launch\n  links:\n    - \"[[Launch plan]]\"\nreviewers:\n- alice\n- bob\n---\n# Ship v2";
+
+    #[test]
+    fn parse_collects_unknown_keys_in_order() {
+        let parsed = parse_task_markdown(MARKDOWN_WITH_EXTRA_KEYS).expect("markdown should parse");
+
+        assert_eq!(
+            parsed.extra.keys().collect::<Vec<_>>(),
+            vec!["aliases", "cssclass", "project", "reviewers"]
+        );
+        assert_eq!(parsed.extra.raw("aliases"), Some("aliases: [ship,   v2]"));
+        assert_eq!(
+            parsed.extra.get("cssclass"),
+            Some(serde_yaml::Value::String("wide".to_string()))
+        );
+    }
+
+    #[test]
+    fn render_reproduces_unknown_keys_byte_for_byte() {
+        let parsed = parse_task_markdown(MARKDOWN_WITH_EXTRA_KEYS).expect("markdown should parse");
+
+        let rendered = render_task_markdown(&parsed).expect("task should render");
+
+        assert_eq!(rendered, MARKDOWN_WITH_EXTRA_KEYS);
+    }
+
+    #[test]
+    fn unknown_keys_survive_changes_to_known_fields() {
+        let mut parsed =
+            parse_task_markdown(MARKDOWN_WITH_EXTRA_KEYS).expect("markdown should parse");
+        let original = parse_task_file(MARKDOWN_WITH_EXTRA_KEYS).expect("file should split");
+        let untouched = &original.frontmatter[original
+            .frontmatter
+            .find("aliases:")
+            .expect("aliases should exist")..];
+
+        parsed.move_to(
+            Queue::Done,
+            "2026-03-10T08:00:00Z"
+                .parse()
+                .expect("timestamp should parse"),
+        );
+        let rendered = render_task_markdown(&parsed).expect("task should render");
+        let reparsed = parse_task_markdown(&rendered).expect("rendered task should parse");
+
+        assert!(rendered.contains(untouched));
+        assert_eq!(reparsed.extra, parsed.extra);
+        assert_eq!(reparsed.queue, Queue::Done);
+    }
+
+    #[test]
+    fn tags_roundtrip_and_are_omitted_when_empty() {
+        let mut task = task();
+        let rendered = render_task_markdown(&task).expect("task should render");
+        assert!(!rendered.contains("tags:"));
+
+        task.tags = vec!["ops".to_string(), "release".to_string()];
+        let rendered = render_task_markdown(&task).expect("task should render");
+        let parsed = parse_task_markdown(&rendered).expect("rendered task should parse");
+
+        assert!(rendered.contains("tags:\n- ops\n- release\n"));
+        assert_eq!(parsed.tags, task.tags);
+        assert!(parsed.extra.is_empty());
+    }
+
+    #[test]
+    fn due_and_scheduled_roundtrip_as_plain_dates() {
+        let mut task = task();
+        task.due = Some("2026-11-01".parse().expect("date should parse"));
+        task.scheduled = Some("2026-10-28".parse().expect("date should parse"));
+
+        let rendered = render_task_markdown(&task).expect("task should render");
+        let parsed = parse_task_markdown(&rendered).expect("rendered task should parse");
+
+        assert!(rendered.contains("due: 2026-11-01\nscheduled: 2026-10-28\n"));
+        assert_eq!(parsed, task);
+    }
+
+    #[test]
+    fn history_roundtrips_as_a_list_of_transitions() {
+        let mut task = task();
+        task.move_to(
+            Queue::Done,
+            "2026-03-10T08:00:00Z"
+                .parse()
+                .expect("timestamp should parse"),
+        );
+
+        let rendered = render_task_markdown(&task).expect("task should render");
+        let parsed = parse_task_markdown(&rendered).expect("rendered task should parse");
+
+        assert!(
+            rendered.contains("history:\n- from: now\n  to: done\n  at: 2026-03-10T08:00:00Z\n")
+        );
+        assert_eq!(parsed, task);
+    }
+
+    #[test]
+    fn recur_roundtrips_and_rejects_unknown_rules() {
+        let mut task = task();
+        task.recur = Some("every weekday".parse().expect("rule should parse"));
+
+        let rendered = render_task_markdown(&task).expect("task should render");
+        let parsed = parse_task_markdown(&rendered).expect("rendered task should parse");
+
+        assert!(rendered.contains("recur: every weekday\n"));
+        assert_eq!(parsed, task);
+
+        let markdown = "---\nid: task-1\ntitle: Ship v2\nqueue: inbox\ncreated_at: 2026-03-09T10:34:12Z\nupdated_at: 2026-03-09T10:34:12Z\nrecur: sometimes\n---\n";
+        let error = parse_task_markdown(markdown).expect_err("invalid rule should fail");
+        assert!(error.to_string().contains("invalid recurrence 'sometimes'"));
+    }
+
+    #[test]
+    fn priority_roundtrips_and_accepts_named_levels() {
+        let mut task = task();
+        task.priority = Some(Priority::P1);
+
+        let rendered = render_task_markdown(&task).expect("task should render");
+        let parsed = parse_task_markdown(&rendered).expect("rendered task should parse");
+
+        assert!(rendered.contains("priority: p1\n"));
+        assert_eq!(parsed, task);
+
+        let markdown = "---\nid: task-1\ntitle: Ship v2\nqueue: inbox\ncreated_at: 2026-03-09T10:34:12Z\nupdated_at: 2026-03-09T10:34:12Z\npriority: low\n---\n";
+        let parsed = parse_task_markdown(markdown).expect("markdown should parse");
+        assert_eq!(parsed.priority, Some(Priority::P3));
+
+        let markdown = markdown.replace("priority: low", "priority: soon");
+        let error = parse_task_markdown(&markdown).expect_err("invalid priority should fail");
+        assert!(error.to_string().contains("invalid priority 'soon'"));
+    }
+
+    #[test]
+    fn tags_accept_obsidian_string_form() {
+        let markdown = "---\nid: task-1\ntitle: Ship v2\nqueue: inbox\ncreated_at: 2026-03-09T10:34:12Z\nupdated_at: 2026-03-09T10:34:12Z\ntags: \"#ops, release ops\"\n---\n";
+        let parsed = parse_task_markdown(markdown).expect("markdown should parse");
+
+        assert_eq!(parsed.tags, vec!["ops".to_string(), "release".to_string()]);
+    }
+
+    #[test]
+    fn invalid_tags_fail_the_parse_instead_of_being_dropped() {
+        let with_tags = |tags: &str| {
+            format!(
+                "---\nid: task-1\ntitle: Ship v2\nqueue: inbox\ncreated_at: 2026-03-09T10:34:12Z\nupdated_at: 2026-03-09T10:34:12Z\ntags: {tags}\n---\n"
+            )
+        };
+
+        let error = parse_task_markdown(&with_tags("[ops, \"two words\"]"))
+            .expect_err("a tag with a space should fail");
+        assert!(error.to_string().contains("invalid tag 'two words'"));
+
+        let error = parse_task_markdown(&with_tags("[ops, {team: web}]"))
+            .expect_err("a mapping should fail");
+        assert!(
+            error
+                .to_string()
+                .contains("tags must be strings or numbers")
+        );
+    }
+
+    #[test]
+    fn render_skips_extra_entries_that_shadow_known_keys() {
+        let mut task = task();
+        task.extra
+            .insert("queue", serde_yaml::Value::String("later".to_string()))
+            .expect("value should serialize");
+
+        let rendered = render_task_markdown(&task).expect("task should render");
+        let parsed = parse_task_markdown(&rendered).expect("rendered task should parse");
+
+        assert_eq!(parsed.queue, Queue::Now);
+        assert!(parsed.extra.is_empty());
+    }
 }
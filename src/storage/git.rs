@@ -0,0 +1,473 @@
+use std::{
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+use crate::app::app_error::AppError;
+use crate::storage::{config::GitConfig, merge::merge_task_files};
+
+/// Commit message for task files changed by hand before a sync.
+const OUTSIDE_EDITS_MESSAGE: &str = "update tasks edited outside tqs";
+
+/// Runs `git` in the tasks root. The root may be the repository itself or a
+/// folder inside it, such as an Obsidian vault's tasks folder; commits only
+/// ever include the paths tqs names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Git {
+    dir: PathBuf,
+    remote: String,
+    branch: Option<String>,
+}
+
+/// What `tqs sync` did besides pulling and pushing.
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub branch: String,
+    /// Whether task files edited by hand were committed first.
+    pub committed_outside_edits: bool,
+    /// Task files whose conflicting frontmatter edits were merged, relative
+    /// to the repository root.
+    pub merged: Vec<PathBuf>,
+}
+
+impl Git {
+    pub fn new(dir: &Path, config: &GitConfig) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            remote: config.remote.clone(),
+            branch: config.branch.clone(),
+        }
+    }
+
+    pub fn remote(&self) -> &str {
+        &self.remote
+    }
+
+    /// Commits the current contents of `paths`, which may be files or
+    /// directories under the tasks root; removed paths are committed as
+    /// deletions. Returns whether a commit was made, since paths that did
+    /// not change leave nothing to commit.
+    pub fn commit(&self, message: &str, paths: &[PathBuf]) -> Result<bool, AppError> {
+        let paths = paths
+            .iter()
+            .filter_map(|path| path.strip_prefix(&self.dir).ok())
+            .filter(|path| !path.as_os_str().is_empty())
+            .collect::<Vec<_>>();
+        if paths.is_empty() {
+            return Ok(false);
+        }
+
+        for path in &paths {
+            if self.dir.join(path).exists() {
+                self.run_on(&["add", "-A", "--"], &[path])?;
+            } else {
+                self.run_on(
+                    &["rm", "-r", "-q", "--cached", "--ignore-unmatch", "--"],
+                    &[path],
+                )?;
+            }
+        }
+        let staged = self.run_on(
+            &["diff", "--cached", "--name-only", "--relative", "-z", "--"],
+            &paths,
+        )?;
+        let changed = split_nul(&staged.stdout);
+        if changed.is_empty() {
+            return Ok(false);
+        }
+        self.run_on(&["commit", "-q", "-m", message, "--"], &changed)?;
+        Ok(true)
+    }
+
+    /// Commits anything changed by hand under `dirs`, rebases onto the
+    /// remote branch, and pushes. Conflicts that only touch different
+    /// frontmatter entries of a task are merged; any other conflict aborts
+    /// the rebase and leaves the local branch as it was.
+    pub fn sync(&self, dirs: &[PathBuf]) -> Result<SyncReport, AppError> {
+        self.run(&["rev-parse", "--is-inside-work-tree"])
+            .map_err(|_| {
+                AppError::message(format!(
+                    "cannot sync: {} is not inside a git repository",
+                    self.dir.display()
+                ))
+            })?;
+        let branch = match &self.branch {
+            Some(branch) => branch.clone(),
+            None => {
+                let head = self
+                    .run(&["symbolic-ref", "--short", "-q", "HEAD"])
+                    .map_err(|_| {
+                        AppError::message(
+                            "cannot sync: HEAD is detached; check out a branch or set git.branch",
+                        )
+                    })?;
+                String::from_utf8_lossy(&head.stdout).trim().to_string()
+            }
+        };
+
+        let mut report = SyncReport {
+            committed_outside_edits: self.commit(OUTSIDE_EDITS_MESSAGE, dirs)?,
+            ..SyncReport::default()
+        };
+
+        if self.remote_has_branch(&branch)? {
+            let pull = self.output(&[
+                "pull",
+                "-q",
+                "--rebase",
+                "--autostash",
+                self.remote.as_str(),
+                branch.as_str(),
+            ])?;
+            if !pull.status.success() {
+                report.merged = self.resolve_rebase(&pull)?;
+            }
+        }
+
+        self.run(&[
+            "push",
+            "-q",
+            self.remote.as_str(),
+            &format!("HEAD:refs/heads/{branch}"),
+        ])?;
+        report.branch = branch;
+        Ok(report)
+    }
+
+    fn remote_has_branch(&self, branch: &str) -> Result<bool, AppError> {
+        let output = self.output(&[
+            "ls-remote",
+            "--exit-code",
+            "--heads",
+            self.remote.as_str(),
+            branch,
+        ])?;
+        match output.status.code() {
+            Some(0) => Ok(true),
+            Some(2) => Ok(false),
+            _ => Err(git_error("ls-remote", &output)),
+        }
+    }
+
+    /// Merges each conflicted task file and continues the rebase until it
+    /// finishes, returning the merged files.
+    fn resolve_rebase(&self, failure: &Output) -> Result<Vec<PathBuf>, AppError> {
+        let top = self.run(&["rev-parse", "--show-toplevel"])?;
+        let top = PathBuf::from(String::from_utf8_lossy(&top.stdout).trim());
+        let mut merged = Vec::new();
+
+        loop {
+            let conflicts = self.run(&["diff", "--name-only", "--diff-filter=U", "-z"])?;
+            let conflicts = split_nul(&conflicts.stdout);
+            if conflicts.is_empty() {
+                if merged.is_empty() {
+                    return Err(git_error("pull", failure));
+                }
+                let _ = self.output(&["rebase", "--abort"]);
+                return Err(AppError::message(
+                    "sync stopped: the rebase did not finish; it was aborted and nothing was pushed",
+                ));
+            }
+
+            let mut unmerged = Vec::new();
+            for path in conflicts {
+                let target = self.merge_conflict(&top, &path).inspect_err(|_| {
+                    let _ = self.output(&["rebase", "--abort"]);
+                })?;
+                match target {
+                    Some(target) => merged.push(target),
+                    None => unmerged.push(path.display().to_string()),
+                }
+            }
+            if !unmerged.is_empty() {
+                let _ = self.output(&["rebase", "--abort"]);
+                return Err(AppError::message(format!(
+                    "sync stopped: conflicting changes in {}; the rebase was aborted and nothing was pushed",
+                    unmerged.join(", ")
+                )));
+            }
+
+            let next = Command::new("git")
+                .arg("-C")
+                .arg(&self.dir)
+                .args(["rebase", "--continue"])
+                .env("GIT_EDITOR", "true")
+                .output()?;
+            if next.status.success() {
+                return Ok(merged);
+            }
+        }
+    }
+
+    /// Writes and stages the merge of one conflicted file, returning where
+    /// it was written, or `None` when it cannot be merged. A task moved to
+    /// another queue on one side and edited on the other conflicts as a
+    /// deleted file; the edits are merged into the moved copy. Paths are
+    /// relative to the repository root.
+    fn merge_conflict(&self, top: &Path, path: &Path) -> Result<Option<PathBuf>, AppError> {
+        let Some(base) = self.show(1, path)? else {
+            return Ok(None);
+        };
+        let (target, ours, theirs) = match (self.show(2, path)?, self.show(3, path)?) {
+            (Some(ours), Some(theirs)) => (path.to_path_buf(), ours, theirs),
+            (None, Some(theirs)) => match self.moved_copy(path)? {
+                Some((moved, ours)) => (moved, ours, theirs),
+                None => return Ok(None),
+            },
+            (Some(ours), None) => match self.moved_copy(path)? {
+                Some((moved, theirs)) => (moved, ours, theirs),
+                None => return Ok(None),
+            },
+            (None, None) => return Ok(None),
+        };
+        let Some(merged) = merge_task_files(&base, &ours, &theirs).filter(|merge| merge.is_clean())
+        else {
+            return Ok(None);
+        };
+
+        fs::write(top.join(&target), merged.content)?;
+        if target != path {
+            git_in(top, &["rm", "-q", "-f", "--"], path)?;
+        }
+        git_in(top, &["add", "--"], &target)?;
+        Ok(Some(target))
+    }
+
+    /// The one other task file with the same name, and its contents, such as
+    /// `now/abc.md` for `inbox/abc.md`.
+    fn moved_copy(&self, path: &Path) -> Result<Option<(PathBuf, String)>, AppError> {
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            return Ok(None);
+        };
+        let pattern = format!(":(glob)**/{name}");
+        let listed = self.run(&["ls-files", "-z", "--full-name", "--", &pattern])?;
+        let copies = split_nul(&listed.stdout)
+            .into_iter()
+            .filter(|copy| copy != path)
+            .collect::<Vec<_>>();
+        let [copy] = copies.as_slice() else {
+            return Ok(None);
+        };
+        Ok(self.show(0, copy)?.map(|content| (copy.clone(), content)))
+    }
+
+    /// The file's contents at one merge stage of the index.
+    fn show(&self, stage: u8, path: &Path) -> Result<Option<String>, AppError> {
+        let spec = format!(":{stage}:{}", path.display());
+        let output = self.output(&["show", &spec])?;
+        Ok(output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned()))
+    }
+
+    fn run(&self, args: &[&str]) -> Result<Output, AppError> {
+        self.run_on(args, &[] as &[&Path])
+    }
+
+    /// Runs `git <args> <paths>`, failing with git's own message when it
+    /// exits unsuccessfully.
+    fn run_on<P: AsRef<OsStr>>(&self, args: &[&str], paths: &[P]) -> Result<Output, AppError> {
+        let output = self.output_on(args, paths)?;
+        if output.status.success() {
+            Ok(output)
+        } else {
+            Err(git_error(args[0], &output))
+        }
+    }
+
+    fn output(&self, args: &[&str]) -> Result<Output, AppError> {
+        self.output_on(args, &[] as &[&Path])
+    }
+
+    fn output_on<P: AsRef<OsStr>>(&self, args: &[&str], paths: &[P]) -> Result<Output, AppError> {
+        Command::new("git")
+            .arg("-C")
+            .arg(&self.dir)
+            .args(args)
+            .args(paths)
+            .output()
+            .map_err(|error| AppError::message(format!("could not run git: {error}")))
+    }
+}
+
+fn git_error(command: &str, output: &Output) -> AppError {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let detail = stderr.trim();
+    if detail.is_empty() {
+        AppError::message(format!("git {command} failed with {}", output.status))
+    } else {
+        AppError::message(format!("git {command} failed: {detail}"))
+    }
+}
+
+fn git_in(dir: &Path, args: &[&str], path: &Path) -> Result<Output, AppError> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .arg(path)
+        .output()?;
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(git_error(args[0], &output))
+    }
+}
+
+fn split_nul(output: &[u8]) -> Vec<PathBuf> {
+    output
+        .split(|byte| *byte == 0)
+        .filter(|path| !path.is_empty())
+        .map(|path| PathBuf::from(String::from_utf8_lossy(path).into_owned()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::task::{Queue, Task};
+    use crate::storage::config::{GitConfig, QueueDirs};
+    use crate::storage::repo::TaskRepo;
+    use chrono::{Duration, Utc};
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        process::Command,
+    };
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .output()
+            .expect("git should run");
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    }
+
+    fn identify(dir: &Path) {
+        git(dir, &["config", "user.name", "Test"]);
+        git(dir, &["config", "user.email", "test@example.com"]);
+        git(dir, &["config", "commit.gpgsign", "false"]);
+    }
+
+    fn repo(root: &Path) -> TaskRepo {
+        TaskRepo::new(root.to_path_buf(), QueueDirs::default()).with_git(&GitConfig {
+            remote: "origin".to_string(),
+            branch: Some("main".to_string()),
+        })
+    }
+
+    fn sync(repo: &TaskRepo) -> Result<super::SyncReport, crate::app::app_error::AppError> {
+        let dirs = Queue::all()
+            .iter()
+            .map(|queue| repo.queue_dir(*queue))
+            .collect::<Vec<_>>();
+        repo.git().expect("git should be enabled").sync(&dirs)
+    }
+
+    /// A published task in clone `a`, plus a second clone `b` of it.
+    fn two_clones(temp: &TempDir) -> (TaskRepo, TaskRepo) {
+        git(
+            temp.path(),
+            &["init", "-q", "--bare", "-b", "main", "remote.git"],
+        );
+        git(temp.path(), &["clone", "-q", "remote.git", "a"]);
+        let a = temp.path().join("a");
+        identify(&a);
+        git(&a, &["checkout", "-q", "-b", "main"]);
+        let a = repo(&a);
+        let mut task = Task::new("abc", "Ship it", Utc::now() - Duration::hours(1));
+        task.body = "Notes".to_string();
+        a.create(&task).expect("task should be created");
+        sync(&a).expect("first sync should push");
+
+        git(
+            temp.path(),
+            &["clone", "-q", "-b", "main", "remote.git", "b"],
+        );
+        identify(&temp.path().join("b"));
+        (a, repo(&temp.path().join("b")))
+    }
+
+    #[test]
+    fn every_change_is_committed_without_the_state_dir() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        git(temp.path(), &["init", "-q", "-b", "main"]);
+        identify(temp.path());
+        let repo = repo(temp.path());
+
+        repo.create(&Task::new("abc", "Ship it", Utc::now()))
+            .expect("task should be created");
+        repo.move_to_queue("abc", Queue::Now, Utc::now())
+            .expect("task should move");
+        repo.journal().begin_batch();
+        repo.delete("abc").expect("task should be deleted");
+        repo.undo().expect("delete should be undone");
+
+        assert_eq!(
+            git(temp.path(), &["log", "--format=%s"]),
+            "undo delete abc\ndelete abc\nmove abc inbox\u{2192}now\nadd abc\n"
+        );
+        assert_eq!(git(temp.path(), &["ls-files"]), "now/abc.md\n");
+    }
+
+    #[test]
+    fn sync_merges_edits_to_different_frontmatter_entries() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let (a, b) = two_clones(&temp);
+
+        a.move_to_queue("abc", Queue::Now, Utc::now())
+            .expect("task should move");
+        sync(&a).expect("sync should push the move");
+        let mut task = b.read("abc").expect("task should exist");
+        task.title = "Ship it today".to_string();
+        task.updated_at = Utc::now();
+        b.update(&task).expect("task should be retitled");
+
+        let report = sync(&b).expect("sync should merge the edits");
+
+        assert_eq!(report.branch, "main");
+        assert_eq!(report.merged, vec![PathBuf::from("now/abc.md")]);
+        let merged = b.read("abc").expect("merged task should parse");
+        assert_eq!(merged.title, "Ship it today");
+        assert_eq!(merged.queue, Queue::Now);
+        assert_eq!(merged.body, "Notes");
+        sync(&a).expect("sync should pull the merge");
+        assert_eq!(
+            a.read("abc").expect("task should exist").title,
+            "Ship it today"
+        );
+    }
+
+    #[test]
+    fn sync_aborts_when_both_sides_change_the_same_entry() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let (a, b) = two_clones(&temp);
+        for (repo, title) in [(&a, "Ours"), (&b, "Theirs")] {
+            let mut task = repo.read("abc").expect("task should exist");
+            task.title = title.to_string();
+            repo.update(&task).expect("task should be retitled");
+        }
+        sync(&a).expect("sync should push");
+
+        let error = sync(&b).expect_err("conflicting titles should stop the sync");
+
+        assert!(
+            error
+                .to_string()
+                .contains("conflicting changes in inbox/abc.md; the rebase was aborted")
+        );
+        assert_eq!(b.read("abc").expect("task should exist").title, "Theirs");
+        assert!(!b.root().join(".git/rebase-merge").exists());
+        assert!(fs::read_to_string(b.root().join("inbox/abc.md")).is_ok());
+    }
+}
@@ -1,8 +1,6 @@
 use std::{
-    fs::{self, OpenOptions},
+    fs,
     path::{Path, PathBuf},
-    thread,
-    time::Duration,
 };
 
 use serde::{Deserialize, Serialize};
@@ -13,12 +11,10 @@ use crate::{
         MAX_GENERATED_ID_WIDTH, MIN_GENERATED_ID_WIDTH, encode_generated_id, id_space_size,
         next_sequence_value,
     },
-    storage::{config::ResolvedConfig, repo::TaskRepo},
+    storage::{atomic, config::ResolvedConfig, lock::FileLock, repo::TaskRepo},
 };
 
 const STATE_FILE_VERSION: u32 = 1;
-const LOCK_RETRY_DELAY: Duration = Duration::from_millis(20);
-const MAX_LOCK_ATTEMPTS: usize = 250;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SharedIdAllocator {
@@ -41,7 +37,12 @@ impl SharedIdAllocator {
     }
 
     pub fn generate(&self, repo: &TaskRepo) -> Result<String, AppError> {
-        let _lock = FileLock::acquire(&self.lock_path)?;
+        let _lock = FileLock::acquire(&self.lock_path)?.ok_or_else(|| {
+            AppError::message(format!(
+                "timed out waiting for allocator lock {}",
+                self.lock_path.display()
+            ))
+        })?;
         let mut state = self.load_state()?;
 
         loop {
@@ -57,7 +58,7 @@ impl SharedIdAllocator {
                 current = next;
                 scanned += 1;
 
-                if repo.id_exists(&id) {
+                if repo.id_exists(&id) || repo.trash().contains(&id) {
                     continue;
                 }
 
@@ -97,9 +98,7 @@ impl SharedIdAllocator {
         let serialized = toml::to_string(&persisted).map_err(|error| {
             AppError::message(format!("failed to serialize allocator state: {error}"))
         })?;
-        let temp_path = self.state_path.with_extension("tmp");
-        fs::write(&temp_path, serialized)?;
-        fs::rename(temp_path, &self.state_path)?;
+        atomic::write(&self.state_path, serialized)?;
         Ok(())
     }
 }
@@ -184,7 +183,7 @@ pub(crate) fn state_file_path(state_dir: &Path, tasks_root: &Path) -> PathBuf {
     state_dir.join("id-generator").join(format!("{hash}.toml"))
 }
 
-fn stable_path_hash(path: &Path) -> String {
+pub(crate) fn stable_path_hash(path: &Path) -> String {
     let mut hash = 0xcbf29ce484222325u64;
     for byte in path.to_string_lossy().bytes() {
         hash ^= u64::from(byte);
@@ -193,44 +192,6 @@ fn stable_path_hash(path: &Path) -> String {
     format!("{hash:016x}")
 }
 
-struct FileLock {
-    path: PathBuf,
-}
-
-impl FileLock {
-    fn acquire(path: &Path) -> Result<Self, AppError> {
-        let parent = path.parent().ok_or_else(|| {
-            AppError::message("allocator lock path is missing a parent directory")
-        })?;
-        fs::create_dir_all(parent)?;
-
-        for _ in 0..MAX_LOCK_ATTEMPTS {
-            match OpenOptions::new().write(true).create_new(true).open(path) {
-                Ok(_) => {
-                    return Ok(Self {
-                        path: path.to_path_buf(),
-                    });
-                }
-                Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists => {
-                    thread::sleep(LOCK_RETRY_DELAY);
-                }
-                Err(error) => return Err(AppError::Io(error)),
-            }
-        }
-
-        Err(AppError::message(format!(
-            "timed out waiting for allocator lock {}",
-            path.display()
-        )))
-    }
-}
-
-impl Drop for FileLock {
-    fn drop(&mut self) {
-        let _ = fs::remove_file(&self.path);
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use std::{
@@ -259,6 +220,11 @@ mod tests {
             state_dir: state_dir.to_path_buf(),
             daily_notes_dir: None,
             queue_dirs: QueueDirs::default(),
+            recur_queue: crate::domain::task::Queue::Inbox,
+            views: Vec::new(),
+            templates: Default::default(),
+            auto_archive_after: None,
+            git: None,
         }
     }
 
@@ -320,6 +286,24 @@ mod tests {
         assert_eq!(id.len(), 3);
     }
 
+    #[test]
+    fn allocator_skips_trashed_ids() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let root = temp.path().join("tasks");
+        let state_dir = temp.path().join(".tqs");
+        let repo = TaskRepo::new(root.clone(), QueueDirs::default()).with_state_dir(&state_dir);
+        repo.create(&task("000", "Deleted task"))
+            .expect("task should be created");
+        repo.delete("000").expect("task should be trashed");
+        let allocator = SharedIdAllocator::new(&config(&root, &state_dir));
+
+        let id = allocator
+            .generate(&repo)
+            .expect("allocator should generate");
+
+        assert_ne!(id, "000");
+    }
+
     #[test]
     fn allocator_advances_to_wider_ids_after_exhaustion() {
         let mut state = GeneratorState {
@@ -0,0 +1,372 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    process,
+    sync::{Mutex, MutexGuard},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::app_error::AppError;
+use crate::domain::task::{ExtraFrontmatter, FileVersion, Task};
+use crate::storage::id_state::stable_path_hash;
+
+pub const INDEX_DIR: &str = "index";
+const INDEX_VERSION: u32 = 2;
+
+/// Files modified this recently are parsed but not cached: a second write
+/// within the file system's timestamp granularity could leave both the
+/// mtime and the size unchanged.
+const SETTLE_TIME: Duration = Duration::from_secs(2);
+
+/// Parsed task files cached in `<state_dir>/index/<root hash>.json`, so
+/// listings only parse the files that changed since the last run.
+///
+/// Entries are keyed by path relative to the tasks root and trusted only while
+/// the file's mtime and size match what was recorded. The index is a cache:
+/// an unreadable or outdated file is discarded and rebuilt on the next scan.
+#[derive(Debug)]
+pub struct TaskIndex {
+    path: PathBuf,
+    root: PathBuf,
+    state: Mutex<IndexState>,
+}
+
+#[derive(Debug, Default)]
+struct IndexState {
+    /// `None` until the index file is first needed.
+    entries: Option<BTreeMap<String, IndexEntry>>,
+    dirty: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct IndexFile {
+    version: u32,
+    entries: BTreeMap<String, IndexEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexEntry {
+    #[serde(flatten)]
+    stamp: Stamp,
+    task: Task,
+    #[serde(default, skip_serializing_if = "ExtraFrontmatter::is_empty")]
+    extra: ExtraFrontmatter,
+    /// The file's content hash, for the task's [`FileVersion`].
+    hash: u64,
+}
+
+/// What has to stay the same for a cached entry to be trusted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct Stamp {
+    modified_secs: u64,
+    modified_nanos: u32,
+    size: u64,
+}
+
+impl Stamp {
+    /// The stamp of `metadata`, or `None` when the file is too recent to cache
+    /// or the platform has no modification times.
+    fn settled(metadata: &fs::Metadata, now: SystemTime) -> Option<Self> {
+        let modified = metadata.modified().ok()?;
+        if now.duration_since(modified).ok()? < SETTLE_TIME {
+            return None;
+        }
+        let since_epoch = modified.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            modified_secs: since_epoch.as_secs(),
+            modified_nanos: since_epoch.subsec_nanos(),
+            size: metadata.len(),
+        })
+    }
+
+    fn modified(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::new(self.modified_secs, self.modified_nanos)
+    }
+}
+
+impl TaskIndex {
+    pub fn new(state_dir: &Path, tasks_root: &Path) -> Self {
+        Self {
+            path: index_path(state_dir, tasks_root),
+            root: tasks_root.to_path_buf(),
+            state: Mutex::new(IndexState::default()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The task stored at `path`: the cached copy when the file is unchanged,
+    /// otherwise whatever `parse` returns, which is cached for next time.
+    pub(crate) fn read(
+        &self,
+        path: &Path,
+        parse: impl FnOnce() -> Result<Task, AppError>,
+    ) -> Result<Task, AppError> {
+        let key = self.key(path);
+        let stamp = Stamp::settled(&fs::metadata(path)?, SystemTime::now());
+        let mut state = self.lock();
+        let entries = state.load(&self.path);
+        if let (Some(stamp), Some(entry)) = (stamp, entries.get(&key))
+            && entry.stamp == stamp
+        {
+            let mut task = entry.task.clone();
+            task.extra = entry.extra.clone();
+            task.loaded_from = Some(FileVersion::new(Some(stamp.modified()), entry.hash, &task));
+            return Ok(task);
+        }
+
+        let parsed = parse();
+        let changed = match (&parsed, stamp) {
+            (Ok(task), Some(stamp)) => {
+                let entry = IndexEntry {
+                    stamp,
+                    task: task.clone(),
+                    extra: task.extra.clone(),
+                    hash: task.loaded_from.as_ref().map_or(0, |version| version.hash),
+                };
+                entries.insert(key, entry);
+                true
+            }
+            _ => entries.remove(&key).is_some(),
+        };
+        state.dirty |= changed;
+        parsed
+    }
+
+    /// Drops entries for files that are no longer in `paths`, after a scan
+    /// of every queue directory. Entries in folders nested below `unscanned`,
+    /// which the scan did not descend into, are kept.
+    pub(crate) fn retain(&self, paths: &HashSet<PathBuf>, unscanned: Option<&Path>) {
+        let keys = paths
+            .iter()
+            .map(|path| self.key(path))
+            .collect::<HashSet<_>>();
+        let unscanned = unscanned.map(|dir| PathBuf::from(self.key(dir)));
+        let mut state = self.lock();
+        let entries = state.load(&self.path);
+        let before = entries.len();
+        entries.retain(|key, _| {
+            keys.contains(key)
+                || unscanned.as_deref().is_some_and(|dir| {
+                    Path::new(key)
+                        .parent()
+                        .is_some_and(|parent| parent != dir && parent.starts_with(dir))
+                })
+        });
+        state.dirty |= entries.len() != before;
+    }
+
+    /// Forgets every entry, so the next scan parses every file again.
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.entries = Some(BTreeMap::new());
+        state.dirty = true;
+    }
+
+    /// Why the index file on disk cannot be used, if it exists but is
+    /// unreadable or from another version.
+    pub fn load_error(&self) -> Option<String> {
+        match read_index_file(&self.path) {
+            Ok(_) => None,
+            Err(reason) => reason,
+        }
+    }
+
+    /// Writes the index back when it changed. The file is replaced in one
+    /// rename, so concurrent runs never see a partial index.
+    pub fn save(&self) -> Result<(), AppError> {
+        let mut state = self.lock();
+        if !state.dirty {
+            return Ok(());
+        }
+        let file = IndexFile {
+            version: INDEX_VERSION,
+            entries: state.load(&self.path).clone(),
+        };
+        let serialized = serde_json::to_string(&file)
+            .map_err(|error| AppError::message(format!("failed to serialize index: {error}")))?;
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temp_path = self.path.with_extension(format!("{}.tmp", process::id()));
+        fs::write(&temp_path, serialized)?;
+        fs::rename(&temp_path, &self.path)?;
+        state.dirty = false;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().load(&self.path).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn key(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, IndexState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl IndexState {
+    fn load(&mut self, path: &Path) -> &mut BTreeMap<String, IndexEntry> {
+        let dirty = &mut self.dirty;
+        self.entries
+            .get_or_insert_with(|| match read_index_file(path) {
+                Ok(entries) => entries,
+                Err(reason) => {
+                    // Missing files just start empty; anything else is rewritten.
+                    *dirty |= reason.is_some();
+                    BTreeMap::new()
+                }
+            })
+    }
+}
+
+/// The entries in the index file, or the reason it cannot be used; `None`
+/// when there is no index file yet.
+fn read_index_file(path: &Path) -> Result<BTreeMap<String, IndexEntry>, Option<String>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Err(None),
+        Err(error) => return Err(Some(error.to_string())),
+    };
+    let file =
+        serde_json::from_str::<IndexFile>(&contents).map_err(|error| Some(error.to_string()))?;
+    if file.version != INDEX_VERSION {
+        return Err(Some(format!("unsupported index version {}", file.version)));
+    }
+    Ok(file.entries)
+}
+
+pub(crate) fn index_path(state_dir: &Path, tasks_root: &Path) -> PathBuf {
+    let root = tasks_root
+        .canonicalize()
+        .unwrap_or_else(|_| tasks_root.to_path_buf());
+    state_dir
+        .join(INDEX_DIR)
+        .join(format!("{}.json", stable_path_hash(&root)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TaskIndex;
+    use crate::domain::task::Task;
+    use std::{
+        cell::Cell,
+        collections::HashSet,
+        fs::{self, File},
+        path::Path,
+        time::{Duration, SystemTime},
+    };
+    use tempfile::TempDir;
+
+    fn write_settled(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().expect("path has a parent")).expect("dir should exist");
+        fs::write(path, contents).expect("file should be written");
+        File::options()
+            .write(true)
+            .open(path)
+            .and_then(|file| file.set_modified(SystemTime::now() - Duration::from_secs(3600)))
+            .expect("mtime should be set");
+    }
+
+    fn read_counting(index: &TaskIndex, path: &Path, title: &str, parses: &Cell<u32>) -> Task {
+        index
+            .read(path, || {
+                parses.set(parses.get() + 1);
+                Ok(Task::new(
+                    "abc",
+                    title,
+                    "2026-03-09T10:34:12Z"
+                        .parse()
+                        .expect("timestamp should parse"),
+                ))
+            })
+            .expect("task should be read")
+    }
+
+    #[test]
+    fn unchanged_files_are_served_from_the_saved_index() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let root = temp.path();
+        let path = root.join("inbox").join("abc.md");
+        write_settled(&path, "first");
+        let parses = Cell::new(0);
+
+        let index = TaskIndex::new(&root.join(".tqs"), root);
+        read_counting(&index, &path, "First", &parses);
+        index.save().expect("index should save");
+        assert!(index.path().exists());
+
+        let index = TaskIndex::new(&root.join(".tqs"), root);
+        let task = read_counting(&index, &path, "Ignored", &parses);
+        assert_eq!(task.title, "First");
+        assert_eq!(parses.get(), 1);
+
+        write_settled(&path, "second version");
+        let task = read_counting(&index, &path, "Second", &parses);
+        assert_eq!(task.title, "Second");
+        assert_eq!(parses.get(), 2);
+    }
+
+    #[test]
+    fn recent_files_are_not_cached_and_missing_files_are_dropped() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let root = temp.path();
+        let fresh = root.join("inbox").join("abc.md");
+        fs::create_dir_all(fresh.parent().expect("path has a parent")).expect("dir should exist");
+        fs::write(&fresh, "fresh").expect("file should be written");
+        let settled = root.join("done").join("2026").join("def.md");
+        write_settled(&settled, "settled");
+        let parses = Cell::new(0);
+
+        let index = TaskIndex::new(&root.join(".tqs"), root);
+        read_counting(&index, &fresh, "Fresh", &parses);
+        read_counting(&index, &fresh, "Fresh", &parses);
+        assert_eq!(parses.get(), 2);
+
+        read_counting(&index, &settled, "Settled", &parses);
+        assert_eq!(index.len(), 1);
+        index.retain(&HashSet::from([fresh.clone()]), Some(&root.join("done")));
+        assert_eq!(index.len(), 1);
+        index.retain(&HashSet::from([fresh.clone()]), None);
+        assert!(index.is_empty());
+    }
+
+    #[test]
+    fn unreadable_index_files_are_reported_and_replaced() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let root = temp.path();
+        let index = TaskIndex::new(&root.join(".tqs"), root);
+        assert_eq!(index.load_error(), None);
+
+        fs::create_dir_all(index.path().parent().expect("index has a parent"))
+            .expect("index dir should exist");
+        fs::write(index.path(), "{\"version\": 99, \"entries\": {}}")
+            .expect("index should be written");
+        assert_eq!(
+            index.load_error(),
+            Some("unsupported index version 99".to_string())
+        );
+
+        assert!(index.is_empty());
+        index.save().expect("index should save");
+        assert_eq!(index.load_error(), None);
+    }
+}
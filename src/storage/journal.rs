@@ -0,0 +1,670 @@
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::app::app_error::AppError;
+use crate::domain::task::Queue;
+use crate::storage::atomic;
+
+pub const JOURNAL_FILE: &str = "journal.jsonl";
+const ENTRY_VERSION: u32 = 1;
+/// How many batches the journal keeps, counting undo and redo batches; the
+/// oldest are dropped as new ones start.
+pub const KEEP_BATCHES: usize = 200;
+
+static BATCH_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Log of every change `TaskRepo` makes, kept at
+/// `<state_dir>/journal.jsonl`. Each line records the full contents of the
+/// files an operation touched before and after it ran, so any step can be
+/// reverted or replayed without consulting the tasks themselves.
+///
+/// Entries are grouped into batches: everything one command does is undone
+/// together. A batch stays open until [`Journal::begin_batch`] is called, so
+/// a CLI invocation is a single batch and the TUI starts one per key press.
+/// Only the last [`KEEP_BATCHES`] batches are kept.
+#[derive(Debug)]
+pub struct Journal {
+    path: PathBuf,
+    batch: Mutex<Option<String>>,
+    keep: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Operation {
+    Create,
+    Update,
+    Move {
+        queue: Queue,
+    },
+    Edit,
+    /// A manual reorder within a queue; see `domain::rank`.
+    Rank,
+    /// A done task moved into its dated archive folder.
+    Archive,
+    Delete,
+    Restore,
+    /// A failed bulk action putting back the files it had already changed.
+    Rollback,
+    Undo {
+        batch: String,
+    },
+    Redo {
+        batch: String,
+    },
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Create => f.write_str("add"),
+            Self::Update => f.write_str("update"),
+            Self::Move { queue } => write!(f, "move to {queue}"),
+            Self::Edit => f.write_str("edit"),
+            Self::Rank => f.write_str("rank"),
+            Self::Archive => f.write_str("archive"),
+            Self::Delete => f.write_str("delete"),
+            Self::Restore => f.write_str("restore"),
+            Self::Rollback => f.write_str("rollback"),
+            Self::Undo { .. } => f.write_str("undo"),
+            Self::Redo { .. } => f.write_str("redo"),
+        }
+    }
+}
+
+/// One file's contents around an operation; `None` means the file did not
+/// exist.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileChange {
+    pub path: PathBuf,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub version: u32,
+    pub batch: String,
+    pub at: DateTime<Utc>,
+    pub operation: Operation,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    pub changes: Vec<FileChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchState {
+    Applied,
+    Undone,
+}
+
+/// The entries of one batch, numbered by its position in the journal.
+#[derive(Debug, Clone)]
+pub struct Batch {
+    pub number: usize,
+    pub id: String,
+    pub entries: Vec<JournalEntry>,
+    pub state: BatchState,
+}
+
+impl Batch {
+    pub fn at(&self) -> DateTime<Utc> {
+        self.entries[0].at
+    }
+
+    /// The batch's net effect: each touched file's contents before the first
+    /// entry and after the last, leaving out files that ended up unchanged.
+    pub fn net_changes(&self) -> Vec<FileChange> {
+        let mut net: Vec<FileChange> = Vec::new();
+        for change in self.entries.iter().flat_map(|entry| &entry.changes) {
+            match net.iter_mut().find(|existing| existing.path == change.path) {
+                Some(existing) => existing.after = change.after.clone(),
+                None => net.push(change.clone()),
+            }
+        }
+        net.retain(|change| change.before != change.after);
+        net
+    }
+
+    /// The batch this one undid or redid, if it is an undo or redo.
+    fn reverses(&self) -> Option<&str> {
+        match &self.entries[0].operation {
+            Operation::Undo { batch } | Operation::Redo { batch } => Some(batch),
+            _ => None,
+        }
+    }
+}
+
+/// Describes a batch for `tqs history` and status messages, e.g.
+/// `move to next a1b, c2d` or `undo #12`.
+pub fn describe(batch: &Batch, batches: &[Batch]) -> String {
+    if let Some(target) = batch.reverses() {
+        let number = batches
+            .iter()
+            .find(|candidate| candidate.id == target)
+            .map(|candidate| format!("#{}", candidate.number))
+            .unwrap_or_else(|| "an earlier change".to_string());
+        return format!("{} {number}", batch.entries[0].operation);
+    }
+
+    let mut parts: Vec<(String, Vec<&str>)> = Vec::new();
+    for entry in &batch.entries {
+        let operation = entry.operation.to_string();
+        let ids = match parts.last_mut() {
+            Some((last, ids)) if *last == operation => ids,
+            _ => {
+                parts.push((operation, Vec::new()));
+                &mut parts.last_mut().expect("part was just pushed").1
+            }
+        };
+        if let Some(task) = entry.task.as_deref()
+            && !ids.contains(&task)
+        {
+            ids.push(task);
+        }
+    }
+    parts
+        .into_iter()
+        .map(|(operation, ids)| {
+            if ids.is_empty() {
+                operation
+            } else {
+                format!("{operation} {}", ids.join(", "))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+impl Journal {
+    pub fn new(state_dir: &Path) -> Self {
+        Self {
+            path: state_dir.join(JOURNAL_FILE),
+            batch: Mutex::new(None),
+            keep: KEEP_BATCHES,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Makes the next recorded entry start a new batch.
+    pub fn begin_batch(&self) {
+        *self.batch.lock().expect("journal batch lock poisoned") = None;
+    }
+
+    /// Appends an entry to the current batch. Changes that left a file as it
+    /// was are dropped, and an entry without changes is not written at all.
+    pub fn record(
+        &self,
+        operation: Operation,
+        task: Option<&str>,
+        changes: Vec<FileChange>,
+    ) -> Result<(), AppError> {
+        let changes = changes
+            .into_iter()
+            .filter(|change| change.before != change.after)
+            .map(|change| {
+                Ok(FileChange {
+                    path: std::path::absolute(&change.path)?,
+                    ..change
+                })
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let (batch, started) = {
+            let mut current = self.batch.lock().expect("journal batch lock poisoned");
+            let started = current.is_none();
+            (current.get_or_insert_with(new_batch_id).clone(), started)
+        };
+        if started {
+            self.prune()?;
+        }
+        let entry = JournalEntry {
+            version: ENTRY_VERSION,
+            batch,
+            at: Utc::now(),
+            operation,
+            task: task.map(str::to_string),
+            changes,
+        };
+        let mut line = serde_json::to_string(&entry).map_err(|error| {
+            AppError::message(format!("failed to serialize journal entry: {error}"))
+        })?;
+        line.push('\n');
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    /// Drops the oldest batches so that, with the one about to start, at most
+    /// `keep` remain. Undo and redo entries whose batch was dropped are left
+    /// in place; they no longer affect the undo and redo stacks. Lines that
+    /// fail to parse are dropped with the rewrite.
+    fn prune(&self) -> Result<(), AppError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(error) => return Err(error.into()),
+        };
+
+        #[derive(Deserialize)]
+        struct BatchId {
+            batch: String,
+        }
+        let lines = contents
+            .lines()
+            .map(|line| {
+                let batch = serde_json::from_str::<BatchId>(line).ok()?.batch;
+                Some((line, batch))
+            })
+            .collect::<Vec<_>>();
+        let mut kept: Vec<&str> = Vec::new();
+        for (_, batch) in lines.iter().rev().flatten() {
+            if !kept.contains(&batch.as_str()) {
+                kept.push(batch);
+            }
+        }
+        let keep = self.keep.saturating_sub(1);
+        if kept.len() <= keep {
+            return Ok(());
+        }
+        kept.truncate(keep);
+
+        let mut pruned = String::new();
+        for (line, _) in lines
+            .iter()
+            .flatten()
+            .filter(|(_, batch)| kept.contains(&batch.as_str()))
+        {
+            pruned.push_str(line);
+            pruned.push('\n');
+        }
+        atomic::write(&self.path, pruned)?;
+        Ok(())
+    }
+
+    /// Reads every entry in order. Lines that fail to parse, such as one cut
+    /// short by a crash, are skipped with a warning.
+    pub fn entries(&self) -> Result<Vec<JournalEntry>, AppError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut entries = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<JournalEntry>(line) {
+                Ok(entry) => entries.push(entry),
+                Err(error) => eprintln!(
+                    "Warning: skipping malformed journal line {} in {}: {error}",
+                    index + 1,
+                    self.path.display()
+                ),
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Every batch that changed something, oldest first, with whether it is
+    /// currently applied or undone.
+    pub fn batches(&self) -> Result<Vec<Batch>, AppError> {
+        let mut batches: Vec<Batch> = Vec::new();
+        for entry in self.entries()? {
+            match batches.iter_mut().find(|batch| batch.id == entry.batch) {
+                Some(batch) => batch.entries.push(entry),
+                None => batches.push(Batch {
+                    number: batches.len() + 1,
+                    id: entry.batch.clone(),
+                    entries: vec![entry],
+                    state: BatchState::Applied,
+                }),
+            }
+        }
+        batches.retain(|batch| !batch.net_changes().is_empty());
+
+        let (_, undone) = stacks(&batches);
+        for index in undone {
+            batches[index].state = BatchState::Undone;
+        }
+        Ok(batches)
+    }
+
+    /// Reverts the most recent batch that is still applied and returns it.
+    pub fn undo(&self) -> Result<Batch, AppError> {
+        let batches = self.batches()?;
+        let (applied, _) = stacks(&batches);
+        let Some(&index) = applied.last() else {
+            return Err(AppError::usage("nothing to undo"));
+        };
+        let batch = &batches[index];
+        let reverted = batch
+            .net_changes()
+            .into_iter()
+            .map(|change| FileChange {
+                path: change.path,
+                before: change.after,
+                after: change.before,
+            })
+            .collect::<Vec<_>>();
+        self.replay(
+            Operation::Undo {
+                batch: batch.id.clone(),
+            },
+            &reverted,
+            || format!("cannot undo {}", describe(batch, &batches)),
+        )?;
+        Ok(batch.clone())
+    }
+
+    /// Reapplies the most recently undone batch and returns it.
+    pub fn redo(&self) -> Result<Batch, AppError> {
+        let batches = self.batches()?;
+        let (_, undone) = stacks(&batches);
+        let Some(&index) = undone.last() else {
+            return Err(AppError::usage("nothing to redo"));
+        };
+        let batch = &batches[index];
+        self.replay(
+            Operation::Redo {
+                batch: batch.id.clone(),
+            },
+            &batch.net_changes(),
+            || format!("cannot redo {}", describe(batch, &batches)),
+        )?;
+        Ok(batch.clone())
+    }
+
+    /// Writes each change's `after` contents, first checking that every file
+    /// still holds its `before` contents so edits made since are never
+    /// overwritten. The replay is recorded as a batch of its own.
+    fn replay(
+        &self,
+        operation: Operation,
+        changes: &[FileChange],
+        context: impl Fn() -> String,
+    ) -> Result<(), AppError> {
+        for change in changes {
+            if read_contents(&change.path)? != change.before {
+                return Err(AppError::usage(format!(
+                    "{}: {} has changed since",
+                    context(),
+                    change.path.display()
+                )));
+            }
+        }
+
+        for (index, change) in changes.iter().enumerate() {
+            if let Err(error) = write_contents(&change.path, change.after.as_deref()) {
+                for written in changes[..index].iter().rev() {
+                    let _ = write_contents(&written.path, written.before.as_deref());
+                }
+                return Err(AppError::message(format!("{}: {error}", context())));
+            }
+        }
+
+        self.begin_batch();
+        let result = self.record(operation, None, changes.to_vec());
+        self.begin_batch();
+        result
+    }
+}
+
+/// Replays undo and redo over the batches, returning the indexes of batches
+/// still applied and of those undone, each with the most recent last. A new
+/// change clears the undone stack, as in an editor.
+fn stacks(batches: &[Batch]) -> (Vec<usize>, Vec<usize>) {
+    let mut applied: Vec<usize> = Vec::new();
+    let mut undone: Vec<usize> = Vec::new();
+    for (index, batch) in batches.iter().enumerate() {
+        match &batch.entries[0].operation {
+            Operation::Undo { batch: target } => {
+                if let Some(position) = applied.iter().rposition(|&i| batches[i].id == *target) {
+                    undone.push(applied.remove(position));
+                }
+            }
+            Operation::Redo { batch: target } => {
+                if let Some(position) = undone.iter().rposition(|&i| batches[i].id == *target) {
+                    applied.push(undone.remove(position));
+                }
+            }
+            _ => {
+                applied.push(index);
+                undone.clear();
+            }
+        }
+    }
+    (applied, undone)
+}
+
+fn new_batch_id() -> String {
+    format!(
+        "{}-{}-{}",
+        Utc::now().timestamp_micros(),
+        std::process::id(),
+        BATCH_COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+/// Reads a file's contents, or `None` if it does not exist.
+pub fn read_contents(path: &Path) -> Result<Option<String>, AppError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Writes `contents` to `path`, or removes the file when it is `None`.
+fn write_contents(path: &Path, contents: Option<&str>) -> Result<(), AppError> {
+    match contents {
+        Some(contents) => {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            atomic::write(path, contents)?;
+        }
+        None => atomic::remove(path)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BatchState, FileChange, Journal, Operation, describe};
+    use crate::domain::task::Queue;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn change(path: &std::path::Path, before: Option<&str>, after: Option<&str>) -> FileChange {
+        FileChange {
+            path: path.to_path_buf(),
+            before: before.map(str::to_string),
+            after: after.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn undo_and_redo_replay_batches_in_order() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let journal = Journal::new(&temp.path().join(".tqs"));
+        let file = temp.path().join("task.md");
+
+        fs::write(&file, "one").expect("file should be written");
+        journal
+            .record(
+                Operation::Create,
+                Some("t1"),
+                vec![change(&file, None, Some("one"))],
+            )
+            .expect("change should be recorded");
+        journal.begin_batch();
+        fs::write(&file, "two").expect("file should be written");
+        journal
+            .record(
+                Operation::Move { queue: Queue::Next },
+                Some("t1"),
+                vec![change(&file, Some("one"), Some("two"))],
+            )
+            .expect("change should be recorded");
+
+        let undone = journal.undo().expect("undo should succeed");
+        assert_eq!(undone.number, 2);
+        assert_eq!(fs::read_to_string(&file).expect("file should exist"), "one");
+        journal.undo().expect("undo should succeed");
+        assert!(!file.exists());
+        assert!(journal.undo().is_err());
+
+        journal.redo().expect("redo should succeed");
+        assert_eq!(fs::read_to_string(&file).expect("file should exist"), "one");
+        let batches = journal.batches().expect("journal should be readable");
+        assert_eq!(batches[0].state, BatchState::Applied);
+        assert_eq!(batches[1].state, BatchState::Undone);
+        assert_eq!(describe(&batches[1], &batches), "move to next t1");
+        assert_eq!(describe(&batches[4], &batches), "redo #1");
+    }
+
+    #[test]
+    fn undo_refuses_to_overwrite_later_edits() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let journal = Journal::new(&temp.path().join(".tqs"));
+        let file = temp.path().join("task.md");
+        fs::write(&file, "two").expect("file should be written");
+        journal
+            .record(
+                Operation::Edit,
+                Some("t1"),
+                vec![change(&file, Some("one"), Some("two"))],
+            )
+            .expect("change should be recorded");
+        fs::write(&file, "three").expect("file should be written");
+
+        let error = journal.undo().expect_err("undo should refuse");
+
+        assert!(error.to_string().contains("cannot undo edit t1"));
+        assert!(error.to_string().ends_with("has changed since"));
+        assert_eq!(
+            fs::read_to_string(&file).expect("file should exist"),
+            "three"
+        );
+    }
+
+    #[test]
+    fn new_changes_clear_redo_and_rolled_back_batches_are_hidden() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let journal = Journal::new(&temp.path().join(".tqs"));
+        let file = temp.path().join("task.md");
+        fs::write(&file, "two").expect("file should be written");
+        journal
+            .record(
+                Operation::Edit,
+                Some("t1"),
+                vec![change(&file, Some("one"), Some("two"))],
+            )
+            .expect("change should be recorded");
+        journal.undo().expect("undo should succeed");
+
+        journal.begin_batch();
+        let other = temp.path().join("other.md");
+        journal
+            .record(
+                Operation::Create,
+                Some("t2"),
+                vec![change(&other, None, Some("x"))],
+            )
+            .expect("change should be recorded");
+        journal
+            .record(
+                Operation::Rollback,
+                None,
+                vec![change(&other, Some("x"), None)],
+            )
+            .expect("change should be recorded");
+        assert_eq!(
+            journal.batches().expect("journal should be readable").len(),
+            2
+        );
+
+        journal.begin_batch();
+        journal
+            .record(
+                Operation::Create,
+                Some("t3"),
+                vec![change(&other, None, Some("y"))],
+            )
+            .expect("change should be recorded");
+        assert!(journal.redo().is_err());
+    }
+
+    #[test]
+    fn only_the_newest_batches_are_kept() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let journal = Journal {
+            keep: 3,
+            ..Journal::new(&temp.path().join(".tqs"))
+        };
+        let file = temp.path().join("task.md");
+        let mut before = None;
+        for step in ["one", "two", "three", "four", "five"] {
+            journal.begin_batch();
+            fs::write(&file, step).expect("file should be written");
+            journal
+                .record(
+                    Operation::Edit,
+                    Some("t1"),
+                    vec![change(&file, before, Some(step))],
+                )
+                .expect("change should be recorded");
+            before = Some(step);
+        }
+
+        let batches = journal.batches().expect("journal should be readable");
+        assert_eq!(batches.len(), 3);
+        assert_eq!(
+            batches[0].entries[0].changes[0].after.as_deref(),
+            Some("three")
+        );
+        let lines = fs::read_to_string(journal.path())
+            .expect("journal should exist")
+            .lines()
+            .count();
+        assert_eq!(lines, 3);
+
+        journal.undo().expect("undo should succeed");
+        journal.undo().expect("undo should succeed");
+        assert_eq!(
+            fs::read_to_string(&file).expect("file should exist"),
+            "three"
+        );
+        let batches = journal.batches().expect("journal should be readable");
+        assert_eq!(batches.len(), 3);
+        assert_eq!(describe(&batches[2], &batches), "undo an earlier change");
+        assert!(journal.undo().is_err());
+    }
+}
@@ -0,0 +1,532 @@
+use std::{
+    fs::{self, File, Metadata, OpenOptions, TryLockError},
+    io::{ErrorKind, Write},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    process,
+    sync::{Condvar, Mutex, PoisonError},
+    thread::{self, ThreadId},
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::app::app_error::AppError;
+use crate::storage::id_state::stable_path_hash;
+
+pub const LOCKS_DIR: &str = "locks";
+/// How long to wait for a lock another process holds.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+const LOCK_RETRY_DELAY: Duration = Duration::from_millis(20);
+/// Locks are only held for the length of one change, so one this old was
+/// left behind by a process that crashed or was killed.
+pub const STALE_LOCK_AGE: Duration = Duration::from_secs(60);
+
+/// An advisory lock on a file holding the owner's pid and hostname. The
+/// operating system lock (`flock` on unix) is what excludes other holders,
+/// and it is released when its owner exits, however it exits; the file is
+/// removed when the lock is dropped, and its contents only say who held it.
+pub(crate) struct FileLock {
+    path: PathBuf,
+    file: File,
+}
+
+impl FileLock {
+    /// Waits up to `LOCK_TIMEOUT` to lock the file; `Ok(None)` when another
+    /// process still holds it by then. A file left behind by a process that
+    /// died is not locked by anyone, so it is simply taken over.
+    pub(crate) fn acquire(path: &Path) -> Result<Option<Self>, AppError> {
+        let parent = path
+            .parent()
+            .ok_or_else(|| AppError::message("lock path is missing a parent directory"))?;
+        fs::create_dir_all(parent)?;
+
+        let deadline = Instant::now() + LOCK_TIMEOUT;
+        loop {
+            if let Some(lock) = Self::try_acquire(path)? {
+                return Ok(Some(lock));
+            }
+            if Instant::now() >= deadline {
+                return Ok(None);
+            }
+            thread::sleep(LOCK_RETRY_DELAY);
+        }
+    }
+
+    /// Locks the file at `path` if nobody holds it, without waiting.
+    fn try_acquire(path: &Path) -> Result<Option<Self>, AppError> {
+        loop {
+            let mut file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(path)?;
+            match file.try_lock() {
+                Ok(()) => {}
+                Err(TryLockError::WouldBlock) => return Ok(None),
+                Err(TryLockError::Error(error)) => return Err(AppError::Io(error)),
+            }
+            // The previous holder removes the file just before unlocking it,
+            // so the file locked here may no longer be the one at `path`.
+            if !names_file(path, &file)? {
+                continue;
+            }
+            file.set_len(0)?;
+            let _ = write!(file, "{}", Owner::current());
+            return Ok(Some(Self {
+                path: path.to_path_buf(),
+                file,
+            }));
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        // Removed while still locked, and only if it is still this file, so
+        // nobody can lock a file at this path that is about to disappear.
+        if names_file(&self.path, &self.file).unwrap_or(false) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Removes a lock file nobody holds, returning whether it was removed; one
+/// that was locked again since it was found stale is left alone.
+pub fn remove_stale(path: &Path) -> Result<bool, AppError> {
+    Ok(FileLock::try_acquire(path)?.is_some())
+}
+
+/// Whether `path` currently names the open `file`.
+fn names_file(path: &Path, file: &File) -> Result<bool, AppError> {
+    match fs::metadata(path) {
+        Ok(metadata) => Ok(same_file(&metadata, &file.metadata()?)),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(false),
+        Err(error) => Err(AppError::Io(error)),
+    }
+}
+
+#[cfg(unix)]
+fn same_file(left: &Metadata, right: &Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    left.dev() == right.dev() && left.ino() == right.ino()
+}
+
+/// Elsewhere an open file cannot be replaced under its path.
+#[cfg(not(unix))]
+fn same_file(_left: &Metadata, _right: &Metadata) -> bool {
+    true
+}
+
+/// Whether a process, this one included, holds the lock on `path`.
+fn is_held(path: &Path) -> bool {
+    File::open(path).is_ok_and(|file| matches!(file.try_lock(), Err(TryLockError::WouldBlock)))
+}
+
+/// The process a lock file says created it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Owner {
+    pid: Option<u32>,
+    host: Option<String>,
+}
+
+impl Owner {
+    fn current() -> Self {
+        Self {
+            pid: Some(process::id()),
+            host: hostname(),
+        }
+    }
+
+    /// Reads the pid from the first line and the hostname from the second;
+    /// older lock files have only the pid.
+    fn parse(contents: &str) -> Self {
+        let mut lines = contents.lines().map(str::trim);
+        Self {
+            pid: lines.next().and_then(|pid| pid.parse().ok()),
+            host: lines
+                .next()
+                .filter(|host| !host.is_empty())
+                .map(str::to_string),
+        }
+    }
+
+    /// Whether the owner is known to be gone: a process on this host that no
+    /// longer exists. An owner on another host, or one the file does not
+    /// name, is only given up on after [`STALE_LOCK_AGE`].
+    fn is_gone(&self, age: Duration) -> bool {
+        match (self.pid, &self.host) {
+            (Some(pid), Some(host)) if Some(host) == hostname().as_ref() => !process_alive(pid),
+            _ => age >= STALE_LOCK_AGE,
+        }
+    }
+}
+
+impl std::fmt::Display for Owner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(pid) = self.pid {
+            writeln!(f, "{pid}")?;
+        }
+        if let Some(host) = &self.host {
+            writeln!(f, "{host}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn hostname() -> Option<String> {
+    let mut buffer = [0u8; 256];
+    if unsafe { libc::gethostname(buffer.as_mut_ptr().cast(), buffer.len()) } != 0 {
+        return None;
+    }
+    let len = buffer.iter().position(|byte| *byte == 0)?;
+    String::from_utf8(buffer[..len].to_vec())
+        .ok()
+        .filter(|host| !host.is_empty())
+}
+
+#[cfg(not(unix))]
+fn hostname() -> Option<String> {
+    None
+}
+
+/// Signal 0 checks that a process exists without disturbing it; one owned
+/// by another user refuses it but is still alive.
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    let signalled = unsafe { libc::kill(pid, 0) } == 0;
+    signalled || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+fn process_alive(_pid: u32) -> bool {
+    true
+}
+
+/// The lock serializing changes to one tasks root across `tqs` processes,
+/// at `<state_dir>/locks/<root hash>.lock`.
+///
+/// It is reentrant within the thread holding it: an operation that calls
+/// another while holding it keeps the one lock file, which is removed when
+/// the outermost guard is dropped. Other threads of the same process wait
+/// for it like other processes do.
+pub struct RepoLock {
+    path: PathBuf,
+    held: Mutex<Held>,
+    released: Condvar,
+}
+
+#[derive(Default)]
+struct Held {
+    /// The thread holding the lock and how many of its guards are alive.
+    owner: Option<(ThreadId, usize)>,
+    file: Option<FileLock>,
+}
+
+impl RepoLock {
+    pub fn new(state_dir: &Path, tasks_root: &Path) -> Self {
+        let root = tasks_root
+            .canonicalize()
+            .unwrap_or_else(|_| tasks_root.to_path_buf());
+        Self {
+            path: state_dir
+                .join(LOCKS_DIR)
+                .join(format!("{}.lock", stable_path_hash(&root))),
+            held: Mutex::new(Held::default()),
+            released: Condvar::new(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Takes the lock until the returned guard is dropped, failing with
+    /// [`AppError::RepositoryBusy`] when another process or thread keeps it
+    /// longer than the timeout.
+    pub fn acquire(&self) -> Result<RepoLockGuard<'_>, AppError> {
+        let current = thread::current().id();
+        let deadline = Instant::now() + LOCK_TIMEOUT;
+        let mut held = self.held.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            match held.owner {
+                Some((owner, depth)) if owner == current => {
+                    held.owner = Some((owner, depth + 1));
+                    break;
+                }
+                Some(_) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        return Err(self.busy());
+                    }
+                    held = self
+                        .released
+                        .wait_timeout(held, remaining)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0;
+                }
+                None => {
+                    let file = FileLock::acquire(&self.path)?.ok_or_else(|| self.busy())?;
+                    held.file = Some(file);
+                    held.owner = Some((current, 1));
+                    break;
+                }
+            }
+        }
+        Ok(RepoLockGuard {
+            lock: self,
+            not_send: PhantomData,
+        })
+    }
+
+    fn busy(&self) -> AppError {
+        AppError::repository_busy(self.path.to_string_lossy().to_string())
+    }
+}
+
+/// Releases one hold on a [`RepoLock`] when dropped. It stays on the thread
+/// that took it, since that thread is the one recorded as the owner.
+pub struct RepoLockGuard<'a> {
+    lock: &'a RepoLock,
+    not_send: PhantomData<*const ()>,
+}
+
+impl Drop for RepoLockGuard<'_> {
+    fn drop(&mut self) {
+        let mut held = self
+            .lock
+            .held
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        match held.owner {
+            Some((owner, depth)) if depth > 1 => held.owner = Some((owner, depth - 1)),
+            _ => {
+                held.owner = None;
+                held.file = None;
+                self.lock.released.notify_all();
+            }
+        }
+    }
+}
+
+/// A lock file nobody holds whose owner is gone: a dead process on this
+/// host, or any owner once the file has outlived [`STALE_LOCK_AGE`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleLock {
+    pub path: PathBuf,
+    /// The process that created it, when the file says.
+    pub pid: Option<u32>,
+    /// The host that process ran on, when the file says.
+    pub host: Option<String>,
+    pub age: Duration,
+}
+
+/// Stale `.lock` files directly inside `dir`; a missing directory has none.
+pub fn stale_locks(dir: &Path, now: SystemTime) -> Result<Vec<StaleLock>, AppError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if matches!(error.kind(), ErrorKind::NotFound | ErrorKind::NotADirectory) => {
+            return Ok(Vec::new());
+        }
+        Err(error) => return Err(AppError::Io(error)),
+    };
+
+    let mut stale = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("lock") {
+            continue;
+        }
+        stale.extend(read_stale(&path, now));
+    }
+    stale.sort_by(|left, right| left.path.cmp(&right.path));
+    Ok(stale)
+}
+
+/// The lock file at `path` when it is stale; one that someone holds, or that
+/// was released meanwhile, is not.
+fn read_stale(path: &Path, now: SystemTime) -> Option<StaleLock> {
+    if is_held(path) {
+        return None;
+    }
+    let modified = fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()?;
+    let age = now.duration_since(modified).unwrap_or_default();
+    let owner = Owner::parse(&fs::read_to_string(path).ok()?);
+    owner.is_gone(age).then(|| StaleLock {
+        path: path.to_path_buf(),
+        pid: owner.pid,
+        host: owner.host,
+        age,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FileLock, Owner, RepoLock, STALE_LOCK_AGE, hostname, remove_stale, stale_locks};
+    use crate::app::app_error::AppError;
+    use std::{
+        fs, process,
+        sync::{
+            Barrier,
+            atomic::{AtomicUsize, Ordering},
+        },
+        thread,
+        time::{Duration, Instant, SystemTime},
+    };
+    use tempfile::TempDir;
+
+    /// The pid of a process that has already exited.
+    fn dead_pid() -> u32 {
+        let mut child = process::Command::new("true")
+            .spawn()
+            .expect("child should start");
+        child.wait().expect("child should exit");
+        child.id()
+    }
+
+    #[test]
+    fn repo_lock_is_reentrant_and_released_with_the_outermost_guard() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let lock = RepoLock::new(&temp.path().join(".tqs"), temp.path());
+
+        let outer = lock.acquire().expect("lock should be free");
+        let inner = lock.acquire().expect("lock should be reentrant");
+        drop(inner);
+        assert!(lock.path().exists());
+
+        drop(outer);
+        assert!(!lock.path().exists());
+    }
+
+    #[test]
+    fn repo_lock_makes_other_threads_wait_for_the_holder() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let lock = RepoLock::new(&temp.path().join(".tqs"), temp.path());
+        let guard = lock.acquire().expect("lock should be free");
+
+        thread::scope(|scope| {
+            let waiter = scope.spawn(|| {
+                let _guard = lock.acquire().expect("lock should be released");
+                Instant::now()
+            });
+            thread::sleep(Duration::from_millis(100));
+            let released = Instant::now();
+            drop(guard);
+            assert!(waiter.join().expect("waiter should finish") >= released);
+        });
+        assert!(!lock.path().exists());
+    }
+
+    #[test]
+    fn repo_lock_held_elsewhere_reports_the_repository_busy() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let lock = RepoLock::new(&temp.path().join(".tqs"), temp.path());
+        let _other = FileLock::acquire(lock.path())
+            .expect("lock should be created")
+            .expect("lock should be free");
+
+        let err = lock.acquire().err().expect("a held lock should time out");
+
+        assert!(matches!(err, AppError::RepositoryBusy { .. }));
+        assert!(err.to_string().starts_with("repository busy"));
+    }
+
+    #[test]
+    fn lock_left_by_a_dead_process_is_taken_over() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let path = temp.path().join("repo.lock");
+        let owner = Owner {
+            pid: Some(dead_pid()),
+            host: hostname(),
+        };
+        fs::write(&path, owner.to_string()).expect("lock should be written");
+
+        let lock = FileLock::acquire(&path)
+            .expect("lock should be created")
+            .expect("a dead owner's lock should be broken");
+
+        assert_eq!(
+            Owner::parse(&fs::read_to_string(&path).expect("lock should exist")),
+            Owner::current()
+        );
+        drop(lock);
+    }
+
+    #[test]
+    fn racing_takeovers_of_a_stale_lock_leave_one_holder_at_a_time() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let path = temp.path().join("repo.lock");
+        let owner = Owner {
+            pid: Some(dead_pid()),
+            host: hostname(),
+        };
+        fs::write(&path, owner.to_string()).expect("lock should be written");
+
+        let start = Barrier::new(8);
+        let holders = AtomicUsize::new(0);
+        let most = AtomicUsize::new(0);
+        thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    start.wait();
+                    let lock = FileLock::acquire(&path)
+                        .expect("lock should be created")
+                        .expect("every racer should get the lock in turn");
+                    let now = holders.fetch_add(1, Ordering::SeqCst) + 1;
+                    most.fetch_max(now, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(10));
+                    holders.fetch_sub(1, Ordering::SeqCst);
+                    drop(lock);
+                });
+            }
+        });
+
+        assert_eq!(most.load(Ordering::SeqCst), 1);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn removing_a_stale_lock_leaves_a_held_one_alone() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let path = temp.path().join("repo.lock");
+        let held = FileLock::acquire(&path)
+            .expect("lock should be created")
+            .expect("lock should be free");
+
+        assert!(!remove_stale(&path).expect("lock should be checked"));
+        assert!(path.exists());
+
+        drop(held);
+        fs::write(&path, "4242\nelsewhere\n").expect("lock should be written");
+        assert!(remove_stale(&path).expect("lock should be checked"));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn stale_locks_skip_live_owners_and_fresh_foreign_ones() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let _ours = FileLock::acquire(&temp.path().join("ours.lock"))
+            .expect("lock should be created")
+            .expect("lock should be free");
+        fs::write(temp.path().join("foreign.lock"), "4242\nelsewhere\n")
+            .expect("lock should be written");
+        fs::write(temp.path().join("notes.txt"), "not a lock").expect("file should be written");
+
+        assert!(
+            stale_locks(temp.path(), SystemTime::now())
+                .expect("locks should be listed")
+                .is_empty()
+        );
+
+        let later = SystemTime::now() + STALE_LOCK_AGE;
+        let stale = stale_locks(temp.path(), later).expect("locks should be listed");
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].pid, Some(4242));
+        assert_eq!(stale[0].host.as_deref(), Some("elsewhere"));
+    }
+}
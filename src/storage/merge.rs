@@ -0,0 +1,329 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::storage::format::{frontmatter_entries, parse_task_file, parse_task_markdown};
+
+/// Opens our side of a conflict: the version tqs was writing.
+pub const OURS_MARKER: &str = "<<<<<<< tqs";
+pub const SEPARATOR: &str = "=======";
+/// Closes the other side of a conflict: the version found on disk.
+pub const THEIRS_MARKER: &str = ">>>>>>> on disk";
+
+/// Two edits of one task file merged against their common base.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Merge {
+    pub content: String,
+    /// Frontmatter keys both sides changed differently, then `body` if body
+    /// lines did; empty when the merge is clean.
+    pub conflicts: Vec<String>,
+}
+
+impl Merge {
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Merges two edits of one task file made from a common `base`: entry by
+/// entry through the frontmatter, then line by line through the body.
+///
+/// An entry changed on one side takes that side's text, and entries are
+/// compared by value, so reformatting alone is not a change. `updated_at`
+/// changed on both sides takes the later time. Any other entry changed on
+/// both keeps our text and lists both versions between conflict markers at
+/// the end of the body; conflicting body lines are marked where they are.
+///
+/// Returns `None` when a version is not a task file whose frontmatter splits
+/// into entries, or when the merged file is not a valid task.
+pub fn merge_task_files(base: &str, ours: &str, theirs: &str) -> Option<Merge> {
+    let base = TaskFile::parse(base)?;
+    let ours = TaskFile::parse(ours)?;
+    let theirs = TaskFile::parse(theirs)?;
+
+    let mut keys = ours.keys();
+    for key in theirs.keys() {
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    let mut entries = Vec::new();
+    let mut conflicts = Vec::new();
+    let (mut ours_conflicting, mut theirs_conflicting) = (Vec::new(), Vec::new());
+    for key in keys {
+        let (ours_entry, theirs_entry) = (ours.entry(key), theirs.entry(key));
+        match pick(base.entry(key), ours_entry, theirs_entry) {
+            Some(entry) => entries.extend(entry.map(|entry| entry.raw.as_str())),
+            None if key == "updated_at" => entries.push(later(ours_entry?, theirs_entry?)?),
+            None => {
+                conflicts.push(key.to_string());
+                entries.extend(ours_entry.map(|entry| entry.raw.as_str()));
+                ours_conflicting.extend(ours_entry.map(|entry| entry.raw.as_str()));
+                theirs_conflicting.extend(theirs_entry.map(|entry| entry.raw.as_str()));
+            }
+        }
+    }
+
+    let (mut body, body_conflicted) = merge_lines(&base.body, &ours.body, &theirs.body);
+    if body_conflicted {
+        conflicts.push("body".to_string());
+    }
+    if !ours_conflicting.is_empty() || !theirs_conflicting.is_empty() {
+        if !body.is_empty() && !body.ends_with('\n') {
+            body.push('\n');
+        }
+        body.push_str(&conflict_block(&ours_conflicting, &theirs_conflicting));
+    }
+
+    let mut content = format!("---\n{}\n---", entries.join("\n"));
+    if !body.is_empty() {
+        content.push('\n');
+        content.push_str(&body);
+    }
+    parse_task_markdown(&content).ok()?;
+    Some(Merge { content, conflicts })
+}
+
+/// Merges the lines of `ours` and `theirs` against `base` and reports whether
+/// any conflicted. Lines kept by all three versions anchor the merge; between
+/// anchors, a side that changed wins, and two different changes are both
+/// kept between conflict markers.
+fn merge_lines(base: &str, ours: &str, theirs: &str) -> (String, bool) {
+    if ours == theirs || theirs == base {
+        return (ours.to_string(), false);
+    }
+    if ours == base {
+        return (theirs.to_string(), false);
+    }
+
+    let base = base.split('\n').collect::<Vec<_>>();
+    let ours = ours.split('\n').collect::<Vec<_>>();
+    let theirs = theirs.split('\n').collect::<Vec<_>>();
+    let ours_matches = matches(&base, &ours);
+    let theirs_matches = matches(&base, &theirs);
+
+    let mut merged = Vec::new();
+    let mut conflicted = false;
+    let (mut base_at, mut ours_at, mut theirs_at) = (0, 0, 0);
+    loop {
+        let anchor = (base_at..base.len())
+            .find_map(|line| Some((line, ours_matches[line]?, theirs_matches[line]?)));
+        let (base_end, ours_end, theirs_end) =
+            anchor.unwrap_or((base.len(), ours.len(), theirs.len()));
+
+        let chunk = pick(
+            Some(&base[base_at..base_end]),
+            Some(&ours[ours_at..ours_end]),
+            Some(&theirs[theirs_at..theirs_end]),
+        );
+        match chunk {
+            Some(lines) => merged.extend(lines.into_iter().flatten().copied()),
+            None => {
+                conflicted = true;
+                merged.push(OURS_MARKER);
+                merged.extend(&ours[ours_at..ours_end]);
+                merged.push(SEPARATOR);
+                merged.extend(&theirs[theirs_at..theirs_end]);
+                merged.push(THEIRS_MARKER);
+            }
+        }
+
+        let Some((base_line, ours_line, theirs_line)) = anchor else {
+            break;
+        };
+        merged.push(base[base_line]);
+        (base_at, ours_at, theirs_at) = (base_line + 1, ours_line + 1, theirs_line + 1);
+    }
+    (merged.join("\n"), conflicted)
+}
+
+/// For each line of `base`, the line of `other` it pairs with in a longest
+/// common subsequence of the two.
+fn matches(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut lengths = vec![vec![0u32; other.len() + 1]; base.len() + 1];
+    for i in (0..base.len()).rev() {
+        for j in (0..other.len()).rev() {
+            lengths[i][j] = if base[i] == other[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut matched = vec![None; base.len()];
+    let (mut i, mut j) = (0, 0);
+    while i < base.len() && j < other.len() {
+        if base[i] == other[j] {
+            matched[i] = Some(j);
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matched
+}
+
+fn conflict_block(ours: &[&str], theirs: &[&str]) -> String {
+    let mut lines = vec![OURS_MARKER];
+    lines.extend(ours);
+    lines.push(SEPARATOR);
+    lines.extend(theirs);
+    lines.push(THEIRS_MARKER);
+    lines.join("\n")
+}
+
+/// The side that changed, or `None` when both changed differently.
+fn pick<T: PartialEq>(base: Option<T>, ours: Option<T>, theirs: Option<T>) -> Option<Option<T>> {
+    if ours == theirs || theirs == base {
+        Some(ours)
+    } else if ours == base {
+        Some(theirs)
+    } else {
+        None
+    }
+}
+
+fn later<'a>(ours: &'a Entry, theirs: &'a Entry) -> Option<&'a str> {
+    let at = |entry: &Entry| -> Option<DateTime<Utc>> {
+        let parsed: BTreeMap<String, DateTime<Utc>> = serde_yaml::from_str(&entry.raw).ok()?;
+        parsed.into_values().next()
+    };
+    Some(if at(ours)? >= at(theirs)? {
+        &ours.raw
+    } else {
+        &theirs.raw
+    })
+}
+
+/// One top-level frontmatter entry: its original text and parsed value.
+struct Entry {
+    key: String,
+    raw: String,
+    value: serde_yaml::Value,
+}
+
+/// Entries are the same when their values are, however they are written.
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+struct TaskFile {
+    entries: Vec<Entry>,
+    body: String,
+}
+
+impl TaskFile {
+    fn parse(content: &str) -> Option<Self> {
+        let parsed = parse_task_file(content).ok()?;
+        let entries = frontmatter_entries(&parsed.frontmatter)?
+            .into_iter()
+            .map(|(key, raw)| {
+                let mapping: serde_yaml::Mapping = serde_yaml::from_str(&raw).ok()?;
+                let (_, value) = mapping.into_iter().next()?;
+                Some(Entry { key, raw, value })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            entries,
+            body: parsed.body,
+        })
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.entries
+            .iter()
+            .map(|entry| entry.key.as_str())
+            .collect()
+    }
+
+    fn entry(&self, key: &str) -> Option<&Entry> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::merge_task_files;
+
+    const BASE: &str = "---\nid: abc\ntitle: Ship it\nqueue: inbox\ncreated_at: 2026-03-09T10:34:12Z\nupdated_at: 2026-03-09T10:34:12Z\n---\nNotes";
+
+    #[test]
+    fn merges_changes_to_different_fields() {
+        let ours = BASE.replace("queue: inbox", "queue: now").replace(
+            "updated_at: 2026-03-09T10:34:12Z",
+            "updated_at: 2026-03-10T08:00:00Z",
+        );
+        let theirs = BASE
+            .replace("title: Ship it", "title: Ship it today\nproject: launch")
+            .replace(
+                "updated_at: 2026-03-09T10:34:12Z",
+                "updated_at: 2026-03-11T08:00:00Z",
+            );
+
+        let merged = merge_task_files(BASE, &ours, &theirs).expect("fields should merge");
+
+        assert!(merged.is_clean());
+        assert_eq!(
+            merged.content,
+            "---\nid: abc\ntitle: Ship it today\nqueue: now\ncreated_at: 2026-03-09T10:34:12Z\nupdated_at: 2026-03-11T08:00:00Z\nproject: launch\n---\nNotes"
+        );
+    }
+
+    #[test]
+    fn reformatted_entries_do_not_count_as_changes() {
+        let base = BASE.replace("---\nNotes", "tags:\n- ops\n- web\n---\nNotes");
+        let ours = base.replace("title: Ship it", "title: Ours");
+        let theirs = BASE.replace("---\nNotes", "tags: [ops, web]\n---\nNotes");
+
+        let merged = merge_task_files(&base, &ours, &theirs).expect("files should merge");
+
+        assert!(merged.is_clean());
+        assert!(merged.content.contains("title: Ours"));
+    }
+
+    #[test]
+    fn merges_body_lines_and_marks_overlapping_ones() {
+        let base = BASE.replace("Notes", "# Ship it\n\n- one\n- two\n- three");
+        let ours = base.replace("- one", "- one, done");
+        let theirs = base.replace("- three", "- three\n- four");
+
+        let merged = merge_task_files(&base, &ours, &theirs).expect("bodies should merge");
+        assert!(merged.is_clean());
+        assert!(
+            merged
+                .content
+                .ends_with("# Ship it\n\n- one, done\n- two\n- three\n- four")
+        );
+
+        let theirs = base.replace("- one", "- one, dropped");
+        let merged = merge_task_files(&base, &ours, &theirs).expect("bodies should merge");
+        assert_eq!(merged.conflicts, vec!["body"]);
+        assert!(merged.content.ends_with(
+            "# Ship it\n\n<<<<<<< tqs\n- one, done\n=======\n- one, dropped\n>>>>>>> on disk\n- two\n- three"
+        ));
+    }
+
+    #[test]
+    fn conflicting_fields_keep_ours_and_list_both_in_the_body() {
+        let retitled = |title: &str| BASE.replace("title: Ship it", &format!("title: {title}"));
+
+        let merged = merge_task_files(BASE, &retitled("Ours"), &retitled("Theirs"))
+            .expect("files should merge");
+
+        assert_eq!(merged.conflicts, vec!["title"]);
+        assert_eq!(
+            merged.content,
+            format!(
+                "{}\n<<<<<<< tqs\ntitle: Ours\n=======\ntitle: Theirs\n>>>>>>> on disk",
+                retitled("Ours")
+            )
+        );
+    }
+}
@@ -1,7 +1,15 @@
+pub mod archive;
+pub mod atomic;
 pub mod config;
 pub mod daily_notes;
 pub mod doctor;
 pub mod editor;
 pub mod format;
+pub mod git;
 pub mod id_state;
+pub mod index;
+pub mod journal;
+pub mod lock;
+pub mod merge;
 pub mod repo;
+pub mod trash;
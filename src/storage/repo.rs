@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     fs,
     path::{Path, PathBuf},
 };
@@ -8,11 +9,19 @@ use chrono::{DateTime, Utc};
 use crate::app::app_error::AppError;
 use crate::domain::{
     id::validate_user_id,
-    task::{Queue, Task},
+    rank,
+    task::{FileVersion, Queue, QueueTransition, Task},
 };
 use crate::storage::{
-    config::QueueDirs,
+    archive, atomic,
+    config::{GitConfig, QueueDirs},
     format::{parse_task_markdown, render_task_markdown},
+    git::Git,
+    index::TaskIndex,
+    journal::{self, Batch, FileChange, Journal, Operation, read_contents},
+    lock::{RepoLock, RepoLockGuard},
+    merge::{Merge, merge_task_files},
+    trash::Trash,
 };
 
 #[derive(Debug, Clone)]
@@ -24,30 +33,69 @@ pub struct StoredTask {
 pub struct TaskRepo {
     root: PathBuf,
     queue_dirs: QueueDirs,
+    state_dir: PathBuf,
+    trash: Trash,
+    journal: Journal,
+    index: TaskIndex,
+    lock: RepoLock,
+    git: Option<Git>,
 }
 
 impl TaskRepo {
+    /// Creates a repo whose trash, journal, and index live in `<root>/.tqs`; use
+    /// [`TaskRepo::with_state_dir`] when the config puts state elsewhere.
     pub fn new(root: PathBuf, queue_dirs: QueueDirs) -> Self {
-        Self { root, queue_dirs }
+        let state_dir = root.join(".tqs");
+        Self {
+            trash: Trash::new(&state_dir),
+            journal: Journal::new(&state_dir),
+            index: TaskIndex::new(&state_dir, &root),
+            lock: RepoLock::new(&state_dir, &root),
+            state_dir,
+            root,
+            queue_dirs,
+            git: None,
+        }
+    }
+
+    pub fn with_state_dir(mut self, state_dir: &Path) -> Self {
+        self.trash = Trash::new(state_dir);
+        self.journal = Journal::new(state_dir);
+        self.index = TaskIndex::new(state_dir, &self.root);
+        self.lock = RepoLock::new(state_dir, &self.root);
+        self.state_dir = state_dir.to_path_buf();
+        self
+    }
+
+    /// Commits every change to the task files, for a root kept in git.
+    pub fn with_git(mut self, config: &GitConfig) -> Self {
+        self.git = Some(Git::new(&self.root, config));
+        self
     }
 
     pub fn root(&self) -> &Path {
         &self.root
     }
 
+    pub fn queue_dirs(&self) -> &QueueDirs {
+        &self.queue_dirs
+    }
+
     pub fn queue_dir(&self, queue: Queue) -> PathBuf {
-        self.root.join(self.queue_dirs.dir_name(queue))
+        self.root.join(self.queue_dirs.dir_name(&queue))
     }
 
     pub fn task_path(&self, queue: Queue, id: &str) -> PathBuf {
         self.queue_dir(queue).join(format!("{id}.md"))
     }
 
+    /// Whether a live or archived task already uses `id`.
     pub fn id_exists(&self, id: &str) -> bool {
-        self.find_by_id(id).is_ok()
+        self.find_by_id(id).is_ok() || self.find_archived(id).is_ok()
     }
 
     pub fn create(&self, task: &Task) -> Result<PathBuf, AppError> {
+        let _lock = self.lock()?;
         validate_user_id(&task.id)?;
         let path = self.task_path(task.queue, &task.id);
         self.ensure_path_is_within_root(&path)?;
@@ -56,8 +104,17 @@ impl TaskRepo {
             return Err(AppError::usage(format!("id '{}' already exists", task.id)));
         }
 
-        fs::create_dir_all(self.queue_dir(task.queue))?;
-        fs::write(&path, render_task_markdown(task)?)?;
+        let content = render_task_markdown(task)?;
+        self.journaled(
+            Operation::Create,
+            &task.id,
+            std::slice::from_ref(&path),
+            || {
+                fs::create_dir_all(self.queue_dir(task.queue))?;
+                atomic::write(&path, content)?;
+                Ok(())
+            },
+        )?;
         Ok(path)
     }
 
@@ -65,13 +122,48 @@ impl TaskRepo {
         Ok(self.find_by_id(id)?.task)
     }
 
+    /// Looks for `<id>.md` in each queue directory rather than scanning them
+    /// all, since a task file is always named after its id. Archived tasks
+    /// are not included; see [`TaskRepo::find_archived`].
     pub fn find_by_id(&self, id: &str) -> Result<StoredTask, AppError> {
         validate_user_id(id)?;
-        let mut matches = self
-            .scan_all()?
+        let paths = self
+            .queue_dirs
+            .queues()
+            .into_iter()
+            .map(|queue| self.task_path(queue, id))
+            .collect::<Vec<_>>();
+        self.find_at(id, &paths)
+    }
+
+    /// Looks for `<id>.md` in each dated archive folder.
+    pub fn find_archived(&self, id: &str) -> Result<StoredTask, AppError> {
+        validate_user_id(id)?;
+        let paths = archive::archive_dirs(&self.queue_dir(Queue::Done))?
             .into_iter()
-            .filter(|stored| stored.task.id == id)
+            .map(|dir| dir.join(format!("{id}.md")))
             .collect::<Vec<_>>();
+        self.find_at(id, &paths)
+    }
+
+    fn find_at(&self, id: &str, paths: &[PathBuf]) -> Result<StoredTask, AppError> {
+        let mut matches = Vec::new();
+        for path in paths {
+            if !path.is_file() {
+                continue;
+            }
+            match self.read_indexed(path) {
+                Ok(task) => matches.push(StoredTask {
+                    task,
+                    path: path.clone(),
+                }),
+                Err(AppError::InvalidTaskFile { path, reason }) => {
+                    eprintln!("Warning: skipping malformed task file {path}: {reason}");
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        self.save_index();
 
         match matches.len() {
             0 => Err(AppError::not_found(id)),
@@ -84,36 +176,311 @@ impl TaskRepo {
     }
 
     pub fn update(&self, task: &Task) -> Result<PathBuf, AppError> {
+        Ok(self.write_task(task, Operation::Update)?.path)
+    }
+
+    /// Sets a task's manual rank. `updated_at` is left alone: reordering a
+    /// queue does not count as touching the tasks in it.
+    pub fn set_rank(&self, id: &str, rank: i64) -> Result<PathBuf, AppError> {
+        let _lock = self.lock()?;
+        let mut task = self.read(id)?;
+        task.rank = Some(rank);
+        Ok(self.write_task(&task, Operation::Rank)?.path)
+    }
+
+    /// Writes `task` over its file and returns the task as written. When the
+    /// file changed on disk after `task` was read, the two edits are merged
+    /// instead; if they conflict, the merge is still written, with conflict
+    /// markers, and reported as [`AppError::EditConflict`].
+    fn write_task(&self, task: &Task, operation: Operation) -> Result<StoredTask, AppError> {
+        let _lock = self.lock()?;
         validate_user_id(&task.id)?;
         let existing = self.find_by_id(&task.id)?;
-        let target_path = self.task_path(task.queue, &task.id);
+        let mut content = render_task_markdown(task)?;
+        let mut written = task.clone();
+        let mut conflicts = Vec::new();
+        if let Some(merge) = self.merge_disk_changes(task, &existing, &content)? {
+            written = parse_task_markdown(&merge.content).map_err(|error| {
+                AppError::invalid_task_file(
+                    existing.path.to_string_lossy().to_string(),
+                    error.to_string(),
+                )
+            })?;
+            content = merge.content;
+            conflicts = merge.conflicts;
+        }
+        let target_path = self.task_path(written.queue, &written.id);
         self.ensure_path_is_within_root(&target_path)?;
-        fs::create_dir_all(self.queue_dir(task.queue))?;
-        fs::write(&target_path, render_task_markdown(task)?)?;
 
-        if existing.path != target_path && existing.path.exists() {
-            fs::remove_file(existing.path)?;
+        let message = match operation {
+            Operation::Move { queue } => {
+                format!("move {} {}\u{2192}{queue}", task.id, existing.task.queue)
+            }
+            _ => format!("{operation} {}", task.id),
+        };
+        let paths = [existing.path.clone(), target_path.clone()];
+        self.journaled_as(operation, &task.id, &message, &paths, || {
+            fs::create_dir_all(self.queue_dir(written.queue))?;
+            atomic::write(&target_path, &content)?;
+
+            // The new copy is complete and on disk before the old one goes,
+            // so a crash in between leaves two whole copies for `doctor
+            // --fix` to settle, never none.
+            if existing.path != target_path {
+                atomic::remove(&existing.path)?;
+            }
+            Ok(())
+        })?;
+
+        if !conflicts.is_empty() {
+            return Err(AppError::edit_conflict(
+                target_path.to_string_lossy().to_string(),
+                conflicts,
+            ));
+        }
+        let modified = fs::metadata(&target_path)?.modified().ok();
+        written.loaded_from = Some(FileVersion::new(
+            modified,
+            FileVersion::content_hash(&content),
+            &written,
+        ));
+        Ok(StoredTask {
+            task: written,
+            path: target_path,
+        })
+    }
+
+    /// Merges `ours`, the rendered `task`, with the file on disk when that is
+    /// no longer the version `task` was read from. The version it was read
+    /// from is the base. Tasks not read from a file, such as editor content,
+    /// are written as they are.
+    fn merge_disk_changes(
+        &self,
+        task: &Task,
+        existing: &StoredTask,
+        ours: &str,
+    ) -> Result<Option<Merge>, AppError> {
+        let (Some(read), Some(current)) = (&task.loaded_from, &existing.task.loaded_from) else {
+            return Ok(None);
+        };
+        if read == current {
+            return Ok(None);
         }
 
-        Ok(target_path)
+        let base = render_task_markdown(&read.base)?;
+        let theirs = fs::read_to_string(&existing.path)?;
+        merge_task_files(&base, ours, &theirs).map(Some).ok_or_else(|| {
+            AppError::message(format!(
+                "task file {} changed on disk and the edits cannot be merged; nothing was written",
+                existing.path.display()
+            ))
+        })
     }
 
-    pub fn delete(&self, id: &str) -> Result<(), AppError> {
-        let stored = self.find_by_id(id)?;
-        fs::remove_file(stored.path)?;
+    pub fn trash(&self) -> &Trash {
+        &self.trash
+    }
+
+    pub fn journal(&self) -> &Journal {
+        &self.journal
+    }
+
+    pub fn git(&self) -> Option<&Git> {
+        self.git.as_ref()
+    }
+
+    /// Takes the repository write lock until the guard is dropped. Every
+    /// change below takes it for itself, from its first read to its last
+    /// write; take it here to hold it across several.
+    pub fn lock(&self) -> Result<RepoLockGuard<'_>, AppError> {
+        self.lock.acquire()
+    }
+
+    /// Reverts the most recent change and returns it; see [`Journal::undo`].
+    pub fn undo(&self) -> Result<Batch, AppError> {
+        let _lock = self.lock()?;
+        let batch = self.journal.undo()?;
+        self.commit_replay("undo", &batch)?;
+        Ok(batch)
+    }
+
+    /// Reapplies the most recently undone change; see [`Journal::redo`].
+    pub fn redo(&self) -> Result<Batch, AppError> {
+        let _lock = self.lock()?;
+        let batch = self.journal.redo()?;
+        self.commit_replay("redo", &batch)?;
+        Ok(batch)
+    }
+
+    fn commit_replay(&self, verb: &str, batch: &Batch) -> Result<(), AppError> {
+        if self.git.is_some() {
+            let batches = self.journal.batches()?;
+            let paths = batch
+                .net_changes()
+                .into_iter()
+                .map(|change| change.path)
+                .collect::<Vec<_>>();
+            self.commit(
+                &format!("{verb} {}", journal::describe(batch, &batches)),
+                &paths,
+            );
+        }
         Ok(())
     }
 
+    /// Commits `paths` when the root is kept in git. Files in the state
+    /// directory belong to this machine and are never committed. The change
+    /// itself has already happened, so a failed commit is only a warning.
+    pub fn commit(&self, message: &str, paths: &[PathBuf]) {
+        let Some(git) = &self.git else {
+            return;
+        };
+        let paths = paths
+            .iter()
+            .filter(|path| !path.starts_with(&self.state_dir))
+            .cloned()
+            .collect::<Vec<_>>();
+        if let Err(error) = git.commit(message, &paths) {
+            eprintln!("Warning: could not commit \"{message}\": {error}");
+        }
+    }
+
+    pub fn index(&self) -> &TaskIndex {
+        &self.index
+    }
+
+    /// Discards the index and parses every task file again, returning the
+    /// number of tasks indexed. Malformed files are left to `doctor`.
+    pub fn rebuild_index(&self) -> Result<usize, AppError> {
+        self.index.clear();
+        let tasks = self.scan(false, true)?;
+        self.index.save()?;
+        Ok(tasks.len())
+    }
+
+    /// Moves a done task into its dated folder under the done directory,
+    /// such as `done/2026/10/`, where plain scans no longer read it. The file
+    /// itself is left unchanged.
+    pub fn archive(&self, id: &str) -> Result<StoredTask, AppError> {
+        let _lock = self.lock()?;
+        let stored = self.find_by_id(id)?;
+        if !stored.task.queue.is_done() {
+            return Err(AppError::usage(format!(
+                "cannot archive {id}: only done tasks can be archived"
+            )));
+        }
+
+        let done_dir = self.queue_dir(Queue::Done);
+        let path = done_dir
+            .join(archive::archive_subdir(&stored.task))
+            .join(format!("{id}.md"));
+        self.ensure_path_is_within_root(&path)?;
+        if path.exists() {
+            return Err(AppError::message(format!(
+                "cannot archive {id}: {} already exists",
+                path.display()
+            )));
+        }
+
+        let paths = [stored.path.clone(), path.clone()];
+        self.journaled(Operation::Archive, id, &paths, || {
+            atomic::rename(&stored.path, &path)?;
+            // The dated folders may have just been created; their own
+            // entries need syncing too before the task is safely in them.
+            for dir in path
+                .ancestors()
+                .skip(2)
+                .take_while(|dir| dir.starts_with(&done_dir))
+            {
+                atomic::sync_dir(dir)?;
+            }
+            Ok(())
+        })?;
+        Ok(StoredTask {
+            task: stored.task,
+            path,
+        })
+    }
+
+    /// Archives every done task finished before `cutoff`, returning them
+    /// with their new paths.
+    pub fn archive_done_before(&self, cutoff: DateTime<Utc>) -> Result<Vec<StoredTask>, AppError> {
+        let _lock = self.lock()?;
+        let done = self
+            .scan_all()?
+            .into_iter()
+            .filter(|stored| stored.task.queue.is_done())
+            .filter(|stored| archive::archived_on(&stored.task) < cutoff)
+            .collect::<Vec<_>>();
+        done.iter()
+            .map(|stored| self.archive(&stored.task.id))
+            .collect()
+    }
+
+    /// Moves a task into the trash and returns its path there.
+    pub fn delete(&self, id: &str) -> Result<PathBuf, AppError> {
+        let _lock = self.lock()?;
+        let stored = self.find_by_id(id)?;
+        let paths = [
+            stored.path.clone(),
+            self.trash.task_path(id),
+            self.trash.metadata_path(id),
+        ];
+        self.journaled(Operation::Delete, id, &paths, || {
+            self.trash.put(&stored, Utc::now())
+        })
+    }
+
+    /// Removes `path`, a second copy of task `id` such as one left behind by
+    /// an interrupted move. The removal is journaled as a delete, so `undo`
+    /// brings the copy back.
+    pub fn remove_copy(&self, id: &str, path: &Path) -> Result<(), AppError> {
+        let _lock = self.lock()?;
+        self.ensure_path_is_within_root(path)?;
+        self.journaled(Operation::Delete, id, &[path.to_path_buf()], || {
+            atomic::remove(path)?;
+            Ok(())
+        })
+    }
+
+    /// Moves a trashed task back into the queue it was deleted from.
+    pub fn restore(&self, id: &str) -> Result<StoredTask, AppError> {
+        let _lock = self.lock()?;
+        let trashed = self.trash.find(id)?;
+        if self.id_exists(id) {
+            return Err(AppError::usage(format!(
+                "cannot restore '{id}': a task with that id already exists"
+            )));
+        }
+
+        let path = self.task_path(trashed.task.queue, id);
+        self.ensure_path_is_within_root(&path)?;
+        let paths = [
+            path.clone(),
+            self.trash.task_path(id),
+            self.trash.metadata_path(id),
+        ];
+        self.journaled(Operation::Restore, id, &paths, || {
+            fs::create_dir_all(self.queue_dir(trashed.task.queue))?;
+            self.trash.restore_to(id, &path)
+        })?;
+        Ok(StoredTask {
+            task: trashed.task,
+            path,
+        })
+    }
+
     pub fn move_to_queue(
         &self,
         id: &str,
         queue: Queue,
         now: DateTime<Utc>,
     ) -> Result<(Task, PathBuf, bool), AppError> {
+        let _lock = self.lock()?;
         let mut task = self.read(id)?;
         let changed = task.move_to(queue, now);
-        let path = self.update(&task)?;
-        Ok((task, path, changed))
+        let written = self.write_task(&task, Operation::Move { queue })?;
+        Ok((written.task, written.path, changed))
     }
 
     pub fn replace_edited(
@@ -122,6 +489,7 @@ impl TaskRepo {
         content: &str,
         now: DateTime<Utc>,
     ) -> Result<(Task, PathBuf), AppError> {
+        let _lock = self.lock()?;
         let existing = self.find_by_id(original_id)?;
         let mut task = parse_task_markdown(content).map_err(|e| {
             AppError::invalid_task_file(existing.path.to_string_lossy().to_string(), e.to_string())
@@ -131,9 +499,19 @@ impl TaskRepo {
             return Err(AppError::usage("editing a task cannot change its id"));
         }
 
+        // Changing `queue:` by hand is a move too, unless the edit already
+        // added the transition itself.
+        let previous = existing.task.queue;
+        if task.queue != previous && task.history.last().map(|last| last.to) != Some(task.queue) {
+            task.history.push(QueueTransition {
+                from: previous,
+                to: task.queue,
+                at: now,
+            });
+        }
         task.normalize(now);
-        let path = self.update(&task)?;
-        Ok((task, path))
+        let written = self.write_task(&task, Operation::Edit)?;
+        Ok((written.task, written.path))
     }
 
     pub fn finalize_added_edit(
@@ -143,6 +521,7 @@ impl TaskRepo {
         content: &str,
         now: DateTime<Utc>,
     ) -> Result<(Task, PathBuf), AppError> {
+        let _lock = self.lock()?;
         self.ensure_path_is_within_root(path)?;
 
         let mut task = parse_task_markdown(content).map_err(|error| {
@@ -154,51 +533,73 @@ impl TaskRepo {
         }
 
         task.normalize(now);
-        let path = self.update(&task)?;
-        Ok((task, path))
+        let written = self.write_task(&task, Operation::Edit)?;
+        Ok((written.task, written.path))
     }
 
+    /// Every live task; archived tasks are left out.
     pub fn scan_all(&self) -> Result<Vec<StoredTask>, AppError> {
+        let tasks = self.scan(true, false)?;
+        self.save_index();
+        Ok(tasks)
+    }
+
+    /// Every task, archived ones included.
+    pub fn scan_with_archive(&self) -> Result<Vec<StoredTask>, AppError> {
+        let tasks = self.scan(true, true)?;
+        self.save_index();
+        Ok(tasks)
+    }
+
+    /// Reads every task in the queue directories through the index, warning
+    /// about malformed files when `warn` is set. The archive folders under
+    /// the done directory are read only when `include_archive` is set.
+    fn scan(&self, warn: bool, include_archive: bool) -> Result<Vec<StoredTask>, AppError> {
         let mut tasks = Vec::new();
+        let mut seen = HashSet::new();
 
         if !self.root.exists() {
             return Ok(tasks);
         }
 
-        for queue in Queue::all().iter().copied() {
+        for queue in self.queue_dirs.queues() {
             let dir = self.queue_dir(queue);
             if !dir.exists() {
                 continue;
             }
 
+            let mut paths = Vec::new();
             for entry in fs::read_dir(&dir)? {
-                let entry = entry?;
-                let path = entry.path();
-
-                if !path.is_file() {
-                    continue;
-                }
-
-                if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
-                    continue;
+                let path = entry?.path();
+                if path.is_file() && path.extension().and_then(|ext| ext.to_str()) == Some("md") {
+                    paths.push(path);
                 }
+            }
+            if include_archive && queue.is_done() {
+                paths.extend(archive::archived_files(&dir)?);
+            }
 
-                match self.read_task_from_path(&path) {
+            for path in paths {
+                seen.insert(path.clone());
+                match self.read_indexed(&path) {
                     Ok(task) => tasks.push(StoredTask { task, path }),
                     Err(AppError::InvalidTaskFile { path, reason }) => {
-                        eprintln!("Warning: skipping malformed task file {path}: {reason}");
+                        if warn {
+                            eprintln!("Warning: skipping malformed task file {path}: {reason}");
+                        }
                     }
                     Err(error) => return Err(error),
                 }
             }
         }
+        let unscanned = (!include_archive).then(|| self.queue_dir(Queue::Done));
+        self.index.retain(&seen, unscanned.as_deref());
 
         tasks.sort_by(|left, right| {
             left.task
                 .queue
                 .cmp(&right.task.queue)
-                .then_with(|| right.task.updated_at.cmp(&left.task.updated_at))
-                .then_with(|| left.task.id.cmp(&right.task.id))
+                .then_with(|| rank::compare(&left.task, &right.task))
         });
         Ok(tasks)
     }
@@ -219,12 +620,81 @@ impl TaskRepo {
             .collect())
     }
 
+    /// Runs `change` and records the contents of `paths` before and after it
+    /// in the journal, committing them as `<operation> <id>`.
+    fn journaled<T>(
+        &self,
+        operation: Operation,
+        id: &str,
+        paths: &[PathBuf],
+        change: impl FnOnce() -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        let message = format!("{operation} {id}");
+        self.journaled_as(operation, id, &message, paths, change)
+    }
+
+    fn journaled_as<T>(
+        &self,
+        operation: Operation,
+        id: &str,
+        message: &str,
+        paths: &[PathBuf],
+        change: impl FnOnce() -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        let before = paths
+            .iter()
+            .map(|path| read_contents(path))
+            .collect::<Result<Vec<_>, _>>()?;
+        let result = change()?;
+
+        let mut changes = Vec::with_capacity(paths.len());
+        for (path, before) in paths.iter().zip(before) {
+            if changes
+                .iter()
+                .any(|change: &FileChange| &change.path == path)
+            {
+                continue;
+            }
+            changes.push(FileChange {
+                path: path.clone(),
+                before,
+                after: read_contents(path)?,
+            });
+        }
+        self.journal.record(operation, Some(id), changes)?;
+        self.commit(message, paths);
+        Ok(result)
+    }
+
+    fn read_indexed(&self, path: &Path) -> Result<Task, AppError> {
+        self.ensure_path_is_within_root(path)?;
+        self.index.read(path, || self.read_task_from_path(path))
+    }
+
+    /// The index only saves time, so failing to write it is not worth
+    /// failing the command over.
+    fn save_index(&self) {
+        if let Err(error) = self.index.save() {
+            eprintln!(
+                "Warning: could not save task index {}: {error}",
+                self.index.path().display()
+            );
+        }
+    }
+
     fn read_task_from_path(&self, path: &Path) -> Result<Task, AppError> {
         self.ensure_path_is_within_root(path)?;
+        let modified = fs::metadata(path)?.modified().ok();
         let content = fs::read_to_string(path)?;
-        let task = parse_task_markdown(&content).map_err(|error| {
+        let mut task = parse_task_markdown(&content).map_err(|error| {
             AppError::invalid_task_file(path.to_string_lossy().to_string(), error.to_string())
         })?;
+        if !self.queue_dirs.contains(task.queue) {
+            return Err(AppError::invalid_task_file(
+                path.to_string_lossy().to_string(),
+                format!("queue '{}' is not configured", task.queue),
+            ));
+        }
 
         let expected_filename = format!("{}.md", task.id);
         if path.file_name().and_then(|value| value.to_str()) != Some(expected_filename.as_str()) {
@@ -234,6 +704,8 @@ impl TaskRepo {
             ));
         }
 
+        let hash = FileVersion::content_hash(&content);
+        task.loaded_from = Some(FileVersion::new(modified, hash, &task));
         Ok(task)
     }
 
@@ -270,8 +742,13 @@ mod tests {
     use crate::domain::task::{Queue, Task};
     use crate::storage::config::QueueDirs;
     use crate::storage::format::render_task_markdown;
+    use crate::storage::journal::Operation;
     use chrono::Utc;
-    use std::{fs, path::Path};
+    use std::{
+        fs,
+        path::Path,
+        time::{Duration, SystemTime},
+    };
     use tempfile::TempDir;
 
     fn task(id: &str, title: &str, queue: Queue) -> Task {
@@ -302,6 +779,70 @@ mod tests {
         assert!(!created_path.exists());
     }
 
+    #[test]
+    fn move_keeps_unknown_frontmatter_keys_verbatim() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let repo = TaskRepo::new(temp.path().to_path_buf(), QueueDirs::default());
+        let created_path = repo
+            .create(&task("task-1", "Ship v2", Queue::Inbox))
+            .expect("task should be created");
+        let extra = "aliases: [ship,  v2]\nproject:\n  name: Launch\n";
+        let content = fs::read_to_string(&created_path).expect("task should be readable");
+        fs::write(
+            &created_path,
+            content.replacen("---\n#", &format!("{extra}---\n#"), 1),
+        )
+        .expect("task should be rewritten");
+
+        let (_, moved_path, _) = repo
+            .move_to_queue("task-1", Queue::Now, Utc::now())
+            .expect("task should move");
+
+        let moved = fs::read_to_string(moved_path).expect("moved task should be readable");
+        assert!(moved.contains(&format!("{extra}---\n#")));
+    }
+
+    #[test]
+    fn indexed_tasks_keep_unknown_frontmatter_and_see_external_edits() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let root = temp.path().to_path_buf();
+        let path = TaskRepo::new(root.clone(), QueueDirs::default())
+            .create(&task("task-1", "Ship v2", Queue::Inbox))
+            .expect("task should be created");
+        let settle = |path: &Path| {
+            fs::File::options()
+                .write(true)
+                .open(path)
+                .and_then(|file| file.set_modified(SystemTime::now() - Duration::from_secs(3600)))
+                .expect("mtime should be set");
+        };
+        let content = fs::read_to_string(&path).expect("task should be readable");
+        fs::write(
+            &path,
+            content.replacen("---\n#", "project: Launch\n---\n#", 1),
+        )
+        .expect("task should be rewritten");
+        settle(&path);
+
+        let repo = TaskRepo::new(root.clone(), QueueDirs::default());
+        repo.scan_all().expect("scan should succeed");
+        assert_eq!(repo.index().len(), 1);
+
+        let repo = TaskRepo::new(root.clone(), QueueDirs::default());
+        let cached = repo.read("task-1").expect("task should be read");
+        assert_eq!(cached.extra.keys().collect::<Vec<_>>(), ["project"]);
+
+        let content = fs::read_to_string(&path).expect("task should be readable");
+        fs::write(
+            &path,
+            content.replace("title: Ship v2", "title: Ship v3 now"),
+        )
+        .expect("task should be rewritten");
+        settle(&path);
+        let listed = repo.list().expect("list should succeed");
+        assert_eq!(listed[0].title, "Ship v3 now");
+    }
+
     #[test]
     fn scans_all_queue_directories() {
         let temp = TempDir::new().expect("temp dir should exist");
@@ -326,10 +867,19 @@ mod tests {
         fs::create_dir_all(&inbox).expect("inbox should exist");
         fs::write(inbox.join("bad.md"), "---\nid: bad\nqueue: inbox\n---\n")
             .expect("bad file should be written");
+        fs::write(
+            inbox.join("stray.md"),
+            "---\nid: stray\ntitle: Stray\nqueue: waiting\ncreated_at: 2026-03-09T10:34:12Z\nupdated_at: 2026-03-09T10:34:12Z\n---\n",
+        )
+        .expect("stray file should be written");
 
         let tasks = repo.list().expect("scan should succeed");
         assert_eq!(tasks.len(), 1);
         assert_eq!(tasks[0].id, "good");
+        assert!(matches!(
+            repo.read_task_from_path(&inbox.join("stray.md")),
+            Err(AppError::InvalidTaskFile { reason, .. }) if reason == "queue 'waiting' is not configured"
+        ));
     }
 
     #[test]
@@ -355,6 +905,7 @@ mod tests {
                 next: "up-next".to_string(),
                 later: "backlog".to_string(),
                 done: "archive".to_string(),
+                custom: Vec::new(),
             },
         );
 
@@ -366,21 +917,135 @@ mod tests {
     }
 
     #[test]
-    fn delete_removes_task_file() {
+    fn delete_moves_task_file_to_trash() {
         let temp = TempDir::new().expect("temp dir should exist");
         let repo = TaskRepo::new(temp.path().to_path_buf(), QueueDirs::default());
         repo.create(&task("task-1", "Ship v2", Queue::Inbox))
             .expect("task should be created");
 
-        repo.delete("task-1").expect("task should be deleted");
+        let trashed = repo.delete("task-1").expect("task should be deleted");
 
         assert!(!temp.path().join("inbox").join("task-1.md").exists());
+        assert_eq!(
+            trashed,
+            temp.path().join(".tqs").join("trash").join("task-1.md")
+        );
         assert!(matches!(
             repo.read("task-1"),
             Err(AppError::NotFound { .. })
         ));
     }
 
+    #[test]
+    fn journal_undoes_and_redoes_a_delete() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let repo = TaskRepo::new(temp.path().to_path_buf(), QueueDirs::default());
+        let path = repo
+            .create(&task("task-1", "Ship v2", Queue::Inbox))
+            .expect("task should be created");
+        let original = fs::read_to_string(&path).expect("task should be readable");
+        repo.journal().begin_batch();
+        repo.delete("task-1").expect("task should be deleted");
+
+        let undone = repo.journal().undo().expect("delete should be undone");
+
+        assert_eq!(undone.entries[0].operation, Operation::Delete);
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+        assert!(!repo.trash().contains("task-1"));
+        assert!(!repo.trash().metadata_path("task-1").exists());
+
+        repo.journal().redo().expect("delete should be redone");
+        assert!(!path.exists());
+        assert!(repo.trash().contains("task-1"));
+    }
+
+    #[test]
+    fn archive_moves_old_done_tasks_into_dated_folders() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let repo = TaskRepo::new(temp.path().to_path_buf(), QueueDirs::default());
+        let completed = "2026-03-09T10:34:12Z"
+            .parse()
+            .expect("timestamp should parse");
+        let mut old = task("old-task", "Ship v1", Queue::Done);
+        old.completed_at = Some(completed);
+        repo.create(&old).expect("task should be created");
+        let mut recent = task("new-task", "Ship v2", Queue::Done);
+        recent.completed_at = Some(Utc::now());
+        repo.create(&recent).expect("task should be created");
+        repo.create(&task("open-task", "Ship v3", Queue::Now))
+            .expect("task should be created");
+        repo.journal().begin_batch();
+
+        let archived = repo
+            .archive_done_before(
+                "2026-06-01T00:00:00Z"
+                    .parse()
+                    .expect("timestamp should parse"),
+            )
+            .expect("tasks should be archived");
+
+        let archived_path = temp
+            .path()
+            .join("done")
+            .join("2026")
+            .join("03")
+            .join("old-task.md");
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].path, archived_path);
+        assert!(archived_path.exists());
+        let live = repo.scan_all().expect("scan should succeed");
+        assert!(live.iter().all(|stored| stored.task.id != "old-task"));
+        let all = repo.scan_with_archive().expect("scan should succeed");
+        assert!(all.iter().any(|stored| stored.path == archived_path));
+        assert!(repo.read("old-task").is_err());
+        assert_eq!(
+            repo.find_archived("old-task")
+                .expect("task should be archived")
+                .path,
+            archived_path
+        );
+        assert!(repo.id_exists("old-task"));
+        assert!(repo.archive("open-task").is_err());
+
+        repo.journal().undo().expect("archive should be undone");
+        assert!(!archived_path.exists());
+        assert!(repo.read("old-task").is_ok());
+    }
+
+    #[test]
+    fn restore_returns_trashed_task_to_its_queue() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let repo = TaskRepo::new(temp.path().to_path_buf(), QueueDirs::default());
+        let path = repo
+            .create(&task("task-1", "Ship v2", Queue::Later))
+            .expect("task should be created");
+        let original = fs::read_to_string(&path).expect("task should be readable");
+        repo.delete("task-1").expect("task should be deleted");
+
+        let restored = repo.restore("task-1").expect("task should be restored");
+
+        assert_eq!(restored.path, path);
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+        assert!(!repo.trash().contains("task-1"));
+        assert!(!repo.trash().metadata_path("task-1").exists());
+    }
+
+    #[test]
+    fn restore_refuses_to_overwrite_a_live_task() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let repo = TaskRepo::new(temp.path().to_path_buf(), QueueDirs::default());
+        repo.create(&task("task-1", "Ship v2", Queue::Inbox))
+            .expect("task should be created");
+        repo.delete("task-1").expect("task should be deleted");
+        repo.create(&task("task-1", "Ship v3", Queue::Now))
+            .expect("id should be reusable");
+
+        let error = repo.restore("task-1").expect_err("restore should conflict");
+
+        assert!(error.to_string().contains("already exists"));
+        assert!(repo.trash().contains("task-1"));
+    }
+
     #[test]
     fn find_by_id_rejects_duplicate_ids_across_queues() {
         let temp = TempDir::new().expect("temp dir should exist");
@@ -441,6 +1106,77 @@ mod tests {
         assert_eq!(err.to_string(), "editing a task cannot change its id");
     }
 
+    #[test]
+    fn replace_edited_records_queue_changes_in_history() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let repo = TaskRepo::new(temp.path().to_path_buf(), QueueDirs::default());
+        let task = task("task-1", "Ship v2", Queue::Inbox);
+        repo.create(&task).expect("task should be created");
+
+        let edited = render(&task).replace("queue: inbox", "queue: later");
+        let (edited, path) = repo
+            .replace_edited("task-1", &edited, Utc::now())
+            .expect("edit should apply");
+
+        assert!(path.ends_with("later/task-1.md"));
+        assert_eq!(edited.history.len(), 1);
+        assert_eq!(edited.history[0].from, Queue::Inbox);
+        assert_eq!(edited.history[0].to, Queue::Later);
+    }
+
+    #[test]
+    fn update_merges_edits_made_on_disk_after_the_task_was_read() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let repo = TaskRepo::new(temp.path().to_path_buf(), QueueDirs::default());
+        let mut created = task("task-1", "Ship v2", Queue::Inbox);
+        created.body = "- draft\n- review".to_string();
+        let path = repo.create(&created).expect("task should be created");
+
+        let mut read = repo.read("task-1").expect("task should be read");
+        let on_disk = fs::read_to_string(&path).expect("task should be readable");
+        fs::write(&path, on_disk.replace("- review", "- review\n- publish"))
+            .expect("outside edit should be written");
+
+        read.title = "Ship v2 today".to_string();
+        read.body = "- draft, done\n- review".to_string();
+        repo.update(&read).expect("edits should merge");
+
+        let merged = repo.read("task-1").expect("task should be read");
+        assert_eq!(merged.title, "Ship v2 today");
+        assert_eq!(merged.body, "- draft, done\n- review\n- publish");
+    }
+
+    #[test]
+    fn update_keeps_conflicting_edits_between_markers() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let repo = TaskRepo::new(temp.path().to_path_buf(), QueueDirs::default());
+        let path = repo
+            .create(&task("task-1", "Ship v2", Queue::Inbox))
+            .expect("task should be created");
+
+        let mut read = repo.read("task-1").expect("task should be read");
+        let on_disk = fs::read_to_string(&path).expect("task should be readable");
+        fs::write(&path, on_disk.replace("title: Ship v2", "title: Ship v3"))
+            .expect("outside edit should be written");
+
+        read.title = "Ship it".to_string();
+        let err = repo.update(&read).expect_err("edits should conflict");
+        let AppError::EditConflict { conflicts, .. } = &err else {
+            panic!("expected an edit conflict, got {err}");
+        };
+        assert_eq!(conflicts, &["title"]);
+
+        let merged = repo
+            .read("task-1")
+            .expect("conflicted task should still parse");
+        assert_eq!(merged.title, "Ship it");
+        assert!(
+            merged
+                .body
+                .ends_with("<<<<<<< tqs\ntitle: Ship it\n=======\ntitle: Ship v3\n>>>>>>> on disk")
+        );
+    }
+
     #[test]
     fn finalize_added_edit_rejects_paths_outside_root() {
         let temp = TempDir::new().expect("temp dir should exist");
@@ -0,0 +1,378 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::app::app_error::AppError;
+use crate::domain::task::{Queue, Task};
+use crate::storage::{atomic, format::parse_task_markdown, repo::StoredTask};
+
+pub const TRASH_DIR: &str = "trash";
+const METADATA_VERSION: u32 = 1;
+
+/// Trashed task files that failed to parse, with the reason.
+pub type MalformedEntries = Vec<(PathBuf, String)>;
+
+/// Deleted tasks, kept under `<state_dir>/trash/` until they are restored or
+/// purged. Each entry is the task file exactly as it was (`<id>.md`) plus a
+/// `<id>.toml` sidecar recording when and from which queue it was deleted.
+/// Deleting a task whose id is already in the trash is refused, so the older
+/// copy is never overwritten.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trash {
+    dir: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrashedTask {
+    pub task: Task,
+    /// Location of the task file inside the trash.
+    pub path: PathBuf,
+    pub deleted_at: DateTime<Utc>,
+    /// Queue the task was deleted from.
+    pub queue: Queue,
+}
+
+impl TrashedTask {
+    /// The trashed task in the shape the picker and resolvers expect.
+    pub fn to_stored(&self) -> StoredTask {
+        StoredTask {
+            task: self.task.clone(),
+            path: self.path.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TrashMetadata {
+    version: u32,
+    id: String,
+    queue: Queue,
+    deleted_at: DateTime<Utc>,
+}
+
+impl Trash {
+    pub fn new(state_dir: &Path) -> Self {
+        Self {
+            dir: state_dir.join(TRASH_DIR),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn task_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.md"))
+    }
+
+    pub fn metadata_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.toml"))
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.task_path(id).is_file()
+    }
+
+    /// Moves a task file into the trash and returns its new path. Fails
+    /// without moving anything when the id already has a trashed copy.
+    pub fn put(&self, stored: &StoredTask, deleted_at: DateTime<Utc>) -> Result<PathBuf, AppError> {
+        let id = &stored.task.id;
+        if self.contains(id) {
+            return Err(AppError::usage(format!(
+                "cannot delete '{id}': a deleted task with that id is already in the trash; purge it with `tqs trash purge` first"
+            )));
+        }
+        fs::create_dir_all(&self.dir)?;
+        let metadata = TrashMetadata {
+            version: METADATA_VERSION,
+            id: id.clone(),
+            queue: stored.task.queue,
+            deleted_at,
+        };
+        let serialized = toml::to_string(&metadata).map_err(|error| {
+            AppError::message(format!("failed to serialize trash metadata: {error}"))
+        })?;
+
+        let trash_path = self.task_path(id);
+        move_file(&stored.path, &trash_path)?;
+        if let Err(error) = atomic::write(&self.metadata_path(id), serialized) {
+            move_file(&trash_path, &stored.path)?;
+            return Err(error.into());
+        }
+        Ok(trash_path)
+    }
+
+    /// Lists trashed tasks, most recently deleted first. Files that no longer
+    /// parse are skipped with a warning, as in `TaskRepo::scan_all`.
+    pub fn list(&self) -> Result<Vec<TrashedTask>, AppError> {
+        let (trashed, malformed) = self.scan()?;
+        for (path, reason) in malformed {
+            eprintln!(
+                "Warning: skipping malformed trashed task {}: {reason}",
+                path.display()
+            );
+        }
+        Ok(trashed)
+    }
+
+    /// Reads every trashed task, returning malformed files with the reason
+    /// they failed to parse instead of skipping them.
+    pub fn scan(&self) -> Result<(Vec<TrashedTask>, MalformedEntries), AppError> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(error) if is_missing_dir(&error) => return Ok((Vec::new(), Vec::new())),
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut trashed = Vec::new();
+        let mut malformed = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if !path.is_file() || path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+                continue;
+            }
+            match self.read_entry(&path) {
+                Ok(entry) => trashed.push(entry),
+                Err(AppError::InvalidTaskFile { reason, .. }) => malformed.push((path, reason)),
+                Err(error) => return Err(error),
+            }
+        }
+
+        trashed.sort_by(|left, right| {
+            right
+                .deleted_at
+                .cmp(&left.deleted_at)
+                .then_with(|| left.task.id.cmp(&right.task.id))
+        });
+        Ok((trashed, malformed))
+    }
+
+    pub fn find(&self, id: &str) -> Result<TrashedTask, AppError> {
+        let path = self.task_path(id);
+        if !path.is_file() {
+            return Err(AppError::message(format!("task not in trash: {id}")));
+        }
+        self.read_entry(&path)
+    }
+
+    /// Moves a trashed task file to `path` and drops its metadata.
+    pub fn restore_to(&self, id: &str, path: &Path) -> Result<(), AppError> {
+        move_file(&self.task_path(id), path)?;
+        match fs::remove_file(self.metadata_path(id)) {
+            Err(error) if error.kind() != ErrorKind::NotFound => Err(error.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Removes a trashed task and its metadata for good.
+    pub fn remove(&self, id: &str) -> Result<(), AppError> {
+        fs::remove_file(self.task_path(id))?;
+        match fs::remove_file(self.metadata_path(id)) {
+            Err(error) if error.kind() != ErrorKind::NotFound => Err(error.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Permanently removes every task deleted before `cutoff`.
+    pub fn purge_before(&self, cutoff: DateTime<Utc>) -> Result<Vec<TrashedTask>, AppError> {
+        let expired = self
+            .list()?
+            .into_iter()
+            .filter(|trashed| trashed.deleted_at < cutoff)
+            .collect::<Vec<_>>();
+        for trashed in &expired {
+            self.remove(&trashed.task.id)?;
+        }
+        Ok(expired)
+    }
+
+    /// Metadata files whose task file is gone, left by an interrupted delete
+    /// or restore.
+    pub fn orphaned_metadata(&self) -> Result<Vec<PathBuf>, AppError> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(error) if is_missing_dir(&error) => return Ok(Vec::new()),
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut orphaned = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) == Some("toml")
+                && !path.with_extension("md").is_file()
+            {
+                orphaned.push(path);
+            }
+        }
+        orphaned.sort();
+        Ok(orphaned)
+    }
+
+    fn read_entry(&self, path: &Path) -> Result<TrashedTask, AppError> {
+        let content = fs::read_to_string(path)?;
+        let task = parse_task_markdown(&content).map_err(|error| {
+            AppError::invalid_task_file(path.display().to_string(), error.to_string())
+        })?;
+        let metadata = fs::read_to_string(self.metadata_path(&task.id))
+            .ok()
+            .and_then(|contents| toml::from_str::<TrashMetadata>(&contents).ok());
+
+        // Without metadata, fall back to the file's modification time and the
+        // queue recorded in its frontmatter.
+        let (deleted_at, queue) = match metadata {
+            Some(metadata) => (metadata.deleted_at, metadata.queue),
+            None => (fs::metadata(path)?.modified()?.into(), task.queue),
+        };
+        Ok(TrashedTask {
+            task,
+            path: path.to_path_buf(),
+            deleted_at,
+            queue,
+        })
+    }
+}
+
+/// A trash directory that is absent, or whose parent is not a directory,
+/// simply holds nothing.
+fn is_missing_dir(error: &std::io::Error) -> bool {
+    matches!(error.kind(), ErrorKind::NotFound | ErrorKind::NotADirectory)
+}
+
+/// Renames a file, copying it instead when the rename crosses filesystems.
+fn move_file(from: &Path, to: &Path) -> Result<(), AppError> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to)?;
+    fs::remove_file(from)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Trash;
+    use crate::domain::task::{Queue, Task};
+    use crate::storage::{config::QueueDirs, repo::TaskRepo};
+    use chrono::{Duration, Utc};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn repo_with_task(temp: &TempDir, id: &str) -> TaskRepo {
+        let repo = TaskRepo::new(temp.path().to_path_buf(), QueueDirs::default());
+        let mut task = Task::new(id, format!("Task {id}"), Utc::now());
+        task.queue = Queue::Later;
+        repo.create(&task).expect("task should be created");
+        repo
+    }
+
+    #[test]
+    fn put_keeps_file_bytes_and_records_metadata() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let repo = repo_with_task(&temp, "task-1");
+        let stored = repo.find_by_id("task-1").expect("task should exist");
+        let original = fs::read_to_string(&stored.path).expect("task should be readable");
+        let trash = Trash::new(&temp.path().join(".tqs"));
+
+        let path = trash
+            .put(&stored, Utc::now())
+            .expect("task should be trashed");
+
+        assert!(!stored.path.exists());
+        assert_eq!(
+            fs::read_to_string(&path).expect("trashed task should be readable"),
+            original
+        );
+        let trashed = trash.find("task-1").expect("task should be in the trash");
+        assert_eq!(trashed.queue, Queue::Later);
+        assert_eq!(trashed.task.title, "Task task-1");
+        assert!(trash.contains("task-1"));
+    }
+
+    #[test]
+    fn put_refuses_to_overwrite_an_earlier_trashed_copy() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let repo = repo_with_task(&temp, "task-1");
+        let trash = Trash::new(&temp.path().join(".tqs"));
+        let deleted_at = Utc::now() - Duration::days(3);
+        trash
+            .put(
+                &repo.find_by_id("task-1").expect("task should exist"),
+                deleted_at,
+            )
+            .expect("task should be trashed");
+        let mut task = Task::new("task-1", "Second task-1", Utc::now());
+        task.queue = Queue::Now;
+        repo.create(&task).expect("task should be created");
+        let stored = repo.find_by_id("task-1").expect("task should exist");
+
+        let error = trash
+            .put(&stored, Utc::now())
+            .expect_err("an earlier copy is in the trash");
+
+        assert!(
+            error
+                .to_string()
+                .contains("a deleted task with that id is already in the trash")
+        );
+        assert!(stored.path.exists());
+        let trashed = trash.find("task-1").expect("task should be in the trash");
+        assert_eq!(trashed.task.title, "Task task-1");
+        assert_eq!(trashed.queue, Queue::Later);
+        assert_eq!(trashed.deleted_at, deleted_at);
+    }
+
+    #[test]
+    fn purge_before_removes_only_older_entries() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let repo = repo_with_task(&temp, "old");
+        let mut task = Task::new("new", "Task new", Utc::now());
+        task.queue = Queue::Inbox;
+        repo.create(&task).expect("task should be created");
+        let trash = Trash::new(&temp.path().join(".tqs"));
+        let now = Utc::now();
+        trash
+            .put(
+                &repo.find_by_id("old").expect("task should exist"),
+                now - Duration::days(40),
+            )
+            .expect("task should be trashed");
+        trash
+            .put(&repo.find_by_id("new").expect("task should exist"), now)
+            .expect("task should be trashed");
+
+        let purged = trash
+            .purge_before(now - Duration::days(30))
+            .expect("trash should be purged");
+
+        assert_eq!(purged.len(), 1);
+        assert_eq!(purged[0].task.id, "old");
+        assert!(!trash.contains("old"));
+        assert!(!trash.metadata_path("old").exists());
+        assert!(trash.contains("new"));
+    }
+
+    #[test]
+    fn orphaned_metadata_lists_sidecars_without_task_files() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let repo = repo_with_task(&temp, "task-1");
+        let trash = Trash::new(&temp.path().join(".tqs"));
+        trash
+            .put(
+                &repo.find_by_id("task-1").expect("task should exist"),
+                Utc::now(),
+            )
+            .expect("task should be trashed");
+        fs::remove_file(trash.task_path("task-1")).expect("trashed file should be removed");
+
+        assert_eq!(
+            trash.orphaned_metadata().expect("trash should be readable"),
+            vec![trash.metadata_path("task-1")]
+        );
+        assert!(trash.list().expect("trash should be readable").is_empty());
+    }
+}
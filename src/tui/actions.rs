@@ -2,10 +2,13 @@ use chrono::Utc;
 
 use crate::app::app_error::AppError;
 use crate::app::operations;
+use crate::domain::priority::Priority;
+use crate::domain::rank::RankPosition;
 use crate::domain::task::{Queue, Task};
 use crate::storage::id_state::SharedIdAllocator;
+use crate::storage::journal;
 
-use super::app_state::{Mode, TuiApp};
+use super::app_state::{Mode, QueueFilter, TuiApp};
 
 pub enum SideEffect {
     None,
@@ -22,9 +25,27 @@ pub fn mark_done(app: &mut TuiApp) -> Result<SideEffect, AppError> {
         return Ok(SideEffect::None);
     }
     let task_id = task.id.clone();
-    operations::mark_done(&app.repo, &app.config, &task_id)?;
+    let outcome = operations::mark_done(&app.repo, &app.config, &task_id)?;
     app.refresh()?;
-    app.set_status(format!("Completed: {task_id}"));
+    let mut status = format!("Completed: {task_id}");
+    if let Some((next, _)) = &outcome.next {
+        status.push_str(&format!("; next occurrence {} in {}", next.id, next.queue));
+    }
+    if !outcome.unblocked.is_empty() {
+        let ids = outcome
+            .unblocked
+            .iter()
+            .map(|task| task.id.as_str())
+            .collect::<Vec<_>>();
+        status.push_str(&format!("; unblocked {}", ids.join(", ")));
+    }
+    if !outcome.archived.is_empty() {
+        status.push_str(&format!(
+            "; archived {} older done tasks",
+            outcome.archived.len()
+        ));
+    }
+    app.set_status(status);
     Ok(SideEffect::None)
 }
 
@@ -47,6 +68,46 @@ pub fn move_to_queue(app: &mut TuiApp, queue: Queue) -> Result<SideEffect, AppEr
     Ok(SideEffect::None)
 }
 
+/// Swaps the selected task with the one above it (`up`) or below it, and
+/// keeps it selected. Only queues have a manual order, so views and tags
+/// leave the list alone.
+pub fn reorder(app: &mut TuiApp, up: bool) -> Result<SideEffect, AppError> {
+    let QueueFilter::Single(queue) = app.active_filter() else {
+        app.set_status("Switch to a queue to reorder tasks");
+        return Ok(SideEffect::None);
+    };
+    let Some(index) = app.task_list_state.selected() else {
+        return Ok(SideEffect::None);
+    };
+    let tasks = app.current_queue_tasks();
+    let neighbour = if up {
+        index.checked_sub(1).and_then(|i| tasks.get(i))
+    } else {
+        tasks.get(index + 1)
+    };
+    let (Some(task), Some(neighbour)) = (tasks.get(index), neighbour) else {
+        return Ok(SideEffect::None);
+    };
+    let task_id = task.id.clone();
+    let position = if up {
+        RankPosition::Before(neighbour.id.clone())
+    } else {
+        RankPosition::After(neighbour.id.clone())
+    };
+
+    operations::rank_task(&app.repo, &task_id, &position)?;
+    app.refresh()?;
+    if let Some(index) = app
+        .current_queue_tasks()
+        .iter()
+        .position(|task| task.id == task_id)
+    {
+        app.task_list_state.select(Some(index));
+    }
+    app.set_status(format!("Ranked {task_id} {position} in {queue}"));
+    Ok(SideEffect::None)
+}
+
 pub fn confirm_delete(app: &mut TuiApp) -> Result<SideEffect, AppError> {
     let (task_id, from_triage) = match &app.mode {
         Mode::ConfirmDelete {
@@ -66,11 +127,26 @@ pub fn confirm_delete(app: &mut TuiApp) -> Result<SideEffect, AppError> {
         app.repo.delete(&task_id)?;
         app.mode = Mode::Normal;
         app.refresh()?;
-        app.set_status(format!("Deleted: {task_id}"));
+        app.set_status(format!("Moved to trash: {task_id}"));
     }
     Ok(SideEffect::None)
 }
 
+/// Reverts the most recent change. Failures such as a file edited since are
+/// shown in the status bar rather than closing the dashboard.
+pub fn undo(app: &mut TuiApp) -> Result<SideEffect, AppError> {
+    let status = match app.repo.undo() {
+        Ok(batch) => {
+            let batches = app.repo.journal().batches()?;
+            format!("Undid: {}", journal::describe(&batch, &batches))
+        }
+        Err(error) => format!("Undo failed: {error}"),
+    };
+    app.refresh()?;
+    app.set_status(status);
+    Ok(SideEffect::None)
+}
+
 pub fn triage_move(app: &mut TuiApp, queue: Queue) -> Result<SideEffect, AppError> {
     let Some(task) = app.current_triage_task() else {
         return Ok(SideEffect::None);
@@ -90,6 +166,24 @@ pub fn triage_move(app: &mut TuiApp, queue: Queue) -> Result<SideEffect, AppErro
     Ok(SideEffect::None)
 }
 
+/// Sets the current triage task's priority, or clears it when it already
+/// has that priority. The task stays current so it can still be dispatched.
+pub fn triage_set_priority(app: &mut TuiApp, priority: Priority) -> Result<SideEffect, AppError> {
+    let Some(task) = app.current_triage_task() else {
+        return Ok(SideEffect::None);
+    };
+    let mut task = task.clone();
+    task.priority = (task.priority != Some(priority)).then_some(priority);
+    task.updated_at = Utc::now();
+    app.repo.update(&task)?;
+    app.refresh()?;
+    app.set_status(match task.priority {
+        Some(priority) => format!("{} is now {priority}", task.id),
+        None => format!("Cleared priority for {}", task.id),
+    });
+    Ok(SideEffect::None)
+}
+
 pub fn triage_skip(app: &mut TuiApp) -> Result<SideEffect, AppError> {
     app.triage.summary.skipped += 1;
     app.advance_triage_or_finish();
@@ -105,6 +199,77 @@ pub fn triage_edit(app: &mut TuiApp) -> Result<SideEffect, AppError> {
     })
 }
 
+pub fn review_keep(app: &mut TuiApp) -> Result<SideEffect, AppError> {
+    let Some(task) = app.current_review_task() else {
+        return Ok(SideEffect::None);
+    };
+    let task_id = task.id.clone();
+    operations::keep_reviewed(&app.repo, &task_id)?;
+    app.review.summary.kept += 1;
+    app.refresh()?;
+    app.advance_review_or_finish();
+    Ok(SideEffect::None)
+}
+
+/// Promotes (`up`) or demotes the current review task by one queue; a no-op
+/// when there is no queue in that direction.
+pub fn review_shift(app: &mut TuiApp, up: bool) -> Result<SideEffect, AppError> {
+    let Some(task) = app.current_review_task() else {
+        return Ok(SideEffect::None);
+    };
+    let target = if up {
+        task.queue.promoted()
+    } else {
+        task.queue.demoted()
+    };
+    let Some(target) = target else {
+        return Ok(SideEffect::None);
+    };
+    let task_id = task.id.clone();
+    app.repo.move_to_queue(&task_id, target, Utc::now())?;
+    if up {
+        app.review.summary.promoted += 1;
+    } else {
+        app.review.summary.demoted += 1;
+    }
+    app.refresh()?;
+    app.advance_review_or_finish();
+    Ok(SideEffect::None)
+}
+
+pub fn review_done(app: &mut TuiApp) -> Result<SideEffect, AppError> {
+    let Some(task) = app.current_review_task() else {
+        return Ok(SideEffect::None);
+    };
+    let task_id = task.id.clone();
+    operations::mark_done(&app.repo, &app.config, &task_id)?;
+    app.review.summary.done += 1;
+    app.refresh()?;
+    app.advance_review_or_finish();
+    Ok(SideEffect::None)
+}
+
+pub fn review_delete(app: &mut TuiApp) -> Result<SideEffect, AppError> {
+    app.mode = Mode::Review {
+        confirm_delete: false,
+    };
+    let Some(task) = app.current_review_task() else {
+        return Ok(SideEffect::None);
+    };
+    let task_id = task.id.clone();
+    app.repo.delete(&task_id)?;
+    app.review.summary.deleted += 1;
+    app.refresh()?;
+    app.advance_review_or_finish();
+    Ok(SideEffect::None)
+}
+
+pub fn review_skip(app: &mut TuiApp) -> Result<SideEffect, AppError> {
+    app.review.summary.skipped += 1;
+    app.advance_review_or_finish();
+    Ok(SideEffect::None)
+}
+
 pub fn submit_add_form(app: &mut TuiApp) -> Result<SideEffect, AppError> {
     let (title, queue) = match &app.mode {
         Mode::AddForm { title, queue } => (title.trim().to_string(), *queue),
@@ -116,11 +281,15 @@ pub fn submit_add_form(app: &mut TuiApp) -> Result<SideEffect, AppError> {
         return Ok(SideEffect::None);
     }
 
-    let allocator = SharedIdAllocator::new(&app.config);
-    let id = allocator.generate(&app.repo)?;
-    let mut task = Task::new(id, &title, Utc::now());
-    task.queue = queue;
-    app.repo.create(&task)?;
+    {
+        // Held from the id to the file, so a busy repository stops both.
+        let _lock = app.repo.lock()?;
+        let allocator = SharedIdAllocator::new(&app.config);
+        let id = allocator.generate(&app.repo)?;
+        let mut task = Task::new(id, &title, Utc::now());
+        task.queue = queue;
+        app.repo.create(&task)?;
+    }
 
     app.mode = Mode::Normal;
     app.refresh()?;
@@ -144,10 +313,15 @@ mod tests {
             state_dir: root.join(".tqs"),
             daily_notes_dir: None,
             queue_dirs: QueueDirs::default(),
+            recur_queue: Queue::Inbox,
+            views: Vec::new(),
+            templates: Default::default(),
+            auto_archive_after: None,
+            git: None,
         };
         let repo = TaskRepo::new(root.clone(), QueueDirs::default());
         for (id, queue) in tasks {
-            let mut task = Task::new(id.to_string(), &format!("Task {id}"), Utc::now());
+            let mut task = Task::new(id.to_string(), format!("Task {id}"), Utc::now());
             task.queue = *queue;
             repo.create(&task).unwrap();
         }
@@ -218,6 +392,29 @@ mod tests {
         assert!(app.active_status_message().unwrap().contains("already in"));
     }
 
+    // --- undo ---
+
+    #[test]
+    fn undo_reverts_last_move_and_reports_conflicts() {
+        let temp = TempDir::new().unwrap();
+        let mut app = make_app_with_tasks(&temp, &[("a1", Queue::Now)]);
+        app.repo.journal().begin_batch();
+        move_to_queue(&mut app, Queue::Later).unwrap();
+
+        undo(&mut app).unwrap();
+        assert_eq!(app.repo.read("a1").unwrap().queue, Queue::Now);
+        assert_eq!(app.active_status_message(), Some("Undid: move to later a1"));
+
+        let path = app.repo.find_by_id("a1").unwrap().path;
+        std::fs::write(&path, "edited by hand").unwrap();
+        undo(&mut app).unwrap();
+        assert!(
+            app.active_status_message()
+                .unwrap()
+                .starts_with("Undo failed: cannot undo add a1")
+        );
+    }
+
     // --- confirm_delete ---
 
     #[test]
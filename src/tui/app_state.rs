@@ -1,38 +1,88 @@
 use std::fmt;
 use std::time::Instant;
 
+use chrono::{Days, Local, Utc};
 use ratatui::widgets::ListState;
 
 use crate::app::app_error::AppError;
+use crate::app::operations;
+use crate::domain::deps::BlockedIndex;
+use crate::domain::filter::{queue_counts, tag_counts};
+use crate::domain::query::Query;
+use crate::domain::stats::{self, Bucket, Stats, StatsOptions};
 use crate::domain::task::{Queue, Task};
-use crate::storage::config::ResolvedConfig;
+use crate::storage::config::{QueueDirs, QueueGroup, ResolvedConfig};
 use crate::storage::repo::TaskRepo;
 
-/// What the sidebar can show: a queue, a separator line, or "all".
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use super::watch::TaskWatcher;
+
+/// What the sidebar can show: a queue, a separator line, "all", a section
+/// heading, a saved view, or a tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SidebarEntry {
     Queue(Queue),
     Separator,
     All,
+    Heading(&'static str),
+    View(String),
+    Tag(String),
 }
 
-/// The sidebar layout with visual separators between groups.
-const SIDEBAR_ENTRIES: &[SidebarEntry] = &[
-    SidebarEntry::Queue(Queue::Now),
-    SidebarEntry::Queue(Queue::Next),
-    SidebarEntry::Queue(Queue::Later),
-    SidebarEntry::Separator,
-    SidebarEntry::Queue(Queue::Inbox),
-    SidebarEntry::Separator,
-    SidebarEntry::Queue(Queue::Done),
-    SidebarEntry::All,
-];
+impl SidebarEntry {
+    pub fn is_selectable(&self) -> bool {
+        !matches!(self, Self::Separator | Self::Heading(_))
+    }
+}
+
+/// The queue part of the sidebar: the active group, the inbox group, and the
+/// done group with "all", separated by lines. Custom queues join the group
+/// their config names, in sort position order.
+fn queue_sidebar_entries(queue_dirs: &QueueDirs) -> Vec<SidebarEntry> {
+    let queues = queue_dirs.queues();
+    let mut entries = Vec::new();
+    for group in [QueueGroup::Active, QueueGroup::Inbox, QueueGroup::Done] {
+        if !entries.is_empty() {
+            entries.push(SidebarEntry::Separator);
+        }
+        entries.extend(
+            queues
+                .iter()
+                .filter(|queue| queue_dirs.group(**queue) == group)
+                .map(|queue| SidebarEntry::Queue(*queue)),
+        );
+    }
+    entries.push(SidebarEntry::All);
+    entries
+}
+
+/// Builds the sidebar: the queue groups, a "Views" section with the saved
+/// views from config, and a "Tags" section listing every tag in use.
+fn build_sidebar_entries(tasks: &[Task], config: &ResolvedConfig) -> Vec<SidebarEntry> {
+    let mut entries = queue_sidebar_entries(&config.queue_dirs);
+    if !config.views.is_empty() {
+        entries.push(SidebarEntry::Heading("Views"));
+        entries.extend(
+            config
+                .views
+                .iter()
+                .map(|view| SidebarEntry::View(view.name.clone())),
+        );
+    }
+    let tags = tag_counts(tasks);
+    if !tags.is_empty() {
+        entries.push(SidebarEntry::Heading("Tags"));
+        entries.extend(tags.into_iter().map(|(tag, _)| SidebarEntry::Tag(tag)));
+    }
+    entries
+}
 
 /// Which tasks to show in the task list.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum QueueFilter {
     Single(Queue),
     All,
+    View(String),
+    Tag(String),
 }
 
 impl fmt::Display for QueueFilter {
@@ -40,25 +90,23 @@ impl fmt::Display for QueueFilter {
         match self {
             Self::Single(q) => write!(f, "{q}"),
             Self::All => write!(f, "all"),
+            Self::View(name) => write!(f, "{name}"),
+            Self::Tag(tag) => write!(f, "#{tag}"),
         }
     }
 }
 
 pub struct QueueCounts {
-    counts: [usize; 5],
+    counts: Vec<(Queue, usize)>,
     pub total: usize,
 }
 
 impl QueueCounts {
     pub fn get(&self, queue: Queue) -> usize {
-        let idx = match queue {
-            Queue::Inbox => 0,
-            Queue::Now => 1,
-            Queue::Next => 2,
-            Queue::Later => 3,
-            Queue::Done => 4,
-        };
-        self.counts[idx]
+        self.counts
+            .iter()
+            .find(|(counted, _)| *counted == queue)
+            .map_or(0, |(_, count)| *count)
     }
 }
 
@@ -90,7 +138,10 @@ impl FocusedPanel {
     }
 }
 
-pub use crate::app::operations::TriageSummary;
+pub use crate::app::operations::{ReviewSummary, TriageSummary};
+
+/// The window shown by the stats overlay, matching the `tqs stats` default.
+pub const STATS_WINDOW_DAYS: u64 = 30;
 
 pub enum Mode {
     Normal,
@@ -111,6 +162,21 @@ pub enum Mode {
         list_state: ListState,
     },
     Triage,
+    /// Walks now, next, and later like `tqs review`; `confirm_delete` is set
+    /// while waiting for `y` after `x`.
+    Review {
+        confirm_delete: bool,
+    },
+    /// Overlay with `tqs stats` figures for the last `STATS_WINDOW_DAYS` days.
+    Stats(Stats),
+}
+
+/// Review progress, kept outside Mode like `TriageState`.
+#[derive(Default)]
+pub struct ReviewState {
+    pub task_ids: Vec<String>,
+    pub index: usize,
+    pub summary: ReviewSummary,
 }
 
 /// Triage state lives outside Mode because it must survive transitions
@@ -128,8 +194,10 @@ pub struct TuiApp {
 
     // Cached task data
     pub tasks: Vec<Task>,
+    pub blocked: BlockedIndex,
 
     // Navigation
+    pub sidebar: Vec<SidebarEntry>,
     pub active_sidebar_index: usize,
     pub task_list_state: ListState,
 
@@ -143,35 +211,55 @@ pub struct TuiApp {
     // Triage state (persists across ConfirmDelete/MoveTarget sub-modes)
     pub triage: TriageState,
 
+    // Review state
+    pub review: ReviewState,
+
     // Transient status message
     pub status_message: Option<(String, Instant)>,
 
     // Redraw flag — set when state changes, cleared after draw
     pub needs_redraw: bool,
+
+    // Notices task files changed outside the TUI
+    watcher: TaskWatcher,
 }
 
 impl TuiApp {
     pub fn new(config: ResolvedConfig, repo: TaskRepo) -> Result<Self, AppError> {
+        let watcher = TaskWatcher::new(&repo);
         let tasks = repo.list()?;
+        let sidebar = build_sidebar_entries(&tasks, &config);
+        let blocked = BlockedIndex::from_tasks(&tasks);
         let mut app = Self {
             config,
             repo,
             tasks,
+            blocked,
+            sidebar,
             active_sidebar_index: 0,
             task_list_state: ListState::default(),
             focused_panel: FocusedPanel::TaskList,
             detail_scroll: 0,
             mode: Mode::Normal,
             triage: TriageState::default(),
+            review: ReviewState::default(),
             status_message: None,
             needs_redraw: true,
+            watcher,
         };
         app.select_first_task();
         Ok(app)
     }
 
+    /// Reloads every task from disk. The watcher is brought up to date
+    /// first, so the TUI's own writes, which are always followed by a
+    /// refresh, are not reported as outside changes later, while anything
+    /// written after this point still is.
     pub fn refresh(&mut self) -> Result<(), AppError> {
+        self.watcher.resync();
         self.tasks = self.repo.list()?;
+        self.blocked = BlockedIndex::from_tasks(&self.tasks);
+        self.rebuild_sidebar();
         let count = self.current_queue_tasks().len();
         if count == 0 {
             self.task_list_state.select(None);
@@ -183,32 +271,100 @@ impl TuiApp {
         Ok(())
     }
 
+    /// Whether task files changed on disk since the last check or refresh;
+    /// see [`TaskWatcher::poll`].
+    pub fn poll_external_changes(&mut self, now: Instant) -> bool {
+        self.watcher.poll(now)
+    }
+
+    /// Reloads tasks after they changed on disk outside the TUI. Unlike
+    /// `refresh`, the selection follows the selected task to its new position
+    /// and the list keeps its scroll offset. The status bar says so when the
+    /// selected task was edited, moved out of the list, or removed.
+    pub fn merge_external_changes(&mut self) -> Result<(), AppError> {
+        let previous = self.selected_task().cloned();
+        let offset = self.task_list_state.offset();
+        self.refresh()?;
+        *self.task_list_state.offset_mut() = offset;
+
+        let Some(previous) = previous else {
+            return Ok(());
+        };
+        let position = self
+            .current_queue_tasks()
+            .iter()
+            .position(|task| task.id == previous.id);
+        if let Some(position) = position {
+            self.task_list_state.select(Some(position));
+        }
+        let message = match self.tasks.iter().find(|task| task.id == previous.id) {
+            None => Some(format!("Task {} was removed outside tqs", previous.id)),
+            Some(task) if task.queue != previous.queue => Some(format!(
+                "Task {} was moved to {} outside tqs",
+                previous.id, task.queue
+            )),
+            Some(task) if *task != previous => {
+                Some(format!("Task {} was changed outside tqs", previous.id))
+            }
+            Some(_) => None,
+        };
+        if let Some(message) = message {
+            self.set_status(message);
+        }
+        Ok(())
+    }
+
     pub fn sidebar_entries(&self) -> &[SidebarEntry] {
-        SIDEBAR_ENTRIES
+        &self.sidebar
+    }
+
+    /// Rebuilds the sidebar after the task set changed, keeping the active
+    /// entry when it still exists. A tag that is no longer used falls back to
+    /// the first queue.
+    fn rebuild_sidebar(&mut self) {
+        let active = self.sidebar.get(self.active_sidebar_index).cloned();
+        self.sidebar = build_sidebar_entries(&self.tasks, &self.config);
+        self.active_sidebar_index = active
+            .and_then(|active| self.sidebar.iter().position(|entry| *entry == active))
+            .unwrap_or(0);
     }
 
     pub fn active_filter(&self) -> QueueFilter {
-        match SIDEBAR_ENTRIES[self.active_sidebar_index] {
-            SidebarEntry::Queue(q) => QueueFilter::Single(q),
+        match &self.sidebar[self.active_sidebar_index] {
+            SidebarEntry::Queue(q) => QueueFilter::Single(*q),
             SidebarEntry::All => QueueFilter::All,
-            SidebarEntry::Separator => unreachable!("separator cannot be active sidebar entry"),
+            SidebarEntry::View(name) => QueueFilter::View(name.clone()),
+            SidebarEntry::Tag(tag) => QueueFilter::Tag(tag.clone()),
+            SidebarEntry::Separator | SidebarEntry::Heading(_) => {
+                unreachable!("separators and headings cannot be the active sidebar entry")
+            }
         }
     }
 
+    /// Tasks matching the named saved view, in view order.
+    pub fn view_tasks(&self, name: &str) -> Vec<&Task> {
+        self.config
+            .views
+            .iter()
+            .find(|view| view.name == name)
+            .and_then(|view| {
+                view.apply(
+                    &self.tasks,
+                    Local::now().date_naive(),
+                    &self.config.queue_dirs.queues(),
+                )
+                .ok()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn tag_count(&self, tag: &str) -> usize {
+        self.tasks.iter().filter(|task| task.has_tag(tag)).count()
+    }
+
     pub fn queue_counts(&self) -> QueueCounts {
-        let mut counts = [0usize; 5];
-        for task in &self.tasks {
-            let idx = match task.queue {
-                Queue::Inbox => 0,
-                Queue::Now => 1,
-                Queue::Next => 2,
-                Queue::Later => 3,
-                Queue::Done => 4,
-            };
-            counts[idx] += 1;
-        }
         QueueCounts {
-            counts,
+            counts: queue_counts(&self.tasks, &self.config.queue_dirs.queues()),
             total: self.tasks.len(),
         }
     }
@@ -217,6 +373,8 @@ impl TuiApp {
         match self.active_filter() {
             QueueFilter::Single(queue) => self.tasks.iter().filter(|t| t.queue == queue).collect(),
             QueueFilter::All => self.tasks.iter().collect(),
+            QueueFilter::View(name) => self.view_tasks(&name),
+            QueueFilter::Tag(tag) => self.tasks.iter().filter(|t| t.has_tag(&tag)).collect(),
         }
     }
 
@@ -228,31 +386,26 @@ impl TuiApp {
     }
 
     pub fn next_queue(&mut self) {
-        self.active_sidebar_index = next_selectable(self.active_sidebar_index, 1);
+        self.active_sidebar_index = next_selectable(&self.sidebar, self.active_sidebar_index, 1);
         debug_assert!(
-            !matches!(
-                SIDEBAR_ENTRIES[self.active_sidebar_index],
-                SidebarEntry::Separator
-            ),
+            self.sidebar[self.active_sidebar_index].is_selectable(),
             "next_queue landed on a separator"
         );
         self.select_first_task();
     }
 
     pub fn prev_queue(&mut self) {
-        self.active_sidebar_index = next_selectable(self.active_sidebar_index, -1);
+        self.active_sidebar_index = next_selectable(&self.sidebar, self.active_sidebar_index, -1);
         debug_assert!(
-            !matches!(
-                SIDEBAR_ENTRIES[self.active_sidebar_index],
-                SidebarEntry::Separator
-            ),
+            self.sidebar[self.active_sidebar_index].is_selectable(),
             "prev_queue landed on a separator"
         );
         self.select_first_task();
     }
 
     pub fn select_queue_by_index(&mut self, index: usize) {
-        let selectable: Vec<usize> = SIDEBAR_ENTRIES
+        let selectable: Vec<usize> = self
+            .sidebar
             .iter()
             .enumerate()
             .filter(|(_, e)| matches!(e, SidebarEntry::Queue(_) | SidebarEntry::All))
@@ -265,7 +418,8 @@ impl TuiApp {
     }
 
     pub fn jump_to_queue(&mut self, queue: Queue) {
-        if let Some(idx) = SIDEBAR_ENTRIES
+        if let Some(idx) = self
+            .sidebar
             .iter()
             .position(|e| *e == SidebarEntry::Queue(queue))
         {
@@ -296,8 +450,10 @@ impl TuiApp {
         self.detail_scroll = 0;
     }
 
+    /// Re-runs the search query. While the query does not parse, the previous
+    /// results stay put and the search title shows the error instead.
     pub fn update_search_results(&mut self) {
-        use crate::domain::filter::matches_query;
+        let queues = self.config.queue_dirs.queues();
         let Mode::Search {
             query,
             results,
@@ -306,10 +462,13 @@ impl TuiApp {
         else {
             return;
         };
+        let Ok(query) = Query::parse(query, Local::now().date_naive(), &queues) else {
+            return;
+        };
         *results = self
             .tasks
             .iter()
-            .filter(|t| matches_query(t, query))
+            .filter(|t| query.matches(t))
             .map(|t| (t.id.clone(), t.queue))
             .collect();
         if results.is_empty() {
@@ -351,6 +510,46 @@ impl TuiApp {
         self.tasks.iter().find(|t| t.id == *task_id)
     }
 
+    /// Stats for the last `STATS_WINDOW_DAYS` days, limited to the active
+    /// tag when a tag is selected in the sidebar.
+    /// Queues a new task can be added to: every queue but done, in order.
+    pub fn add_queues(&self) -> Vec<Queue> {
+        self.config
+            .queue_dirs
+            .queues()
+            .into_iter()
+            .filter(|queue| !queue.is_done())
+            .collect()
+    }
+
+    /// Custom queues offered as move targets on keys 5-9, in sort position order.
+    pub fn custom_move_targets(&self) -> Vec<Queue> {
+        self.config
+            .queue_dirs
+            .queues()
+            .into_iter()
+            .filter(|queue| matches!(queue, Queue::Custom(_)))
+            .take(5)
+            .collect()
+    }
+
+    pub fn stats(&self) -> Stats {
+        let now = Local::now();
+        let tag = match self.active_filter() {
+            QueueFilter::Tag(tag) => Some(tag),
+            _ => None,
+        };
+        let options = StatsOptions {
+            since: Utc::now()
+                .checked_sub_days(Days::new(STATS_WINDOW_DAYS))
+                .unwrap_or_default(),
+            by: Bucket::Week,
+            tag,
+            queues: self.config.queue_dirs.queues(),
+        };
+        stats::compute(&self.tasks, &options, now)
+    }
+
     pub fn enter_triage(&mut self) {
         let inbox_ids: Vec<String> = self
             .tasks
@@ -379,6 +578,43 @@ impl TuiApp {
         }
     }
 
+    pub fn current_review_task(&self) -> Option<&Task> {
+        let task_id = self.review.task_ids.get(self.review.index)?;
+        self.tasks.iter().find(|t| t.id == *task_id)
+    }
+
+    pub fn enter_review(&mut self) {
+        let task_ids: Vec<String> = operations::review_order(&self.tasks)
+            .into_iter()
+            .map(|t| t.id.clone())
+            .collect();
+        if task_ids.is_empty() {
+            self.set_status("Nothing to review — now, next, and later are empty");
+            return;
+        }
+        self.review = ReviewState {
+            task_ids,
+            index: 0,
+            summary: ReviewSummary::default(),
+        };
+        self.mode = Mode::Review {
+            confirm_delete: false,
+        };
+    }
+
+    pub fn advance_review_or_finish(&mut self) {
+        self.review.index += 1;
+        if self.review.index >= self.review.task_ids.len() {
+            self.finish_review();
+        }
+    }
+
+    pub fn finish_review(&mut self) {
+        let summary = self.review.summary.to_string();
+        self.mode = Mode::Normal;
+        self.set_status(format!("Review: {summary}"));
+    }
+
     pub fn set_status(&mut self, message: impl Into<String>) {
         self.status_message = Some((message.into(), Instant::now()));
     }
@@ -403,13 +639,14 @@ impl TuiApp {
     }
 }
 
-/// Find the next selectable sidebar index (skipping separators), wrapping around.
-fn next_selectable(current: usize, direction: i32) -> usize {
-    let len = SIDEBAR_ENTRIES.len();
+/// Find the next selectable sidebar index (skipping separators and headings),
+/// wrapping around.
+fn next_selectable(entries: &[SidebarEntry], current: usize, direction: i32) -> usize {
+    let len = entries.len();
     let mut idx = current;
     loop {
         idx = ((idx as i32 + direction).rem_euclid(len as i32)) as usize;
-        if !matches!(SIDEBAR_ENTRIES[idx], SidebarEntry::Separator) {
+        if entries[idx].is_selectable() {
             return idx;
         }
     }
@@ -418,10 +655,11 @@ fn next_selectable(current: usize, direction: i32) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::storage::config::{QueueDirs, ResolvedConfig};
+    use crate::domain::view::SavedView;
     use crate::storage::repo::TaskRepo;
     use chrono::Utc;
     use ratatui::widgets::ListState;
+    use std::time::Duration;
     use tempfile::TempDir;
 
     fn make_app(temp: &TempDir) -> TuiApp {
@@ -432,6 +670,11 @@ mod tests {
             state_dir: root.join(".tqs"),
             daily_notes_dir: None,
             queue_dirs: QueueDirs::default(),
+            recur_queue: Queue::Inbox,
+            views: Vec::new(),
+            templates: Default::default(),
+            auto_archive_after: None,
+            git: None,
         };
         let repo = TaskRepo::new(root, QueueDirs::default());
         TuiApp::new(config, repo).unwrap()
@@ -445,10 +688,15 @@ mod tests {
             state_dir: root.join(".tqs"),
             daily_notes_dir: None,
             queue_dirs: QueueDirs::default(),
+            recur_queue: Queue::Inbox,
+            views: Vec::new(),
+            templates: Default::default(),
+            auto_archive_after: None,
+            git: None,
         };
         let repo = TaskRepo::new(root.clone(), QueueDirs::default());
         for (id, queue) in tasks {
-            let mut task = Task::new(id.to_string(), &format!("Task {id}"), Utc::now());
+            let mut task = Task::new(id.to_string(), format!("Task {id}"), Utc::now());
             task.queue = *queue;
             repo.create(&task).unwrap();
         }
@@ -475,19 +723,178 @@ mod tests {
 
     #[test]
     fn next_selectable_skips_separators() {
-        // SIDEBAR_ENTRIES: Now(0), Next(1), Later(2), Sep(3), Inbox(4), Sep(5), Done(6), All(7)
+        // Default queue entries: Now(0), Next(1), Later(2), Sep(3), Inbox(4), Sep(5), Done(6), All(7)
         // From Later(2), next should skip Sep(3) and land on Inbox(4)
-        assert_eq!(next_selectable(2, 1), 4);
+        assert_eq!(
+            next_selectable(&queue_sidebar_entries(&QueueDirs::default()), 2, 1),
+            4
+        );
         // From Inbox(4), prev should skip Sep(3) and land on Later(2)
-        assert_eq!(next_selectable(4, -1), 2);
+        assert_eq!(
+            next_selectable(&queue_sidebar_entries(&QueueDirs::default()), 4, -1),
+            2
+        );
     }
 
     #[test]
     fn next_selectable_wraps_around() {
         // From All(7), next should wrap to Now(0)
-        assert_eq!(next_selectable(7, 1), 0);
+        assert_eq!(
+            next_selectable(&queue_sidebar_entries(&QueueDirs::default()), 7, 1),
+            0
+        );
         // From Now(0), prev should wrap to All(7)
-        assert_eq!(next_selectable(0, -1), 7);
+        assert_eq!(
+            next_selectable(&queue_sidebar_entries(&QueueDirs::default()), 0, -1),
+            7
+        );
+    }
+
+    #[test]
+    fn next_selectable_skips_tag_heading() {
+        let mut entries = queue_sidebar_entries(&QueueDirs::default());
+        entries.push(SidebarEntry::Heading("Tags"));
+        entries.push(SidebarEntry::Tag("ops".to_string()));
+        // From All(7), next should skip Heading(8) and land on the tag(9)
+        assert_eq!(next_selectable(&entries, 7, 1), 9);
+    }
+
+    // --- tags ---
+
+    fn make_app_with_tagged_tasks(temp: &TempDir, tasks: &[(&str, Queue, &[&str])]) -> TuiApp {
+        let root = temp.path().to_path_buf();
+        let config = ResolvedConfig {
+            obsidian_vault_dir: None,
+            tasks_root: root.clone(),
+            state_dir: root.join(".tqs"),
+            daily_notes_dir: None,
+            queue_dirs: QueueDirs::default(),
+            recur_queue: Queue::Inbox,
+            views: Vec::new(),
+            templates: Default::default(),
+            auto_archive_after: None,
+            git: None,
+        };
+        let repo = TaskRepo::new(root.clone(), QueueDirs::default());
+        for (id, queue, tags) in tasks {
+            let mut task = Task::new(id.to_string(), format!("Task {id}"), Utc::now());
+            task.queue = *queue;
+            task.tags = tags.iter().map(|tag| tag.to_string()).collect();
+            repo.create(&task).unwrap();
+        }
+        TuiApp::new(config, repo).unwrap()
+    }
+
+    #[test]
+    fn sidebar_lists_tags_below_queues() {
+        let temp = TempDir::new().unwrap();
+        let app = make_app_with_tagged_tasks(
+            &temp,
+            &[
+                ("a1", Queue::Now, &["ops"]),
+                ("a2", Queue::Inbox, &["aws", "ops"]),
+            ],
+        );
+        let entries = app.sidebar_entries();
+        assert_eq!(
+            entries.len(),
+            queue_sidebar_entries(&QueueDirs::default()).len() + 3
+        );
+        assert_eq!(entries[8], SidebarEntry::Heading("Tags"));
+        assert_eq!(entries[9], SidebarEntry::Tag("aws".to_string()));
+        assert_eq!(entries[10], SidebarEntry::Tag("ops".to_string()));
+        assert_eq!(app.tag_count("ops"), 2);
+    }
+
+    #[test]
+    fn sidebar_has_no_tags_section_without_tags() {
+        let temp = TempDir::new().unwrap();
+        let app = make_app_with_tasks(&temp, &[("a1", Queue::Now)]);
+        assert_eq!(
+            app.sidebar_entries(),
+            queue_sidebar_entries(&QueueDirs::default())
+        );
+    }
+
+    #[test]
+    fn tag_filter_shows_tasks_across_queues() {
+        let temp = TempDir::new().unwrap();
+        let mut app = make_app_with_tagged_tasks(
+            &temp,
+            &[
+                ("a1", Queue::Now, &["ops"]),
+                ("a2", Queue::Inbox, &["ops"]),
+                ("a3", Queue::Inbox, &[]),
+            ],
+        );
+        app.active_sidebar_index = 9; // #ops
+        assert_eq!(app.active_filter(), QueueFilter::Tag("ops".to_string()));
+        assert_eq!(app.current_queue_tasks().len(), 2);
+    }
+
+    #[test]
+    fn refresh_falls_back_when_active_tag_disappears() {
+        let temp = TempDir::new().unwrap();
+        let mut app = make_app_with_tagged_tasks(&temp, &[("a1", Queue::Now, &["ops"])]);
+        app.active_sidebar_index = 9; // #ops
+        app.repo.delete("a1").unwrap();
+        app.refresh().unwrap();
+        assert_eq!(app.active_sidebar_index, 0);
+        assert_eq!(app.active_filter(), QueueFilter::Single(Queue::Now));
+    }
+
+    // --- views ---
+
+    #[test]
+    fn sidebar_lists_views_between_queues_and_tags() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        let repo = TaskRepo::new(root.clone(), QueueDirs::default());
+        for (id, queue, tags) in [
+            ("a1", Queue::Now, vec!["ops"]),
+            ("a2", Queue::Inbox, vec!["ops"]),
+            ("a3", Queue::Next, vec![]),
+        ] {
+            let mut task = Task::new(id, format!("Task {id}"), Utc::now());
+            task.queue = queue;
+            task.tags = tags.into_iter().map(str::to_string).collect();
+            repo.create(&task).unwrap();
+        }
+        let config = ResolvedConfig {
+            obsidian_vault_dir: None,
+            tasks_root: root.clone(),
+            state_dir: root.join(".tqs"),
+            daily_notes_dir: None,
+            queue_dirs: QueueDirs::default(),
+            recur_queue: Queue::Inbox,
+            views: vec![SavedView {
+                name: "ops-active".to_string(),
+                query: "tag:ops -queue:inbox".to_string(),
+                sort: None,
+                columns: SavedView::DEFAULT_COLUMNS.to_vec(),
+            }],
+            templates: Default::default(),
+            auto_archive_after: None,
+            git: None,
+        };
+        let mut app = TuiApp::new(config, repo).unwrap();
+
+        let entries = app.sidebar_entries();
+        assert_eq!(entries[8], SidebarEntry::Heading("Views"));
+        assert_eq!(entries[9], SidebarEntry::View("ops-active".to_string()));
+        assert_eq!(entries[10], SidebarEntry::Heading("Tags"));
+
+        app.active_sidebar_index = 9;
+        assert_eq!(
+            app.active_filter(),
+            QueueFilter::View("ops-active".to_string())
+        );
+        let ids = app
+            .current_queue_tasks()
+            .into_iter()
+            .map(|task| task.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["a1"]);
     }
 
     // --- TuiApp::new ---
@@ -693,6 +1100,99 @@ mod tests {
         assert_eq!(app.task_list_state.selected(), None);
     }
 
+    #[test]
+    fn merging_external_changes_keeps_the_selected_task() {
+        let temp = TempDir::new().unwrap();
+        let mut app = make_app_with_tasks(&temp, &[("a1", Queue::Now), ("a2", Queue::Now)]);
+        let selected = app.selected_task().unwrap().id.clone();
+        *app.task_list_state.offset_mut() = 1;
+
+        let mut newer = Task::new("a3".to_string(), "Task a3", Utc::now() + Days::new(1));
+        newer.queue = Queue::Now;
+        app.repo.create(&newer).unwrap();
+        app.merge_external_changes().unwrap();
+
+        assert_eq!(app.current_queue_tasks()[0].id, "a3");
+        assert_eq!(app.selected_task().unwrap().id, selected);
+        assert_eq!(app.task_list_state.offset(), 1);
+        assert_eq!(app.active_status_message(), None);
+    }
+
+    #[test]
+    fn merging_external_changes_reports_edited_moved_and_removed_tasks() {
+        let temp = TempDir::new().unwrap();
+        let mut app = make_app_with_tasks(&temp, &[("a1", Queue::Now), ("a2", Queue::Now)]);
+        let selected = app.selected_task().unwrap().id.clone();
+
+        let mut task = app.repo.read(&selected).unwrap();
+        task.title = "Renamed elsewhere".to_string();
+        app.repo.update(&task).unwrap();
+        app.merge_external_changes().unwrap();
+        assert_eq!(
+            app.active_status_message(),
+            Some(format!("Task {selected} was changed outside tqs").as_str())
+        );
+
+        app.repo
+            .move_to_queue(&selected, Queue::Later, Utc::now())
+            .unwrap();
+        app.merge_external_changes().unwrap();
+        assert_eq!(
+            app.active_status_message(),
+            Some(format!("Task {selected} was moved to later outside tqs").as_str())
+        );
+
+        let other = app.selected_task().unwrap().id.clone();
+        app.repo.delete(&other).unwrap();
+        app.merge_external_changes().unwrap();
+        assert_eq!(
+            app.active_status_message(),
+            Some(format!("Task {other} was removed outside tqs").as_str())
+        );
+        assert_eq!(app.task_list_state.selected(), None);
+    }
+
+    #[test]
+    fn leaving_a_tag_list_without_changing_queue_is_not_a_move() {
+        let temp = TempDir::new().unwrap();
+        let mut app = make_app_with_tagged_tasks(
+            &temp,
+            &[("a1", Queue::Now, &["ops"]), ("a2", Queue::Now, &["ops"])],
+        );
+        app.active_sidebar_index = app
+            .sidebar_entries()
+            .iter()
+            .position(|entry| *entry == SidebarEntry::Tag("ops".to_string()))
+            .unwrap();
+        app.task_list_state.select(Some(0));
+        let selected = app.selected_task().unwrap().id.clone();
+
+        let mut task = app.repo.read(&selected).unwrap();
+        task.tags.clear();
+        app.repo.update(&task).unwrap();
+        app.merge_external_changes().unwrap();
+
+        assert_eq!(
+            app.active_status_message(),
+            Some(format!("Task {selected} was changed outside tqs").as_str())
+        );
+    }
+
+    #[test]
+    fn own_changes_are_not_reported_by_the_watcher() {
+        let temp = TempDir::new().unwrap();
+        let mut app = make_app_with_tasks(&temp, &[("a1", Queue::Now), ("a2", Queue::Now)]);
+        let later = || Instant::now() + Duration::from_secs(2);
+
+        crate::tui::actions::move_to_queue(&mut app, Queue::Later).unwrap();
+        assert!(!app.poll_external_changes(later()));
+
+        let mut outside = Task::new("a3".to_string(), "Task a3", Utc::now());
+        outside.queue = Queue::Now;
+        app.repo.create(&outside).unwrap();
+        assert!(app.poll_external_changes(later() + Duration::from_secs(2)));
+    }
+
     // --- search ---
 
     #[test]
@@ -742,6 +1242,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn update_search_results_uses_query_syntax_and_keeps_results_on_errors() {
+        let temp = TempDir::new().unwrap();
+        let mut app = make_app_with_tasks(&temp, &[("a1", Queue::Inbox), ("b1", Queue::Now)]);
+        app.mode = Mode::Search {
+            query: "task -queue:inbox".to_string(),
+            results: Vec::new(),
+            list_state: ListState::default(),
+        };
+        app.update_search_results();
+
+        if let Mode::Search { query, .. } = &mut app.mode {
+            query.push_str(" due<");
+        }
+        app.update_search_results();
+
+        if let Mode::Search { results, .. } = &app.mode {
+            assert_eq!(results, &vec![("b1".to_string(), Queue::Now)]);
+        } else {
+            panic!("expected Search mode");
+        }
+    }
+
     #[test]
     fn update_search_results_noop_outside_search_mode() {
         let temp = TempDir::new().unwrap();
@@ -846,5 +1369,6 @@ mod tests {
     fn queue_filter_display() {
         assert_eq!(QueueFilter::Single(Queue::Now).to_string(), "now");
         assert_eq!(QueueFilter::All.to_string(), "all");
+        assert_eq!(QueueFilter::Tag("ops".to_string()).to_string(), "#ops");
     }
 }
@@ -4,7 +4,7 @@ use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
 use ratatui::widgets::ListState;
 
 use crate::app::app_error::AppError;
-use crate::domain::task::Queue;
+use crate::domain::{priority::Priority, task::Queue};
 
 use super::actions::{self, SideEffect};
 use super::app_state::{FocusedPanel, Mode, TuiApp};
@@ -21,6 +21,8 @@ pub fn poll_event(timeout: Duration) -> std::io::Result<Option<Event>> {
 /// Map a key event to state mutations on the app, given the current mode.
 /// Returns a SideEffect that the main loop may need to handle.
 pub fn handle_key(app: &mut TuiApp, key: KeyEvent) -> Result<SideEffect, AppError> {
+    // Whatever this key changes is undone as one step.
+    app.repo.journal().begin_batch();
     match &app.mode {
         Mode::Normal => handle_normal_key(app, key),
         Mode::AddForm { .. } => handle_add_form_key(app, key),
@@ -28,6 +30,17 @@ pub fn handle_key(app: &mut TuiApp, key: KeyEvent) -> Result<SideEffect, AppErro
         Mode::MoveTarget { .. } => handle_move_target_key(app, key),
         Mode::Search { .. } => handle_search_key(app, key),
         Mode::Triage => handle_triage_key(app, key),
+        Mode::Review { confirm_delete } => {
+            if *confirm_delete {
+                handle_review_confirm_key(app, key)
+            } else {
+                handle_review_key(app, key)
+            }
+        }
+        Mode::Stats(_) => {
+            app.mode = Mode::Normal;
+            Ok(SideEffect::None)
+        }
     }
 }
 
@@ -62,12 +75,20 @@ fn handle_normal_key(app: &mut TuiApp, key: KeyEvent) -> Result<SideEffect, AppE
             }
         },
 
+        // Manual ordering within the current queue
+        KeyCode::Char('K') if app.focused_panel == FocusedPanel::TaskList => {
+            return actions::reorder(app, true);
+        }
+        KeyCode::Char('J') if app.focused_panel == FocusedPanel::TaskList => {
+            return actions::reorder(app, false);
+        }
+
         // Tab cycles queues regardless of panel focus
         KeyCode::Tab => app.next_queue(),
         KeyCode::BackTab => app.prev_queue(),
 
-        // Direct queue jump (1-6) regardless of panel focus
-        KeyCode::Char(c @ '1'..='6') => {
+        // Direct queue jump (1-9) regardless of panel focus
+        KeyCode::Char(c @ '1'..='9') => {
             let index = (c as usize) - ('1' as usize);
             app.select_queue_by_index(index);
         }
@@ -75,10 +96,8 @@ fn handle_normal_key(app: &mut TuiApp, key: KeyEvent) -> Result<SideEffect, AppE
         // Task actions
         KeyCode::Char('d') => return actions::mark_done(app),
         KeyCode::Char('s') => return actions::start_task(app),
-        KeyCode::Char('m') => {
-            if app.selected_task().is_some() {
-                app.mode = Mode::MoveTarget { from_triage: false };
-            }
+        KeyCode::Char('m') if app.selected_task().is_some() => {
+            app.mode = Mode::MoveTarget { from_triage: false };
         }
         KeyCode::Char('x') => {
             if let Some(task) = app.selected_task() {
@@ -102,6 +121,12 @@ fn handle_normal_key(app: &mut TuiApp, key: KeyEvent) -> Result<SideEffect, AppE
         // Triage
         KeyCode::Char('t') => app.enter_triage(),
 
+        // Review
+        KeyCode::Char('R') => app.enter_review(),
+
+        // Stats overlay
+        KeyCode::Char('S') => app.mode = Mode::Stats(app.stats()),
+
         // Add task
         KeyCode::Char('a') => {
             app.mode = Mode::AddForm {
@@ -119,6 +144,8 @@ fn handle_normal_key(app: &mut TuiApp, key: KeyEvent) -> Result<SideEffect, AppE
             }
         }
 
+        KeyCode::Char('u') => return actions::undo(app),
+
         // Refresh
         KeyCode::Char('r') => {
             app.refresh()?;
@@ -139,13 +166,15 @@ fn handle_add_form_key(app: &mut TuiApp, key: KeyEvent) -> Result<SideEffect, Ap
             app.mode = Mode::Normal;
         }
         KeyCode::Tab => {
+            let queues = app.add_queues();
             if let Mode::AddForm { queue, .. } = &mut app.mode {
-                *queue = add_form::cycle_queue(*queue);
+                *queue = add_form::cycle_queue(*queue, &queues);
             }
         }
         KeyCode::BackTab => {
+            let queues = app.add_queues();
             if let Mode::AddForm { queue, .. } = &mut app.mode {
-                *queue = add_form::cycle_queue_back(*queue);
+                *queue = add_form::cycle_queue_back(*queue, &queues);
             }
         }
         KeyCode::Backspace => {
@@ -263,6 +292,10 @@ fn handle_triage_key(app: &mut TuiApp, key: KeyEvent) -> Result<SideEffect, AppE
 
         // Triage-specific
         KeyCode::Char(' ') => actions::triage_skip(app),
+        KeyCode::Char(c @ '0'..='3') => {
+            let index = (c as usize) - ('0' as usize);
+            actions::triage_set_priority(app, Priority::ALL[index])
+        }
         KeyCode::Char('q') | KeyCode::Esc => {
             let summary = app.triage.summary.to_string();
             app.mode = Mode::Normal;
@@ -273,6 +306,41 @@ fn handle_triage_key(app: &mut TuiApp, key: KeyEvent) -> Result<SideEffect, AppE
     }
 }
 
+fn handle_review_key(app: &mut TuiApp, key: KeyEvent) -> Result<SideEffect, AppError> {
+    match key.code {
+        KeyCode::Char('k') => actions::review_keep(app),
+        KeyCode::Char('+') | KeyCode::Char('=') => actions::review_shift(app, true),
+        KeyCode::Char('-') => actions::review_shift(app, false),
+        KeyCode::Char('d') => actions::review_done(app),
+        KeyCode::Char('x') => {
+            if app.current_review_task().is_some() {
+                app.mode = Mode::Review {
+                    confirm_delete: true,
+                };
+            }
+            Ok(SideEffect::None)
+        }
+        KeyCode::Char(' ') => actions::review_skip(app),
+        KeyCode::Char('q') | KeyCode::Esc => {
+            app.finish_review();
+            Ok(SideEffect::None)
+        }
+        _ => Ok(SideEffect::None),
+    }
+}
+
+fn handle_review_confirm_key(app: &mut TuiApp, key: KeyEvent) -> Result<SideEffect, AppError> {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Enter => actions::review_delete(app),
+        _ => {
+            app.mode = Mode::Review {
+                confirm_delete: false,
+            };
+            Ok(SideEffect::None)
+        }
+    }
+}
+
 fn handle_move_target_key(app: &mut TuiApp, key: KeyEvent) -> Result<SideEffect, AppError> {
     let from_triage = matches!(app.mode, Mode::MoveTarget { from_triage: true });
     let cancel_mode = if from_triage {
@@ -286,6 +354,16 @@ fn handle_move_target_key(app: &mut TuiApp, key: KeyEvent) -> Result<SideEffect,
         KeyCode::Char('n') | KeyCode::Char('2') => do_move(app, Queue::Now, from_triage),
         KeyCode::Char('x') | KeyCode::Char('3') => do_move(app, Queue::Next, from_triage),
         KeyCode::Char('l') | KeyCode::Char('4') => do_move(app, Queue::Later, from_triage),
+        KeyCode::Char(digit @ '5'..='9') => {
+            let index = digit as usize - '5' as usize;
+            match app.custom_move_targets().get(index).copied() {
+                Some(queue) => do_move(app, queue, from_triage),
+                None => {
+                    app.mode = cancel_mode;
+                    Ok(SideEffect::None)
+                }
+            }
+        }
         _ => {
             app.mode = cancel_mode;
             Ok(SideEffect::None)
@@ -306,8 +384,8 @@ fn do_move(app: &mut TuiApp, queue: Queue, from_triage: bool) -> Result<SideEffe
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::task::Task;
-    use crate::storage::config::{QueueDirs, ResolvedConfig};
+    use crate::domain::task::{QueueName, Task};
+    use crate::storage::config::{CustomQueue, QueueDirs, QueueGroup, ResolvedConfig};
     use crate::storage::repo::TaskRepo;
     use chrono::Utc;
     use tempfile::TempDir;
@@ -324,6 +402,11 @@ mod tests {
             state_dir: root.join(".tqs"),
             daily_notes_dir: None,
             queue_dirs: QueueDirs::default(),
+            recur_queue: Queue::Inbox,
+            views: Vec::new(),
+            templates: Default::default(),
+            auto_archive_after: None,
+            git: None,
         };
         let repo = TaskRepo::new(root, QueueDirs::default());
         TuiApp::new(config, repo).unwrap()
@@ -337,6 +420,11 @@ mod tests {
             state_dir: root.join(".tqs"),
             daily_notes_dir: None,
             queue_dirs: QueueDirs::default(),
+            recur_queue: Queue::Inbox,
+            views: Vec::new(),
+            templates: Default::default(),
+            auto_archive_after: None,
+            git: None,
         };
         let repo = TaskRepo::new(root, QueueDirs::default());
         let mut task = Task::new("abc".to_string(), "Test task", Utc::now());
@@ -381,6 +469,67 @@ mod tests {
         assert_eq!(app.focused_panel, FocusedPanel::Detail);
     }
 
+    #[test]
+    fn shift_r_reviews_queues_stalest_first_and_reports_summary() {
+        let temp = TempDir::new().unwrap();
+        let mut app = test_app(&temp);
+        let long_ago = Utc::now() - chrono::TimeDelta::days(30);
+        for (id, queue, updated_at) in [
+            ("l2", Queue::Later, Utc::now()),
+            ("l1", Queue::Later, long_ago),
+            ("n1", Queue::Now, Utc::now()),
+        ] {
+            let mut task = Task::new(id, format!("Task {id}"), long_ago);
+            task.queue = queue;
+            task.updated_at = updated_at;
+            app.repo.create(&task).unwrap();
+        }
+        app.refresh().unwrap();
+
+        handle_key(&mut app, key(KeyCode::Char('R'))).unwrap();
+        assert_eq!(app.review.task_ids, ["n1", "l1", "l2"]);
+
+        handle_key(&mut app, key(KeyCode::Char('+'))).unwrap();
+        assert_eq!(app.review.index, 0, "now cannot be promoted");
+        handle_key(&mut app, key(KeyCode::Char('-'))).unwrap();
+        assert_eq!(app.repo.read("n1").unwrap().queue, Queue::Next);
+
+        handle_key(&mut app, key(KeyCode::Char('k'))).unwrap();
+        assert!(app.repo.read("l1").unwrap().updated_at > long_ago);
+
+        handle_key(&mut app, key(KeyCode::Char('x'))).unwrap();
+        assert!(matches!(
+            app.mode,
+            Mode::Review {
+                confirm_delete: true
+            }
+        ));
+        handle_key(&mut app, key(KeyCode::Char('y'))).unwrap();
+        assert!(app.repo.read("l2").is_err());
+
+        assert!(matches!(app.mode, Mode::Normal));
+        assert_eq!(
+            app.active_status_message(),
+            Some("Review: 1 kept, 1 demoted, 1 deleted")
+        );
+    }
+
+    #[test]
+    fn shift_s_opens_stats_and_any_key_closes() {
+        let temp = TempDir::new().unwrap();
+        let mut app = test_app_with_task(&temp);
+        handle_key(&mut app, key(KeyCode::Char('S'))).unwrap();
+        let Mode::Stats(stats) = &app.mode else {
+            panic!("expected the stats overlay");
+        };
+        assert_eq!(stats.queues[1].open, 1);
+        assert_eq!(stats.queues[1].oldest[0].id, "abc");
+
+        let result = handle_key(&mut app, key(KeyCode::Char('q'))).unwrap();
+        assert!(matches!(result, SideEffect::None));
+        assert!(matches!(app.mode, Mode::Normal));
+    }
+
     #[test]
     fn a_enters_add_form() {
         let temp = TempDir::new().unwrap();
@@ -514,6 +663,78 @@ mod tests {
         assert_eq!(tasks.len(), 1);
     }
 
+    #[test]
+    fn move_target_digits_move_to_custom_queues() {
+        let temp = TempDir::new().unwrap();
+        let mut app = test_app_with_task(&temp);
+        let waiting = Queue::Custom(QueueName::new("waiting").unwrap());
+        app.config.queue_dirs.custom.push(CustomQueue {
+            queue: waiting,
+            dir: "waiting".to_string(),
+            position: 35,
+            group: QueueGroup::Active,
+            active: true,
+        });
+        app.repo = TaskRepo::new(temp.path().to_path_buf(), app.config.queue_dirs.clone());
+        app.mode = Mode::MoveTarget { from_triage: false };
+
+        handle_key(&mut app, key(KeyCode::Char('5'))).unwrap();
+        assert!(matches!(app.mode, Mode::Normal));
+        assert_eq!(app.tasks[0].queue, waiting);
+        assert!(temp.path().join("waiting").join("abc.md").exists());
+    }
+
+    #[test]
+    fn shift_j_and_k_reorder_the_selected_task() {
+        let temp = TempDir::new().unwrap();
+        let mut app = test_app_with_task(&temp);
+        let mut other = Task::new("def".to_string(), "Other task", Utc::now());
+        other.queue = Queue::Now;
+        app.repo.create(&other).unwrap();
+        app.refresh().unwrap();
+        app.focused_panel = FocusedPanel::TaskList;
+        let ids = |app: &TuiApp| {
+            app.current_queue_tasks()
+                .iter()
+                .map(|task| task.id.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&app), ["def", "abc"]);
+        app.task_list_state.select(Some(0));
+
+        handle_key(&mut app, key(KeyCode::Char('J'))).unwrap();
+        assert_eq!(ids(&app), ["abc", "def"]);
+        assert_eq!(app.selected_task().unwrap().id, "def");
+
+        handle_key(&mut app, key(KeyCode::Char('K'))).unwrap();
+        assert_eq!(ids(&app), ["def", "abc"]);
+        assert_eq!(app.selected_task().unwrap().id, "def");
+        assert!(app.repo.read("def").unwrap().rank.is_some());
+    }
+
+    #[test]
+    fn triage_digits_set_priority_before_dispatching() {
+        let temp = TempDir::new().unwrap();
+        let mut app = test_app(&temp);
+        app.repo
+            .create(&Task::new("abc".to_string(), "Inbox task", Utc::now()))
+            .unwrap();
+        app.refresh().unwrap();
+        app.enter_triage();
+
+        handle_key(&mut app, key(KeyCode::Char('1'))).unwrap();
+        assert!(matches!(app.mode, Mode::Triage));
+        assert_eq!(app.repo.read("abc").unwrap().priority, Some(Priority::P1));
+        handle_key(&mut app, key(KeyCode::Char('1'))).unwrap();
+        assert_eq!(app.repo.read("abc").unwrap().priority, None);
+
+        handle_key(&mut app, key(KeyCode::Char('0'))).unwrap();
+        handle_key(&mut app, key(KeyCode::Char('s'))).unwrap();
+        let task = app.repo.read("abc").unwrap();
+        assert_eq!(task.queue, Queue::Now);
+        assert_eq!(task.priority, Some(Priority::P0));
+    }
+
     #[test]
     fn j_scrolls_detail_when_detail_focused() {
         let temp = TempDir::new().unwrap();
@@ -2,12 +2,13 @@ mod actions;
 mod app_state;
 mod event;
 mod ui;
+mod watch;
 mod widgets;
 
 use std::io;
 use std::io::Write as _;
 use std::process::Command;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crossterm::{
     event::Event,
@@ -70,7 +71,25 @@ fn run_loop(
         match poll_event()? {
             Some(Event::Key(key)) => {
                 app.needs_redraw = true;
-                match event::handle_key(app, key)? {
+                let effect = match event::handle_key(app, key) {
+                    Ok(effect) => effect,
+                    // The merged file, markers and all, is already written;
+                    // show it and say what conflicted instead of quitting.
+                    Err(error @ AppError::EditConflict { .. }) => {
+                        app.merge_external_changes()?;
+                        app.set_status(error.to_string());
+                        SideEffect::None
+                    }
+                    // Every action takes the lock before its first change and
+                    // keeps it through its last, multi-step ones included, so
+                    // a busy repository means this one changed nothing.
+                    Err(error @ AppError::RepositoryBusy { .. }) => {
+                        app.set_status(format!("Nothing was changed: {error}"));
+                        SideEffect::None
+                    }
+                    Err(error) => return Err(error),
+                };
+                match effect {
                     SideEffect::None => {}
                     SideEffect::Quit => return Ok(()),
                     SideEffect::SuspendForEditor { task_id } => {
@@ -86,6 +105,13 @@ fn run_loop(
             }
             _ => {}
         }
+
+        if app.poll_external_changes(Instant::now()) {
+            if let Err(e) = app.merge_external_changes() {
+                app.set_status(format!("Reload failed: {e}"));
+            }
+            app.needs_redraw = true;
+        }
     }
 }
 
@@ -1,3 +1,4 @@
+use chrono::Local;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
@@ -10,6 +11,7 @@ use super::{
     app_state::{FocusedPanel, Mode, TuiApp},
     widgets,
 };
+use crate::domain::query::Query;
 
 pub fn draw(frame: &mut Frame, app: &mut TuiApp) {
     let outer = Layout::default()
@@ -22,6 +24,8 @@ pub fn draw(frame: &mut Frame, app: &mut TuiApp) {
 
     if is_triage_context(&app.mode) {
         draw_triage(frame, main_area, app);
+    } else if matches!(app.mode, Mode::Review { .. }) {
+        draw_review(frame, main_area, app);
     } else if matches!(app.mode, Mode::Search { .. }) {
         draw_search(frame, main_area, app);
     } else {
@@ -32,7 +36,12 @@ pub fn draw(frame: &mut Frame, app: &mut TuiApp) {
 
     // Overlay: add form
     if let Mode::AddForm { title, queue } = &app.mode {
-        widgets::add_form::render(frame, title, *queue);
+        widgets::add_form::render(frame, title, *queue, &app.add_queues());
+    }
+
+    // Overlay: stats
+    if let Mode::Stats(stats) = &app.mode {
+        widgets::stats::render(frame, stats);
     }
 }
 
@@ -40,7 +49,7 @@ fn draw_normal(frame: &mut Frame, area: Rect, app: &mut TuiApp) {
     let panels = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Length(14),
+            Constraint::Length(18),
             Constraint::Min(20),
             Constraint::Percentage(35),
         ])
@@ -63,6 +72,7 @@ fn draw_normal(frame: &mut Frame, area: Rect, app: &mut TuiApp) {
         task_list_area,
         filter,
         &tasks,
+        &app.blocked,
         selected_index,
         focused == FocusedPanel::TaskList,
     );
@@ -79,10 +89,23 @@ fn draw_normal(frame: &mut Frame, area: Rect, app: &mut TuiApp) {
 fn draw_triage(frame: &mut Frame, area: Rect, app: &TuiApp) {
     let progress = format!("{}/{}", app.triage.index + 1, app.triage.task_ids.len());
     let task = app.current_triage_task();
-    widgets::triage::render(frame, area, task, &progress);
+    let blockers = task
+        .map(|task| app.blocked.blockers(&task.id))
+        .unwrap_or_default();
+    widgets::triage::render(frame, area, task, blockers, &progress);
+}
+
+fn draw_review(frame: &mut Frame, area: Rect, app: &TuiApp) {
+    let progress = format!("{}/{}", app.review.index + 1, app.review.task_ids.len());
+    let task = app.current_review_task();
+    let blockers = task
+        .map(|task| app.blocked.blockers(&task.id))
+        .unwrap_or_default();
+    widgets::review::render(frame, area, task, blockers, &progress);
 }
 
 fn draw_search(frame: &mut Frame, area: Rect, app: &mut TuiApp) {
+    let queues = app.config.queue_dirs.queues();
     let Mode::Search {
         query,
         results,
@@ -97,6 +120,14 @@ fn draw_search(frame: &mut Frame, area: Rect, app: &mut TuiApp) {
         .constraints([Constraint::Length(3), Constraint::Min(1)])
         .split(area);
 
+    let title = match Query::parse(query, Local::now().date_naive(), &queues) {
+        Ok(_) => Line::from(format!(" Search ({} results) ", results.len())),
+        Err(error) => Line::from(Span::styled(
+            format!(" {error} "),
+            Style::default().fg(Color::Red),
+        )),
+    };
+
     // Search input
     let input = Paragraph::new(Line::from(vec![
         Span::styled("/ ", Style::default().fg(Color::Yellow)),
@@ -105,11 +136,7 @@ fn draw_search(frame: &mut Frame, area: Rect, app: &mut TuiApp) {
             Style::default().add_modifier(Modifier::BOLD),
         ),
     ]))
-    .block(
-        Block::default()
-            .borders(Borders::BOTTOM)
-            .title(format!(" Search ({} results) ", results.len())),
-    );
+    .block(Block::default().borders(Borders::BOTTOM).title(title));
     frame.render_widget(input, rows[0]);
 
     // Results list
@@ -0,0 +1,137 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::storage::repo::TaskRepo;
+
+/// How often the task directories are checked for outside changes.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Notices changes made to the tasks root and queue directories outside the
+/// TUI, such as an Obsidian edit, a `git pull`, or a second `tqs` process.
+///
+/// Each check stats the directories and the Markdown files directly inside
+/// them and compares the result with the previous check; nothing is parsed.
+/// Archived tasks are not watched, like they are not listed.
+pub struct TaskWatcher {
+    dirs: Vec<PathBuf>,
+    snapshot: Snapshot,
+    last_check: Instant,
+}
+
+/// Modification time and size of every watched path.
+type Snapshot = BTreeMap<PathBuf, (Option<SystemTime>, u64)>;
+
+impl TaskWatcher {
+    pub fn new(repo: &TaskRepo) -> Self {
+        let mut dirs = vec![repo.root().to_path_buf()];
+        for queue in repo.queue_dirs().queues() {
+            let dir = repo.queue_dir(queue);
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+        let snapshot = snapshot(&dirs);
+        Self {
+            dirs,
+            snapshot,
+            last_check: Instant::now(),
+        }
+    }
+
+    /// Whether anything changed since the last check. Checks closer together
+    /// than `WATCH_INTERVAL` report no change without touching the disk.
+    pub fn poll(&mut self, now: Instant) -> bool {
+        if now.duration_since(self.last_check) < WATCH_INTERVAL {
+            return false;
+        }
+        self.last_check = now;
+        let current = snapshot(&self.dirs);
+        let changed = current != self.snapshot;
+        self.snapshot = current;
+        changed
+    }
+
+    /// Takes the current state as seen, without reporting it as a change.
+    pub fn resync(&mut self) {
+        self.snapshot = snapshot(&self.dirs);
+    }
+}
+
+/// Unreadable or missing directories simply contribute nothing, so a queue
+/// directory appearing later shows up as a change.
+fn snapshot(dirs: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    for dir in dirs {
+        stamp(&mut snapshot, dir);
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) == Some("md") {
+                stamp(&mut snapshot, &path);
+            }
+        }
+    }
+    snapshot
+}
+
+fn stamp(snapshot: &mut Snapshot, path: &Path) {
+    if let Ok(metadata) = fs::metadata(path) {
+        snapshot.insert(
+            path.to_path_buf(),
+            (metadata.modified().ok(), metadata.len()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TaskWatcher, WATCH_INTERVAL};
+    use crate::storage::config::QueueDirs;
+    use crate::storage::repo::TaskRepo;
+    use std::{fs, time::Instant};
+    use tempfile::TempDir;
+
+    #[test]
+    fn reports_created_edited_and_removed_task_files() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let repo = TaskRepo::new(temp.path().to_path_buf(), QueueDirs::default());
+        let mut watcher = TaskWatcher::new(&repo);
+        let mut now = Instant::now();
+        let mut tick = || {
+            now += WATCH_INTERVAL;
+            now
+        };
+        assert!(!watcher.poll(tick()));
+
+        let path = temp.path().join("inbox").join("abc.md");
+        fs::create_dir_all(temp.path().join("inbox")).expect("queue dir should exist");
+        fs::write(&path, "first").expect("task should be written");
+        assert!(watcher.poll(tick()));
+        assert!(!watcher.poll(tick()));
+
+        fs::write(&path, "second version").expect("task should be written");
+        assert!(watcher.poll(tick()));
+
+        fs::remove_file(&path).expect("task should be removed");
+        assert!(watcher.poll(tick()));
+    }
+
+    #[test]
+    fn checks_wait_for_the_interval() {
+        let temp = TempDir::new().expect("temp dir should exist");
+        let repo = TaskRepo::new(temp.path().to_path_buf(), QueueDirs::default());
+        let mut watcher = TaskWatcher::new(&repo);
+        fs::create_dir_all(temp.path().join("now")).expect("queue dir should exist");
+        fs::write(temp.path().join("now").join("abc.md"), "task").expect("task should be written");
+
+        let start = Instant::now();
+        assert!(!watcher.poll(start));
+        assert!(watcher.poll(start + WATCH_INTERVAL));
+    }
+}
@@ -1,27 +1,26 @@
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
+use super::centered_rect;
 use crate::domain::task::Queue;
 
-/// Queues available for new tasks (not Done).
-const ADD_QUEUES: [Queue; 4] = [Queue::Inbox, Queue::Now, Queue::Next, Queue::Later];
-
-pub fn cycle_queue(current: Queue) -> Queue {
-    let idx = ADD_QUEUES.iter().position(|q| *q == current).unwrap_or(0);
-    ADD_QUEUES[(idx + 1) % ADD_QUEUES.len()]
+/// `queues` are the queues available for new tasks, from `TuiApp::add_queues`.
+pub fn cycle_queue(current: Queue, queues: &[Queue]) -> Queue {
+    let idx = queues.iter().position(|q| *q == current).unwrap_or(0);
+    queues[(idx + 1) % queues.len()]
 }
 
-pub fn cycle_queue_back(current: Queue) -> Queue {
-    let idx = ADD_QUEUES.iter().position(|q| *q == current).unwrap_or(0);
-    ADD_QUEUES[(idx + ADD_QUEUES.len() - 1) % ADD_QUEUES.len()]
+pub fn cycle_queue_back(current: Queue, queues: &[Queue]) -> Queue {
+    let idx = queues.iter().position(|q| *q == current).unwrap_or(0);
+    queues[(idx + queues.len() - 1) % queues.len()]
 }
 
-pub fn render(frame: &mut Frame, title: &str, queue: Queue) {
+pub fn render(frame: &mut Frame, title: &str, queue: Queue, queues: &[Queue]) {
     let area = centered_rect(50, 7, frame.area());
 
     frame.render_widget(Clear, area);
@@ -55,7 +54,7 @@ pub fn render(frame: &mut Frame, title: &str, queue: Queue) {
     frame.render_widget(Paragraph::new(title_line), rows[0]);
 
     // Queue selector
-    let queue_spans: Vec<Span> = ADD_QUEUES
+    let queue_spans: Vec<Span> = queues
         .iter()
         .map(|q| {
             if *q == queue {
@@ -88,23 +87,3 @@ pub fn render(frame: &mut Frame, title: &str, queue: Queue) {
     ]);
     frame.render_widget(Paragraph::new(help), rows[3]);
 }
-
-fn centered_rect(percent_x: u16, height: u16, area: Rect) -> Rect {
-    let vertical = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Min(0),
-            Constraint::Length(height),
-            Constraint::Min(0),
-        ])
-        .split(area);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(vertical[1])[1]
-}
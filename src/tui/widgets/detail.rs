@@ -1,12 +1,14 @@
+use chrono::{Local, Utc};
 use ratatui::{
     Frame,
     layout::Rect,
+    style::{Modifier, Style},
     text::Line,
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 
 use super::panel_border_style;
-use crate::domain::task::Task;
+use crate::domain::{date::format_duration, task::Task};
 
 pub fn render(
     frame: &mut Frame,
@@ -33,11 +35,12 @@ pub fn render(
         .title(title)
         .border_style(border_style);
 
-    let lines: Vec<Line> = task
+    let mut lines: Vec<Line> = task
         .body
         .lines()
         .map(|l| Line::from(l.to_string()))
         .collect();
+    lines.extend(history_lines(task));
 
     let paragraph = Paragraph::new(lines)
         .block(block)
@@ -46,3 +49,29 @@ pub fn render(
 
     frame.render_widget(paragraph, area);
 }
+
+/// Time spent in each queue, shown below the body once the task has moved.
+fn history_lines(task: &Task) -> Vec<Line<'static>> {
+    if task.history.is_empty() {
+        return Vec::new();
+    }
+
+    let dim = Style::default().add_modifier(Modifier::DIM);
+    let now = Utc::now();
+    let mut lines = vec![Line::from(""), Line::styled("History", dim)];
+    for stint in task.queue_stints() {
+        let mut spent = format_duration(stint.duration(now));
+        if stint.until.is_none() {
+            spent.push_str(" so far");
+        }
+        lines.push(Line::styled(
+            format!(
+                "{}  {:<5}  {spent}",
+                stint.since.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+                stint.queue.to_string()
+            ),
+            dim,
+        ));
+    }
+    lines
+}
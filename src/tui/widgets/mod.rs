@@ -1,11 +1,16 @@
 pub mod add_form;
 pub mod detail;
+pub mod review;
 pub mod sidebar;
+pub mod stats;
 pub mod status_bar;
 pub mod task_list;
 pub mod triage;
 
-use ratatui::style::{Color, Style};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+};
 
 pub fn panel_border_style(focused: bool) -> Style {
     if focused {
@@ -14,3 +19,24 @@ pub fn panel_border_style(focused: bool) -> Style {
         Style::default().fg(Color::DarkGray)
     }
 }
+
+/// A popup area `percent_x` wide and `height` rows tall, centered in `area`.
+pub fn centered_rect(percent_x: u16, height: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(height),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
@@ -0,0 +1,37 @@
+use chrono::Utc;
+use ratatui::{Frame, layout::Rect};
+
+use super::triage::{hint_line, render_card};
+use crate::domain::{date::format_duration, task::Task};
+
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    task: Option<&Task>,
+    blockers: &[String],
+    progress: &str,
+) {
+    let mut hints = vec![("k", "keep")];
+    if task.is_some_and(|task| task.queue.promoted().is_some()) {
+        hints.push(("+", "promote"));
+    }
+    if task.is_some_and(|task| task.queue.demoted().is_some()) {
+        hints.push(("-", "demote"));
+    }
+    hints.extend([
+        ("d", "done"),
+        ("x", "del"),
+        ("Space", "skip"),
+        ("q", "quit"),
+    ]);
+
+    let title = match task {
+        Some(task) => format!(
+            " Review {} {progress} · updated {} ago ",
+            task.queue,
+            format_duration(Utc::now() - task.updated_at)
+        ),
+        None => format!(" Review {progress} "),
+    };
+    render_card(frame, area, &title, task, blockers, hint_line(&hints));
+}
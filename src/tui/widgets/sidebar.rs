@@ -28,6 +28,20 @@ pub fn render(frame: &mut Frame, area: Rect, app: &TuiApp, focused: bool) {
                 let is_active = i == app.active_sidebar_index;
                 queue_item("all", counts.total, is_active)
             }
+            SidebarEntry::Heading(title) => ListItem::new(Line::from(Span::styled(
+                format!("  {title}"),
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            ))),
+            SidebarEntry::View(name) => {
+                let is_active = i == app.active_sidebar_index;
+                queue_item(name, app.view_tasks(name).len(), is_active)
+            }
+            SidebarEntry::Tag(tag) => {
+                let is_active = i == app.active_sidebar_index;
+                queue_item(&format!("#{tag}"), app.tag_count(tag), is_active)
+            }
         })
         .collect();
 
@@ -39,12 +53,22 @@ pub fn render(frame: &mut Frame, area: Rect, app: &TuiApp, focused: bool) {
     frame.render_widget(list, area);
 }
 
+/// Width of the label column; longer labels (usually views and tags) are truncated.
+const LABEL_WIDTH: usize = 10;
+
 fn queue_item(label: &str, count: usize, is_active: bool) -> ListItem<'static> {
     let marker = if is_active { ">" } else { " " };
+    let label = if label.chars().count() > LABEL_WIDTH {
+        let mut truncated = label.chars().take(LABEL_WIDTH - 1).collect::<String>();
+        truncated.push('…');
+        truncated
+    } else {
+        label.to_string()
+    };
     let line = Line::from(vec![
         Span::raw(format!("{marker} ")),
         Span::styled(
-            format!("{:<6}", label),
+            format!("{label:<LABEL_WIDTH$}"),
             if is_active {
                 Style::default()
                     .fg(Color::Magenta)
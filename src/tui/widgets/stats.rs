@@ -0,0 +1,121 @@
+use chrono::Local;
+use ratatui::{
+    Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::centered_rect;
+use crate::domain::{date::format_duration, stats::Stats};
+
+/// Widest completion bar, in cells.
+const BAR_WIDTH: usize = 20;
+
+pub fn render(frame: &mut Frame, stats: &Stats) {
+    let lines = stats_lines(stats);
+    let height = (lines.len() as u16 + 2).min(frame.area().height);
+    let area = centered_rect(60, height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let title = match &stats.tag {
+        Some(tag) => format!(" Stats #{tag} "),
+        None => " Stats ".to_string(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_bottom(" any key:close ")
+        .border_style(Style::default().fg(Color::Cyan));
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn stats_lines(stats: &Stats) -> Vec<Line<'static>> {
+    let label = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let dim = Style::default().add_modifier(Modifier::DIM);
+
+    let mut lines = vec![
+        Line::styled(
+            format!(
+                "{} to {}",
+                stats.since.with_timezone(&Local).format("%Y-%m-%d"),
+                stats.until.with_timezone(&Local).format("%Y-%m-%d")
+            ),
+            dim,
+        ),
+        Line::from(vec![
+            Span::styled("Completed ", label),
+            Span::raw(format!("{} (by {})", stats.completed, stats.by)),
+        ]),
+    ];
+
+    let most = stats
+        .periods
+        .iter()
+        .map(|period| period.completed)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    for period in &stats.periods {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {}  ", period.start), dim),
+            Span::raw(format!("{:>3} ", period.completed)),
+            Span::styled(
+                "\u{2588}".repeat(period.completed * BAR_WIDTH / most),
+                Style::default().fg(Color::Green),
+            ),
+        ]));
+    }
+
+    let lead_time = match &stats.lead_time {
+        Some(lead_time) => format!(
+            "median {}, mean {}, longest {}",
+            format_duration(lead_time.median),
+            format_duration(lead_time.mean),
+            format_duration(lead_time.max)
+        ),
+        None => "no completed tasks".to_string(),
+    };
+    lines.push(Line::from(vec![
+        Span::styled("Lead time ", label),
+        Span::raw(lead_time),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Inbox flow ", label),
+        Span::raw(format!(
+            "{} in, {} out",
+            stats.inbox.inflow, stats.inbox.outflow
+        )),
+    ]));
+
+    lines.push(Line::from(""));
+    lines.push(Line::styled("Queue ages", label));
+    for queue in &stats.queues {
+        let median = queue
+            .median_age
+            .map(|age| format!(", median {}", format_duration(age)))
+            .unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<5}  ", queue.queue.to_string()),
+                Style::default().fg(Color::Magenta),
+            ),
+            Span::raw(format!("{} open{median}", queue.open)),
+        ]));
+        for task in &queue.oldest {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("    {}  ", task.id),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(format!("{:>4}  ", format_duration(task.age)), dim),
+                Span::raw(task.title.clone()),
+            ]));
+        }
+    }
+    lines
+}
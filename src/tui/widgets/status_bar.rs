@@ -11,9 +11,18 @@ use crate::tui::app_state::{Mode, TuiApp};
 pub fn render(frame: &mut Frame, area: Rect, app: &TuiApp) {
     let line = match &app.mode {
         Mode::Normal => normal_line(app, area.width),
-        Mode::AddForm { .. } | Mode::Triage | Mode::Search { .. } => return,
+        Mode::AddForm { .. } | Mode::Triage | Mode::Search { .. } | Mode::Stats(_) => return,
         Mode::ConfirmDelete { task_id, .. } => confirm_delete_line(task_id),
-        Mode::MoveTarget { .. } => move_target_line(),
+        Mode::Review {
+            confirm_delete: false,
+        } => return,
+        Mode::Review {
+            confirm_delete: true,
+        } => match app.current_review_task() {
+            Some(task) => confirm_delete_line(&task.id),
+            None => return,
+        },
+        Mode::MoveTarget { .. } => move_target_line(app),
     };
 
     let bar = Paragraph::new(line);
@@ -36,6 +45,8 @@ fn normal_line(app: &TuiApp, area_width: u16) -> Line<'static> {
         Span::raw(":panel "),
         hint("j/k"),
         Span::raw(":nav "),
+        hint("J/K"),
+        Span::raw(":reorder "),
         hint("Tab"),
         Span::raw(":queue "),
         hint("a"),
@@ -50,10 +61,16 @@ fn normal_line(app: &TuiApp, area_width: u16) -> Line<'static> {
         Span::raw(":del "),
         hint("e"),
         Span::raw(":edit "),
+        hint("u"),
+        Span::raw(":undo "),
         hint("/"),
         Span::raw(":search "),
         hint("t"),
         Span::raw(":triage "),
+        hint("R"),
+        Span::raw(":review "),
+        hint("S"),
+        Span::raw(":stats "),
         hint("q"),
         Span::raw(":quit"),
     ];
@@ -75,15 +92,15 @@ fn normal_line(app: &TuiApp, area_width: u16) -> Line<'static> {
 fn confirm_delete_line(task_id: &str) -> Line<'static> {
     Line::from(vec![
         mode_badge("Delete"),
-        Span::raw(format!(" Delete {task_id}? ")),
+        Span::raw(format!(" Move {task_id} to the trash? ")),
         hint("y"),
         Span::raw(":yes "),
         Span::raw("any other key:cancel"),
     ])
 }
 
-fn move_target_line() -> Line<'static> {
-    Line::from(vec![
+fn move_target_line(app: &TuiApp) -> Line<'static> {
+    let mut spans = vec![
         mode_badge("Move"),
         Span::raw(" Move to: "),
         hint("i"),
@@ -94,8 +111,13 @@ fn move_target_line() -> Line<'static> {
         Span::raw(":next "),
         hint("l"),
         Span::raw(":later "),
-        Span::raw("Esc:cancel"),
-    ])
+    ];
+    for (digit, queue) in ('5'..='9').zip(app.custom_move_targets()) {
+        spans.push(hint(&digit.to_string()));
+        spans.push(Span::raw(format!(":{queue} ")));
+    }
+    spans.push(Span::raw("Esc:cancel"));
+    Line::from(spans)
 }
 
 fn mode_badge(label: &str) -> Span<'static> {
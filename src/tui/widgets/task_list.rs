@@ -1,3 +1,4 @@
+use chrono::Local;
 use ratatui::{
     Frame,
     layout::Rect,
@@ -7,7 +8,10 @@ use ratatui::{
 };
 
 use super::panel_border_style;
-use crate::domain::task::Task;
+use crate::domain::date::{DueStatus, describe_due};
+use crate::domain::deps::BlockedIndex;
+use crate::domain::priority::Priority;
+use crate::domain::task::{Task, format_tags};
 use crate::tui::app_state::QueueFilter;
 
 pub fn render(
@@ -15,15 +19,22 @@ pub fn render(
     area: Rect,
     filter: QueueFilter,
     tasks: &[&Task],
+    blocked: &BlockedIndex,
     selected: Option<usize>,
     focused: bool,
 ) {
-    let title = match filter {
+    let title = match &filter {
         QueueFilter::Single(queue) => format!(" Tasks in queue {queue} ({}) ", tasks.len()),
         QueueFilter::All => format!(" All tasks ({}) ", tasks.len()),
+        QueueFilter::View(name) => format!(" View {name} ({}) ", tasks.len()),
+        QueueFilter::Tag(tag) => format!(" Tasks tagged #{tag} ({}) ", tasks.len()),
     };
 
-    let show_queue_tag = matches!(filter, QueueFilter::All);
+    let today = Local::now().date_naive();
+    let show_queue_tag = matches!(
+        filter,
+        QueueFilter::All | QueueFilter::View(_) | QueueFilter::Tag(_)
+    );
 
     let items: Vec<ListItem> = tasks
         .iter()
@@ -35,11 +46,38 @@ pub fn render(
                     Style::default().fg(Color::Magenta),
                 ));
             }
-            spans.push(Span::styled(
-                format!("{:<8}", task.id),
-                Style::default().fg(Color::Cyan),
-            ));
-            spans.push(Span::raw(&task.title));
+            let blockers = blocked.blockers(&task.id);
+            let dim = if blockers.is_empty() {
+                Style::default()
+            } else {
+                Style::default().add_modifier(Modifier::DIM)
+            };
+            spans.push(Span::styled(format!("{:<8}", task.id), dim.fg(Color::Cyan)));
+            if let Some(priority) = task.priority {
+                spans.push(Span::styled(
+                    format!("[{priority}] "),
+                    priority_style(priority),
+                ));
+            }
+            spans.push(Span::styled(&task.title, dim));
+            if !task.tags.is_empty() {
+                spans.push(Span::styled(
+                    format!("  {}", format_tags(&task.tags)),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            if let (Some(due), Some(status)) = (task.due, task.due_status(today)) {
+                spans.push(Span::styled(
+                    format!("  [{}]", describe_due(due, today)),
+                    due_style(status),
+                ));
+            }
+            if !blockers.is_empty() {
+                spans.push(Span::styled(
+                    format!("  [blocked by {}]", blockers.join(", ")),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
             ListItem::new(Line::from(spans))
         })
         .collect();
@@ -61,3 +99,22 @@ pub fn render(
     let mut state = ListState::default().with_selected(selected);
     frame.render_stateful_widget(list, area, &mut state);
 }
+
+pub(crate) fn priority_style(priority: Priority) -> Style {
+    match priority {
+        Priority::P0 => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        Priority::P1 => Style::default().fg(Color::Red),
+        Priority::P2 => Style::default().fg(Color::Yellow),
+        Priority::P3 => Style::default().fg(Color::DarkGray),
+    }
+}
+
+fn due_style(status: DueStatus) -> Style {
+    match status {
+        DueStatus::Overdue => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        DueStatus::Today => Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+        DueStatus::Upcoming => Style::default().fg(Color::DarkGray),
+    }
+}
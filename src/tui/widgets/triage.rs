@@ -6,12 +6,49 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 
+use super::task_list::priority_style;
 use crate::domain::task::Task;
 
-pub fn render(frame: &mut Frame, area: Rect, task: Option<&Task>, progress: &str) {
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    task: Option<&Task>,
+    blockers: &[String],
+    progress: &str,
+) {
+    // Action hints — same keys as normal mode, plus Space to skip
+    let hints = hint_line(&[
+        ("d", "done"),
+        ("s", "start"),
+        ("m", "move"),
+        ("x", "del"),
+        ("e", "edit"),
+        ("0-3", "priority"),
+        ("Space", "skip"),
+        ("q", "quit"),
+    ]);
+    render_card(
+        frame,
+        area,
+        &format!(" Triage {progress} "),
+        task,
+        blockers,
+        hints,
+    );
+}
+
+/// One task with its body and a line of key hints, as shown by triage and review.
+pub fn render_card(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    task: Option<&Task>,
+    blockers: &[String],
+    hints: Line,
+) {
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(format!(" Triage {progress} "))
+        .title(title.to_string())
         .border_style(Style::default().fg(Color::Yellow));
 
     let Some(task) = task else {
@@ -32,11 +69,30 @@ pub fn render(frame: &mut Frame, area: Rect, task: Option<&Task>, progress: &str
         ])
         .split(inner);
 
-    // Header: task id + title
-    let header = Line::from(vec![
-        Span::styled(format!("{}  ", task.id), Style::default().fg(Color::Cyan)),
-        Span::styled(&task.title, Style::default().add_modifier(Modifier::BOLD)),
-    ]);
+    // Header: task id + title, dimmed with its blockers when blocked
+    let title_style = if blockers.is_empty() {
+        Style::default().add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().add_modifier(Modifier::BOLD | Modifier::DIM)
+    };
+    let mut header_spans = vec![Span::styled(
+        format!("{}  ", task.id),
+        Style::default().fg(Color::Cyan),
+    )];
+    if let Some(priority) = task.priority {
+        header_spans.push(Span::styled(
+            format!("[{priority}] "),
+            priority_style(priority),
+        ));
+    }
+    header_spans.push(Span::styled(&task.title, title_style));
+    if !blockers.is_empty() {
+        header_spans.push(Span::styled(
+            format!("  [blocked by {}]", blockers.join(", ")),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    let header = Line::from(header_spans);
     frame.render_widget(Paragraph::new(header), rows[0]);
 
     // Body preview
@@ -48,22 +104,21 @@ pub fn render(frame: &mut Frame, area: Rect, task: Option<&Task>, progress: &str
     let body = Paragraph::new(body_lines).wrap(Wrap { trim: false });
     frame.render_widget(body, rows[1]);
 
-    // Action hints — same keys as normal mode, plus Space to skip
-    let hints = Line::from(vec![
-        Span::styled("d", Style::default().fg(Color::Yellow)),
-        Span::raw(":done "),
-        Span::styled("s", Style::default().fg(Color::Yellow)),
-        Span::raw(":start "),
-        Span::styled("m", Style::default().fg(Color::Yellow)),
-        Span::raw(":move "),
-        Span::styled("x", Style::default().fg(Color::Yellow)),
-        Span::raw(":del "),
-        Span::styled("e", Style::default().fg(Color::Yellow)),
-        Span::raw(":edit "),
-        Span::styled("Space", Style::default().fg(Color::Yellow)),
-        Span::raw(":skip "),
-        Span::styled("q", Style::default().fg(Color::Yellow)),
-        Span::raw(":quit"),
-    ]);
     frame.render_widget(Paragraph::new(hints), rows[2]);
 }
+
+/// Key hints such as `d:done s:start`, with the keys highlighted.
+pub fn hint_line(hints: &[(&str, &str)]) -> Line<'static> {
+    let mut spans = Vec::new();
+    for (index, (key, label)) in hints.iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::raw(" "));
+        }
+        spans.push(Span::styled(
+            key.to_string(),
+            Style::default().fg(Color::Yellow),
+        ));
+        spans.push(Span::raw(format!(":{label}")));
+    }
+    Line::from(spans)
+}
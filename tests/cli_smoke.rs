@@ -318,6 +318,375 @@ fn move_prompts_for_queue_when_missing() {
     assert!(temp.path().join("now").join("task-1.md").exists());
 }
 
+#[test]
+fn review_walks_open_queues_and_prints_summary() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    write_task(
+        temp.path(),
+        "now",
+        "task-1",
+        "Current work",
+        "# Current work",
+    );
+    write_task(temp.path(), "next", "task-2", "Soon", "# Soon");
+    write_task(temp.path(), "later", "task-3", "Old idea", "# Old idea");
+    write_task(temp.path(), "later", "task-4", "Older idea", "# Older idea");
+    write_task(temp.path(), "inbox", "task-5", "Untriaged", "# Untriaged");
+
+    // Demote task-1, keep task-2, promote task-3, then quit at task-4.
+    tqs_cmd()
+        .env("TQS_TEST_MODE", "1")
+        .write_stdin("1\n0\n1\n\n")
+        .arg("--root")
+        .arg(temp.path())
+        .arg("review")
+        .assert()
+        .success()
+        .stdout(
+            contains("Reviewing now (1 tasks)")
+                .and(contains("Reviewing later (2 tasks)"))
+                .and(contains("updated"))
+                .and(contains("Untriaged").not())
+                .and(contains("1 kept, 1 promoted, 1 demoted")),
+        );
+
+    assert!(temp.path().join("next").join("task-1.md").exists());
+    assert!(temp.path().join("next").join("task-3.md").exists());
+    assert!(temp.path().join("later").join("task-4.md").exists());
+    let kept = fs::read_to_string(temp.path().join("next").join("task-2.md"))
+        .expect("task should be readable");
+    assert!(!kept.contains("updated_at: 2026-03-09T10:34:12Z"));
+}
+
+#[test]
+fn bulk_commands_preview_confirm_and_apply_to_every_task() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    write_task(temp.path(), "later", "task-1", "Old idea", "# Old idea");
+    write_task(temp.path(), "later", "task-2", "Older idea", "# Older idea");
+    write_task(
+        temp.path(),
+        "next",
+        "task-3",
+        "Current work",
+        "# Current work",
+    );
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["move", "task-1", "task-2", "inbox"])
+        .assert()
+        .code(2)
+        .stdout(contains("2 tasks to move to inbox:"))
+        .stderr(contains("pass --yes to apply"));
+    assert!(temp.path().join("later").join("task-1.md").exists());
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["move", "task-1", "task-2", "task-3", "next", "--yes"])
+        .assert()
+        .success()
+        .stdout(
+            contains("Moved task: task-1")
+                .and(contains("Moved task: task-2"))
+                .and(contains("Task task-3 is already in next"))
+                .and(contains("Moved 2 tasks to next, 1 unchanged")),
+        );
+    assert!(temp.path().join("next").join("task-1.md").exists());
+    assert!(temp.path().join("next").join("task-2.md").exists());
+
+    tqs_cmd()
+        .env("TQS_TEST_MODE", "1")
+        .write_stdin("y\n")
+        .arg("--root")
+        .arg(temp.path())
+        .args(["done", "--where", "title:idea"])
+        .assert()
+        .success()
+        .stdout(contains("2 tasks to complete:").and(contains("Completed 2 tasks")));
+    assert!(temp.path().join("done").join("task-1.md").exists());
+    assert!(temp.path().join("done").join("task-2.md").exists());
+    assert!(temp.path().join("next").join("task-3.md").exists());
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["delete", "task-1", "missing", "--yes"])
+        .assert()
+        .failure()
+        .stderr(contains("task not found: missing"));
+    assert!(temp.path().join("done").join("task-1.md").exists());
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["delete", "--where", "queue:done", "-y"])
+        .assert()
+        .success()
+        .stdout(contains("Deleted 2 tasks"));
+    assert!(!temp.path().join("done").join("task-1.md").exists());
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["start", "--where", "tag:none"])
+        .assert()
+        .success()
+        .stdout(contains("No matching tasks"));
+}
+
+#[test]
+fn delete_moves_tasks_to_trash_for_restore_and_purge() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    write_task(temp.path(), "next", "task-1", "Ship v2", "# Ship v2");
+    let trash = temp.path().join(".tqs").join("trash");
+
+    tqs_cmd()
+        .env("TQS_TEST_MODE", "1")
+        .write_stdin("y\n")
+        .arg("--root")
+        .arg(temp.path())
+        .args(["delete", "task-1"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "Deleted task: task-1 (restore with `tqs restore task-1`)",
+        ));
+    assert!(!temp.path().join("next").join("task-1.md").exists());
+    assert!(trash.join("task-1.md").exists());
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["trash", "list"])
+        .assert()
+        .success()
+        .stdout(contains("[next] task-1").and(contains("Ship v2")));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["restore", "task-1"])
+        .assert()
+        .success()
+        .stdout(contains("Restored task: task-1"));
+    assert!(temp.path().join("next").join("task-1.md").exists());
+    assert!(!trash.join("task-1.md").exists());
+
+    tqs_cmd()
+        .env("TQS_TEST_MODE", "1")
+        .write_stdin("y\n")
+        .arg("--root")
+        .arg(temp.path())
+        .args(["delete", "task-1"])
+        .assert()
+        .success();
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["trash", "purge"])
+        .assert()
+        .code(2)
+        .stderr(contains("pass --yes or --older-than"));
+    assert!(trash.join("task-1.md").exists());
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["trash", "purge", "--older-than", "30d"])
+        .assert()
+        .success()
+        .stdout(contains("Purged 0 tasks from the trash"));
+    assert!(trash.join("task-1.md").exists());
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["trash", "purge", "--yes"])
+        .assert()
+        .success()
+        .stdout(contains("Purged task: task-1").and(contains("Purged 1 tasks from the trash")));
+    assert!(!trash.join("task-1.md").exists());
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .arg("trash")
+        .assert()
+        .success()
+        .stdout(contains("Trash is empty"));
+}
+
+#[test]
+fn undo_redo_and_history_replay_recorded_changes() {
+    let temp = TempDir::new().expect("temp dir should exist");
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["undo"])
+        .assert()
+        .code(2)
+        .stderr(contains("nothing to undo"));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["add", "--no-edit", "--id", "task-1", "Ship v2"])
+        .assert()
+        .success();
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["move", "task-1", "next"])
+        .assert()
+        .success();
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["undo"])
+        .assert()
+        .success()
+        .stdout(contains("Undid #2: move to next task-1"));
+    assert!(temp.path().join("inbox").join("task-1.md").exists());
+    assert!(!temp.path().join("next").join("task-1.md").exists());
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .arg("history")
+        .assert()
+        .success()
+        .stdout(
+            contains("#3")
+                .and(contains("undo #2"))
+                .and(contains("move to next task-1  (undone)"))
+                .and(contains("add task-1")),
+        );
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["redo"])
+        .assert()
+        .success()
+        .stdout(contains("Redid #2: move to next task-1"));
+    assert!(temp.path().join("next").join("task-1.md").exists());
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["redo"])
+        .assert()
+        .code(2)
+        .stderr(contains("nothing to redo"));
+}
+
+#[test]
+fn show_lists_queue_history_after_moves() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    write_task(temp.path(), "inbox", "task-1", "Ship v2", "# Ship v2");
+
+    for args in [["move", "task-1", "next"], ["done", "task-1", "--no-edit"]] {
+        tqs_cmd()
+            .arg("--root")
+            .arg(temp.path())
+            .args(args)
+            .assert()
+            .success();
+    }
+
+    let content = fs::read_to_string(temp.path().join("done").join("task-1.md"))
+        .expect("task should be readable");
+    assert!(content.contains("history:\n- from: inbox\n  to: next\n"));
+    assert!(content.contains("- from: next\n  to: done\n"));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["show", "task-1"])
+        .assert()
+        .success()
+        .stdout(
+            contains("History:")
+                .and(contains("inbox"))
+                .and(contains("next   0m"))
+                .and(contains("so far")),
+        );
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["--format", "json", "show", "task-1"])
+        .assert()
+        .success()
+        .stdout(contains("\"history\": [").and(contains("\"from\": \"inbox\"")));
+}
+
+#[test]
+fn stats_reports_completions_lead_time_and_queue_ages() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    write_task(
+        temp.path(),
+        "later",
+        "old-1",
+        "Renew domain",
+        "# Renew domain",
+    );
+    for (id, tag) in [("task-1", "ops"), ("task-2", "home")] {
+        tqs_cmd()
+            .arg("--root")
+            .arg(temp.path())
+            .args(["add", "--no-edit", "--id", id, "--tag", tag, "Ship v2"])
+            .assert()
+            .success();
+    }
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["done", "--no-edit", "task-1"])
+        .assert()
+        .success();
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["stats", "--by", "day", "--since", "2d"])
+        .assert()
+        .success()
+        .stdout(
+            contains("Completed: 1 (by day)")
+                .and(contains("Lead time: median 0m"))
+                .and(contains("Inbox flow: 2 in, 1 out"))
+                .and(contains("inbox  1 open"))
+                .and(contains("old-1")),
+        );
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["--format", "json", "stats", "--tag", "home"])
+        .assert()
+        .success()
+        .stdout(
+            contains("\"completed\": 0")
+                .and(contains("\"lead_time\": null"))
+                .and(contains("\"tag\": \"home\""))
+                .and(contains("\"id\": \"task-2\"")),
+        );
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["stats", "--by", "month"])
+        .assert()
+        .code(2)
+        .stderr(contains("expected day or week"));
+}
+
 #[test]
 fn done_is_idempotent() {
     let temp = TempDir::new().expect("temp dir should exist");
@@ -325,75 +694,612 @@ fn done_is_idempotent() {
     tqs_cmd()
         .arg("--root")
         .arg(temp.path())
-        .arg("add")
-        .arg("--no-edit")
-        .arg("--id")
-        .arg("task-1")
-        .arg("Ship v2")
+        .arg("add")
+        .arg("--no-edit")
+        .arg("--id")
+        .arg("task-1")
+        .arg("Ship v2")
+        .assert()
+        .success();
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .arg("done")
+        .arg("--no-edit")
+        .arg("task-1")
+        .assert()
+        .success()
+        .stdout(contains("Completed task: task-1"));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .arg("done")
+        .arg("--no-edit")
+        .arg("task-1")
+        .assert()
+        .success()
+        .stdout(contains("already done"));
+}
+
+#[test]
+fn show_prints_metadata_path_and_body() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    write_task(
+        temp.path(),
+        "inbox",
+        "task-1",
+        "Ship v2",
+        "# Ship v2\n\n## Notes",
+    );
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .arg("show")
+        .arg("task-1")
+        .assert()
+        .success()
+        .stdout(contains("Path:").and(contains("# Ship v2")));
+}
+
+#[test]
+fn find_matches_body_text() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    write_task(
+        temp.path(),
+        "next",
+        "task-1",
+        "Investigate billing",
+        "# Investigate billing\n\nLook at cost explorer.",
+    );
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .arg("find")
+        .arg("cost explorer")
+        .assert()
+        .success()
+        .stdout(contains("task-1").and(contains("Investigate billing")));
+}
+
+#[test]
+fn find_and_list_where_accept_query_syntax() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    write_task(
+        temp.path(),
+        "next",
+        "task-1",
+        "Investigate billing",
+        "# Investigate billing\n\nLook at cost explorer.",
+    );
+    write_task(
+        temp.path(),
+        "now",
+        "task-2",
+        "Draft billing memo",
+        "# Draft billing memo",
+    );
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["find", "billing -title:draft"])
+        .assert()
+        .success()
+        .stdout(contains("task-1").and(contains("task-2").not()));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["list", "--where", "queue:now \"billing memo\""])
+        .assert()
+        .success()
+        .stdout(contains("task-2").and(contains("task-1").not()));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["list", "--where", "(queue:now OR queue:next) -explorer"])
+        .assert()
+        .success()
+        .stdout(contains("task-2").and(contains("task-1").not()));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["find", "billing (queue:now"])
+        .assert()
+        .failure()
+        .stderr(contains("invalid query at column 9: '(' is never closed"));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["find", "-title:draft"])
+        .assert()
+        .success()
+        .stdout(contains("task-1").and(contains("task-2").not()));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["list", "--where", "-queue:now"])
+        .assert()
+        .success()
+        .stdout(contains("task-1").and(contains("task-2").not()));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["find", "queue:nosuch"])
+        .assert()
+        .code(2)
+        .stderr(contains("queue 'nosuch' is not configured"));
+}
+
+#[test]
+fn tag_command_updates_tags_used_by_list_and_find_filters() {
+    let temp = TempDir::new().expect("temp dir should exist");
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["add", "--no-edit", "--id", "task-1", "--tag", "ops"])
+        .arg("Rotate keys")
+        .assert()
+        .success();
+    write_task(
+        temp.path(),
+        "inbox",
+        "task-2",
+        "Plan offsite",
+        "# Plan offsite",
+    );
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["tag", "task-1", "+aws", "-ops"])
+        .assert()
+        .success()
+        .stdout(contains("Updated tags for task-1: #aws"));
+
+    let content = fs::read_to_string(temp.path().join("inbox").join("task-1.md"))
+        .expect("task file should exist");
+    assert!(content.contains("tags:\n- aws\n"));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["list", "inbox", "--tag", "aws"])
+        .assert()
+        .success()
+        .stdout(contains("task-1").and(contains("task-2").not()));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["find", "--tag", "aws"])
+        .assert()
+        .success()
+        .stdout(contains("task-1").and(contains("task-2").not()));
+}
+
+#[test]
+fn due_command_sets_date_and_list_marks_overdue_tasks() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    write_task(temp.path(), "now", "task-1", "File taxes", "# File taxes");
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["due", "task-1", "2020-01-01"])
+        .assert()
+        .success()
+        .stdout(contains("Task task-1 is overdue"));
+
+    let content = fs::read_to_string(temp.path().join("now").join("task-1.md"))
+        .expect("task file should exist");
+    assert!(content.contains("due: 2020-01-01\n"));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["list", "now"])
+        .assert()
+        .success()
+        .stdout(contains("task-1  File taxes").and(contains("[overdue ")));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["due", "task-1", "none"])
+        .assert()
+        .success()
+        .stdout(contains("Cleared due date for task task-1"));
+
+    let content = fs::read_to_string(temp.path().join("now").join("task-1.md"))
+        .expect("task file should exist");
+    assert!(!content.contains("due:"));
+}
+
+#[test]
+fn add_rejects_unparseable_due_date() {
+    let temp = TempDir::new().expect("temp dir should exist");
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["add", "--no-edit", "--due", "someday", "Ship v2"])
+        .assert()
+        .failure()
+        .stderr(contains("invalid date 'someday'"));
+}
+
+#[test]
+fn done_on_recurring_task_creates_next_occurrence_in_inbox() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args([
+            "add",
+            "--no-edit",
+            "--id",
+            "task-1",
+            "--queue",
+            "now",
+            "--recur",
+            "every 1w",
+            "--due",
+            "today",
+            "--content=- [x] water the ficus",
+            "Water plants",
+        ])
+        .assert()
+        .success();
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["done", "--no-edit", "task-1"])
+        .assert()
+        .success()
+        .stdout(contains("Completed task: task-1").and(contains("Next occurrence: ")));
+
+    let done = fs::read_to_string(temp.path().join("done").join("task-1.md"))
+        .expect("completed task should stay in done");
+    assert!(done.contains("recur: every 1w\n"));
+
+    let inbox = fs::read_dir(temp.path().join("inbox"))
+        .expect("inbox should exist")
+        .map(|entry| entry.expect("entry should be readable").path())
+        .collect::<Vec<_>>();
+    assert_eq!(inbox.len(), 1);
+    let next = fs::read_to_string(&inbox[0]).expect("next occurrence should exist");
+    assert!(next.contains("title: Water plants\n"));
+    assert!(next.contains("recur: every 1w\n"));
+    assert!(next.contains("due: "));
+    assert!(next.contains("- [ ] water the ficus"));
+}
+
+#[test]
+fn block_marks_tasks_blocked_until_the_blocker_is_done() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    write_task(temp.path(), "now", "task-1", "Deploy", "# Deploy");
+    write_task(temp.path(), "now", "task-2", "Review PR", "# Review PR");
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["block", "task-1", "--on", "task-2"])
+        .assert()
+        .success()
+        .stdout(contains("Task task-1 is now blocked by task-2"));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["block", "task-2", "--on", "task-1"])
+        .assert()
+        .failure()
+        .stderr(contains("would create a dependency cycle"));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["list", "now"])
+        .assert()
+        .success()
+        .stdout(contains("[blocked by task-2]"));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["done", "--no-edit", "task-2"])
+        .assert()
+        .success()
+        .stdout(contains("Unblocked: task-1"));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["list", "now"])
+        .assert()
+        .success()
+        .stdout(contains("blocked by").not());
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["unblock", "task-1"])
+        .assert()
+        .success()
+        .stdout(contains("Task task-1 is no longer blocked"));
+
+    let content = fs::read_to_string(temp.path().join("now").join("task-1.md"))
+        .expect("task file should exist");
+    assert!(!content.contains("blocked_by"));
+}
+
+#[test]
+fn priority_is_set_by_add_and_prio_and_sorts_the_list() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    write_task(temp.path(), "now", "task-1", "Deploy", "# Deploy");
+    write_task(temp.path(), "now", "task-2", "Review PR", "# Review PR");
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["add", "--no-edit", "--queue", "now", "--priority", "low"])
+        .args(["--id", "task-3", "Fix CI"])
+        .assert()
+        .success();
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["prio", "task-2", "p0"])
+        .assert()
+        .success()
+        .stdout(contains("Task task-2 is now p0"));
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["prio", "task-2", "urgent"])
+        .assert()
+        .failure()
+        .stderr(contains("invalid priority 'urgent'"));
+
+    let assert = tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["list", "now", "--sort", "priority"])
+        .assert()
+        .success()
+        .stdout(contains("[p0] Review PR").and(contains("[p3] Fix CI")));
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).expect("stdout is utf-8");
+    let position = |id: &str| stdout.find(id).expect("task should be listed");
+    assert!(position("task-2") < position("task-3"));
+    assert!(position("task-3") < position("task-1"));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["find", "priority<=p1"])
+        .assert()
+        .success()
+        .stdout(contains("task-2").and(contains("task-3").not()));
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["list", "now", "--template", "{id} {priority}"])
+        .assert()
+        .success()
+        .stdout(contains("task-2 p0").and(contains("task-3 p3")));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["prio", "task-2", "none"])
         .assert()
-        .success();
+        .success()
+        .stdout(contains("Cleared priority for task task-2"));
+    let stored = fs::read_to_string(temp.path().join("now").join("task-2.md"))
+        .expect("task file should exist");
+    assert!(!stored.contains("priority:"));
+    let stored = fs::read_to_string(temp.path().join("now").join("task-3.md"))
+        .expect("task file should exist");
+    assert!(stored.contains("priority: p3"));
+}
+
+#[test]
+fn archive_moves_old_done_tasks_out_of_plain_listings() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    write_task(temp.path(), "done", "old-task", "Ship v1", "# Ship v1");
+    write_task(temp.path(), "now", "open-task", "Ship v2", "# Ship v2");
 
     tqs_cmd()
         .arg("--root")
         .arg(temp.path())
-        .arg("done")
-        .arg("--no-edit")
-        .arg("task-1")
+        .args(["archive", "--older-than", "30d"])
         .assert()
         .success()
-        .stdout(contains("Completed task: task-1"));
+        .stdout(contains("Archived task: old-task").and(contains("Archived 1 done tasks")));
+
+    let archived = temp
+        .path()
+        .join("done")
+        .join("2026")
+        .join("03")
+        .join("old-task.md");
+    assert!(archived.exists());
+    assert!(!temp.path().join("done").join("old-task.md").exists());
 
     tqs_cmd()
         .arg("--root")
         .arg(temp.path())
-        .arg("done")
-        .arg("--no-edit")
-        .arg("task-1")
+        .args(["list", "done"])
         .assert()
         .success()
-        .stdout(contains("already done"));
+        .stdout(contains("old-task").not());
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["find", "Ship"])
+        .assert()
+        .success()
+        .stdout(contains("open-task").and(contains("old-task").not()));
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["find", "Ship", "--include-archive"])
+        .assert()
+        .success()
+        .stdout(contains("open-task").and(contains("old-task")));
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["show", "old-task"])
+        .assert()
+        .failure();
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["show", "old-task", "--include-archive"])
+        .assert()
+        .success()
+        .stdout(contains("Ship v1"));
 }
 
 #[test]
-fn show_prints_metadata_path_and_body() {
+fn git_root_commits_changes_and_syncs_with_a_path_remote() {
     let temp = TempDir::new().expect("temp dir should exist");
-    write_task(
+    let git = |dir: &std::path::Path, args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .output()
+            .expect("git should run");
+        assert!(output.status.success(), "git {args:?} failed");
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+    let tasks_root = temp.path().join("tasks");
+    fs::create_dir_all(&tasks_root).expect("tasks root should exist");
+    git(
         temp.path(),
-        "inbox",
-        "task-1",
-        "Ship v2",
-        "# Ship v2\n\n## Notes",
+        &["init", "-q", "--bare", "-b", "main", "remote.git"],
+    );
+    git(&tasks_root, &["init", "-q", "-b", "main"]);
+    git(&tasks_root, &["config", "user.name", "Test"]);
+    git(&tasks_root, &["config", "user.email", "test@example.com"]);
+    git(&tasks_root, &["config", "commit.gpgsign", "false"]);
+    let remote = temp.path().join("remote.git");
+    git(
+        &tasks_root,
+        &["remote", "add", "origin", &remote.display().to_string()],
     );
 
     tqs_cmd()
         .arg("--root")
-        .arg(temp.path())
-        .arg("show")
-        .arg("task-1")
+        .arg(&tasks_root)
+        .arg("sync")
+        .assert()
+        .failure()
+        .stderr(contains("git is not enabled"));
+
+    let config_home = temp.path().join("config-home");
+    fs::create_dir_all(config_home.join("tqs")).expect("config dir should exist");
+    fs::write(
+        config_home.join("tqs").join("config.toml"),
+        format!("tasks_root = '{}'\n\n[git]\n", tasks_root.display()),
+    )
+    .expect("config file should be written");
+    let cmd = || {
+        let mut cmd = tqs_cmd();
+        cmd.env("XDG_CONFIG_HOME", &config_home);
+        cmd
+    };
+
+    cmd()
+        .args(["add", "--no-edit", "--id", "task-1", "Ship v2"])
+        .assert()
+        .success();
+    cmd().args(["start", "task-1"]).assert().success();
+    fs::write(tasks_root.join("notes.md"), "scratch").expect("note should be written");
+
+    cmd()
+        .arg("sync")
         .assert()
         .success()
-        .stdout(contains("Path:").and(contains("# Ship v2")));
+        .stdout(contains("Synced with origin/main"));
+
+    assert_eq!(
+        git(&remote, &["log", "--format=%s", "main"]),
+        "move task-1 inbox\u{2192}now\nadd task-1\n"
+    );
+    assert_eq!(git(&tasks_root, &["ls-files"]), "now/task-1.md\n");
 }
 
 #[test]
-fn find_matches_body_text() {
+fn rank_orders_tasks_within_a_queue_and_persists_it() {
     let temp = TempDir::new().expect("temp dir should exist");
-    write_task(
-        temp.path(),
-        "next",
-        "task-1",
-        "Investigate billing",
-        "# Investigate billing\n\nLook at cost explorer.",
-    );
+    write_task(temp.path(), "now", "task-1", "Deploy", "# Deploy");
+    write_task(temp.path(), "now", "task-2", "Review PR", "# Review PR");
+    write_task(temp.path(), "now", "task-3", "Fix CI", "# Fix CI");
+    write_task(temp.path(), "next", "task-4", "Plan", "# Plan");
+    let now_order = |root: &std::path::Path| {
+        let assert = tqs_cmd()
+            .arg("--root")
+            .arg(root)
+            .args(["list", "now"])
+            .assert()
+            .success();
+        let stdout =
+            String::from_utf8(assert.get_output().stdout.clone()).expect("stdout is utf-8");
+        let mut ids = ["task-1", "task-2", "task-3"];
+        ids.sort_by_key(|id| stdout.find(id).expect("task should be listed"));
+        ids
+    };
 
     tqs_cmd()
         .arg("--root")
         .arg(temp.path())
-        .arg("find")
-        .arg("cost explorer")
+        .args(["rank", "task-3", "--top"])
         .assert()
         .success()
-        .stdout(contains("task-1").and(contains("Investigate billing")));
+        .stdout(contains("Ranked task task-3 at the top in now"));
+    assert_eq!(now_order(temp.path()), ["task-3", "task-1", "task-2"]);
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["rank", "task-1", "--after", "task-2"])
+        .assert()
+        .success()
+        .stdout(contains("Ranked task task-1 after task-2 in now"));
+    assert_eq!(now_order(temp.path()), ["task-3", "task-2", "task-1"]);
+    let stored = fs::read_to_string(temp.path().join("now").join("task-1.md"))
+        .expect("task file should exist");
+    assert!(stored.contains("rank: "));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["rank", "task-1", "--before", "task-4"])
+        .assert()
+        .failure()
+        .stderr(contains("task-4 is in next, not in now with task-1"));
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["rank", "task-1"])
+        .assert()
+        .failure();
 }
 
 #[test]
@@ -419,6 +1325,79 @@ fn old_command_names_are_rejected() {
         .stderr(contains("unrecognized subcommand"));
 }
 
+#[test]
+fn read_commands_emit_versioned_json() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    write_task(temp.path(), "now", "task-1", "Ship v2", "# Ship v2");
+    write_task(temp.path(), "inbox", "task-2", "Review PR", "# Review PR");
+
+    let output = tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["list", "--format", "json"])
+        .output()
+        .expect("list should run");
+    assert!(output.status.success());
+    let document: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("list should print JSON");
+    assert_eq!(document["version"], 1);
+    let tasks = document["tasks"]
+        .as_array()
+        .expect("tasks should be a list");
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[0]["id"], "task-2");
+    assert_eq!(tasks[0]["queue"], "inbox");
+    assert_eq!(tasks[0]["created_at"], "2026-03-09T10:34:12Z");
+    assert_eq!(tasks[0]["due"], serde_json::Value::Null);
+    assert!(
+        tasks[0]["path"]
+            .as_str()
+            .is_some_and(|path| path.ends_with("task-2.md"))
+    );
+
+    let output = tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["now", "--format", "jsonl"])
+        .output()
+        .expect("now should run");
+    let stdout = String::from_utf8(output.stdout).expect("stdout is utf-8");
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 1);
+    let record: serde_json::Value = serde_json::from_str(lines[0]).expect("line should be JSON");
+    assert_eq!(record["title"], "Ship v2");
+
+    let output = tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["--format", "json", "show", "task-1"])
+        .output()
+        .expect("show should run");
+    let document: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("show should print JSON");
+    assert_eq!(document["task"]["body"], "# Ship v2");
+
+    let output = tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["doctor", "--format", "json"])
+        .output()
+        .expect("doctor should run");
+    let document: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("doctor should print JSON");
+    assert_eq!(document["errors"], 0);
+    assert_eq!(document["diagnostics"][0]["severity"], "ok");
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["done", "task-1", "--format", "json"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(contains("--format json and jsonl are only supported by"));
+}
+
 #[test]
 fn show_resolves_unique_title_substring() {
     let temp = TempDir::new().expect("temp dir should exist");
@@ -518,6 +1497,203 @@ fn add_uses_configured_queue_directory_names() {
     assert!(!tasks_root.join("inbox").join("task-1.md").exists());
 }
 
+#[test]
+fn custom_queues_from_config_work_like_built_in_queues() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    let config_home = temp.path().join("config-home");
+    let config_dir = config_home.join("tqs");
+    let tasks_root = temp.path().join("configured-tasks");
+    std::fs::create_dir_all(&config_dir).expect("config dir should exist");
+    std::fs::write(
+        config_dir.join("config.toml"),
+        format!(
+            "tasks_root = '{}'\n[queues.waiting]\ndir = 'waiting-on'\nposition = 25\nactive = true\n",
+            tasks_root.display()
+        ),
+    )
+    .expect("config file should be written");
+    let cmd = || {
+        let mut cmd = tqs_cmd();
+        cmd.env("XDG_CONFIG_HOME", &config_home);
+        cmd
+    };
+
+    cmd()
+        .args(["add", "--no-edit", "--id", "task-1", "--queue", "waiting"])
+        .arg("Hear back from vendor")
+        .assert()
+        .success();
+    assert!(tasks_root.join("waiting-on").join("task-1.md").exists());
+
+    cmd()
+        .args(["add", "--no-edit", "--id", "task-2", "Ship v2"])
+        .assert()
+        .success();
+    cmd().args(["move", "task-2", "WAITING"]).assert().success();
+
+    cmd()
+        .args(["list", "waiting"])
+        .assert()
+        .success()
+        .stdout(contains("waiting (2)"))
+        .stdout(contains("Ship v2"));
+    cmd()
+        .args(["list", "--where", "queue:waiting", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(contains("\"queue\": \"waiting\""));
+    cmd()
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(contains("now (0)"))
+        .stdout(contains("waiting (2)"));
+    cmd()
+        .args(["doctor"])
+        .assert()
+        .success()
+        .stdout(contains("scanned 2 Markdown task file(s)"));
+    cmd()
+        .args(["move", "task-1", "--to", "someday"])
+        .assert()
+        .code(2)
+        .stderr(contains(
+            "expected one of: inbox, now, next, later, done, waiting",
+        ));
+}
+
+#[test]
+fn view_runs_saved_queries_from_config() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    let config_home = temp.path().join("config-home");
+    let config_dir = config_home.join("tqs");
+    let tasks_root = temp.path().join("tasks");
+    std::fs::create_dir_all(&config_dir).expect("config dir should exist");
+    std::fs::write(
+        config_dir.join("config.toml"),
+        format!(
+            "tasks_root = '{}'\n\n[views.waiting]\nquery = 'body:waiting -queue:done'\nsort = '-title'\ncolumns = ['queue', 'id', 'title']\n",
+            tasks_root.display()
+        ),
+    )
+    .expect("config file should be written");
+    write_task(&tasks_root, "next", "task-1", "Alpha", "waiting on legal");
+    write_task(&tasks_root, "now", "task-2", "Bravo", "waiting on finance");
+    write_task(
+        &tasks_root,
+        "done",
+        "task-3",
+        "Charlie",
+        "waiting on nobody",
+    );
+
+    tqs_cmd()
+        .env("XDG_CONFIG_HOME", &config_home)
+        .args(["view", "waiting"])
+        .assert()
+        .success()
+        .stdout(
+            contains("waiting (2)")
+                .and(
+                    predicates::str::is_match(r"now\s+task-2  Bravo[\s\S]*next\s+task-1  Alpha")
+                        .unwrap(),
+                )
+                .and(contains("task-3").not()),
+        );
+
+    tqs_cmd()
+        .env("XDG_CONFIG_HOME", &config_home)
+        .arg("view")
+        .assert()
+        .success()
+        .stdout(contains("waiting").and(contains("body:waiting -queue:done")));
+
+    tqs_cmd()
+        .env("XDG_CONFIG_HOME", &config_home)
+        .args(["view", "sprint"])
+        .assert()
+        .failure()
+        .stderr(contains("unknown view 'sprint'; expected one of: waiting"));
+}
+
+#[test]
+fn list_and_show_render_templates() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    let config_home = temp.path().join("config-home");
+    let config_dir = config_home.join("tqs");
+    let tasks_root = temp.path().join("tasks");
+    std::fs::create_dir_all(&config_dir).expect("config dir should exist");
+    std::fs::write(
+        config_dir.join("config.toml"),
+        format!(
+            "tasks_root = '{}'\n\n[templates]\nbrief = '{{id}}: {{first_line}}'\nbroken = '{{owner}}'\n",
+            tasks_root.display()
+        ),
+    )
+    .expect("config file should be written");
+    write_task(
+        &tasks_root,
+        "now",
+        "task-1",
+        "Alpha",
+        "# Alpha\n\nCall legal\n",
+    );
+    write_task(&tasks_root, "next", "task-2", "Bravo", "Send invoice\n");
+
+    tqs_cmd()
+        .env("XDG_CONFIG_HOME", &config_home)
+        .args(["list", "--template", "{id} [{queue}] {title}"])
+        .assert()
+        .success()
+        .stdout(contains("task-1 [now] Alpha\ntask-2 [next] Bravo\n"));
+
+    tqs_cmd()
+        .env("XDG_CONFIG_HOME", &config_home)
+        .args(["list", "next", "--template", "brief"])
+        .assert()
+        .success()
+        .stdout(predicates::str::diff("task-2: Send invoice\n"));
+
+    tqs_cmd()
+        .env("XDG_CONFIG_HOME", &config_home)
+        .args(["show", "task-1", "--template", "brief"])
+        .assert()
+        .success()
+        .stdout(predicates::str::diff("task-1: Call legal\n"));
+
+    tqs_cmd()
+        .env("XDG_CONFIG_HOME", &config_home)
+        .args(["list", "--template", "{owner}"])
+        .assert()
+        .code(2)
+        .stderr(contains(
+            "invalid template at column 1: unknown field 'owner'",
+        ));
+
+    tqs_cmd()
+        .env("XDG_CONFIG_HOME", &config_home)
+        .args(["show", "task-1", "--template", "broken"])
+        .assert()
+        .failure()
+        .stderr(contains("invalid config: template 'broken'"));
+
+    tqs_cmd()
+        .env("XDG_CONFIG_HOME", &config_home)
+        .args(["list", "--template", "missing"])
+        .assert()
+        .code(2)
+        .stderr(contains(
+            "unknown template 'missing'; expected one of: brief, broken",
+        ));
+
+    tqs_cmd()
+        .env("XDG_CONFIG_HOME", &config_home)
+        .args(["--format", "json", "list", "--template", "brief"])
+        .assert()
+        .code(2)
+        .stderr(contains("--template cannot be combined with --format"));
+}
+
 #[test]
 fn command_fails_cleanly_when_tasks_root_is_not_configured() {
     let temp = TempDir::new().expect("temp dir should exist");
@@ -666,6 +1842,28 @@ fn command_fails_when_obsidian_vault_alias_is_mixed_with_tasks_root() {
         ));
 }
 
+#[test]
+fn config_command_prints_json() {
+    let temp = TempDir::new().expect("temp dir should exist");
+
+    let output = tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .args(["config", "--format", "json"])
+        .output()
+        .expect("config should run");
+    assert!(output.status.success());
+    let document: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("config should print JSON");
+    assert_eq!(document["version"], 1);
+    assert_eq!(
+        document["config"]["resolved"]["tasks_root"],
+        temp.path().display().to_string()
+    );
+    assert_eq!(document["config"]["resolved"]["queues"]["done"], "done");
+    assert_eq!(document["config"]["resolved"]["recur_queue"], "inbox");
+}
+
 #[test]
 fn config_command_respects_root_precedence() {
     let temp = TempDir::new().expect("temp dir should exist");
@@ -197,3 +197,70 @@ fn add_with_content_sets_body_and_skips_editor() {
     assert!(content.contains("# Ship v2"));
     assert!(content.contains("Some details here"));
 }
+
+#[test]
+fn unknown_frontmatter_keys_survive_start_move_and_done() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    let extra = "aliases: [ship,  v2]\ncssclass: wide # obsidian\nproject:\n  name: Launch\n";
+    write_raw_task(
+        temp.path(),
+        "inbox",
+        "task-1",
+        &format!("completed_at: null\ndaily_note: null\n{extra}"),
+        "# Task",
+    );
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .arg("start")
+        .arg("task-1")
+        .assert()
+        .success();
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .arg("move")
+        .arg("task-1")
+        .arg("later")
+        .assert()
+        .success();
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .arg("done")
+        .arg("--no-edit")
+        .arg("task-1")
+        .assert()
+        .success();
+
+    let content = std::fs::read_to_string(temp.path().join("done").join("task-1.md"))
+        .expect("done file should exist");
+    assert!(content.contains("queue: done"));
+    assert!(content.contains(&format!("{extra}---\n")));
+}
+
+#[test]
+fn done_with_an_overflowing_recurrence_fails_cleanly_and_leaves_the_task() {
+    let temp = TempDir::new().expect("temp dir should exist");
+    write_raw_task(
+        temp.path(),
+        "inbox",
+        "task-1",
+        "recur: every 4000000000d\n",
+        "# Task",
+    );
+
+    tqs_cmd()
+        .arg("--root")
+        .arg(temp.path())
+        .arg("done")
+        .arg("--no-edit")
+        .arg("task-1")
+        .assert()
+        .code(2)
+        .stderr(contains("no next date"));
+
+    assert!(temp.path().join("inbox").join("task-1.md").exists());
+    assert!(!temp.path().join("done").exists());
+}